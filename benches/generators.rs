@@ -0,0 +1,69 @@
+//! Throughput benchmarks for the core generators.
+//!
+//! Run with `cargo bench`, or save a baseline with `cargo bench -- --save-baseline main` and
+//! compare a change against it with `cargo bench -- --baseline main`.
+//!
+//! `advance 30C10` does nothing with each combination but count it, so it measures the position
+//! advance itself, along with rewriting the changed slots of the buffer lent by `for_each_ref`.
+//! For reference, replacing the lexicographic position advance in `Combinations` with a single
+//! slice-based pass, and having `for_each_ref` rewrite only the positions which changed, took
+//! `advance 30C10` from about 500 ms to about 185 ms, and `for_each_ref 30C10` from about 525 ms
+//! to about 205 ms, on the machine where they were measured.  The other benchmarks, which are
+//! dominated by allocating each item, moved by less than the run-to-run noise of around 25% on
+//! that machine.
+
+use std::hint::black_box;
+
+use combinatorial::{Combinations, Permutations};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn combinations(c: &mut Criterion) {
+    let mut group = c.benchmark_group("combinations");
+    group.sample_size(10);
+    group.bench_function("next 30C10 first 1M", |b| {
+        b.iter(|| {
+            Combinations::of_size(0..30u32, 10)
+                .take(1_000_000)
+                .map(|combo| combo[0])
+                .sum::<u32>()
+        })
+    });
+    group.bench_function("for_each_ref 30C10", |b| {
+        b.iter(|| {
+            let mut total = 0u32;
+            Combinations::of_size(0..30u32, 10).for_each_ref(|combo| total += combo[9]);
+            black_box(total)
+        })
+    });
+    group.bench_function("advance 30C10", |b| {
+        b.iter(|| {
+            let mut steps = 0u32;
+            Combinations::of_size(0..30u32, 10).for_each_ref(|_| steps += 1);
+            black_box(steps)
+        })
+    });
+    group.bench_function("count 20 all sizes", |b| {
+        b.iter(|| Combinations::all(0..20u32).count())
+    });
+    group.finish();
+}
+
+fn permutations(c: &mut Criterion) {
+    let mut group = c.benchmark_group("permutations");
+    group.sample_size(10);
+    group.bench_function("next_into 10P10", |b| {
+        b.iter(|| {
+            let mut perms = Permutations::of_length(0..10u32, 10);
+            let mut buffer = Vec::new();
+            let mut total = 0u32;
+            while perms.next_into(&mut buffer) {
+                total += buffer[9];
+            }
+            black_box(total)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, combinations, permutations);
+criterion_main!(benches);
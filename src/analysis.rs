@@ -0,0 +1,74 @@
+/// Returns the indices of a longest strictly increasing subsequence of the given sequence.
+///
+/// The indices are returned in increasing order, so mapping them back into the sequence yields the
+/// subsequence itself.  If several longest increasing subsequences exist, the one which ends with
+/// the smallest possible final element is returned.  The subsequence is found using patience
+/// sorting, which takes `O(n log n)` time.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::longest_increasing_subsequence;
+///
+/// let sequence = [3, 1, 4, 1, 5, 9, 2, 6];
+/// let indices = longest_increasing_subsequence(&sequence);
+/// assert_eq!(indices, vec![1, 2, 4, 7]);
+/// let values: Vec<i32> = indices.iter().map(|i| sequence[*i]).collect();
+/// assert_eq!(values, vec![1, 4, 5, 6]);
+///
+/// assert_eq!(longest_increasing_subsequence(&['c', 'b', 'a']), vec![2]);
+/// assert_eq!(longest_increasing_subsequence::<u8>(&[]), Vec::<usize>::new());
+/// ```
+pub fn longest_increasing_subsequence<T: Ord>(sequence: &[T]) -> Vec<usize> {
+    // `tails[len]` holds the index of the smallest element which ends an increasing subsequence of
+    // length `len + 1`, and `predecessors[i]` holds the index of the element before `i` in the
+    // best subsequence ending at `i`.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut predecessors: Vec<Option<usize>> = Vec::with_capacity(sequence.len());
+    for (index, element) in sequence.iter().enumerate() {
+        let pile = tails.partition_point(|tail| sequence[*tail] < *element);
+        predecessors.push(pile.checked_sub(1).map(|prev| tails[prev]));
+        if pile == tails.len() {
+            tails.push(index);
+        } else {
+            tails[pile] = index;
+        }
+    }
+    let mut indices = Vec::with_capacity(tails.len());
+    let mut current = tails.last().copied();
+    while let Some(index) = current {
+        indices.push(index);
+        current = predecessors[index];
+    }
+    indices.reverse();
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_increasing_subsequence() {
+        assert_eq!(longest_increasing_subsequence(&[1]), vec![0]);
+        assert_eq!(
+            longest_increasing_subsequence(&[1, 2, 3, 4]),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(longest_increasing_subsequence(&[4, 3, 2, 1]), vec![3]);
+        assert_eq!(longest_increasing_subsequence(&[2, 2, 2]), vec![2]);
+        assert_eq!(
+            longest_increasing_subsequence(&[0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15]),
+            vec![0, 4, 6, 9, 13, 15]
+        );
+    }
+
+    #[test]
+    fn test_longest_increasing_subsequence_is_increasing() {
+        let sequence = [5, 1, 6, 2, 7, 3, 8, 4, 0, 9];
+        let indices = longest_increasing_subsequence(&sequence);
+        assert_eq!(indices.len(), 5);
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+        assert!(indices.windows(2).all(|w| sequence[w[0]] < sequence[w[1]]));
+    }
+}
@@ -0,0 +1,82 @@
+use std::f64::consts::PI;
+
+/// The number below which [`log_factorial`] sums logarithms directly rather than using Stirling's
+/// series.
+const SUMMED_BELOW: usize = 256;
+
+/// Returns the natural logarithm of `n` factorial.
+///
+/// This gives the magnitude of factorials far too large to compute exactly, such as to estimate
+/// how long an enumeration would take before starting it.  Below 256, the logarithms of the
+/// factors are summed, and above that, Stirling's series is used, so the result is accurate to
+/// at least twelve significant digits in either case.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{factorial, log_factorial};
+///
+/// assert_eq!(log_factorial(0), 0.0);
+/// assert!((log_factorial(20) - (factorial(20u64) as f64).ln()).abs() < 1e-12);
+///
+/// // 1000! has 2568 decimal digits.
+/// let digits = log_factorial(1000) / 10f64.ln();
+/// assert_eq!(digits.ceil(), 2568.0);
+/// ```
+pub fn log_factorial(n: usize) -> f64 {
+    if n < SUMMED_BELOW {
+        return (2..=n).map(|i| (i as f64).ln()).sum();
+    }
+    let n = n as f64;
+    let series = 1.0 / (12.0 * n) - 1.0 / (360.0 * n.powi(3)) + 1.0 / (1260.0 * n.powi(5));
+    n * n.ln() - n + 0.5 * (2.0 * PI * n).ln() + series
+}
+
+/// Returns the natural logarithm of the binomial coefficient `n` choose `k`, or negative infinity
+/// if `k` is greater than `n`, since the coefficient is then zero.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{combination_count, log_binomial};
+///
+/// let exact = combination_count(100, 50).unwrap() as f64;
+/// assert!((log_binomial(100, 50) - exact.ln()).abs() < 1e-10);
+/// assert_eq!(log_binomial(5, 6), f64::NEG_INFINITY);
+///
+/// // Enumerating every 30-subset of 60 elements at a billion per second takes over three years.
+/// let years = (log_binomial(60, 30) - 1e9f64.ln() - (365.25 * 86400f64).ln()).exp();
+/// assert!(years > 3.0 && years < 4.0);
+/// ```
+pub fn log_binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    log_factorial(n) - log_factorial(k) - log_factorial(n - k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{combination_count, permutation_count};
+
+    #[test]
+    fn test_approximate_matches_exact_counts() {
+        let close = |approximate: f64, exact: u128| {
+            let exact = (exact as f64).ln();
+            assert!((approximate - exact).abs() <= 1e-12 * exact.max(1.0));
+        };
+        for n in 0..=34 {
+            close(log_factorial(n), permutation_count(n, n).unwrap());
+        }
+        for n in 0..=120 {
+            for k in 0..=n {
+                close(log_binomial(n, k), combination_count(n, k).unwrap());
+            }
+        }
+        for n in SUMMED_BELOW - 5..SUMMED_BELOW + 5 {
+            let summed: f64 = (2..=n).map(|i| (i as f64).ln()).sum();
+            assert!((log_factorial(n) - summed).abs() < 1e-10);
+        }
+    }
+}
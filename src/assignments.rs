@@ -0,0 +1,263 @@
+/// An iterator which generates every assignment of `true` or `false` to a given number of boolean
+/// variables, that is, every row of a truth table.
+///
+/// By default, assignments are yielded in truth-table order, counting up in binary with the first
+/// variable as the most significant bit, so they are in lexicographic order with `false` before
+/// `true`.  Variables may be fixed to a given value, in which case only the assignments consistent
+/// with them are generated, and [`Assignments::gray_code`] changes the order so that consecutive
+/// assignments differ in exactly one variable.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Assignments;
+///
+/// let mut assignments = Assignments::of(2);
+/// assert_eq!(assignments.next(), Some(vec![false, false]));
+/// assert_eq!(assignments.next(), Some(vec![false, true]));
+/// assert_eq!(assignments.next(), Some(vec![true, false]));
+/// assert_eq!(assignments.next(), Some(vec![true, true]));
+/// assert_eq!(assignments.next(), None);
+/// ```
+#[derive(Clone)]
+pub struct Assignments {
+    fixed: Vec<Option<bool>>,
+    gray: bool,
+    /// The binary digits of the number of assignments yielded so far, one per free variable, with
+    /// the most significant first.
+    counter: Vec<bool>,
+    done: bool,
+}
+
+impl Assignments {
+    /// Creates a new `Assignments` iterator which will yield all `2^n` assignments to `n`
+    /// variables.  With no variables, a single empty assignment is yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Assignments;
+    ///
+    /// assert_eq!(Assignments::of(10).count(), 1024);
+    /// assert_eq!(Assignments::of(0).collect::<Vec<_>>(), vec![vec![]]);
+    /// ```
+    pub fn of(n: usize) -> Self {
+        Assignments {
+            fixed: vec![None; n],
+            gray: false,
+            counter: vec![false; n],
+            done: false,
+        }
+    }
+
+    /// Fixes the given variable to the given value, so that only the assignments in which it has
+    /// that value are yielded.  The other variables are still yielded in the same relative order.
+    /// If the variable is out of range, or has already been fixed to the opposite value, then no
+    /// assignments are yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Assignments;
+    ///
+    /// let assignments: Vec<Vec<bool>> = Assignments::of(3).fix(1, true).collect();
+    /// assert_eq!(
+    ///     assignments,
+    ///     vec![
+    ///         vec![false, true, false],
+    ///         vec![false, true, true],
+    ///         vec![true, true, false],
+    ///         vec![true, true, true],
+    ///     ]
+    /// );
+    ///
+    /// assert_eq!(Assignments::of(3).fix(3, true).next(), None);
+    /// assert_eq!(Assignments::of(3).fix(0, true).fix(0, false).next(), None);
+    /// ```
+    pub fn fix(mut self, variable: usize, value: bool) -> Self {
+        match self.fixed.get_mut(variable) {
+            Some(slot @ None) => {
+                *slot = Some(value);
+                self.counter.pop();
+            }
+            Some(Some(existing)) if *existing == value => (),
+            _ => self.done = true,
+        }
+        self
+    }
+
+    /// Sets the iterator to yield the assignments in reflected Gray code order, so that each
+    /// assignment differs from the one before it in exactly one of the variables which are not
+    /// fixed.  This is useful when evaluating an expression incrementally, since only the effect
+    /// of a single variable changing needs to be computed at each step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Assignments;
+    ///
+    /// let mut assignments = Assignments::of(2).gray_code();
+    /// assert_eq!(assignments.next(), Some(vec![false, false]));
+    /// assert_eq!(assignments.next(), Some(vec![false, true]));
+    /// assert_eq!(assignments.next(), Some(vec![true, true]));
+    /// assert_eq!(assignments.next(), Some(vec![true, false]));
+    /// assert_eq!(assignments.next(), None);
+    /// ```
+    pub fn gray_code(mut self) -> Self {
+        self.gray = true;
+        self
+    }
+
+    /// Writes the next assignment into the given buffer, replacing its previous contents, and
+    /// advances the internal iterator.  Returns `false`, leaving the buffer untouched, if there
+    /// are no more assignments.
+    ///
+    /// Unlike [`next`](Iterator::next), this does not allocate a new vector for every
+    /// assignment, so reusing one buffer across calls avoids an allocation per item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Assignments;
+    ///
+    /// // Count the assignments satisfying (a or b) and (not a or c).
+    /// let mut assignments = Assignments::of(3);
+    /// let mut buffer = Vec::new();
+    /// let mut count = 0;
+    /// while assignments.next_into(&mut buffer) {
+    ///     if (buffer[0] || buffer[1]) && (!buffer[0] || buffer[2]) {
+    ///         count += 1;
+    ///     }
+    /// }
+    /// assert_eq!(count, 4);
+    /// assert_eq!(buffer, vec![true, true, true]);
+    /// ```
+    pub fn next_into(&mut self, buffer: &mut Vec<bool>) -> bool {
+        if self.done {
+            return false;
+        }
+        // The reflected Gray code flips each digit of the counter whose more significant
+        // neighbour is set.
+        let mut digits = self.counter.iter();
+        let mut previous = false;
+        buffer.clear();
+        buffer.extend(self.fixed.iter().map(|fixed| {
+            fixed.unwrap_or_else(|| {
+                let digit = *digits.next().expect("one counter digit per free variable");
+                let value = digit ^ (self.gray && previous);
+                previous = digit;
+                value
+            })
+        }));
+        match self.counter.iter().rposition(|digit| !digit) {
+            Some(index) => {
+                self.counter[index] = true;
+                self.counter[index + 1..].fill(false);
+            }
+            None => self.done = true,
+        }
+        true
+    }
+}
+
+impl Iterator for Assignments {
+    type Item = Vec<bool>;
+
+    /// Returns the next assignment and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut assignment = Vec::with_capacity(self.fixed.len());
+        self.next_into(&mut assignment).then_some(assignment)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let remaining = u32::try_from(self.counter.len())
+            .ok()
+            .and_then(|free| 1u128.checked_shl(free))
+            .map(|total| {
+                let yielded = self
+                    .counter
+                    .iter()
+                    .fold(0, |yielded, &digit| yielded << 1 | digit as u128);
+                total - yielded
+            })
+            .and_then(|remaining| usize::try_from(remaining).ok());
+        (remaining.unwrap_or(usize::MAX), remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::cartesian_power;
+
+    #[test]
+    fn test_assignments_match_cartesian_power() {
+        for n in 0..8 {
+            let assignments: Vec<Vec<bool>> = Assignments::of(n).collect();
+            assert_eq!(assignments, cartesian_power(&[false, true], n));
+            assert_eq!(Assignments::of(n).size_hint(), (1 << n, Some(1 << n)));
+        }
+    }
+
+    #[test]
+    fn test_assignments_gray_code() {
+        for n in 0..8 {
+            let assignments: Vec<Vec<bool>> = Assignments::of(n).gray_code().collect();
+            assert_eq!(assignments.len(), 1 << n);
+            assert!(assignments[0].iter().all(|value| !value));
+            for pair in assignments.windows(2) {
+                let changes = pair[0].iter().zip(&pair[1]).filter(|(a, b)| a != b);
+                assert_eq!(changes.count(), 1);
+            }
+            let mut sorted = assignments.clone();
+            sorted.sort();
+            assert_eq!(sorted, Assignments::of(n).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_assignments_fixed() {
+        let expected: Vec<Vec<bool>> = Assignments::of(5).filter(|a| !a[0] && a[3]).collect();
+        let assignments = Assignments::of(5).fix(3, true).fix(0, false);
+        assert_eq!(assignments.size_hint(), (8, Some(8)));
+        assert_eq!(assignments.collect::<Vec<_>>(), expected);
+        let gray: Vec<Vec<bool>> = Assignments::of(5).fix(2, true).gray_code().collect();
+        assert!(gray.iter().all(|a| a[2]));
+        for pair in gray.windows(2) {
+            let changes = pair[0].iter().zip(&pair[1]).filter(|(a, b)| a != b);
+            assert_eq!(changes.count(), 1);
+        }
+        assert_eq!(Assignments::of(2).fix(0, true).fix(1, false).count(), 1);
+    }
+
+    #[test]
+    fn test_assignments_many_variables() {
+        for gray in [false, true] {
+            let mut assignments = Assignments::of(200);
+            if gray {
+                assignments = assignments.gray_code();
+            }
+            assert_eq!(assignments.size_hint(), (usize::MAX, None));
+            assert_eq!(assignments.next(), Some(vec![false; 200]));
+            let mut expected = vec![false; 200];
+            expected[199] = true;
+            assert_eq!(assignments.next(), Some(expected.clone()));
+            expected[198] = true;
+            expected[199] = gray;
+            assert_eq!(assignments.next(), Some(expected));
+        }
+        let mut assignments = Assignments::of(130);
+        for variable in 2..130 {
+            assignments = assignments.fix(variable, variable % 2 == 0);
+        }
+        assert_eq!(assignments.size_hint(), (4, Some(4)));
+        let assignments: Vec<Vec<bool>> = assignments.collect();
+        assert_eq!(assignments.len(), 4);
+        assert!(assignments
+            .iter()
+            .all(|a| a[2..].iter().step_by(2).all(|&value| value)));
+    }
+}
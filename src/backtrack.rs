@@ -0,0 +1,162 @@
+/// An iterator which performs an exhaustive backtracking search, yielding every sequence of
+/// choices of a given length which can be built up one choice at a time.
+///
+/// The search is driven by a closure which is given the partial sequence chosen so far and
+/// returns the candidates for the next choice.  Constraints are enforced by only returning the
+/// candidates which are consistent with the partial sequence, and a branch is pruned by returning
+/// no candidates at all, so no sequence extending it is ever searched.  This is the same
+/// depth-first strategy used by the built-in generators such as
+/// [`Permutations`](crate::Permutations), and sequences are yielded in the order in which the
+/// candidates are returned at each level.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Backtracker;
+///
+/// // Placements of eight queens on a chessboard, one per row, so that none attack each other.
+/// let queens = Backtracker::new(8, |placed: &[usize]| {
+///     let row = placed.len();
+///     (0..8)
+///         .filter(|col| {
+///             placed.iter().enumerate().all(|(other_row, other_col)| {
+///                 other_col != col && row - other_row != col.abs_diff(*other_col)
+///             })
+///         })
+///         .collect::<Vec<usize>>()
+/// });
+/// assert_eq!(queens.count(), 92);
+/// ```
+pub struct Backtracker<C, I: IntoIterator<Item = C>, F> {
+    length: usize,
+    candidates: F,
+    partial: Vec<C>,
+    stack: Vec<I::IntoIter>,
+    started: bool,
+    done: bool,
+}
+
+impl<C, I, F> Backtracker<C, I, F>
+where
+    I: IntoIterator<Item = C>,
+    F: FnMut(&[C]) -> I,
+{
+    /// Creates a new `Backtracker` which will yield every sequence of the given length in which
+    /// each choice is one of the candidates returned by the given closure for the choices before
+    /// it.  A length of 0 yields a single empty sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Backtracker;
+    ///
+    /// // Binary strings of length 4 with no two adjacent ones.
+    /// let strings = Backtracker::new(4, |bits: &[u8]| match bits.last() {
+    ///     Some(1) => vec![0],
+    ///     _ => vec![0, 1],
+    /// });
+    /// let strings: Vec<Vec<u8>> = strings.collect();
+    /// assert_eq!(strings.len(), 8);
+    /// assert_eq!(strings[0], vec![0, 0, 0, 0]);
+    /// assert_eq!(strings[7], vec![1, 0, 1, 0]);
+    /// ```
+    pub fn new(length: usize, candidates: F) -> Self {
+        Backtracker {
+            length,
+            candidates,
+            partial: Vec::with_capacity(length),
+            stack: Vec::with_capacity(length),
+            started: false,
+            done: false,
+        }
+    }
+}
+
+impl<C, I, F> Iterator for Backtracker<C, I, F>
+where
+    C: Clone,
+    I: IntoIterator<Item = C>,
+    F: FnMut(&[C]) -> I,
+{
+    type Item = Vec<C>;
+
+    /// Returns the next complete sequence of choices and advances the search.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.started {
+            self.partial.pop();
+        } else {
+            self.started = true;
+            if self.length == 0 {
+                self.done = true;
+                return Some(Vec::new());
+            }
+            let first = (self.candidates)(&self.partial).into_iter();
+            self.stack.push(first);
+        }
+        while let Some(level) = self.stack.last_mut() {
+            match level.next() {
+                Some(choice) => {
+                    self.partial.push(choice);
+                    if self.partial.len() == self.length {
+                        return Some(self.partial.clone());
+                    }
+                    let next = (self.candidates)(&self.partial).into_iter();
+                    self.stack.push(next);
+                }
+                None => {
+                    self.stack.pop();
+                    self.partial.pop();
+                }
+            }
+        }
+        self.done = true;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Permutations;
+
+    #[test]
+    fn test_backtracker_matches_permutations() {
+        for n in 0..6 {
+            for length in 0..=n {
+                let perms = Backtracker::new(length, |chosen: &[usize]| {
+                    (0..n)
+                        .filter(|i| !chosen.contains(i))
+                        .collect::<Vec<usize>>()
+                });
+                assert_eq!(
+                    perms.collect::<Vec<_>>(),
+                    Permutations::of_length(0..n, length).collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_backtracker_pruning() {
+        let counts: Vec<usize> = (0..9)
+            .map(|n| {
+                Backtracker::new(n, |placed: &[usize]| {
+                    let row = placed.len();
+                    (0..n)
+                        .filter(|col| {
+                            placed.iter().enumerate().all(|(other_row, other_col)| {
+                                other_col != col && row - other_row != col.abs_diff(*other_col)
+                            })
+                        })
+                        .collect::<Vec<usize>>()
+                })
+                .count()
+            })
+            .collect();
+        assert_eq!(counts, vec![1, 1, 0, 0, 2, 10, 4, 40, 92]);
+        assert_eq!(Backtracker::new(3, |_: &[u8]| Vec::new()).next(), None);
+    }
+}
@@ -0,0 +1,104 @@
+/// An extension trait, implemented for every iterator, for taking items from a generator in
+/// batches rather than one at a time.
+///
+/// Handing a consumer thousands of items at once, rather than calling [`next`](Iterator::next)
+/// through a trait object for each, amortizes the cost of the call and keeps the items together
+/// in memory, which suits pipelines such as SIMD or GPU kernels which process many candidates at
+/// a time.  To share a single enumeration between threads in batches, see
+/// [`SharedCombinations`](crate::SharedCombinations).
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{BatchedIterator, Combinations};
+///
+/// let mut combos = Combinations::of_size(0..6, 3);
+/// let mut total = 0;
+/// loop {
+///     let batch = combos.next_batch(8);
+///     if batch.is_empty() {
+///         break;
+///     }
+///     assert!(batch.len() == 8 || batch.len() == 20 % 8);
+///     total += batch.len();
+/// }
+/// assert_eq!(total, 20);
+/// ```
+pub trait BatchedIterator: Iterator {
+    /// Returns the next batch of at most `size` items, advancing the iterator past them.  The
+    /// batch is smaller than `size` only if the iterator is exhausted, and empty once no items
+    /// remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{BatchedIterator, Permutations};
+    ///
+    /// let mut perms = Permutations::of_length(1..4, 2);
+    /// assert_eq!(perms.next_batch(4), vec![vec![1, 2], vec![1, 3], vec![2, 1], vec![2, 3]]);
+    /// assert_eq!(perms.next_batch(4), vec![vec![3, 1], vec![3, 2]]);
+    /// assert!(perms.next_batch(4).is_empty());
+    /// ```
+    fn next_batch(&mut self, size: usize) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        let mut batch = Vec::with_capacity(size.min(self.size_hint().0));
+        batch.extend(self.by_ref().take(size));
+        batch
+    }
+
+    /// Replaces the contents of the given buffer with the next batch of at most `size` items, as
+    /// by [`next_batch`](BatchedIterator::next_batch), and returns the number of items written.
+    /// Reusing one buffer for every batch avoids allocating a new one each time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{BatchedIterator, Combinations};
+    ///
+    /// let mut subsets = Combinations::all(0..10);
+    /// let mut buffer = Vec::new();
+    /// let mut sizes = 0;
+    /// while subsets.next_batch_into(&mut buffer, 100) > 0 {
+    ///     sizes += buffer.iter().map(|subset| subset.len()).sum::<usize>();
+    /// }
+    /// assert_eq!(sizes, 10 * 512);
+    /// assert!(buffer.capacity() >= 100);
+    /// ```
+    fn next_batch_into(&mut self, buffer: &mut Vec<Self::Item>, size: usize) -> usize
+    where
+        Self: Sized,
+    {
+        buffer.clear();
+        buffer.extend(self.by_ref().take(size));
+        buffer.len()
+    }
+}
+
+impl<I: Iterator> BatchedIterator for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_batch_matches_iteration() {
+        for size in [0, 1, 7, 1000] {
+            let mut combos = Combinations::of_size(0..8, 4);
+            let mut buffer = Vec::new();
+            let mut batched = Vec::new();
+            while combos.next_batch_into(&mut buffer, size) > 0 {
+                assert!(buffer.len() <= size);
+                batched.append(&mut buffer);
+                batched.extend(combos.next_batch(size));
+            }
+            if size > 0 {
+                assert_eq!(batched, Combinations::of_size(0..8, 4).collect::<Vec<_>>());
+            } else {
+                assert!(batched.is_empty());
+            }
+        }
+    }
+}
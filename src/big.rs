@@ -0,0 +1,115 @@
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+use crate::bell_number;
+
+/// Returns `n` factorial as a big integer.  This is only available with the `bigint` feature.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{factorial, factorial_big};
+/// use num_bigint::BigUint;
+///
+/// assert_eq!(factorial_big(20), BigUint::from(factorial(20u64)));
+/// assert_eq!(factorial_big(100).to_string().len(), 158);
+/// ```
+pub fn factorial_big(n: usize) -> BigUint {
+    permutation_count_big(n, n)
+}
+
+/// Returns the binomial coefficient `n` choose `k` as a big integer, or zero if `k` is greater
+/// than `n`.  This is only available with the `bigint` feature.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{binomial_big, combination_count};
+/// use num_bigint::BigUint;
+///
+/// assert_eq!(binomial_big(100, 50), BigUint::from(combination_count(100, 50).unwrap()));
+/// assert_eq!(combination_count(1000, 500), None);
+/// assert_eq!(binomial_big(1000, 500).to_string().len(), 300);
+/// assert_eq!(binomial_big(5, 6), BigUint::from(0u8));
+/// ```
+pub fn binomial_big(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return BigUint::zero();
+    }
+    (0..k.min(n - k)).fold(BigUint::one(), |count, i| count * (n - i) / (i + 1))
+}
+
+/// Returns the `n`th Catalan number as a big integer.  This is only available with the `bigint`
+/// feature.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{catalan, catalan_big};
+/// use num_bigint::BigUint;
+///
+/// assert_eq!(catalan_big(30), BigUint::from(catalan(30)));
+/// let expected = "896519947090131496687170070074100632420837521538745909320";
+/// assert_eq!(catalan_big(100).to_string(), expected);
+/// ```
+pub fn catalan_big(n: usize) -> BigUint {
+    binomial_big(2 * n, n) / (n + 1)
+}
+
+/// Returns the `n`th Bell number as a big integer.  This is only available with the `bigint`
+/// feature.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{bell_big, checked_bell_number};
+/// use num_bigint::BigUint;
+///
+/// assert_eq!(bell_big(42), BigUint::from(checked_bell_number(42).unwrap()));
+/// assert_eq!(checked_bell_number(43), None);
+/// assert!(bell_big(43) > BigUint::from(u128::MAX));
+/// ```
+pub fn bell_big(n: usize) -> BigUint {
+    bell_number(n)
+}
+
+/// Returns the number of permutations of length `k` of `n` distinct elements as a big integer.
+pub(crate) fn permutation_count_big(n: usize, k: usize) -> BigUint {
+    if k > n {
+        return BigUint::zero();
+    }
+    (n - k + 1..=n).fold(BigUint::one(), |count, factor| count * factor)
+}
+
+/// Returns the number of multisets of size `k` of `n` distinct elements as a big integer.
+pub(crate) fn multiset_count_big(n: usize, k: usize) -> BigUint {
+    if n == 0 {
+        return BigUint::from(u8::from(k == 0));
+    }
+    binomial_big(n + k - 1, k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{catalan, checked_bell_number, combination_count, permutation_count};
+
+    #[test]
+    fn test_big_matches_checked_counts() {
+        for n in 0..45 {
+            let exact = |count: Option<u128>, big: BigUint| {
+                assert_eq!(count, u128::try_from(big).ok());
+            };
+            exact(permutation_count(n, n), factorial_big(n));
+            exact(checked_bell_number(n), bell_big(n));
+            assert_eq!(catalan_big(n + 1) * (n + 2), catalan_big(n) * (4 * n + 2));
+            if usize::try_from(catalan_big(n)).is_ok() {
+                assert_eq!(catalan_big(n), BigUint::from(catalan(n)));
+            }
+            for k in 0..=n + 1 {
+                exact(combination_count(n, k), binomial_big(n, k));
+                exact(permutation_count(n, k), permutation_count_big(n, k));
+            }
+        }
+    }
+}
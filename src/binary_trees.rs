@@ -0,0 +1,312 @@
+use std::fmt;
+
+/// The shape of a full binary tree, in which every node is either a leaf or has exactly two
+/// children.
+///
+/// A full binary tree with `n` leaves corresponds to a full parenthesization of a product of `n`
+/// factors, so the shapes can be used to evaluate an expression in every possible association
+/// order.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::BinaryTree;
+///
+/// let tree = BinaryTree::node(BinaryTree::node(BinaryTree::Leaf, BinaryTree::Leaf), BinaryTree::Leaf);
+/// assert_eq!(tree.leaves(), 3);
+/// assert_eq!(tree.to_string(), "((x x) x)");
+/// assert_eq!(tree.fold(&[8, 4, 2], |a, b| a / b), Some(1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BinaryTree {
+    /// A leaf, which has no children.
+    Leaf,
+    /// An internal node with a left and a right subtree.
+    Node(Box<BinaryTree>, Box<BinaryTree>),
+}
+
+impl BinaryTree {
+    /// Creates an internal node with the given left and right subtrees.
+    pub fn node(left: BinaryTree, right: BinaryTree) -> Self {
+        BinaryTree::Node(Box::new(left), Box::new(right))
+    }
+
+    /// Returns the number of leaves in the tree.
+    pub fn leaves(&self) -> usize {
+        match self {
+            BinaryTree::Leaf => 1,
+            BinaryTree::Node(left, right) => left.leaves() + right.leaves(),
+        }
+    }
+
+    /// Evaluates the parenthesization described by the tree, using the given values for the leaves
+    /// from left to right and the given function to combine the values of two subtrees.  Returns
+    /// `None` if the number of values differs from the number of leaves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::BinaryTrees;
+    ///
+    /// let results: Vec<i32> = BinaryTrees::with_leaves(3)
+    ///     .map(|tree| tree.fold(&[10, 5, 2], |a, b| a - b).unwrap())
+    ///     .collect();
+    /// assert_eq!(results, vec![3, 7]);
+    /// ```
+    pub fn fold<T: Clone>(&self, values: &[T], mut combine: impl FnMut(T, T) -> T) -> Option<T> {
+        let mut values = values.iter();
+        let result = self.fold_from(&mut values, &mut combine)?;
+        match values.next() {
+            Some(_) => None,
+            None => Some(result),
+        }
+    }
+
+    /// Recursively evaluates the tree, consuming leaf values from the given iterator.
+    fn fold_from<'a, T: Clone + 'a>(
+        &self,
+        values: &mut impl Iterator<Item = &'a T>,
+        combine: &mut impl FnMut(T, T) -> T,
+    ) -> Option<T> {
+        match self {
+            BinaryTree::Leaf => values.next().cloned(),
+            BinaryTree::Node(left, right) => {
+                let left = left.fold_from(values, combine)?;
+                let right = right.fold_from(values, combine)?;
+                Some(combine(left, right))
+            }
+        }
+    }
+
+    /// Builds a tree from its preorder encoding, in which `true` denotes an internal node and
+    /// `false` denotes a leaf.
+    fn from_preorder(encoding: &mut impl Iterator<Item = bool>) -> Self {
+        match encoding.next() {
+            Some(true) => {
+                let left = BinaryTree::from_preorder(encoding);
+                let right = BinaryTree::from_preorder(encoding);
+                BinaryTree::node(left, right)
+            }
+            _ => BinaryTree::Leaf,
+        }
+    }
+}
+
+impl fmt::Display for BinaryTree {
+    /// Formats the tree as a full parenthesization, writing each leaf as `x`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryTree::Leaf => write!(f, "x"),
+            BinaryTree::Node(left, right) => write!(f, "({} {})", left, right),
+        }
+    }
+}
+
+/// An iterator which generates all shapes of full binary trees with a given number of leaves.
+///
+/// There are `catalan(n - 1)` such trees with `n` leaves.  The trees are yielded in lexicographic
+/// order of their preorder encodings, where internal nodes sort before leaves, so the first tree
+/// is the one which leans entirely to the left.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{catalan, BinaryTrees};
+///
+/// let trees: Vec<String> = BinaryTrees::with_leaves(4).map(|tree| tree.to_string()).collect();
+/// assert_eq!(
+///     trees,
+///     vec![
+///         "(((x x) x) x)",
+///         "((x (x x)) x)",
+///         "((x x) (x x))",
+///         "(x ((x x) x))",
+///         "(x (x (x x)))",
+///     ]
+/// );
+///
+/// assert_eq!(BinaryTrees::with_leaves(9).count(), catalan(8));
+/// ```
+#[derive(Clone)]
+pub struct BinaryTrees {
+    internal_nodes: usize,
+    encoding: Vec<bool>,
+    opened: usize,
+    started: bool,
+    done: bool,
+}
+
+impl BinaryTrees {
+    /// Creates a new `BinaryTrees` iterator which will yield every full binary tree with the given
+    /// number of leaves.  Since every tree has at least one leaf, no trees have zero leaves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{BinaryTree, BinaryTrees};
+    ///
+    /// let mut trees = BinaryTrees::with_leaves(1);
+    /// assert_eq!(trees.next(), Some(BinaryTree::Leaf));
+    /// assert_eq!(trees.next(), None);
+    ///
+    /// assert_eq!(BinaryTrees::with_leaves(0).next(), None);
+    /// ```
+    pub fn with_leaves(leaves: usize) -> Self {
+        let internal_nodes = leaves.saturating_sub(1);
+        BinaryTrees {
+            internal_nodes,
+            encoding: Vec::with_capacity(2 * internal_nodes),
+            opened: 0,
+            started: false,
+            done: leaves == 0,
+        }
+    }
+
+    /// Returns the tree which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if none remain.  Since the next tree is only found
+    /// when the iterator advances, this searches for it using a copy of the iterator, so it costs
+    /// about as much as a call to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::BinaryTrees;
+    ///
+    /// let mut trees = BinaryTrees::with_leaves(3);
+    /// assert_eq!(trees.current().unwrap().to_string(), "((x x) x)");
+    /// assert_eq!(trees.next().unwrap().to_string(), "((x x) x)");
+    /// assert_eq!(trees.current().unwrap().to_string(), "(x (x x))");
+    /// ```
+    pub fn current(&self) -> Option<BinaryTree> {
+        self.clone().next()
+    }
+
+    /// Returns `true` if the given symbol may be appended to the current partial encoding.
+    fn can_place(&self, internal: bool) -> bool {
+        if internal {
+            self.opened < self.internal_nodes
+        } else {
+            self.encoding.len() - self.opened < self.opened
+        }
+    }
+
+    /// Appends the given symbol to the current partial encoding.
+    fn place(&mut self, internal: bool) {
+        if internal {
+            self.opened += 1;
+        }
+        self.encoding.push(internal);
+    }
+
+    /// Removes and returns the last symbol of the current partial encoding, if any.
+    fn unplace(&mut self) -> Option<bool> {
+        let internal = self.encoding.pop()?;
+        if internal {
+            self.opened -= 1;
+        }
+        Some(internal)
+    }
+
+    /// Completes the encoding with the lexicographically smallest valid symbols, backtracking as
+    /// necessary.  If `previous` is given, the search resumes by trying symbols after `previous`
+    /// in the next position.  Returns `true` if the encoding was completed, or `false` if no
+    /// further trees exist.
+    fn fill(&mut self, mut previous: Option<bool>) -> bool {
+        loop {
+            if previous.is_none() && self.encoding.len() == 2 * self.internal_nodes {
+                return true;
+            }
+            let candidates: &[bool] = match previous {
+                None => &[true, false],
+                Some(true) => &[false],
+                Some(false) => &[],
+            };
+            match candidates.iter().copied().find(|c| self.can_place(*c)) {
+                Some(internal) => {
+                    self.place(internal);
+                    previous = None;
+                }
+                None => match self.unplace() {
+                    Some(internal) => previous = Some(internal),
+                    None => return false,
+                },
+            }
+        }
+    }
+}
+
+impl Iterator for BinaryTrees {
+    type Item = BinaryTree;
+
+    /// Returns the next tree and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let found = if self.started {
+            match self.unplace() {
+                Some(internal) => self.fill(Some(internal)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.fill(None)
+        };
+        if !found {
+            self.done = true;
+            return None;
+        }
+        Some(BinaryTree::from_preorder(
+            &mut self.encoding.iter().copied(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalan;
+
+    #[test]
+    fn test_binary_trees_counts() {
+        assert_eq!(BinaryTrees::with_leaves(0).count(), 0);
+        for leaves in 1..12 {
+            assert_eq!(
+                BinaryTrees::with_leaves(leaves).count(),
+                catalan(leaves - 1)
+            );
+        }
+        let mut items = BinaryTrees::with_leaves(5);
+        while let Some(current) = items.current() {
+            assert_eq!(items.next(), Some(current));
+        }
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn test_binary_trees_unique_with_correct_leaves() {
+        let trees: Vec<BinaryTree> = BinaryTrees::with_leaves(7).collect();
+        assert!(trees.iter().all(|tree| tree.leaves() == 7));
+        let mut sorted = trees.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), trees.len());
+    }
+
+    #[test]
+    fn test_binary_tree_fold() {
+        let tree = BinaryTree::node(BinaryTree::Leaf, BinaryTree::Leaf);
+        assert_eq!(tree.fold(&[1, 2], |a, b| a + b), Some(3));
+        assert_eq!(tree.fold(&[1], |a, b| a + b), None);
+        assert_eq!(tree.fold(&[1, 2, 3], |a, b| a + b), None);
+        let strings: Vec<String> = BinaryTrees::with_leaves(3)
+            .map(|tree| {
+                tree.fold(&["a", "b", "c"].map(String::from), |a, b| {
+                    format!("({}{})", a, b)
+                })
+                .unwrap()
+            })
+            .collect();
+        assert_eq!(strings, vec!["((ab)c)", "(a(bc))"]);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{sorted_unique, Combinations, WithComplements};
+
+/// An iterator which generates the ways to split a set of elements into two parts.
+///
+/// Splits are either labeled, in which case `(a, b)` and `(b, a)` are distinct and there are
+/// `2^n` of them, or unlabeled, in which case each split is yielded once, with the first part
+/// containing the smallest element, and there are `2^(n - 1)` of them.  Both parts are sorted,
+/// and the splits are yielded in the order of the first parts generated by [`Combinations::all`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Bipartitions;
+///
+/// let mut splits = Bipartitions::unlabeled(vec!['a', 'b', 'c']);
+/// assert_eq!(splits.next(), Some((vec!['a'], vec!['b', 'c'])));
+/// assert_eq!(splits.next(), Some((vec!['a', 'b'], vec!['c'])));
+/// assert_eq!(splits.next(), Some((vec!['a', 'c'], vec!['b'])));
+/// assert_eq!(splits.next(), Some((vec!['a', 'b', 'c'], vec![])));
+/// assert_eq!(splits.next(), None);
+///
+/// assert_eq!(Bipartitions::labeled(1..=3).count(), 8);
+/// ```
+#[derive(Clone)]
+pub struct Bipartitions<T> {
+    elements: Vec<T>,
+    /// The number of leading elements which are always in the first part: one for unlabeled
+    /// splits of a nonempty set, and zero otherwise.
+    fixed: usize,
+    splits: WithComplements<T>,
+    done: bool,
+}
+
+/// The indices of the elements in the two parts of a split.
+type SplitIndices = (Vec<usize>, Vec<usize>);
+
+impl<T: Ord + Clone> Bipartitions<T> {
+    /// Creates a new `Bipartitions` iterator which will yield every ordered pair of disjoint
+    /// parts whose union is the set of elements in the given iterable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Bipartitions;
+    ///
+    /// let mut splits = Bipartitions::labeled(vec![1, 2]);
+    /// assert_eq!(splits.next(), Some((vec![], vec![1, 2])));
+    /// assert_eq!(splits.next(), Some((vec![1], vec![2])));
+    /// assert_eq!(splits.next(), Some((vec![2], vec![1])));
+    /// assert_eq!(splits.next(), Some((vec![1, 2], vec![])));
+    /// assert_eq!(splits.next(), None);
+    /// ```
+    pub fn labeled(elements: impl IntoIterator<Item = T>) -> Self {
+        let elements = sorted_unique(elements);
+        Bipartitions {
+            splits: Combinations::all_unchecked(elements.clone()).with_complements(),
+            elements,
+            fixed: 0,
+            done: false,
+        }
+    }
+
+    /// Creates a new `Bipartitions` iterator which will yield every unordered pair of disjoint
+    /// parts whose union is the set of elements in the given iterable.  The first part of each
+    /// split always contains the smallest element.  An empty set has one split into two empty
+    /// parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Bipartitions;
+    ///
+    /// assert_eq!(Bipartitions::unlabeled(0..10).count(), 1 << 9);
+    ///
+    /// let mut splits = Bipartitions::unlabeled(Vec::<usize>::new());
+    /// assert_eq!(splits.next(), Some((vec![], vec![])));
+    /// assert_eq!(splits.next(), None);
+    /// ```
+    pub fn unlabeled(elements: impl IntoIterator<Item = T>) -> Self {
+        let elements = sorted_unique(elements);
+        let fixed = elements.len().min(1);
+        Bipartitions {
+            splits: Combinations::all_unchecked(elements[fixed..].to_vec()).with_complements(),
+            elements,
+            fixed,
+            done: false,
+        }
+    }
+
+    /// Restricts the iterator to splits into two parts of equal size.  If there is an odd number
+    /// of elements, then no splits are yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Bipartitions;
+    ///
+    /// let mut teams = Bipartitions::unlabeled(vec!["ann", "bob", "cat", "dan"]).balanced();
+    /// assert_eq!(teams.next(), Some((vec!["ann", "bob"], vec!["cat", "dan"])));
+    /// assert_eq!(teams.next(), Some((vec!["ann", "cat"], vec!["bob", "dan"])));
+    /// assert_eq!(teams.next(), Some((vec!["ann", "dan"], vec!["bob", "cat"])));
+    /// assert_eq!(teams.next(), None);
+    ///
+    /// assert_eq!(Bipartitions::labeled(0..4).balanced().count(), 6);
+    /// assert_eq!(Bipartitions::labeled(0..5).balanced().next(), None);
+    /// ```
+    pub fn balanced(mut self) -> Self {
+        let total = self.elements.len();
+        if total % 2 == 1 {
+            self.done = true;
+            return self;
+        }
+        let rest = self.elements[self.fixed..].to_vec();
+        self.splits =
+            Combinations::of_size_unchecked(rest, total / 2 - self.fixed).with_complements();
+        self
+    }
+
+    /// Returns the sorted, deduplicated elements which this iterator splits into two parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Bipartitions;
+    ///
+    /// assert_eq!(Bipartitions::unlabeled(vec![3, 1, 3, 2]).elements(), &[1, 2, 3]);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the split which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if none remain.  Since the next split is only found
+    /// when the iterator advances, this searches for it using a copy of the iterator, so it costs
+    /// about as much as a call to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Bipartitions;
+    ///
+    /// let mut splits = Bipartitions::labeled(vec![1, 2]);
+    /// assert_eq!(splits.next(), Some((vec![], vec![1, 2])));
+    /// assert_eq!(splits.current(), Some((vec![1], vec![2])));
+    /// assert_eq!(splits.next(), Some((vec![1], vec![2])));
+    /// ```
+    pub fn current(&self) -> Option<(Vec<T>, Vec<T>)> {
+        self.clone().next()
+    }
+
+    /// Consumes the iterator and returns its elements, as given by [`Bipartitions::elements`],
+    /// along with the indices of the elements in each part of the next split which would have
+    /// been yielded, or `None` if no splits remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Bipartitions;
+    ///
+    /// let mut splits = Bipartitions::unlabeled(vec!['c', 'b', 'a']);
+    /// assert_eq!(splits.next(), Some((vec!['a'], vec!['b', 'c'])));
+    /// assert_eq!(splits.into_parts(), (vec!['a', 'b', 'c'], Some((vec![0, 1], vec![2]))));
+    /// ```
+    pub fn into_parts(mut self) -> (Vec<T>, Option<SplitIndices>) {
+        let next = self.next().map(|(part, complement)| {
+            let indices = |part: Vec<T>| -> Vec<usize> {
+                let index = |element: &T| self.elements.binary_search(element).unwrap();
+                part.iter().map(index).collect()
+            };
+            (indices(part), indices(complement))
+        });
+        (self.elements, next)
+    }
+}
+
+impl<T: Ord + Clone> Iterator for Bipartitions<T> {
+    type Item = (Vec<T>, Vec<T>);
+
+    /// Returns the next split and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (part, complement) = self.splits.next()?;
+        let fixed = self.elements[..self.fixed].iter().cloned();
+        Some((fixed.chain(part).collect(), complement))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_bipartitions_each_split_once() {
+        for n in 0..8 {
+            let labeled: Vec<(Vec<usize>, Vec<usize>)> = Bipartitions::labeled(0..n).collect();
+            assert_eq!(labeled.len(), 1 << n);
+            assert!(labeled.iter().all(|(a, b)| {
+                let mut union: Vec<usize> = a.iter().chain(b.iter()).copied().collect();
+                union.sort_unstable();
+                union == (0..n).collect::<Vec<usize>>()
+            }));
+            let unordered: BTreeSet<BTreeSet<Vec<usize>>> = labeled
+                .iter()
+                .map(|(a, b)| [a.clone(), b.clone()].into_iter().collect())
+                .collect();
+            let unlabeled: Vec<(Vec<usize>, Vec<usize>)> = Bipartitions::unlabeled(0..n).collect();
+            assert_eq!(unlabeled.len(), unordered.len());
+            let balanced: Vec<(Vec<usize>, Vec<usize>)> =
+                Bipartitions::unlabeled(0..n).balanced().collect();
+            let expected: Vec<(Vec<usize>, Vec<usize>)> = unlabeled
+                .into_iter()
+                .filter(|(a, b)| a.len() == b.len())
+                .collect();
+            assert_eq!(balanced, expected);
+        }
+        let mut items = Bipartitions::unlabeled(0..5);
+        while let Some(current) = items.current() {
+            assert_eq!(items.next(), Some(current));
+        }
+        assert_eq!(items.next(), None);
+    }
+}
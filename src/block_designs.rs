@@ -0,0 +1,398 @@
+use crate::{combination_count, sorted_unique};
+
+/// An iterator which generates every way to choose a given number of distinct blocks, each of a
+/// given size, from a set of elements, optionally keeping only the choices in which every pair of
+/// elements appears together in at least one block.
+///
+/// Each design is yielded as a list of blocks, with the elements of each block in increasing
+/// order and the blocks in lexicographic order, and designs are yielded in lexicographic order of
+/// their blocks.  With [`BlockDesigns::covering_pairs`], the designs are the pair coverings with
+/// the given number of blocks.  The search chooses blocks one at a time and abandons a partial
+/// design as soon as the remaining blocks cannot complete it, which is far faster than filtering
+/// every combination of combinations.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::BlockDesigns;
+///
+/// let mut designs = BlockDesigns::new(1..=4, 2, 2);
+/// assert_eq!(designs.next(), Some(vec![vec![1, 2], vec![1, 3]]));
+/// assert_eq!(designs.next(), Some(vec![vec![1, 2], vec![1, 4]]));
+/// assert_eq!(designs.count(), 13);
+///
+/// // The seven lines of the Fano plane cover every pair of seven points.
+/// let fano = BlockDesigns::new(0..7, 3, 7).covering_pairs();
+/// assert_eq!(fano.count(), 30);
+/// ```
+pub struct BlockDesigns<T> {
+    elements: Vec<T>,
+    block_size: usize,
+    block_count: usize,
+    covering: bool,
+    design: Vec<Vec<usize>>,
+    coverage: Vec<usize>,
+    uncovered_from: Vec<usize>,
+    uncovered: usize,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord + Clone> BlockDesigns<T> {
+    /// Creates a new `BlockDesigns` iterator which will yield every choice of `block_count`
+    /// distinct blocks of `block_size` of the unique elements in the given iterable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{binomial, BlockDesigns};
+    ///
+    /// // Any 3 of the 10 pairs of 5 elements.
+    /// assert_eq!(BlockDesigns::new(0..5, 2, 3).count(), binomial(10, 3));
+    /// assert_eq!(BlockDesigns::new(0..5, 6, 1).count(), 0);
+    /// assert_eq!(BlockDesigns::new(0..5, 2, 0).next(), Some(vec![]));
+    /// ```
+    pub fn new(
+        elements: impl IntoIterator<Item = T>,
+        block_size: usize,
+        block_count: usize,
+    ) -> Self {
+        let elements = sorted_unique(elements);
+        let n = elements.len();
+        BlockDesigns {
+            elements,
+            block_size,
+            block_count,
+            covering: false,
+            design: Vec::with_capacity(block_count),
+            coverage: vec![0; n * n],
+            uncovered_from: (0..n).map(|i| n - 1 - i).collect(),
+            uncovered: n * n.saturating_sub(1) / 2,
+            started: false,
+            done: false,
+        }
+    }
+}
+
+impl<T: Clone> BlockDesigns<T> {
+    /// Restricts the designs to those in which every pair of distinct elements appears together
+    /// in at least one block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::BlockDesigns;
+    ///
+    /// // At least six triples are needed to cover the pairs of six elements.
+    /// assert_eq!(BlockDesigns::new(0..6, 3, 5).covering_pairs().count(), 0);
+    /// let first = BlockDesigns::new(0..6, 3, 6).covering_pairs().next().unwrap();
+    /// assert_eq!(first[..3], [vec![0, 1, 2], vec![0, 1, 3], vec![0, 4, 5]]);
+    /// assert_eq!(first[3..], [vec![1, 4, 5], vec![2, 3, 4], vec![2, 3, 5]]);
+    /// ```
+    pub fn covering_pairs(mut self) -> Self {
+        self.covering = true;
+        self
+    }
+
+    /// Returns the index into the coverage counts of the pair of the given element indices, with
+    /// `a` less than `b`.
+    fn pair_index(&self, a: usize, b: usize) -> usize {
+        a * self.elements.len() + b
+    }
+
+    /// Returns the first block, which holds the first `block_size` elements, if there are enough.
+    fn first_block(&self) -> Option<Vec<usize>> {
+        (self.block_size <= self.elements.len()).then(|| (0..self.block_size).collect())
+    }
+
+    /// Returns the block which follows the given block in lexicographic order, if any.
+    fn next_block(&self, mut block: Vec<usize>) -> Option<Vec<usize>> {
+        let n = self.elements.len();
+        let k = block.len();
+        let index = (0..k).rev().find(|i| block[*i] < n - k + i)?;
+        let start = block[index] + 1;
+        for (offset, position) in block[index..].iter_mut().enumerate() {
+            *position = start + offset;
+        }
+        Some(block)
+    }
+
+    /// Returns the number of blocks from the given block onwards in lexicographic order, or `None`
+    /// if the count does not fit in a `u128`.
+    fn blocks_from(&self, block: &[usize]) -> Option<u128> {
+        let n = self.elements.len();
+        let k = block.len();
+        let mut before = 0u128;
+        let mut start = 0;
+        for (index, position) in block.iter().enumerate() {
+            for skipped in start..*position {
+                before += combination_count(n - 1 - skipped, k - 1 - index)?;
+            }
+            start = position + 1;
+        }
+        Some(combination_count(n, k)? - before)
+    }
+
+    /// Adds one to the coverage count of every pair in the block, or subtracts one if `add` is
+    /// `false`, keeping track of the pairs which are not covered.
+    fn cover(&mut self, block: &[usize], add: bool) {
+        for (i, a) in block.iter().enumerate() {
+            for b in block[i + 1..].iter() {
+                let pair = self.pair_index(*a, *b);
+                if add {
+                    self.coverage[pair] += 1;
+                    if self.coverage[pair] == 1 {
+                        self.uncovered -= 1;
+                        self.uncovered_from[*a] -= 1;
+                    }
+                } else {
+                    self.coverage[pair] -= 1;
+                    if self.coverage[pair] == 0 {
+                        self.uncovered += 1;
+                        self.uncovered_from[*a] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the given block can never be part of a complete design extending the
+    /// current partial design, and neither can any block after it in lexicographic order.
+    fn exhausted_at(&self, block: &[usize]) -> bool {
+        let needed = (self.block_count - self.design.len()) as u128;
+        if self.blocks_from(block).is_some_and(|count| count < needed) {
+            return true;
+        }
+        if self.covering {
+            // Every later block begins at or after this one, so the pairs of an earlier element
+            // could no longer be covered.
+            let first = block.first().copied().unwrap_or(self.elements.len());
+            if self.uncovered_from[..first].iter().any(|count| *count > 0) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns `true` if the pairs which are still uncovered could be covered by the blocks which
+    /// remain to be chosen.
+    fn can_cover(&self) -> bool {
+        let remaining = self.block_count - self.design.len();
+        let per_block = self.block_size * self.block_size.saturating_sub(1) / 2;
+        !self.covering || self.uncovered <= remaining.saturating_mul(per_block)
+    }
+
+    /// Removes the last block from the design and returns it.
+    fn pop_block(&mut self) -> Option<Vec<usize>> {
+        let block = self.design.pop()?;
+        self.cover(&block, false);
+        Some(block)
+    }
+
+    /// Advances to the next complete design, returning `true` if one was found.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let mut candidate = if self.started {
+            match self.pop_block() {
+                Some(block) => self.next_block(block),
+                None => None,
+            }
+        } else {
+            self.started = true;
+            if self.design.len() == self.block_count {
+                return self.can_cover() || self.finish();
+            }
+            self.first_block()
+        };
+        loop {
+            match candidate {
+                Some(block) if self.exhausted_at(&block) => candidate = None,
+                Some(block) => {
+                    self.cover(&block, true);
+                    self.design.push(block);
+                    if !self.can_cover() {
+                        let block = self.pop_block().unwrap();
+                        candidate = self.next_block(block);
+                    } else if self.design.len() == self.block_count {
+                        return true;
+                    } else {
+                        let last = self.design.last().unwrap().clone();
+                        candidate = self.next_block(last);
+                    }
+                }
+                None => match self.pop_block() {
+                    Some(block) => candidate = self.next_block(block),
+                    None => return self.finish(),
+                },
+            }
+        }
+    }
+
+    /// Marks the iterator as done and returns `false`.
+    fn finish(&mut self) -> bool {
+        self.done = true;
+        false
+    }
+}
+
+impl<T: Clone> Iterator for BlockDesigns<T> {
+    type Item = Vec<Vec<T>>;
+
+    /// Returns the next design and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        Some(
+            self.design
+                .iter()
+                .map(|block| block.iter().map(|p| self.elements[*p].clone()).collect())
+                .collect(),
+        )
+    }
+}
+
+/// Returns a Steiner triple system on the points `0..n`: a set of triples in which every pair of
+/// distinct points appears in exactly one triple.  Such a system exists exactly when `n` is 1 or 3
+/// more than a multiple of 6, and `None` is returned for any other `n`.
+///
+/// The system is built directly, using the Bose construction when `n` is 3 more than a multiple of
+/// 6 and the Skolem construction otherwise, so it is found instantly for any `n`.  It has
+/// `n * (n - 1) / 6` triples, each in increasing order, sorted lexicographically.  For small `n`,
+/// every such system can be enumerated as a pair covering with that many blocks of size 3 using
+/// [`BlockDesigns::covering_pairs`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{steiner_triple_system, BlockDesigns};
+///
+/// let fano = steiner_triple_system(7).unwrap();
+/// assert_eq!(fano.len(), 7);
+/// assert_eq!(fano[0], [0, 1, 3]);
+///
+/// let fano: Vec<Vec<usize>> = fano.iter().map(|triple| triple.to_vec()).collect();
+/// assert!(BlockDesigns::new(0..7, 3, 7).covering_pairs().any(|design| design == fano));
+///
+/// assert_eq!(steiner_triple_system(9).map(|triples| triples.len()), Some(12));
+/// assert_eq!(steiner_triple_system(11), None);
+/// ```
+pub fn steiner_triple_system(n: usize) -> Option<Vec<[usize; 3]>> {
+    let mut triples = match n % 6 {
+        3 => bose_triples(n / 3),
+        1 => skolem_triples(n / 3),
+        _ => return None,
+    };
+    for triple in triples.iter_mut() {
+        triple.sort();
+    }
+    triples.sort();
+    Some(triples)
+}
+
+/// Returns the triples of the Bose construction of a Steiner triple system on `3 * m` points, for
+/// odd `m`, with the point `(x, i)` of `Z_m × Z_3` numbered `x + i * m`.  The construction uses
+/// the idempotent commutative quasigroup `x ∘ y = (x + y) / 2` on `Z_m`.
+fn bose_triples(m: usize) -> Vec<[usize; 3]> {
+    let point = |x: usize, i: usize| x + (i % 3) * m;
+    let product = |x: usize, y: usize| (x + y) * m.div_ceil(2) % m;
+    let mut triples: Vec<[usize; 3]> = (0..m)
+        .map(|x| [point(x, 0), point(x, 1), point(x, 2)])
+        .collect();
+    for x in 0..m {
+        for y in x + 1..m {
+            for i in 0..3 {
+                triples.push([point(x, i), point(y, i), point(product(x, y), i + 1)]);
+            }
+        }
+    }
+    triples
+}
+
+/// Returns the triples of the Skolem construction of a Steiner triple system on `3 * m + 1`
+/// points, for even `m`, with the point `(x, i)` of `Z_m × Z_3` numbered `x + i * m` and the
+/// extra point numbered `3 * m`.  The construction uses the half-idempotent commutative
+/// quasigroup on `Z_m` given by relabelling the sums `2j` and `2j + 1` as `j` and `j + m / 2`.
+fn skolem_triples(m: usize) -> Vec<[usize; 3]> {
+    let half = m / 2;
+    let point = |x: usize, i: usize| x + (i % 3) * m;
+    let product = |x: usize, y: usize| {
+        let sum = (x + y) % m;
+        sum / 2 + (sum % 2) * half
+    };
+    let mut triples: Vec<[usize; 3]> = (0..half)
+        .map(|x| [point(x, 0), point(x, 1), point(x, 2)])
+        .collect();
+    for x in half..m {
+        for i in 0..3 {
+            triples.push([point(x, i), point(x - half, i + 1), 3 * m]);
+        }
+    }
+    for x in 0..m {
+        for y in x + 1..m {
+            for i in 0..3 {
+                triples.push([point(x, i), point(y, i), point(product(x, y), i + 1)]);
+            }
+        }
+    }
+    triples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_block_designs_match_nested_combinations() {
+        for n in 0..6 {
+            for k in 0..=n + 1 {
+                for b in 0..4 {
+                    let blocks: Vec<Vec<usize>> = Combinations::of_size(0..n, k).collect();
+                    let expected: Vec<Vec<Vec<usize>>> =
+                        Combinations::of_size_positional(blocks, b).collect();
+                    let designs: Vec<Vec<Vec<usize>>> = BlockDesigns::new(0..n, k, b).collect();
+                    assert_eq!(designs, expected);
+
+                    let covers = |design: &Vec<Vec<usize>>| {
+                        Combinations::of_size(0..n, 2).all(|pair| {
+                            design
+                                .iter()
+                                .any(|block| pair.iter().all(|x| block.contains(x)))
+                        })
+                    };
+                    let expected: Vec<Vec<Vec<usize>>> =
+                        expected.into_iter().filter(covers).collect();
+                    let designs: Vec<Vec<Vec<usize>>> =
+                        BlockDesigns::new(0..n, k, b).covering_pairs().collect();
+                    assert_eq!(designs, expected, "n = {n}, k = {k}, b = {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_designs_steiner_triple_systems() {
+        for n in 0..60 {
+            let Some(triples) = steiner_triple_system(n) else {
+                assert!(n % 6 != 1 && n % 6 != 3);
+                continue;
+            };
+            assert_eq!(triples.len(), n * (n - 1) / 6);
+            let mut coverage = vec![0; n * n];
+            for [a, b, c] in triples {
+                assert!(a < b && b < c && c < n);
+                for (x, y) in [(a, b), (a, c), (b, c)] {
+                    coverage[x * n + y] += 1;
+                }
+            }
+            for x in 0..n {
+                for y in x + 1..n {
+                    assert_eq!(coverage[x * n + y], 1, "n = {n}, pair ({x}, {y})");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::sorted_unique;
+
+/// The tree of subsets of a set of elements, searched by [`maximize`], created by [`subsets_of`].
+///
+/// The root is the empty subset, and the children of a subset add one element after the last
+/// element it contains, so every subset appears exactly once, and the elements which may still be
+/// added below a subset are always the elements after its last one.  The subsets are visited
+/// depth first, in the same order as the lexicographic order of the subsets themselves.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::subsets_of;
+///
+/// let tree = subsets_of(vec![3, 1, 2, 3]);
+/// assert_eq!(tree.elements(), &[1, 2, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SubsetTree<T> {
+    elements: Vec<T>,
+}
+
+/// Returns the tree of subsets of the elements in the given iterable, to be searched by
+/// [`maximize`].  The elements are sorted and deduplicated.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{maximize, subsets_of};
+///
+/// let best = maximize(
+///     subsets_of([4, -3, 2, -1]),
+///     |subset: &[i32]| Some(subset.iter().sum::<i32>()),
+///     |chosen: &[i32], rest: &[i32]| {
+///         Some(chosen.iter().chain(rest.iter().filter(|x| **x > 0)).sum::<i32>())
+///     },
+/// );
+/// assert_eq!(best, Some((vec![2, 4], 6)));
+/// ```
+pub fn subsets_of<T: Ord + Clone>(elements: impl IntoIterator<Item = T>) -> SubsetTree<T> {
+    SubsetTree {
+        elements: sorted_unique(elements),
+    }
+}
+
+impl<T> SubsetTree<T> {
+    /// Returns the sorted, deduplicated elements whose subsets make up the tree.
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+}
+
+/// Searches the given tree of subsets by branch and bound, returning a subset with the greatest
+/// value of the objective along with that value, or `None` if the objective is `None` for every
+/// subset, meaning that none is feasible.
+///
+/// The objective is given a subset, in increasing order, and returns its value, or `None` if the
+/// subset is not feasible.  The bound is given the subset at a node of the tree and the elements
+/// which may still be added below it, and returns an upper bound on the objective of every subset
+/// which contains the given subset and is otherwise drawn from those elements, including the
+/// given subset itself, or `None` if none of them is feasible.  Whenever the bound is not greater
+/// than the best value found so far, the node and everything below it is pruned.  A tighter bound
+/// prunes more of the tree, but a bound which is ever too small may cause the best subset to be
+/// missed.
+///
+/// Among subsets with equal values, the first in lexicographic order is returned.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{maximize, subsets_of};
+///
+/// // A knapsack of capacity 10, with items given as (weight, value).
+/// let items = [(5, 10), (4, 40), (6, 30), (3, 50)];
+/// let weight = |subset: &[usize]| subset.iter().map(|i| items[*i].0).sum::<u32>();
+/// let value = |subset: &[usize]| subset.iter().map(|i| items[*i].1).sum::<u32>();
+///
+/// let best = maximize(
+///     subsets_of(0..items.len()),
+///     |subset| (weight(subset) <= 10).then(|| value(subset)),
+///     // Adding items never reduces the weight, so an overweight subset is never extended, and
+///     // the value can be no more than that of the subset with every remaining item added.
+///     |chosen, rest| (weight(chosen) <= 10).then(|| value(chosen) + value(rest)),
+/// );
+/// assert_eq!(best, Some((vec![1, 3], 90)));
+/// ```
+pub fn maximize<T, S, O, B>(tree: SubsetTree<T>, objective: O, bound: B) -> Option<(Vec<T>, S)>
+where
+    T: Clone,
+    S: PartialOrd,
+    O: FnMut(&[T]) -> Option<S>,
+    B: FnMut(&[T], &[T]) -> Option<S>,
+{
+    let mut search = Search {
+        elements: tree.elements,
+        objective,
+        bound,
+        chosen: Vec::new(),
+        best: None,
+    };
+    search.explore(0);
+    search.best
+}
+
+/// The state of a branch and bound search by [`maximize`].
+struct Search<T, S, O, B> {
+    elements: Vec<T>,
+    objective: O,
+    bound: B,
+    chosen: Vec<T>,
+    best: Option<(Vec<T>, S)>,
+}
+
+impl<T, S, O, B> Search<T, S, O, B>
+where
+    T: Clone,
+    S: PartialOrd,
+    O: FnMut(&[T]) -> Option<S>,
+    B: FnMut(&[T], &[T]) -> Option<S>,
+{
+    /// Returns `true` if the given value is greater than the best value found so far.
+    fn improves(&self, value: &S) -> bool {
+        self.best.as_ref().is_none_or(|(_, best)| value > best)
+    }
+
+    /// Searches the subtree below the chosen subset, in which the elements from `start` onwards
+    /// may still be added, unless the bound shows that it cannot improve on the best subset.
+    fn explore(&mut self, start: usize) {
+        match (self.bound)(&self.chosen, &self.elements[start..]) {
+            Some(bound) if self.improves(&bound) => {}
+            _ => return,
+        }
+        if let Some(value) = (self.objective)(&self.chosen) {
+            if self.improves(&value) {
+                self.best = Some((self.chosen.clone(), value));
+            }
+        }
+        for next in start..self.elements.len() {
+            self.chosen.push(self.elements[next].clone());
+            self.explore(next + 1);
+            self.chosen.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_branch_and_bound_matches_exhaustive_search() {
+        let items = [
+            (7, 3),
+            (2, 9),
+            (5, 5),
+            (4, 4),
+            (9, 12),
+            (1, 1),
+            (6, 8),
+            (3, 2),
+        ];
+        let weight = |subset: &[usize]| subset.iter().map(|i| items[*i].0).sum::<i32>();
+        let value = |subset: &[usize]| subset.iter().map(|i| items[*i].1).sum::<i32>();
+        for capacity in 0..=40 {
+            let mut subsets: Vec<Vec<usize>> = Combinations::all(0..items.len()).collect();
+            subsets.sort();
+            let expected = subsets
+                .into_iter()
+                .filter(|subset| weight(subset) <= capacity)
+                .map(|subset| (value(&subset), subset))
+                .reduce(|best, next| if next.0 > best.0 { next } else { best })
+                .map(|(value, subset)| (subset, value));
+            let objective = |subset: &[usize]| (weight(subset) <= capacity).then(|| value(subset));
+            let loose = maximize(subsets_of(0..items.len()), objective, |_, _| Some(i32::MAX));
+            let tight = maximize(subsets_of(0..items.len()), objective, |chosen, rest| {
+                (weight(chosen) <= capacity).then(|| value(chosen) + value(rest))
+            });
+            assert_eq!(loose, expected);
+            assert_eq!(tight, expected);
+        }
+        let never = maximize(subsets_of(0..4), |_| None::<i32>, |_, _| Some(0));
+        assert_eq!(never, None);
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// An iterator which yields the items of a generator until a shared cancellation flag is set,
+/// created by [`cancellable`].
+///
+/// The flag is checked at the start of every call to [`next`](Iterator::next), so once it is set
+/// the iterator stops at the next item, without having to reach the end of a long enumeration.
+/// Because the flag is an `Arc<AtomicBool>`, it can be set from another thread, or shared between
+/// several nested enumerations so that all of them stop together.  The flag is only read, never
+/// cleared, and the underlying generator is left where it stopped, so it can be recovered with
+/// [`Cancellable::into_inner`] to resume the enumeration later.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{cancellable, Combinations};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+///
+/// let cancel = Arc::new(AtomicBool::new(false));
+/// let mut found = Vec::new();
+/// for outer in cancellable(Combinations::of_size(0..20, 3), cancel.clone()) {
+///     for inner in cancellable(Combinations::of_size(0..20, 3), cancel.clone()) {
+///         if outer.iter().chain(&inner).sum::<usize>() == 50 {
+///             found.push((outer.clone(), inner));
+///             if found.len() == 5 {
+///                 cancel.store(true, Ordering::Relaxed);
+///             }
+///         }
+///     }
+/// }
+/// assert_eq!(found.len(), 5);
+/// ```
+pub struct Cancellable<I> {
+    generator: I,
+    flag: Arc<AtomicBool>,
+}
+
+/// Returns an iterator over the items of the given generator which stops as soon as the given flag
+/// is set.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{cancellable, Permutations};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let cancel = Arc::new(AtomicBool::new(false));
+/// let worker = {
+///     let cancel = cancel.clone();
+///     thread::spawn(move || cancellable(Permutations::of_length(0..20, 20), cancel).count())
+/// };
+/// cancel.store(true, Ordering::Relaxed);
+/// assert!(worker.join().unwrap() < 1_000_000_000);
+/// ```
+pub fn cancellable<I: IntoIterator>(
+    generator: I,
+    flag: Arc<AtomicBool>,
+) -> Cancellable<I::IntoIter> {
+    Cancellable {
+        generator: generator.into_iter(),
+        flag,
+    }
+}
+
+impl<I> Cancellable<I> {
+    /// Returns `true` if the cancellation flag has been set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::cancellable;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let cancel = Arc::new(AtomicBool::new(false));
+    /// let mut numbers = cancellable(0..10, cancel.clone());
+    /// assert_eq!(numbers.next(), Some(0));
+    /// cancel.store(true, Ordering::Relaxed);
+    /// assert!(numbers.is_cancelled());
+    /// assert_eq!(numbers.next(), None);
+    /// ```
+    pub fn is_cancelled(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    /// Consumes the wrapper and returns the underlying generator, positioned at the first item
+    /// which was not yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{cancellable, Combinations};
+    /// use std::sync::atomic::AtomicBool;
+    /// use std::sync::Arc;
+    ///
+    /// let cancel = Arc::new(AtomicBool::new(true));
+    /// let mut combos = cancellable(Combinations::of_size(0..4, 2), cancel);
+    /// assert_eq!(combos.next(), None);
+    /// assert_eq!(combos.into_inner().next(), Some(vec![0, 1]));
+    /// ```
+    pub fn into_inner(self) -> I {
+        self.generator
+    }
+}
+
+impl<I: Iterator> Iterator for Cancellable<I> {
+    type Item = I::Item;
+
+    /// Returns the next item of the generator, or `None` if the flag has been set.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_cancelled() {
+            return None;
+        }
+        self.generator.next()
+    }
+
+    /// Returns bounds on the number of remaining items.  The lower bound is zero, since the
+    /// enumeration may be cancelled at any time.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.is_cancelled() {
+            return (0, Some(0));
+        }
+        (0, self.generator.size_hint().1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_cancel_stops_and_resumes() {
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut combos = cancellable(Combinations::of_size(0..5, 2), cancel.clone());
+        assert_eq!(combos.size_hint(), (0, Some(10)));
+        assert_eq!(combos.by_ref().take(3).count(), 3);
+        cancel.store(true, Ordering::Relaxed);
+        assert_eq!(combos.size_hint(), (0, Some(0)));
+        assert_eq!(combos.next(), None);
+        assert_eq!(combos.next(), None);
+        let rest: Vec<Vec<i32>> = combos.into_inner().collect();
+        assert_eq!(rest.len(), 7);
+        assert_eq!(rest[0], vec![0, 4]);
+    }
+}
@@ -0,0 +1,203 @@
+use std::cmp::Ordering;
+
+use crate::Permutation;
+
+/// How the permutations given to [`CanonicalIterator::canonicalize_under`] act on the items of a
+/// generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SymmetryAction {
+    /// Each permutation rearranges the positions of an item, so that the item `s` is mapped to
+    /// the sequence whose entry at position `p` is the entry of `s` at position `g(p)`, as for
+    /// [`Orbits::of_sequences`](crate::Orbits::of_sequences).
+    #[default]
+    Positions,
+    /// Each permutation relabels the values of an item, replacing each value `v` by `g(v)` and
+    /// keeping the order of the entries.
+    Values,
+    /// Each permutation relabels the values of an item, as for [`SymmetryAction::Values`], and the
+    /// relabelled values are then sorted.  This suits items which are sorted sets, such as the
+    /// items of [`Combinations`](crate::Combinations).
+    SortedValues,
+}
+
+/// An iterator which yields only the items of a generator which are canonical under a list of
+/// symmetries, created by [`CanonicalIterator::canonicalize_under`].
+///
+/// An item is canonical if no symmetry maps it to a lexicographically smaller item.  This is
+/// checked on the fly by applying every symmetry to each item, so no record of previously yielded
+/// items is kept, and memory use depends only on the number of symmetries.  If the symmetries are
+/// every element of a group, such as those of a [`PermutationGroup`](crate::PermutationGroup),
+/// and the generator yields whole orbits of the group, then exactly the smallest item of each
+/// orbit is yielded.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{CanonicalIterator, Permutations, Permutation, PermutationGroup};
+/// use combinatorial::SymmetryAction;
+///
+/// // Seatings of four people around a round table, up to rotation of the table.
+/// let rotation = Permutation::from_images(vec![1, 2, 3, 0]).unwrap();
+/// let rotations = PermutationGroup::generated_by(vec![rotation]);
+/// let seatings: Vec<Vec<usize>> = Permutations::of_length(0..4, 4)
+///     .canonicalize_under(rotations.elements(), SymmetryAction::Positions)
+///     .collect();
+/// assert_eq!(seatings.len(), 6);
+/// assert!(seatings.iter().all(|seating| seating[0] == 0));
+/// ```
+pub struct CanonicalizeUnder<I> {
+    generator: I,
+    symmetries: Vec<Permutation>,
+    action: SymmetryAction,
+}
+
+impl<I> CanonicalizeUnder<I> {
+    /// Returns the symmetries under which items are canonicalized.
+    pub fn symmetries(&self) -> &[Permutation] {
+        &self.symmetries
+    }
+
+    /// Consumes the adaptor and returns the underlying generator, positioned after the last item
+    /// it produced.
+    pub fn into_inner(self) -> I {
+        self.generator
+    }
+
+    /// Returns `true` if no symmetry maps the given item to a lexicographically smaller one.
+    fn is_canonical(&self, item: &[usize]) -> bool {
+        self.symmetries.iter().all(|symmetry| {
+            let is_smaller = match self.action {
+                SymmetryAction::Positions => {
+                    let image = (0..item.len()).map(|p| {
+                        let position = symmetry.image(p);
+                        assert!(
+                            position < item.len(),
+                            "a symmetry maps position {p} beyond an item of length {}",
+                            item.len()
+                        );
+                        item[position]
+                    });
+                    image.cmp(item.iter().copied()) == Ordering::Less
+                }
+                SymmetryAction::Values => {
+                    let image = item.iter().map(|v| symmetry.image(*v));
+                    image.cmp(item.iter().copied()) == Ordering::Less
+                }
+                SymmetryAction::SortedValues => {
+                    let mut image: Vec<usize> = item.iter().map(|v| symmetry.image(*v)).collect();
+                    image.sort_unstable();
+                    image.as_slice() < item
+                }
+            };
+            !is_smaller
+        })
+    }
+}
+
+impl<I: Iterator<Item = Vec<usize>>> Iterator for CanonicalizeUnder<I> {
+    type Item = Vec<usize>;
+
+    /// Returns the next canonical item of the generator, skipping the others.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let item = self.generator.next()?;
+            if self.is_canonical(&item) {
+                return Some(item);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.generator.size_hint().1)
+    }
+}
+
+/// An extension trait, implemented for every iterator over sequences of indices, for skipping the
+/// items which are equivalent under symmetry to an item which is lexicographically smaller.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{CanonicalIterator, Combinations, Permutation, SymmetryAction};
+///
+/// // Pairs of vertices of a hexagon, up to rotation: one for each distance between them.
+/// let rotation = Permutation::from_images(vec![1, 2, 3, 4, 5, 0]).unwrap();
+/// let rotations: Vec<Permutation> = (0..6)
+///     .scan(Permutation::identity(6), |power, _| {
+///         *power = power.compose(&rotation);
+///         Some(power.clone())
+///     })
+///     .collect();
+/// let pairs: Vec<Vec<usize>> = Combinations::of_size(0..6, 2)
+///     .canonicalize_under(rotations, SymmetryAction::SortedValues)
+///     .collect();
+/// assert_eq!(pairs, vec![vec![0, 1], vec![0, 2], vec![0, 3]]);
+/// ```
+pub trait CanonicalIterator: Iterator<Item = Vec<usize>> + Sized {
+    /// Returns an iterator over only the items which no given symmetry, acting as described by
+    /// `action`, maps to a lexicographically smaller item.
+    ///
+    /// # Panics
+    ///
+    /// The returned iterator panics if `action` is [`SymmetryAction::Positions`] and a symmetry
+    /// maps a position of an item to a position beyond its length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{CanonicalIterator, Permutation, Permutations, SymmetryAction};
+    ///
+    /// // Orderings of three items, where the labels 0 and 1 are interchangeable.
+    /// let swap = Permutation::from_images(vec![1, 0]).unwrap();
+    /// let orderings: Vec<Vec<usize>> = Permutations::of_length(0..3, 3)
+    ///     .canonicalize_under(vec![swap], SymmetryAction::Values)
+    ///     .collect();
+    /// assert_eq!(orderings, vec![vec![0, 1, 2], vec![0, 2, 1], vec![2, 0, 1]]);
+    /// ```
+    fn canonicalize_under(
+        self,
+        symmetries: impl IntoIterator<Item = Permutation>,
+        action: SymmetryAction,
+    ) -> CanonicalizeUnder<Self> {
+        CanonicalizeUnder {
+            generator: self,
+            symmetries: symmetries.into_iter().collect(),
+            action,
+        }
+    }
+}
+
+impl<I: Iterator<Item = Vec<usize>>> CanonicalIterator for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Combinations, Orbits, PermutationGroup, Permutations};
+
+    fn perm(images: &[usize]) -> Permutation {
+        Permutation::from_images(images.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_canonical_matches_orbits() {
+        let group = PermutationGroup::generated_by(vec![
+            perm(&[1, 2, 3, 4, 5, 0]),
+            perm(&[5, 4, 3, 2, 1, 0]),
+        ]);
+        for size in 0..=6 {
+            let canonical: Vec<Vec<usize>> = Combinations::of_size(0..6, size)
+                .canonicalize_under(group.elements(), SymmetryAction::SortedValues)
+                .collect();
+            let orbits: Vec<Vec<usize>> = Orbits::of_subsets(&group, size).collect();
+            assert_eq!(canonical, orbits);
+        }
+        let sequences = Permutations::of_length(0..6, 6)
+            .canonicalize_under(group.elements(), SymmetryAction::Positions)
+            .count();
+        assert_eq!(sequences, 720 / 12);
+        let relabellings = Permutations::of_length(0..6, 6)
+            .canonicalize_under(group.elements(), SymmetryAction::Values)
+            .count();
+        assert_eq!(relabellings, 720 / 12);
+    }
+}
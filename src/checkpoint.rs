@@ -0,0 +1,154 @@
+use std::fs;
+use std::io::{Error, ErrorKind, Result};
+use std::path::Path;
+
+/// The bytes at the start of every checkpoint file.
+const MAGIC: &[u8; 4] = b"CMBK";
+
+/// The version of the checkpoint format, which is increased whenever the layout changes so that
+/// files written by an incompatible version are rejected rather than misread.
+const VERSION: u8 = 1;
+
+/// The generator whose progress is recorded in a checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Combinations = 0,
+    Permutations = 1,
+}
+
+/// The progress of a generator through its enumeration, which is saved to and loaded from a
+/// checkpoint file by [`Combinations::save_to`](crate::Combinations::save_to) and similar.
+///
+/// The file consists of the magic bytes, then the version, the kind of generator, and a byte of
+/// flags, followed by the number of elements, the current length, and the positions, each as a
+/// little-endian `u64`, with the positions preceded by their count.  The elements themselves are
+/// not saved, so a checkpoint must be resumed by a generator created with the same elements and
+/// options as the one which saved it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Checkpoint {
+    pub(crate) kind: Kind,
+    pub(crate) started: bool,
+    pub(crate) done: bool,
+    pub(crate) elements: usize,
+    pub(crate) length: usize,
+    pub(crate) positions: Vec<usize>,
+}
+
+/// Returns an error for a checkpoint file which cannot be resumed.
+pub(crate) fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+impl Checkpoint {
+    /// Encodes the checkpoint into its binary format.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + 3 + 8 * (3 + self.positions.len()));
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(self.kind as u8);
+        bytes.push(self.started as u8 | (self.done as u8) << 1);
+        for value in [self.elements, self.length, self.positions.len()]
+            .into_iter()
+            .chain(self.positions.iter().copied())
+        {
+            bytes.extend_from_slice(&(value as u64).to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a checkpoint from its binary format.
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (header, mut rest) = bytes
+            .split_at_checked(7)
+            .ok_or_else(|| invalid("checkpoint is truncated"))?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("not a checkpoint file"));
+        }
+        if header[4] != VERSION {
+            return Err(invalid("unsupported checkpoint version"));
+        }
+        let kind = match header[5] {
+            0 => Kind::Combinations,
+            1 => Kind::Permutations,
+            _ => return Err(invalid("unknown generator in checkpoint")),
+        };
+        let mut read = || -> Result<usize> {
+            let (value, remaining) = rest
+                .split_first_chunk::<8>()
+                .ok_or_else(|| invalid("checkpoint is truncated"))?;
+            rest = remaining;
+            usize::try_from(u64::from_le_bytes(*value))
+                .map_err(|_| invalid("checkpoint value is too large"))
+        };
+        let elements = read()?;
+        let length = read()?;
+        let count = read()?;
+        let positions = (0..count).map(|_| read()).collect::<Result<Vec<usize>>>()?;
+        if !rest.is_empty() {
+            return Err(invalid("unexpected data after checkpoint"));
+        }
+        Ok(Checkpoint {
+            kind,
+            started: header[6] & 1 != 0,
+            done: header[6] & 2 != 0,
+            elements,
+            length,
+            positions,
+        })
+    }
+
+    /// Writes the checkpoint to the file at the given path, replacing it if it exists.  The data
+    /// is written to a temporary file alongside it which is then renamed into place, so an
+    /// interrupted save never leaves a partially written checkpoint behind.
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        fs::write(&temporary, self.to_bytes())?;
+        fs::rename(&temporary, path)
+    }
+
+    /// Reads a checkpoint from the file at the given path, checking that it was saved by a
+    /// generator of the given kind over the given number of elements.
+    pub(crate) fn load(path: &Path, kind: Kind, elements: usize) -> Result<Self> {
+        let checkpoint = Checkpoint::from_bytes(&fs::read(path)?)?;
+        if checkpoint.kind != kind {
+            return Err(invalid("checkpoint was saved by a different generator"));
+        }
+        if checkpoint.elements != elements {
+            return Err(invalid(
+                "checkpoint was saved with a different number of elements",
+            ));
+        }
+        Ok(checkpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let checkpoint = Checkpoint {
+            kind: Kind::Permutations,
+            started: true,
+            done: false,
+            elements: 7,
+            length: 3,
+            positions: vec![4, 0, 6],
+        };
+        let bytes = checkpoint.to_bytes();
+        assert_eq!(Checkpoint::from_bytes(&bytes).unwrap(), checkpoint);
+        for end in 0..bytes.len() {
+            assert!(Checkpoint::from_bytes(&bytes[..end]).is_err());
+        }
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert!(Checkpoint::from_bytes(&extended).is_err());
+        let mut versioned = bytes.clone();
+        versioned[4] = VERSION + 1;
+        let error = Checkpoint::from_bytes(&versioned).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), "unsupported checkpoint version");
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{sorted_unique, Permutations};
+
+/// An iterator which generates circular permutations of a set of elements, yielding one
+/// representative of each class of arrangements which are equivalent up to rotation.
+///
+/// Each representative begins with the smallest element, followed by a permutation of the
+/// remaining elements, so there are `(n - 1)!` representatives of `n` elements.  Representatives
+/// are yielded in lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::CircularPermutations;
+///
+/// let mut seatings = CircularPermutations::new(vec!['a', 'b', 'c', 'd']);
+/// assert_eq!(seatings.next(), Some(vec!['a', 'b', 'c', 'd']));
+/// assert_eq!(seatings.next(), Some(vec!['a', 'b', 'd', 'c']));
+/// assert_eq!(seatings.next(), Some(vec!['a', 'c', 'b', 'd']));
+/// assert_eq!(seatings.count(), 3);
+///
+/// let necklaces = CircularPermutations::new(1..=4).up_to_reflection();
+/// assert_eq!(
+///     necklaces.collect::<Vec<Vec<i32>>>(),
+///     vec![vec![1, 2, 3, 4], vec![1, 2, 4, 3], vec![1, 3, 2, 4]]
+/// );
+/// ```
+#[derive(Clone)]
+pub struct CircularPermutations<T> {
+    elements: Vec<T>,
+    rest: Permutations<T>,
+    up_to_reflection: bool,
+}
+
+impl<T: Ord + Clone> CircularPermutations<T> {
+    /// Creates a new `CircularPermutations` iterator which will yield one representative of each
+    /// rotational equivalence class of arrangements of the elements in the given iterable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{factorial, CircularPermutations};
+    ///
+    /// assert_eq!(CircularPermutations::new(0..7).count(), factorial(6));
+    ///
+    /// let mut single = CircularPermutations::new(vec!["only"]);
+    /// assert_eq!(single.next(), Some(vec!["only"]));
+    /// assert_eq!(single.next(), None);
+    /// ```
+    pub fn new(elements: impl IntoIterator<Item = T>) -> Self {
+        let elements = sorted_unique(elements);
+        let rest = elements.get(1..).unwrap_or_default().to_vec();
+        let length = rest.len();
+        CircularPermutations {
+            elements,
+            rest: Permutations::of_length_unchecked(rest, length),
+            up_to_reflection: false,
+        }
+    }
+
+    /// Also treats arrangements as equivalent if one is the reverse of the other, so only one of
+    /// each mirror-image pair is yielded: the one whose second element is smaller than its last.
+    /// There are `(n - 1)! / 2` such representatives when `n` is at least 3.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{factorial, CircularPermutations};
+    ///
+    /// assert_eq!(CircularPermutations::new(0..7).up_to_reflection().count(), factorial(6) / 2);
+    /// assert_eq!(CircularPermutations::new(0..2).up_to_reflection().count(), 1);
+    /// ```
+    pub fn up_to_reflection(mut self) -> Self {
+        self.up_to_reflection = true;
+        self
+    }
+
+    /// Returns the sorted, deduplicated elements which this iterator arranges in a circle.  Every
+    /// arrangement begins with the first of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CircularPermutations;
+    ///
+    /// assert_eq!(CircularPermutations::new(vec![3, 1, 3, 2]).elements(), &[1, 2, 3]);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the arrangement which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if none remain.  Since the next arrangement is only found
+    /// when the iterator advances, this searches for it using a copy of the iterator, so it costs
+    /// about as much as a call to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CircularPermutations;
+    ///
+    /// let mut seatings = CircularPermutations::new(1..=4).up_to_reflection();
+    /// assert_eq!(seatings.next(), Some(vec![1, 2, 3, 4]));
+    /// assert_eq!(seatings.current(), Some(vec![1, 2, 4, 3]));
+    /// assert_eq!(seatings.next(), Some(vec![1, 2, 4, 3]));
+    /// ```
+    pub fn current(&self) -> Option<Vec<T>> {
+        self.clone().next()
+    }
+
+    /// Consumes the iterator and returns its elements, as given by
+    /// [`CircularPermutations::elements`], along with the indices of the elements of the next
+    /// arrangement which would have been yielded, or `None` if no arrangements remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CircularPermutations;
+    ///
+    /// let mut seatings = CircularPermutations::new(vec!['d', 'c', 'b', 'a']);
+    /// assert_eq!(seatings.next(), Some(vec!['a', 'b', 'c', 'd']));
+    /// assert_eq!(seatings.into_parts(), (vec!['a', 'b', 'c', 'd'], Some(vec![0, 1, 3, 2])));
+    /// ```
+    pub fn into_parts(mut self) -> (Vec<T>, Option<Vec<usize>>) {
+        let next = self.next().map(|arrangement| {
+            let index = |element: &T| self.elements.binary_search(element).unwrap();
+            arrangement.iter().map(index).collect()
+        });
+        (self.elements, next)
+    }
+}
+
+impl<T: Ord + Clone> Iterator for CircularPermutations<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next circular permutation and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = self.rest.next()?;
+            if self.up_to_reflection && rest.len() > 1 && rest[0] > rest[rest.len() - 1] {
+                continue;
+            }
+            return Some(
+                self.elements
+                    .first()
+                    .cloned()
+                    .into_iter()
+                    .chain(rest)
+                    .collect(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// Returns the lexicographically smallest rotation of the given arrangement, and of its
+    /// reverse as well if `reflect` is `true`.
+    fn canonical(arrangement: &[usize], reflect: bool) -> Vec<usize> {
+        let mut variants = vec![arrangement.to_vec()];
+        if reflect {
+            variants.push(arrangement.iter().rev().copied().collect());
+        }
+        variants
+            .iter()
+            .flat_map(|variant| {
+                (0..variant.len().max(1)).map(move |shift| {
+                    let mut rotated = variant.clone();
+                    rotated.rotate_left(shift);
+                    rotated
+                })
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_circular_permutations_one_per_class() {
+        for n in 0..7 {
+            for reflect in [false, true] {
+                let classes: BTreeSet<Vec<usize>> = Permutations::of_length(0..n, n)
+                    .map(|perm| canonical(&perm, reflect))
+                    .collect();
+                let mut circular = CircularPermutations::new(0..n);
+                if reflect {
+                    circular = circular.up_to_reflection();
+                }
+                let reps: Vec<Vec<usize>> =
+                    circular.map(|perm| canonical(&perm, reflect)).collect();
+                assert_eq!(reps, classes.into_iter().collect::<Vec<Vec<usize>>>());
+            }
+        }
+        let mut items = CircularPermutations::new(0..5).up_to_reflection();
+        while let Some(current) = items.current() {
+            assert_eq!(items.next(), Some(current));
+        }
+        assert_eq!(items.next(), None);
+    }
+}
@@ -0,0 +1,50 @@
+use std::marker::PhantomData;
+
+/// An iterator which yields the items of a generator collected directly into a container of type
+/// `C`, rather than into a `Vec`, created by methods such as [`Combinations::collecting`].
+///
+/// The elements of each item are collected straight from the generator's internal state, so no
+/// intermediate `Vec` is allocated for each item.
+///
+/// [`Combinations::collecting`]: crate::Combinations::collecting
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Combinations;
+///
+/// let mut words = Combinations::of_size("abc".chars(), 2).collecting::<String>();
+/// assert_eq!(words.next(), Some(String::from("ab")));
+/// assert_eq!(words.next(), Some(String::from("ac")));
+/// assert_eq!(words.next(), Some(String::from("bc")));
+/// assert_eq!(words.next(), None);
+/// ```
+pub struct Collecting<I, C> {
+    pub(crate) inner: I,
+    container: PhantomData<fn() -> C>,
+}
+
+impl<I, C> Collecting<I, C> {
+    /// Wraps the given generator so that its items are collected into containers of type `C`.
+    pub(crate) fn new(inner: I) -> Self {
+        Collecting {
+            inner,
+            container: PhantomData,
+        }
+    }
+
+    /// Returns the underlying generator, which yields `Vec`s.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let mut words = Permutations::of_length("ab".chars(), 2).collecting::<String>();
+    /// assert_eq!(words.next(), Some(String::from("ab")));
+    /// assert_eq!(words.into_inner().next(), Some(vec!['b', 'a']));
+    /// ```
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
@@ -1,7 +1,34 @@
-use std::collections::BTreeSet;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::Hash;
+use std::iter::Sum;
+use std::ops::Range;
+
+#[cfg(feature = "std")]
+use crate::checkpoint::{invalid, Checkpoint, Kind};
+use crate::collecting::Collecting;
+use crate::cross::RankedIterator;
+use crate::error::{check_size, CombinatorialError, OnOversize};
+use crate::estimate::EstimatedWork;
+use crate::shuffled::Shuffled;
+use crate::small_combinations::{Tuple, TupleCombinations};
+use crate::table::CombinatoricsTable;
+use crate::top_k::best_by_sum;
+use crate::{combination_count, log_binomial};
 
 /// An iterator which generates combinations over a set of elements.
 ///
+/// The elements are sorted and deduplicated, and each combination is yielded with its elements in
+/// increasing order.  Combinations of each size are yielded in lexicographic order of the indices
+/// of their elements, or in colexicographic order if [`Order::Colex`] is selected with
+/// [`Combinations::order`].  When generating combinations of all sizes, smaller combinations are
+/// always yielded before larger ones.
+///
+/// In positional mode, created by [`Combinations::of_size_positional`] or
+/// [`Combinations::all_positional`], the elements are instead kept exactly as given, and
+/// combinations are taken over their positions, so repeated elements are treated as distinct and
+/// each combination lists its elements in their original order.
+///
 /// # Examples
 ///
 /// ```
@@ -16,16 +43,61 @@ use std::collections::BTreeSet;
 /// let ones_and_zeros: Vec<Vec<usize>> = Combinations::all(0..2).collect();
 /// assert_eq!(ones_and_zeros, vec![Vec::new(), vec![0], vec![1], vec![0, 1]]);
 /// ```
+#[derive(Clone)]
 pub struct Combinations<T> {
     elements: Vec<T>,
     positions: Vec<usize>,
     all_sizes: bool,
+    positional: bool,
+    included: Vec<T>,
+    included_slots: Vec<usize>,
+    order: Order,
+    delta_base: Vec<usize>,
     done: bool,
+    /// Whether restrictions such as [`Combinations::must_include`] rule out every combination, so
+    /// that nothing is yielded however the positions are moved.
+    infeasible: bool,
+}
+
+/// The order in which combinations of a given size are generated.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{Combinations, Order};
+///
+/// let lex: Vec<Vec<usize>> = Combinations::of_size(0..4, 2).collect();
+/// assert_eq!(lex, vec![vec![0, 1], vec![0, 2], vec![0, 3], vec![1, 2], vec![1, 3], vec![2, 3]]);
+///
+/// let colex: Vec<Vec<usize>> = Combinations::of_size(0..4, 2).order(Order::Colex).collect();
+/// assert_eq!(colex, vec![vec![0, 1], vec![0, 2], vec![1, 2], vec![0, 3], vec![1, 3], vec![2, 3]]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Order {
+    /// Lexicographic order: combinations are compared by their smallest elements first.
+    #[default]
+    Lex,
+    /// Colexicographic order: combinations are compared by their largest elements first.  In this
+    /// order, the combinations of the first `m` elements all precede those which include any later
+    /// element, and a combination's rank does not depend on the total number of elements.
+    Colex,
 }
 
 /// Converts an iterable input into a sorted vector containing one of every unique item from the
 /// original iterable.
-fn iterable_to_sorted_set<T: Ord + Clone>(elements: impl IntoIterator<Item = T>) -> Vec<T> {
+///
+/// This is how the generators in this crate prepare their elements, so it can be used to prepare
+/// elements once for the `_unchecked` constructors, such as [`Combinations::of_size_unchecked`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::sorted_unique;
+///
+/// assert_eq!(sorted_unique(vec![3, 1, 2, 1, 3]), vec![1, 2, 3]);
+/// assert_eq!(sorted_unique("hello".chars()), vec!['e', 'h', 'l', 'o']);
+/// ```
+pub fn sorted_unique<T: Ord + Clone>(elements: impl IntoIterator<Item = T>) -> Vec<T> {
     elements
         .into_iter()
         .collect::<BTreeSet<T>>()
@@ -33,6 +105,37 @@ fn iterable_to_sorted_set<T: Ord + Clone>(elements: impl IntoIterator<Item = T>)
         .collect::<Vec<T>>()
 }
 
+/// Converts an iterable input into a vector sorted by the given comparison function, keeping only
+/// the first of each run of items which compare as equal.
+pub(crate) fn sorted_unique_by<T>(
+    elements: impl IntoIterator<Item = T>,
+    mut compare: impl FnMut(&T, &T) -> Ordering,
+) -> Vec<T> {
+    let mut elements: Vec<T> = elements.into_iter().collect();
+    elements.sort_by(&mut compare);
+    elements.dedup_by(|later, earlier| compare(earlier, later) == Ordering::Equal);
+    elements
+}
+
+/// Collects the unique items of an iterable input into a vector, in the order in which each item
+/// first appears.
+fn unique_by_hash<T: Hash + Eq + Clone>(elements: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut seen = HashSet::new();
+    elements
+        .into_iter()
+        .filter(|element| seen.insert(element.clone()))
+        .collect()
+}
+
+/// Converts an exact count of remaining items into a size hint, which is exact if the count is
+/// known and fits in a `usize`.
+pub(crate) fn size_hint_from(remaining: Option<u128>) -> (usize, Option<usize>) {
+    match remaining.and_then(|count| usize::try_from(count).ok()) {
+        Some(count) => (count, Some(count)),
+        None => (usize::MAX, None),
+    }
+}
+
 impl<T: Ord + Clone> Combinations<T> {
     /// Creates a new `Combinations` iterator which will yield all combinations of the elements in
     /// the given iterable.
@@ -54,12 +157,7 @@ impl<T: Ord + Clone> Combinations<T> {
     /// assert_eq!(combos.next(), None);
     /// ```
     pub fn all(elements: impl IntoIterator<Item = T>) -> Self {
-        Combinations {
-            elements: iterable_to_sorted_set(elements),
-            positions: Vec::new(),
-            all_sizes: true,
-            done: false,
-        }
+        Combinations::all_unchecked(sorted_unique(elements))
     }
 
     /// Creates a new `Combinations` iterator which will yield all combinations with the specified
@@ -84,190 +182,2351 @@ impl<T: Ord + Clone> Combinations<T> {
     /// assert_eq!(combos.next(), None);
     /// ```
     pub fn of_size(elements: impl IntoIterator<Item = T>, size: usize) -> Self {
-        Combinations {
-            elements: iterable_to_sorted_set(elements),
-            positions: (0..size).collect(),
-            all_sizes: false,
-            done: false,
+        Combinations::of_size_unchecked(sorted_unique(elements), size)
+    }
+
+    /// Restricts the iterator to combinations which contain all of the given elements, which are
+    /// the supersets of the given elements among the combinations.  Rather than filtering, the
+    /// required elements are removed from the underlying set up front and spliced into each
+    /// combination of the remaining elements, so the cost of iterating is proportional to the
+    /// number of combinations which are actually yielded: for `s` required elements, `n - s`
+    /// choose `k - s` rather than `n` choose `k`.  If any of the given elements is not in the set,
+    /// or there are more of them than the size of the combinations, then no combinations are
+    /// yielded.  In positional mode, every position holding one of the given elements is
+    /// required.  This should be called before iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let supersets = Combinations::of_size(0..20, 5).must_include(&[3, 7, 11]);
+    /// assert_eq!(supersets.total_count(), Some(136));
+    /// let filtered = Combinations::of_size(0..20, 5)
+    ///     .filter(|c| c.contains(&3) && c.contains(&7) && c.contains(&11));
+    /// assert!(supersets.eq(filtered));
+    ///
+    /// let mut combos = Combinations::of_size(1..6, 3).must_include(&[2, 4]);
+    /// assert_eq!(combos.next(), Some(vec![1, 2, 4]));
+    /// assert_eq!(combos.next(), Some(vec![2, 3, 4]));
+    /// assert_eq!(combos.next(), Some(vec![2, 4, 5]));
+    /// assert_eq!(combos.next(), None);
+    ///
+    /// assert_eq!(Combinations::all(1..6).must_include(&[3]).count(), 16);
+    /// assert_eq!(Combinations::of_size(1..6, 1).must_include(&[2, 4]).next(), None);
+    /// assert_eq!(Combinations::of_size(1..6, 2).must_include(&[6]).next(), None);
+    ///
+    /// let combos = Combinations::of_size_positional(vec![1, 2, 1, 3], 3).must_include(&[1]);
+    /// assert_eq!(combos.collect::<Vec<_>>(), vec![vec![1, 2, 1], vec![1, 1, 3]]);
+    /// ```
+    #[doc(alias = "supersets")]
+    pub fn must_include(mut self, elements: &[T]) -> Self {
+        for element in elements {
+            if self.position_of(element).is_none() {
+                self.mark_infeasible();
+            }
+            while let Some(index) = self.position_of(element) {
+                self.require_at(index);
+            }
         }
+        self
     }
 
-    /// Adds another position indicator to the internal positions list and resets them to point to
-    /// the first `n` indices in order.
-    fn move_to_next_set_size(&mut self) -> bool {
-        if self.positions.len() >= self.elements.len() {
-            return false;
+    /// Restricts the iterator to the combinations containing the given elements, as by
+    /// [`Combinations::must_include`], which this is another name for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let combos = Combinations::of_size(1..6, 3).containing(&[2, 4]);
+    /// assert_eq!(combos.collect::<Vec<_>>(), vec![vec![1, 2, 4], vec![2, 3, 4], vec![2, 4, 5]]);
+    /// assert_eq!(Combinations::of_size(0..20, 5).containing(&[3, 7, 11]).count(), 136);
+    /// ```
+    pub fn containing(self, elements: &[T]) -> Self {
+        self.must_include(elements)
+    }
+
+    /// Restricts the iterator to combinations which contain none of the given elements, by
+    /// removing them from the underlying set up front.  Elements which are not in the set are
+    /// ignored, but if any of them is also required by [`Combinations::must_include`], then no
+    /// combinations are yielded.  In positional mode, every position holding one of the given
+    /// elements is removed.  This should be called before iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let mut combos = Combinations::of_size(1..5, 2).must_exclude(&[2, 9]);
+    /// assert_eq!(combos.next(), Some(vec![1, 3]));
+    /// assert_eq!(combos.next(), Some(vec![1, 4]));
+    /// assert_eq!(combos.next(), Some(vec![3, 4]));
+    /// assert_eq!(combos.next(), None);
+    ///
+    /// let combos = Combinations::of_size(1..10, 4).must_include(&[1]).must_exclude(&[5, 6]);
+    /// assert_eq!(combos.count(), 20);
+    ///
+    /// let combos = Combinations::all_positional(vec![1, 2, 1]).must_exclude(&[1]);
+    /// assert_eq!(combos.collect::<Vec<_>>(), vec![vec![], vec![2]]);
+    /// ```
+    pub fn must_exclude(mut self, elements: &[T]) -> Self {
+        for element in elements {
+            if self.included.contains(element) {
+                self.mark_infeasible();
+            }
+            while let Some(index) = self.position_of(element) {
+                self.exclude_at(index);
+            }
         }
-        self.positions
-            .iter_mut()
-            .enumerate()
-            .for_each(|(index, pos)| *pos = index);
-        self.positions.push(self.positions.len());
-        true
+        self
     }
 
-    /// Increments the internal positions to correspond to the indices of the next combination of
-    /// the same size.  If the positions are successfully incremented at the current combination
-    /// set size, then returns `true`.  Otherwise, returns `false`.
-    fn move_to_next_position(&mut self) -> bool {
-        if self.elements.len() == 0 {
+    /// Restricts the iterator to combinations which begin with the given elements, by requiring
+    /// them as by [`Combinations::must_include`] and excluding every element which would come
+    /// before the last of them as by [`Combinations::must_exclude`], so only the choices of the
+    /// remaining elements are searched.  If the prefix is not the beginning of any combination,
+    /// such as when it is out of order, then no combinations are yielded.  In positional mode,
+    /// the prefix is matched to the earliest positions holding its elements.  This should be
+    /// called before iterating and before any other restrictions are added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let combos: Vec<Vec<i32>> = Combinations::of_size(1..6, 3).with_prefix(&[2]).collect();
+    /// assert_eq!(combos, vec![vec![2, 3, 4], vec![2, 3, 5], vec![2, 4, 5]]);
+    ///
+    /// let combos: Vec<Vec<i32>> = Combinations::all(1..5).with_prefix(&[1, 3]).collect();
+    /// assert_eq!(combos, vec![vec![1, 3], vec![1, 3, 4]]);
+    ///
+    /// assert_eq!(Combinations::all(1..5).with_prefix(&[3, 1]).next(), None);
+    /// ```
+    pub fn with_prefix(mut self, prefix: &[T]) -> Self {
+        let mut next = 0;
+        let mut chosen = Vec::with_capacity(prefix.len());
+        for element in prefix {
+            match self.elements[next..]
+                .iter()
+                .position(|other| other == element)
+            {
+                Some(offset) => {
+                    next += offset + 1;
+                    chosen.push(next - 1);
+                }
+                None => {
+                    self.mark_infeasible();
+                    return self;
+                }
+            }
+        }
+        // Work backwards so that removing an element does not move the ones still to be handled,
+        // and handle positions rather than values, since in positional mode a value which is
+        // skipped before the prefix ends may still be chosen after it.
+        let mut chosen = chosen.into_iter().rev().peekable();
+        for index in (0..next).rev() {
+            if chosen.next_if_eq(&index).is_some() {
+                self.require_at(index);
+            } else {
+                self.exclude_at(index);
+            }
+        }
+        self
+    }
+
+    /// Creates a new `Combinations` iterator like [`Combinations::of_size`], but returns an error
+    /// instead of an iterator which yields nothing if the given iterable is empty or the size is
+    /// larger than the number of unique elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, CombinatorialError};
+    ///
+    /// assert_eq!(Combinations::try_of_size(1..4, 2).unwrap().count(), 3);
+    /// assert_eq!(
+    ///     Combinations::try_of_size(vec![1, 1, 2], 3).err(),
+    ///     Some(CombinatorialError::SizeTooLarge { size: 3, available: 2 })
+    /// );
+    /// assert_eq!(
+    ///     Combinations::try_of_size(Vec::<i32>::new(), 0).err(),
+    ///     Some(CombinatorialError::EmptyInput)
+    /// );
+    /// ```
+    pub fn try_of_size(
+        elements: impl IntoIterator<Item = T>,
+        size: usize,
+    ) -> Result<Self, CombinatorialError> {
+        let combos = Combinations::of_size(elements, size);
+        check_size(size, combos.elements.len())?;
+        Ok(combos)
+    }
+
+    /// Creates a new `Combinations` iterator like [`Combinations::of_size`], but handles a size
+    /// larger than the number of unique elements as directed by the given [`OnOversize`] policy:
+    /// yielding nothing, yielding the single combination of every element, or returning an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, CombinatorialError, OnOversize};
+    ///
+    /// let combos = Combinations::of_size_with(vec![2, 1, 2], 3, OnOversize::Clamp).unwrap();
+    /// assert_eq!(combos.collect::<Vec<_>>(), vec![vec![1, 2]]);
+    ///
+    /// let combos = Combinations::of_size_with(1..4, 2, OnOversize::Error).unwrap();
+    /// assert_eq!(combos.count(), 3);
+    /// assert_eq!(
+    ///     Combinations::of_size_with(1..4, 4, OnOversize::Error).err(),
+    ///     Some(CombinatorialError::SizeTooLarge { size: 4, available: 3 })
+    /// );
+    /// ```
+    pub fn of_size_with(
+        elements: impl IntoIterator<Item = T>,
+        size: usize,
+        on_oversize: OnOversize,
+    ) -> Result<Self, CombinatorialError> {
+        let elements = sorted_unique(elements);
+        let available = elements.len();
+        let size = on_oversize.resolve(size, available, available)?;
+        Ok(Combinations::of_size_unchecked(elements, size))
+    }
+
+    /// Creates a new `TupleCombinations` iterator which will yield all combinations of the
+    /// elements in the given iterable as tuples, with the size of each combination given by the
+    /// number of fields in the tuple type, from 1 to 12.  The tuple type can usually be inferred
+    /// from how the combinations are used, such as by destructuring them in a `for` loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let mut pairs = Combinations::tuples::<(char, char)>("abc".chars());
+    /// assert_eq!(pairs.next(), Some(('a', 'b')));
+    /// assert_eq!(pairs.next(), Some(('a', 'c')));
+    /// assert_eq!(pairs.next(), Some(('b', 'c')));
+    /// assert_eq!(pairs.next(), None);
+    ///
+    /// let products: Vec<i32> = Combinations::tuples(1..5).map(|(a, b, c)| a * b * c).collect();
+    /// assert_eq!(products, vec![6, 8, 12, 24]);
+    /// ```
+    pub fn tuples<U: Tuple<Item = T>>(
+        elements: impl IntoIterator<Item = T>,
+    ) -> TupleCombinations<U> {
+        TupleCombinations::new(sorted_unique(elements))
+    }
+
+    /// Returns the index of the given element within the sorted, deduplicated elements over which
+    /// this iterator generates combinations, or `None` if the element is not one of them.  In
+    /// positional mode, returns the index of the first occurrence of the element in the original
+    /// sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let combos = Combinations::of_size(vec!['c', 'a', 'b', 'a'], 2);
+    /// assert_eq!(combos.position_of(&'a'), Some(0));
+    /// assert_eq!(combos.position_of(&'c'), Some(2));
+    /// assert_eq!(combos.position_of(&'d'), None);
+    ///
+    /// let combos = Combinations::of_size_positional(vec!['c', 'a', 'b', 'a'], 2);
+    /// assert_eq!(combos.position_of(&'a'), Some(1));
+    /// ```
+    pub fn position_of(&self, element: &T) -> Option<usize> {
+        if self.positional {
+            return self.elements.iter().position(|other| other == element);
+        }
+        self.elements.binary_search(element).ok()
+    }
+
+    /// Returns `true` if the given candidate is a combination which this iterator could yield,
+    /// that is, if it has a valid size and consists of distinct elements from the underlying set
+    /// in increasing order.  In positional mode, the candidate must instead be a subsequence of
+    /// the original elements.  This does not depend on how far the iterator has advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let combos = Combinations::of_size_positional(vec![3, 1, 3], 2);
+    /// assert!(combos.is_valid_combination(&[3, 3]));
+    /// assert!(combos.is_valid_combination(&[1, 3]));
+    /// assert!(!combos.is_valid_combination(&[1, 1]));
+    ///
+    /// let combos = Combinations::of_size(1..5, 2);
+    /// assert!(combos.is_valid_combination(&[1, 3]));
+    /// assert!(!combos.is_valid_combination(&[3, 1]));
+    /// assert!(!combos.is_valid_combination(&[1, 1]));
+    /// assert!(!combos.is_valid_combination(&[1, 5]));
+    /// assert!(!combos.is_valid_combination(&[1, 2, 3]));
+    ///
+    /// let combos = Combinations::all(1..5);
+    /// assert!(combos.is_valid_combination(&[]));
+    /// assert!(combos.is_valid_combination(&[1, 2, 3]));
+    /// ```
+    pub fn is_valid_combination(&self, candidate: &[T]) -> bool {
+        if !self.positional && !candidate.windows(2).all(|pair| pair[0] < pair[1]) {
             return false;
         }
-        let length = self.positions.len();
-        for index in (0..self.positions.len()).rev() {
-            let cur_position = *self.positions.get(index).unwrap();
-            if cur_position >= self.elements.len() - 1 {
+        // Walk the required elements alongside the candidate, and check each of the others
+        // against the remaining elements.
+        let mut required = self.included.iter().peekable();
+        let mut remaining = self.elements.iter();
+        let mut size = 0;
+        for element in candidate {
+            if required.next_if_eq(&element).is_some() {
                 continue;
             }
-            if index == length - 1 || cur_position < self.positions.get(index + 1).unwrap() - 1 {
-                let mut next_position = cur_position + 1;
-                *self.positions.get_mut(index).unwrap() = next_position;
-                for i in index + 1..length {
-                    next_position += 1;
-                    *self.positions.get_mut(i).unwrap() = next_position;
-                }
-                return true;
+            let found = if self.positional {
+                remaining.any(|other| other == element)
+            } else {
+                self.elements.binary_search(element).is_ok()
+            };
+            if !found {
+                return false;
             }
+            size += 1;
         }
-        false
+        let valid_size = if self.all_sizes {
+            size <= self.elements.len()
+        } else {
+            size == self.positions.len()
+        };
+        valid_size && required.peek().is_none()
     }
 
-    /// Returns the current combination, if one exists and is valid.
-    fn get_current_combination(&mut self) -> Option<Vec<T>> {
-        if self.done || self.positions.len() > self.elements.len() {
-            return None;
+    /// Moves the iterator to the given combination, so that iteration begins there rather than at
+    /// the first combination, and continues in the selected order from there.  This allows an
+    /// enumeration to be resumed from a combination recorded elsewhere, or split between workers
+    /// at chosen combinations rather than at ranks.  This should be called before iterating.  If
+    /// the restrictions rule out every combination, the iterator stays empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given combination is not one which this iterator yields, as checked by
+    /// [`Combinations::is_valid_combination`], such as one which does not begin with the prefix
+    /// given to [`Combinations::with_prefix`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, Order};
+    ///
+    /// let mut combos = Combinations::of_size(1..6, 3).starting_from(&[2, 4, 5]);
+    /// assert_eq!(combos.next(), Some(vec![2, 4, 5]));
+    /// assert_eq!(combos.next(), Some(vec![3, 4, 5]));
+    /// assert_eq!(combos.next(), None);
+    ///
+    /// let combos = Combinations::of_size(1..6, 3).order(Order::Colex).starting_from(&[2, 4, 5]);
+    /// assert_eq!(combos.collect::<Vec<_>>(), vec![vec![2, 4, 5], vec![3, 4, 5]]);
+    ///
+    /// let combos = Combinations::all(1..6).starting_from(&[3, 5]);
+    /// assert_eq!(combos.count(), 2 + 10 + 5 + 1);
+    /// ```
+    pub fn starting_from(mut self, start: &[T]) -> Self {
+        if self.infeasible {
+            return self;
         }
-        Some(
-            self.positions
+        assert!(
+            self.is_valid_combination(start),
+            "the starting combination must be one which the iterator yields"
+        );
+        // Skip the required elements, and match the others to the earliest positions which
+        // follow the previous ones, as in positional mode the same element may appear twice.
+        let mut required = self.included.iter().peekable();
+        let mut next = 0;
+        let mut positions = Vec::with_capacity(start.len());
+        for element in start {
+            if required.next_if_eq(&element).is_some() {
+                continue;
+            }
+            let offset = self.elements[next..]
                 .iter()
-                .map(|p| self.elements.get(*p).unwrap().clone())
-                .collect::<Vec<T>>(),
-        )
+                .position(|other| other == element);
+            next += offset.unwrap() + 1;
+            positions.push(next - 1);
+        }
+        self.positions = positions;
+        self
     }
 }
 
-impl<T: Ord + Clone> Iterator for Combinations<T> {
-    type Item = Vec<T>;
-
-    /// Returns the next combination and advances the internal iterator.
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.done {
-            return None;
+impl<T: Clone> Combinations<T> {
+    /// Creates a new `Combinations` iterator which will yield all combinations of the given
+    /// elements, which are used exactly as given rather than being sorted and deduplicated first.
+    ///
+    /// The combinations are generated over the positions of the elements, so this is equivalent
+    /// to [`Combinations::all`] if the elements are sorted and unique, but skips the work of
+    /// checking.  Otherwise, each combination lists its elements in their original order, and
+    /// repeated elements are treated as distinct, but [`Combinations::position_of`] and
+    /// [`Combinations::is_valid_combination`], which rely on the elements being sorted, may give
+    /// incorrect results.  Use [`Combinations::all_positional`] to work with elements which are
+    /// deliberately unsorted or repeated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{sorted_unique, Combinations};
+    ///
+    /// let elements = sorted_unique(vec![3, 1, 2]);
+    /// let combos: Vec<Vec<i32>> = Combinations::all_unchecked(elements).collect();
+    /// assert_eq!(combos, Combinations::all(vec![3, 1, 2]).collect::<Vec<_>>());
+    ///
+    /// let combos: Vec<Vec<i32>> = Combinations::all_unchecked(vec![2, 1]).collect();
+    /// assert_eq!(combos, vec![vec![], vec![2], vec![1], vec![2, 1]]);
+    /// ```
+    pub fn all_unchecked(elements: Vec<T>) -> Self {
+        Combinations {
+            elements,
+            positions: Vec::new(),
+            all_sizes: true,
+            positional: false,
+            included: Vec::new(),
+            included_slots: Vec::new(),
+            order: Order::Lex,
+            delta_base: Vec::new(),
+            done: false,
+            infeasible: false,
         }
-        let combo = self.get_current_combination();
-        if self.move_to_next_position() == false {
-            if self.all_sizes == false || self.move_to_next_set_size() == false {
-                self.done = true;
-            }
+    }
+
+    /// Creates a new `Combinations` iterator which will yield all combinations with the specified
+    /// size of the given elements, which are used exactly as given rather than being sorted and
+    /// deduplicated first.  See [`Combinations::all_unchecked`] for the consequences if the
+    /// elements are not sorted and unique.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let combos: Vec<Vec<i32>> = Combinations::of_size_unchecked(vec![1, 2, 3], 2).collect();
+    /// assert_eq!(combos, vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+    /// ```
+    pub fn of_size_unchecked(elements: Vec<T>, size: usize) -> Self {
+        Combinations {
+            elements,
+            positions: (0..size).collect(),
+            all_sizes: false,
+            positional: false,
+            included: Vec::new(),
+            included_slots: Vec::new(),
+            order: Order::Lex,
+            delta_base: Vec::new(),
+            done: false,
+            infeasible: false,
         }
-        combo
     }
-}
 
-/// An iterator which generates combinations over a set of elements, with replacement.
-///
-/// # Examples
-///
-/// ```
-/// use combinatorial::CombinationsWithReplacement;
-///
-/// let mut abc_tuples = CombinationsWithReplacement::of_size(vec!['a', 'b', 'c'], 2);
-/// assert_eq!(abc_tuples.next(), Some(vec!['a', 'a']));
-/// assert_eq!(abc_tuples.next(), Some(vec!['a', 'b']));
-/// assert_eq!(abc_tuples.next(), Some(vec!['a', 'c']));
-/// assert_eq!(abc_tuples.next(), Some(vec!['b', 'b']));
-/// assert_eq!(abc_tuples.next(), Some(vec!['b', 'c']));
-/// assert_eq!(abc_tuples.next(), Some(vec!['c', 'c']));
-/// assert_eq!(abc_tuples.next(), None);
-///
-/// let mut ones_and_zeros = CombinationsWithReplacement::all(0..2);
-/// assert_eq!(ones_and_zeros.next(), Some(Vec::new()));
-/// assert_eq!(ones_and_zeros.next(), Some(vec![0]));
-/// assert_eq!(ones_and_zeros.next(), Some(vec![1]));
-/// assert_eq!(ones_and_zeros.next(), Some(vec![0, 0]));
-/// assert_eq!(ones_and_zeros.next(), Some(vec![0, 1]));
-/// assert_eq!(ones_and_zeros.next(), Some(vec![1, 1]));
-/// assert_eq!(ones_and_zeros.next(), None);
-/// ```
-pub struct CombinationsWithReplacement<T> {
-    elements: Vec<T>,
-    positions: Vec<usize>,
-    all_sizes: bool,
-    done: bool,
-}
+    /// Creates a new `Combinations` iterator in positional mode, which will yield all
+    /// combinations of the given elements taken by position, keeping the elements in their
+    /// original order and treating repeated elements as distinct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let combos: Vec<Vec<i32>> = Combinations::all_positional(vec![2, 1, 2]).collect();
+    /// assert_eq!(
+    ///     combos,
+    ///     vec![
+    ///         vec![],
+    ///         vec![2],
+    ///         vec![1],
+    ///         vec![2],
+    ///         vec![2, 1],
+    ///         vec![2, 2],
+    ///         vec![1, 2],
+    ///         vec![2, 1, 2],
+    ///     ]
+    /// );
+    /// ```
+    pub fn all_positional(elements: impl IntoIterator<Item = T>) -> Self {
+        let mut combos = Combinations::all_unchecked(elements.into_iter().collect());
+        combos.positional = true;
+        combos
+    }
+
+    /// Creates a new `Combinations` iterator in positional mode, which will yield all
+    /// combinations with the specified size of the given elements taken by position, keeping the
+    /// elements in their original order and treating repeated elements as distinct.  This matches
+    /// the behavior of `itertools.combinations` in Python.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let mut combos = Combinations::of_size_positional(vec![1, 1, 2], 2);
+    /// assert_eq!(combos.next(), Some(vec![1, 1]));
+    /// assert_eq!(combos.next(), Some(vec![1, 2]));
+    /// assert_eq!(combos.next(), Some(vec![1, 2]));
+    /// assert_eq!(combos.next(), None);
+    ///
+    /// // By contrast, the elements are usually deduplicated first.
+    /// assert_eq!(Combinations::of_size(vec![1, 1, 2], 2).count(), 1);
+    /// ```
+    pub fn of_size_positional(elements: impl IntoIterator<Item = T>, size: usize) -> Self {
+        let mut combos = Combinations::of_size_unchecked(elements.into_iter().collect(), size);
+        combos.positional = true;
+        combos
+    }
+
+    /// Creates a new `Combinations` iterator which will yield all combinations of the elements in
+    /// the given iterable, sorted and deduplicated using the given comparison function instead of
+    /// `Ord`, so that combinations are yielded in lexicographic order with respect to it.
+    ///
+    /// Elements which compare as equal are deduplicated, keeping the first of them in the input.
+    /// The sorted elements are used in positional mode, as by [`Combinations::all_positional`],
+    /// so each combination lists its elements in the custom order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let combos: Vec<Vec<i32>> = Combinations::all_by(1..4, |a, b| b.cmp(a)).collect();
+    /// assert_eq!(combos[..5], [vec![], vec![3], vec![2], vec![1], vec![3, 2]]);
+    /// ```
+    pub fn all_by(
+        elements: impl IntoIterator<Item = T>,
+        compare: impl FnMut(&T, &T) -> Ordering,
+    ) -> Self {
+        Combinations::all_positional(sorted_unique_by(elements, compare))
+    }
+
+    /// Creates a new `Combinations` iterator which will yield all combinations with the specified
+    /// size of the elements in the given iterable, sorted and deduplicated using the given
+    /// comparison function instead of `Ord`.  See [`Combinations::all_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let words = vec!["b", "A", "a", "C"];
+    /// let combos = Combinations::of_size_by(words, 2, |a, b| {
+    ///     a.to_lowercase().cmp(&b.to_lowercase())
+    /// });
+    /// assert_eq!(
+    ///     combos.collect::<Vec<Vec<&str>>>(),
+    ///     vec![vec!["A", "b"], vec!["A", "C"], vec!["b", "C"]]
+    /// );
+    /// ```
+    pub fn of_size_by(
+        elements: impl IntoIterator<Item = T>,
+        size: usize,
+        compare: impl FnMut(&T, &T) -> Ordering,
+    ) -> Self {
+        Combinations::of_size_positional(sorted_unique_by(elements, compare), size)
+    }
+
+    /// Creates a new `Combinations` iterator which will yield all combinations of the elements in
+    /// the given iterable, sorted and deduplicated by the keys extracted by the given function,
+    /// like [`slice::sort_by_key`].  Elements with equal keys are deduplicated, keeping the first
+    /// of them in the input.  See [`Combinations::all_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let people = vec![("ann", 31), ("bob", 27), ("cat", 45)];
+    /// let mut combos = Combinations::all_by_key(people, |person| person.1);
+    /// assert_eq!(combos.next(), Some(vec![]));
+    /// assert_eq!(combos.next(), Some(vec![("bob", 27)]));
+    /// assert_eq!(combos.next(), Some(vec![("ann", 31)]));
+    /// ```
+    pub fn all_by_key<K: Ord>(
+        elements: impl IntoIterator<Item = T>,
+        mut key: impl FnMut(&T) -> K,
+    ) -> Self {
+        Combinations::all_by(elements, |a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Creates a new `Combinations` iterator which will yield all combinations with the specified
+    /// size of the elements in the given iterable, sorted and deduplicated by the keys extracted
+    /// by the given function.  See [`Combinations::all_by_key`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let people = vec![("ann", 31), ("bob", 27), ("cat", 45), ("dan", 27)];
+    /// let combos: Vec<Vec<&str>> = Combinations::of_size_by_key(people, 2, |person| person.1)
+    ///     .map(|pair| pair.iter().map(|person| person.0).collect())
+    ///     .collect();
+    /// assert_eq!(combos, vec![vec!["bob", "ann"], vec!["bob", "cat"], vec!["ann", "cat"]]);
+    /// ```
+    pub fn of_size_by_key<K: Ord>(
+        elements: impl IntoIterator<Item = T>,
+        size: usize,
+        mut key: impl FnMut(&T) -> K,
+    ) -> Self {
+        Combinations::of_size_by(elements, size, |a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Sets the order in which combinations of each size are generated.  This should be called
+    /// before iterating, since the first combination is the same in every order but later ones
+    /// are not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, Order};
+    ///
+    /// let mut combos = Combinations::all(vec!['a', 'b', 'c']).order(Order::Colex);
+    /// assert_eq!(combos.next(), Some(vec![]));
+    /// assert_eq!(combos.next(), Some(vec!['a']));
+    /// assert_eq!(combos.next(), Some(vec!['b']));
+    /// assert_eq!(combos.next(), Some(vec!['c']));
+    /// assert_eq!(combos.next(), Some(vec!['a', 'b']));
+    /// assert_eq!(combos.next(), Some(vec!['a', 'c']));
+    /// assert_eq!(combos.next(), Some(vec!['b', 'c']));
+    /// assert_eq!(combos.next(), Some(vec!['a', 'b', 'c']));
+    /// assert_eq!(combos.next(), None);
+    /// ```
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Returns the elements over which this iterator generates combinations: the sorted,
+    /// deduplicated elements, or the original sequence in positional mode, without any elements
+    /// which were removed by [`Combinations::must_include`] or [`Combinations::must_exclude`].
+    /// The indices of the elements in this slice are those returned by
+    /// [`Combinations::position_of`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let combos = Combinations::of_size(vec!['c', 'a', 'b', 'a'], 2);
+    /// assert_eq!(combos.elements(), &['a', 'b', 'c']);
+    /// assert_eq!(combos.must_exclude(&['b']).elements(), &['a', 'c']);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the combination which the next call to [`next`](Iterator::next) will yield,
+    /// without advancing the iterator, or `None` if no combinations remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let mut combos = Combinations::of_size(1..4, 2);
+    /// assert_eq!(combos.current(), Some(vec![1, 2]));
+    /// assert_eq!(combos.next(), Some(vec![1, 2]));
+    /// assert_eq!(combos.current(), Some(vec![1, 3]));
+    /// ```
+    pub fn current(&self) -> Option<Vec<T>> {
+        self.get_current_combination()
+    }
+
+    /// Returns the combination with the given rank, counting from zero, among every combination
+    /// which the iterator yields from the beginning in colexicographic order, or `None` if the
+    /// rank is not less than [`Combinations::total_count`].  When generating combinations of all
+    /// sizes, smaller combinations are counted first.  This does not depend on how far the
+    /// iterator has advanced, or on the order selected for iteration.
+    ///
+    /// In colexicographic order, the rank of a combination with positions `c_1 < c_2 < ... < c_k`
+    /// is the sum of the binomial coefficients `c_i` choose `i`, which does not depend on the
+    /// number of elements.  The positions are recovered by a single pass down through the
+    /// elements, updating one binomial coefficient with a multiplication and a division at each
+    /// step, so this is the fastest way to access combinations at random, even if they are
+    /// iterated in lexicographic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, Order};
+    ///
+    /// let combos = Combinations::of_size(0..5, 3);
+    /// assert_eq!(combos.unrank_colex(0), Some(vec![0, 1, 2]));
+    /// assert_eq!(combos.unrank_colex(1), Some(vec![0, 1, 3]));
+    /// assert_eq!(combos.unrank_colex(3), Some(vec![1, 2, 3]));
+    /// assert_eq!(combos.unrank_colex(9), Some(vec![2, 3, 4]));
+    /// assert_eq!(combos.unrank_colex(10), None);
+    ///
+    /// let colex = Combinations::of_size(0..5, 3).order(Order::Colex);
+    /// for (rank, combo) in colex.enumerate() {
+    ///     assert_eq!(combos.unrank_colex(rank as u128), Some(combo));
+    /// }
+    ///
+    /// let big = Combinations::of_size(0..100, 50);
+    /// let last = big.unrank_colex(big.total_count().unwrap() - 1).unwrap();
+    /// assert_eq!(last, (50..100).collect::<Vec<_>>());
+    /// ```
+    pub fn unrank_colex(&self, mut rank: u128) -> Option<Vec<T>> {
+        if self.infeasible {
+            return None;
+        }
+        let n = self.elements.len();
+        let mut k = self.positions.len();
+        if self.all_sizes {
+            k = 0;
+            while rank >= combination_count(n, k)? {
+                rank -= combination_count(n, k)?;
+                k += 1;
+                if k > n {
+                    return None;
+                }
+            }
+        } else if rank >= combination_count(n, k)? {
+            return None;
+        }
+        let mut positions = vec![0; k];
+        // The count of combinations of size `i` of the first `c` elements, for the current `c`.
+        let mut count = combination_count(n, k)?;
+        let mut c = n;
+        for i in (1..=k).rev() {
+            // Step down to the largest `c` for which `count`, which is `c` choose `i`, is at most
+            // the rank, using `(c - 1) choose i = (c choose i) * (c - i) / c`.
+            loop {
+                count = match count.checked_mul((c - i) as u128) {
+                    Some(product) => product / c as u128,
+                    None => combination_count(c - 1, i)?,
+                };
+                c -= 1;
+                if count <= rank {
+                    break;
+                }
+            }
+            positions[i - 1] = c;
+            rank -= count;
+            // Continue the search for the next position from `c choose (i - 1)`, which is
+            // `(c choose i) * i / (c - i + 1)`.
+            count = match count.checked_mul(i as u128) {
+                Some(product) if c + 1 > i => product / (c + 1 - i) as u128,
+                _ => combination_count(c, i - 1)?,
+            };
+        }
+        Some(self.combination_at(&positions))
+    }
+
+    /// Returns the combination with the given rank, as by [`Combinations::unrank_colex`], but
+    /// looks up every binomial coefficient in the given table instead of computing it.  This
+    /// suits unranking many combinations, such as when sampling at random, with a table shared
+    /// between the calls.  If the table is smaller than the number of elements, this falls back
+    /// to [`Combinations::unrank_colex`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, CombinatoricsTable};
+    ///
+    /// let table = CombinatoricsTable::new(100);
+    /// let combos = Combinations::of_size(0..100, 50);
+    /// for rank in [0, 12345, 1 << 90, combos.total_count().unwrap() - 1] {
+    ///     assert_eq!(combos.unrank_colex_with(rank, &table), combos.unrank_colex(rank));
+    /// }
+    /// ```
+    pub fn unrank_colex_with(
+        &self,
+        mut rank: u128,
+        table: &CombinatoricsTable<u128>,
+    ) -> Option<Vec<T>> {
+        let n = self.elements.len();
+        if self.infeasible || table.size() < n {
+            return self.unrank_colex(rank);
+        }
+        let mut k = self.positions.len();
+        if self.all_sizes {
+            k = 0;
+            while rank >= table.binomial(n, k)? {
+                rank -= table.binomial(n, k)?;
+                k += 1;
+                if k > n {
+                    return None;
+                }
+            }
+        } else if rank >= table.binomial(n, k)? {
+            return None;
+        }
+        let mut positions = vec![0; k];
+        let mut c = n;
+        for i in (1..=k).rev() {
+            // Step down to the largest `c` for which `c` choose `i` is at most the rank.  Counts
+            // which do not fit in a `u128` are larger than any rank.
+            let count = loop {
+                c -= 1;
+                match table.binomial(c, i) {
+                    Some(count) if count <= rank => break count,
+                    _ => {}
+                }
+            };
+            positions[i - 1] = c;
+            rank -= count;
+        }
+        Some(self.combination_at(&positions))
+    }
+
+    /// Consumes the iterator and returns its elements, as given by [`Combinations::elements`],
+    /// along with the indices of the elements of the next combination which would have been
+    /// yielded, or `None` if no combinations remain.  This recovers the prepared elements without
+    /// having to sort and deduplicate them again.  Any elements required by
+    /// [`Combinations::must_include`] are not part of the returned elements or indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let mut combos = Combinations::of_size(vec!['d', 'b', 'a', 'c'], 2);
+    /// assert_eq!(combos.next(), Some(vec!['a', 'b']));
+    /// let (elements, next) = combos.into_parts();
+    /// assert_eq!(elements, vec!['a', 'b', 'c', 'd']);
+    /// assert_eq!(next, Some(vec![0, 2]));
+    ///
+    /// let mut combos = Combinations::of_size(1..3, 2);
+    /// assert_eq!(combos.next(), Some(vec![1, 2]));
+    /// assert_eq!(combos.into_parts(), (vec![1, 2], None));
+    /// ```
+    pub fn into_parts(self) -> (Vec<T>, Option<Vec<usize>>) {
+        let remaining = !self.done && self.positions.len() <= self.elements.len();
+        (self.elements, remaining.then_some(self.positions))
+    }
+
+    /// Returns the total number of combinations which this iterator yields from the beginning,
+    /// regardless of how far it has advanced, or `None` if the count does not fit in a `u128`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// assert_eq!(Combinations::of_size(0..10, 3).total_count(), Some(120));
+    /// assert_eq!(Combinations::all(0..10).total_count(), Some(1024));
+    /// assert_eq!(Combinations::of_size(0..10, 11).total_count(), Some(0));
+    /// assert_eq!(Combinations::of_size(0..10, 3).must_include(&[20]).total_count(), Some(0));
+    /// assert_eq!(Combinations::all(0..128).total_count(), None);
+    /// ```
+    pub fn total_count(&self) -> Option<u128> {
+        if self.infeasible {
+            return Some(0);
+        }
+        let n = self.elements.len();
+        if self.all_sizes {
+            (0..=n).try_fold(0u128, |total, k| {
+                total.checked_add(combination_count(n, k)?)
+            })
+        } else {
+            combination_count(n, self.positions.len())
+        }
+    }
+
+    /// Returns an estimate of the work needed to enumerate every combination which this iterator
+    /// yields from the beginning, from which the time the enumeration would take can be checked
+    /// before starting it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    /// use std::time::Duration;
+    ///
+    /// let work = Combinations::all(0..200u8).estimated_work();
+    /// assert_eq!(work.items(), None);
+    /// assert!((work.log_items() - 200.0 * 2f64.ln()).abs() < 1e-9);
+    /// assert!(!work.is_feasible(Duration::from_secs(86400), 1e9));
+    /// ```
+    pub fn estimated_work(&self) -> EstimatedWork {
+        let n = self.elements.len();
+        let (log_items, length) = if self.all_sizes {
+            (n as f64 * std::f64::consts::LN_2, n)
+        } else {
+            (log_binomial(n, self.positions.len()), self.positions.len())
+        };
+        EstimatedWork::of_vecs::<T>(self.total_count(), log_items, length + self.included.len())
+    }
+
+    /// Returns the total number of combinations which this iterator yields from the beginning, as
+    /// by [`Combinations::total_count`], but as a big integer, so the count is exact however large
+    /// it is.  This is only available with the `bigint` feature.
+    ///
+    /// The `total_count_big` methods return the count directly for generators whose count is
+    /// always known, as here, and an `Option` only for generators such as
+    /// [`CombinationsWithReplacement`] and [`Permutations`](crate::Permutations), whose count may
+    /// be infinite or unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    /// use num_bigint::BigUint;
+    ///
+    /// assert_eq!(Combinations::of_size(0..10, 3).total_count_big(), BigUint::from(120u8));
+    /// assert_eq!(Combinations::all(0..128).total_count_big(), BigUint::from(2u8).pow(128));
+    /// ```
+    #[cfg(feature = "bigint")]
+    pub fn total_count_big(&self) -> num_bigint::BigUint {
+        let n = self.elements.len();
+        if self.infeasible {
+            num_bigint::BigUint::default()
+        } else if self.all_sizes {
+            num_bigint::BigUint::from(2u8).pow(n as u32)
+        } else {
+            crate::binomial_big(n, self.positions.len())
+        }
+    }
+
+    /// Returns the number of combinations which have not yet been yielded, or `None` if the count
+    /// does not fit in a `u128`.
+    fn remaining_count(&self) -> Option<u128> {
+        let n = self.elements.len();
+        let k = self.positions.len();
+        if self.done || self.infeasible || k > n {
+            return Some(0);
+        }
+        let mut before = 0;
+        match self.order {
+            Order::Lex => {
+                let mut start = 0;
+                for (index, position) in self.positions.iter().enumerate() {
+                    for skipped in start..*position {
+                        before += combination_count(n - 1 - skipped, k - 1 - index)?;
+                    }
+                    start = position + 1;
+                }
+            }
+            Order::Colex => {
+                for (index, position) in self.positions.iter().enumerate() {
+                    before += combination_count(*position, index + 1)?;
+                }
+            }
+        }
+        let mut remaining = combination_count(n, k)? - before;
+        if self.all_sizes {
+            for size in k + 1..=n {
+                remaining = remaining.checked_add(combination_count(n, size)?)?;
+            }
+        }
+        Some(remaining)
+    }
+
+    /// Calls the given closure on each remaining combination, passing it a slice of a single
+    /// buffer which is reused for every combination.  Only the elements which differ from the
+    /// previous combination are cloned into the buffer, so this is faster than iterating when the
+    /// combinations only need to be inspected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let mut sums = Vec::new();
+    /// Combinations::of_size(1..5, 2).for_each_ref(|combo| sums.push(combo.iter().sum::<i32>()));
+    /// assert_eq!(sums, vec![3, 4, 5, 5, 6, 7]);
+    /// ```
+    pub fn for_each_ref(mut self, mut f: impl FnMut(&[T])) {
+        if !self.included.is_empty() {
+            return self.for_each(|combo| f(&combo));
+        }
+        let mut buffer: Vec<T> = Vec::with_capacity(self.elements.len());
+        while !self.done {
+            if self.positions.len() <= self.elements.len() {
+                buffer.clear();
+                buffer.extend(self.positions.iter().map(|p| self.elements[*p].clone()));
+                f(&buffer);
+                while let Some(changed) = self.step_position() {
+                    for index in changed {
+                        buffer[index] = self.elements[self.positions[index]].clone();
+                    }
+                    f(&buffer);
+                }
+            }
+            if !self.all_sizes || !self.move_to_next_set_size() {
+                self.done = true;
+            }
+        }
+    }
+
+    /// Writes as many of the remaining combinations as fit into the given buffer, one after
+    /// another in row-major order, advancing the iterator past them, and returns the number of
+    /// combinations written.  Each combination occupies as many consecutive slots as it has
+    /// elements, and any slots after the last complete combination are left untouched.
+    ///
+    /// When generating combinations of all sizes, a single call only writes combinations of one
+    /// size, so that the buffer always holds rows of equal width; the next call continues with
+    /// the next size.  This suits handing combinations to consumers such as GPU kernels or array
+    /// libraries which expect a flat matrix, without any allocation per combination.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let mut combos = Combinations::of_size(0..5, 3);
+    /// let mut buffer = [0; 13];
+    /// assert_eq!(combos.fill_flat(&mut buffer), 4);
+    /// assert_eq!(buffer[..12], [0, 1, 2, 0, 1, 3, 0, 1, 4, 0, 2, 3]);
+    /// assert_eq!(combos.fill_flat(&mut buffer), 4);
+    /// assert_eq!(combos.fill_flat(&mut buffer), 2);
+    /// assert_eq!(buffer[..6], [1, 3, 4, 2, 3, 4]);
+    /// assert_eq!(combos.fill_flat(&mut buffer), 0);
+    /// ```
+    pub fn fill_flat(&mut self, buffer: &mut [T]) -> usize {
+        let size = self.positions.len();
+        let width = size + self.included.len();
+        let mut count = 0;
+        let mut rows = buffer.chunks_exact_mut(width.max(1));
+        while !self.done && self.positions.len() == size && size <= self.elements.len() {
+            let row = match rows.next() {
+                Some(row) => &mut row[..width],
+                None if width == 0 => &mut [],
+                None => break,
+            };
+            if self.included.is_empty() {
+                for (slot, position) in row.iter_mut().zip(self.positions.iter()) {
+                    *slot = self.elements[*position].clone();
+                }
+            } else {
+                row.clone_from_slice(&self.combination_at::<Vec<T>>(&self.positions));
+            }
+            count += 1;
+            self.advance();
+        }
+        count
+    }
+
+    /// Returns the next combination, as by [`next`](Iterator::next), along with the indices of the
+    /// elements which entered and left it relative to the combination returned by the previous
+    /// call to this method, or relative to the empty combination on the first call.  The indices
+    /// are positions in [`Combinations::elements`], and are found by merging the two sorted lists
+    /// of positions, without comparing any elements.  Consecutive combinations often share most
+    /// of their elements, so a consumer which evaluates each combination incrementally only needs
+    /// to account for the few elements which changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{CombinationDelta, Combinations};
+    ///
+    /// let mut combos = Combinations::of_size(vec!['a', 'b', 'c', 'd'], 2);
+    /// let (combo, delta) = combos.next_with_delta().unwrap();
+    /// assert_eq!(combo, vec!['a', 'b']);
+    /// assert_eq!(delta, CombinationDelta { added: vec![0, 1], removed: vec![] });
+    /// let (combo, delta) = combos.next_with_delta().unwrap();
+    /// assert_eq!(combo, vec!['a', 'c']);
+    /// assert_eq!(delta, CombinationDelta { added: vec![2], removed: vec![1] });
+    ///
+    /// // Keep a running sum of each combination.
+    /// let mut combos = Combinations::of_size(1..=6, 3);
+    /// let mut sum = 0;
+    /// while let Some((combo, delta)) = combos.next_with_delta() {
+    ///     sum += delta.added.iter().map(|i| combos.elements()[*i]).sum::<i32>();
+    ///     sum -= delta.removed.iter().map(|i| combos.elements()[*i]).sum::<i32>();
+    ///     assert_eq!(sum, combo.iter().sum::<i32>());
+    /// }
+    /// ```
+    pub fn next_with_delta(&mut self) -> Option<(Vec<T>, CombinationDelta)> {
+        if self.done {
+            return None;
+        }
+        let combo = self.get_current_combination()?;
+        let mut delta = CombinationDelta::default();
+        let mut previous = self.delta_base.iter().peekable();
+        let mut current = self.positions.iter().peekable();
+        loop {
+            match (previous.peek(), current.peek()) {
+                (Some(old), Some(new)) if old == new => {
+                    previous.next();
+                    current.next();
+                }
+                (Some(old), Some(new)) if old < new => delta.removed.extend(previous.next()),
+                (Some(_), Some(_)) | (None, Some(_)) => delta.added.extend(current.next()),
+                (Some(_), None) => delta.removed.extend(previous.next()),
+                (None, None) => break,
+            }
+        }
+        self.delta_base.clone_from(&self.positions);
+        self.advance();
+        Some((combo, delta))
+    }
+
+    /// Converts the iterator into one which yields each combination along with its complement:
+    /// the elements of the underlying set which are not in the combination, in sorted order.  The
+    /// complement is read off from the positions of the current combination in a single pass, so
+    /// no elements are compared or searched for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let mut splits = Combinations::all(vec!['a', 'b']).with_complements();
+    /// assert_eq!(splits.next(), Some((vec![], vec!['a', 'b'])));
+    /// assert_eq!(splits.next(), Some((vec!['a'], vec!['b'])));
+    /// assert_eq!(splits.next(), Some((vec!['b'], vec!['a'])));
+    /// assert_eq!(splits.next(), Some((vec!['a', 'b'], vec![])));
+    /// assert_eq!(splits.next(), None);
+    /// ```
+    pub fn with_complements(self) -> WithComplements<T> {
+        WithComplements { combinations: self }
+    }
+
+    /// Converts the iterator into one which collects each combination directly into a container
+    /// of type `C`, such as a `String` or a `BTreeSet`, instead of a `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use combinatorial::Combinations;
+    ///
+    /// let words: Vec<String> = Combinations::all("ab".chars()).collecting().collect();
+    /// assert_eq!(words, vec!["", "a", "b", "ab"]);
+    ///
+    /// let mut sets = Combinations::of_size(1..4, 2).collecting::<BTreeSet<i32>>();
+    /// assert_eq!(sets.next(), Some(BTreeSet::from([1, 2])));
+    /// assert_eq!(sets.count(), 2);
+    /// ```
+    pub fn collecting<C: FromIterator<T>>(self) -> Collecting<Self, C> {
+        Collecting::new(self)
+    }
+
+    /// Converts the iterator into one which yields every combination exactly once, beginning
+    /// again from the first combination, but in a pseudo-random order determined by the seed.
+    /// Smaller and larger combinations are mixed together when generating combinations of all
+    /// sizes.  Nothing is yielded if the number of combinations does not fit in a `u128`.
+    ///
+    /// See [`Shuffled`] for how the order is chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeSet;
+    /// use combinatorial::Combinations;
+    ///
+    /// let shuffled: BTreeSet<Vec<i32>> = Combinations::all(1..5).shuffled(42).collect();
+    /// assert_eq!(shuffled, Combinations::all(1..5).collect());
+    ///
+    /// let first = Combinations::of_size(0..100, 10).shuffled(1).next();
+    /// assert_ne!(first, Combinations::of_size(0..100, 10).next());
+    /// ```
+    pub fn shuffled(self, seed: u64) -> Shuffled<Self> {
+        let total = self.total_count();
+        Shuffled::new(self, total, seed)
+    }
+
+    /// Returns the `k` combinations which this iterator yields from the beginning with the
+    /// greatest total score, where the score of a combination is the sum of the scores of its
+    /// elements.  The combinations are returned in order of decreasing score, with fewer than `k`
+    /// returned if there are not that many.  Elements required by [`Combinations::must_include`]
+    /// are in every combination, so they do not affect the ranking and are not scored.  Scores
+    /// should be comparable, so floating-point scores must not be NaN.
+    ///
+    /// Rather than scoring every combination, the combinations are explored best-first in a tree
+    /// where no combination scores higher than its parent, so only about `2 k` combinations of
+    /// each size are scored, after sorting the elements by score.  When generating combinations of
+    /// all sizes, the best of each size are merged, with smaller combinations first among equal
+    /// scores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// // The most valuable three of ten items, by value.
+    /// let values = [4.0, 9.5, 1.0, 7.25, 3.0, 9.0, 0.5, 2.0, 6.0, 8.0];
+    /// let best = Combinations::of_size(0..10, 3).top_k_by(|item| values[*item], 2);
+    /// assert_eq!(best, vec![vec![1, 5, 9], vec![1, 3, 5]]);
+    ///
+    /// let weights = [-3, 5, -1, 2];
+    /// let best = Combinations::all(0..4).top_k_by(|item| weights[*item], 3);
+    /// assert_eq!(best, vec![vec![1, 3], vec![1, 2, 3], vec![1]]);
+    /// assert_eq!(Combinations::of_size(0..4, 5).top_k_by(|item| weights[*item], 3).len(), 0);
+    /// ```
+    pub fn top_k_by<S, F>(&self, score: F, k: usize) -> Vec<Vec<T>>
+    where
+        S: PartialOrd + Copy + Sum<S>,
+        F: Fn(&T) -> S,
+    {
+        if self.infeasible {
+            return Vec::new();
+        }
+        let n = self.elements.len();
+        let scores: Vec<S> = self.elements.iter().map(score).collect();
+        let sizes = if self.all_sizes {
+            0..=n
+        } else {
+            self.positions.len()..=self.positions.len()
+        };
+        let mut best: Vec<(S, Vec<usize>)> = Vec::new();
+        for size in sizes {
+            best.extend(best_by_sum(&scores, size, k));
+        }
+        best.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        best.truncate(k);
+        best.iter()
+            .map(|(_, positions)| self.combination_at(positions))
+            .collect()
+    }
+
+    /// Returns the first combination which this iterator yields from the beginning in
+    /// lexicographic order for which the predicate returns `true`, or `None` if there is none.
+    /// When generating combinations of all sizes, smaller combinations are searched first.  This
+    /// does not depend on how far the iterator has advanced, or on the order selected for
+    /// iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let combos = Combinations::of_size(1..10, 3);
+    /// assert_eq!(combos.first_where(|c| c.iter().sum::<i32>() == 20), Some(vec![3, 8, 9]));
+    /// assert_eq!(combos.first_where(|c| c.iter().sum::<i32>() == 30), None);
+    ///
+    /// let sizes = Combinations::all(1..10);
+    /// assert_eq!(sizes.first_where(|c| c.iter().sum::<i32>() == 20), Some(vec![3, 8, 9]));
+    /// ```
+    pub fn first_where(&self, pred: impl FnMut(&[T]) -> bool) -> Option<Vec<T>> {
+        self.first_where_pruned(pred, |_| false)
+    }
+
+    /// Returns the first combination for which the predicate returns `true`, as by
+    /// [`Combinations::first_where`], but searches the combinations as a tree of partial
+    /// combinations, skipping every combination which extends a partial combination for which
+    /// `prune` returns `true`.
+    ///
+    /// Each partial combination is made up of the first few elements of the combinations below it,
+    /// along with any elements required by [`Combinations::must_include`], and `prune` must only
+    /// return `true` if no combination containing all of them satisfies the predicate.  This suits
+    /// monotone conditions, such as a total which can only grow as elements are added, where a
+    /// whole range of combinations can be ruled out at once rather than being tested one by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// // Six distinct odd numbers adding up to 40, pruning any partial combination which has an
+    /// // even number or already adds up to more than 40.
+    /// let combos = Combinations::of_size(1..=30, 6);
+    /// let total = |c: &[u32]| c.iter().sum::<u32>();
+    /// let all_odd = |c: &[u32]| c.iter().all(|x| x % 2 == 1);
+    /// let mut tested = 0;
+    /// let found = combos.first_where_pruned(
+    ///     |c| {
+    ///         tested += 1;
+    ///         total(c) == 40 && all_odd(c)
+    ///     },
+    ///     |partial| total(partial) > 40 || !all_odd(partial),
+    /// );
+    /// assert_eq!(found, Some(vec![1, 3, 5, 7, 9, 15]));
+    /// assert_eq!(tested, 6);
+    /// let position = combos.clone().position(|c| total(&c) == 40 && all_odd(&c));
+    /// assert_eq!(position, Some(23378));
+    /// ```
+    pub fn first_where_pruned(
+        &self,
+        mut pred: impl FnMut(&[T]) -> bool,
+        mut prune: impl FnMut(&[T]) -> bool,
+    ) -> Option<Vec<T>> {
+        if self.infeasible {
+            return None;
+        }
+        let n = self.elements.len();
+        let sizes = if self.all_sizes {
+            0..=n
+        } else {
+            self.positions.len()..=self.positions.len()
+        };
+        let root: Vec<T> = self.combination_at(&[]);
+        if prune(&root) {
+            return None;
+        }
+        for size in sizes {
+            if size == 0 {
+                if pred(&root) {
+                    return Some(root);
+                }
+                continue;
+            }
+            let mut positions = Vec::with_capacity(size);
+            let mut next = 0;
+            loop {
+                if positions.len() < size && next + size - positions.len() <= n {
+                    positions.push(next);
+                    let partial: Vec<T> = self.combination_at(&positions);
+                    if positions.len() == size && pred(&partial) {
+                        return Some(partial);
+                    }
+                    // Move on to the next sibling if this is a complete or pruned combination,
+                    // or descend to its first child otherwise.
+                    if positions.len() == size || prune(&partial) {
+                        positions.pop();
+                    }
+                    next += 1;
+                } else {
+                    match positions.pop() {
+                        Some(position) => next = position + 1,
+                        None => break,
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Moves the positions to the combination with the given rank, counting from zero in
+    /// lexicographic order among every combination which the iterator yields from the beginning.
+    /// The rank must be less than the total count.
+    pub(crate) fn seek_rank(&mut self, mut rank: u128) {
+        let n = self.elements.len();
+        let mut k = self.positions.len();
+        if self.all_sizes {
+            k = 0;
+            while let Some(count) = combination_count(n, k).filter(|count| rank >= *count) {
+                rank -= count;
+                k += 1;
+            }
+        }
+        self.positions.clear();
+        let mut position = 0;
+        for index in 0..k {
+            loop {
+                let count = combination_count(n - 1 - position, k - 1 - index).unwrap();
+                if rank < count {
+                    break;
+                }
+                rank -= count;
+                position += 1;
+            }
+            self.positions.push(position);
+            position += 1;
+        }
+        self.done = self.infeasible;
+    }
+
+    /// Adds another position indicator to the internal positions list and resets them to point to
+    /// the first `n` indices in order.
+    fn move_to_next_set_size(&mut self) -> bool {
+        if self.positions.len() >= self.elements.len() {
+            return false;
+        }
+        self.positions
+            .iter_mut()
+            .enumerate()
+            .for_each(|(index, pos)| *pos = index);
+        self.positions.push(self.positions.len());
+        true
+    }
+
+    /// Increments the internal positions to correspond to the indices of the next combination of
+    /// the same size.  If the positions are successfully incremented at the current combination
+    /// set size, then returns `true`.  Otherwise, returns `false`.
+    fn move_to_next_position(&mut self) -> bool {
+        self.step_position().is_some()
+    }
+
+    /// Increments the internal positions as by [`Self::move_to_next_position`], returning the
+    /// range of indices into the positions which were changed, or `None` if this was the last
+    /// combination of the current size.
+    fn step_position(&mut self) -> Option<Range<usize>> {
+        match self.order {
+            Order::Lex => self.step_lex_position(),
+            Order::Colex => self.step_colex_position(),
+        }
+    }
+
+    /// Increments the internal positions to the next combination of the same size in
+    /// lexicographic order, by advancing the last position which is not already at its largest
+    /// possible index and placing the positions after it immediately after it.
+    fn step_lex_position(&mut self) -> Option<Range<usize>> {
+        let length = self.positions.len();
+        let slack = self.elements.len().checked_sub(length)?;
+        let positions = self.positions.as_mut_slice();
+        let index = (0..length).rev().find(|i| positions[*i] < slack + i)?;
+        let start = positions[index] + 1;
+        for (offset, position) in positions[index..].iter_mut().enumerate() {
+            *position = start + offset;
+        }
+        Some(index..length)
+    }
+
+    /// Increments the internal positions to the next combination of the same size in
+    /// colexicographic order, by advancing the first position which can move up without colliding
+    /// with the next and resetting the positions before it to the smallest indices.
+    fn step_colex_position(&mut self) -> Option<Range<usize>> {
+        let length = self.positions.len();
+        for index in 0..length {
+            let limit = match self.positions.get(index + 1) {
+                Some(next) => *next,
+                None => self.elements.len(),
+            };
+            if self.positions[index] + 1 < limit {
+                self.positions[index] += 1;
+                for (earlier, position) in self.positions[..index].iter_mut().enumerate() {
+                    *position = earlier;
+                }
+                return Some(0..index + 1);
+            }
+        }
+        None
+    }
+
+    /// Returns the current combination, if one exists and is valid.
+    fn get_current_combination<C: FromIterator<T>>(&self) -> Option<C> {
+        if self.done || self.positions.len() > self.elements.len() {
+            return None;
+        }
+        Some(self.combination_at(&self.positions))
+    }
+
+    /// Moves the element at the given index from the underlying set to the required elements, as
+    /// by [`Combinations::must_include`], and shrinks the combinations of the remaining elements
+    /// to make room for it.
+    fn require_at(&mut self, index: usize) {
+        let before = self
+            .included_slots
+            .iter()
+            .take_while(|slot| **slot <= index)
+            .count();
+        self.included_slots[before..]
+            .iter_mut()
+            .for_each(|slot| *slot -= 1);
+        self.included.insert(before, self.elements.remove(index));
+        self.included_slots.insert(before, index);
+        if !self.all_sizes {
+            match self.positions.len().checked_sub(1) {
+                Some(size) => self.positions = (0..size).collect(),
+                None => self.mark_infeasible(),
+            }
+        }
+    }
+
+    /// Records that no combination meets the restrictions, so that nothing is yielded or counted.
+    fn mark_infeasible(&mut self) {
+        self.infeasible = true;
+        self.done = true;
+    }
+
+    /// Removes the element at the given index from the underlying set, as by
+    /// [`Combinations::must_exclude`].
+    fn exclude_at(&mut self, index: usize) {
+        self.elements.remove(index);
+        self.included_slots
+            .iter_mut()
+            .filter(|slot| **slot > index)
+            .for_each(|slot| *slot -= 1);
+    }
+
+    /// Returns the combination of the elements at the given positions, along with any elements
+    /// required by [`Combinations::must_include`].
+    fn combination_at<C: FromIterator<T>>(&self, positions: &[usize]) -> C {
+        if self.included.is_empty() {
+            return positions
+                .iter()
+                .map(|p| self.elements[*p].clone())
+                .collect();
+        }
+        let mut combo = Vec::with_capacity(positions.len() + self.included.len());
+        let mut included = self
+            .included
+            .iter()
+            .zip(self.included_slots.iter())
+            .peekable();
+        for position in positions.iter() {
+            while let Some((element, _)) = included.next_if(|(_, slot)| **slot <= *position) {
+                combo.push(element.clone());
+            }
+            combo.push(self.elements[*position].clone());
+        }
+        combo.extend(included.map(|(element, _)| element.clone()));
+        combo.into_iter().collect()
+    }
+
+    /// Returns the complement of the current combination, if one exists and is valid.
+    fn get_current_complement(&self) -> Option<Vec<T>> {
+        if self.done || self.positions.len() > self.elements.len() {
+            return None;
+        }
+        let mut chosen = self.positions.iter().peekable();
+        let mut complement = Vec::with_capacity(self.elements.len() - self.positions.len());
+        for (index, element) in self.elements.iter().enumerate() {
+            if chosen.peek() == Some(&&index) {
+                chosen.next();
+            } else {
+                complement.push(element.clone());
+            }
+        }
+        Some(complement)
+    }
+
+    /// Advances the internal positions to the next combination, moving on to the next size if
+    /// generating combinations of all sizes, and marks the iterator as done if none remain.
+    fn advance(&mut self) {
+        if !self.move_to_next_position() && (!self.all_sizes || !self.move_to_next_set_size()) {
+            self.done = true;
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> Combinations<T> {
+    /// Creates a new `Combinations` iterator which will yield all combinations of the unique
+    /// elements in the given iterable, for elements which can be hashed but not sorted.
+    ///
+    /// Duplicates are removed using a `HashSet`, keeping the first occurrence of each element, and
+    /// the remaining elements are then used in positional mode, as by
+    /// [`Combinations::all_positional`], so each combination lists its elements in the order in
+    /// which they first appeared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    /// enum Fruit {
+    ///     Pear,
+    ///     Apple,
+    /// }
+    ///
+    /// let fruits = vec![Fruit::Pear, Fruit::Apple, Fruit::Pear];
+    /// let combos: Vec<Vec<Fruit>> = Combinations::all_by_hash(fruits).collect();
+    /// assert_eq!(
+    ///     combos,
+    ///     vec![vec![], vec![Fruit::Pear], vec![Fruit::Apple], vec![Fruit::Pear, Fruit::Apple]]
+    /// );
+    /// ```
+    pub fn all_by_hash(elements: impl IntoIterator<Item = T>) -> Self {
+        let mut combos = Combinations::all_unchecked(unique_by_hash(elements));
+        combos.positional = true;
+        combos
+    }
+
+    /// Creates a new `Combinations` iterator which will yield all combinations with the specified
+    /// size of the unique elements in the given iterable, for elements which can be hashed but
+    /// not sorted.  See [`Combinations::all_by_hash`] for how the elements are prepared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq)]
+    /// struct Weight(f64);
+    ///
+    /// impl Eq for Weight {}
+    ///
+    /// impl std::hash::Hash for Weight {
+    ///     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+    ///         self.0.to_bits().hash(state);
+    ///     }
+    /// }
+    ///
+    /// let weights = [Weight(2.5), Weight(0.5), Weight(2.5), Weight(1.0)];
+    /// let mut combos = Combinations::of_size_by_hash(weights, 2);
+    /// assert_eq!(combos.next(), Some(vec![Weight(2.5), Weight(0.5)]));
+    /// assert_eq!(combos.next(), Some(vec![Weight(2.5), Weight(1.0)]));
+    /// assert_eq!(combos.next(), Some(vec![Weight(0.5), Weight(1.0)]));
+    /// assert_eq!(combos.next(), None);
+    /// ```
+    pub fn of_size_by_hash(elements: impl IntoIterator<Item = T>, size: usize) -> Self {
+        let mut combos = Combinations::of_size_unchecked(unique_by_hash(elements), size);
+        combos.positional = true;
+        combos
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Combinations<T> {
+    /// Saves the progress of the iterator to a checkpoint file at the given path, so that a
+    /// long-running enumeration can later be continued with [`Combinations::resume_from`].  The
+    /// elements themselves are not saved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let path = std::env::temp_dir().join("combinatorial-combinations-save.ckpt");
+    /// let mut combos = Combinations::of_size(0..5, 3);
+    /// combos.nth(3);
+    /// combos.save_to(&path).unwrap();
+    ///
+    /// let mut resumed = Combinations::of_size(0..5, 3).resume_from(&path).unwrap();
+    /// assert_eq!(resumed.next(), combos.next());
+    /// assert_eq!(resumed.count(), 5);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn save_to(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        Checkpoint {
+            kind: Kind::Combinations,
+            started: true,
+            done: self.done,
+            elements: self.elements.len(),
+            length: self.positions.len(),
+            positions: self.positions.clone(),
+        }
+        .save(path.as_ref())
+    }
+
+    /// Restores the progress saved by [`Combinations::save_to`] to this iterator, which must have
+    /// been created with the same elements and options as the one which saved it.  Returns an
+    /// error if the file cannot be read, or if it was not saved by a compatible iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Combinations;
+    ///
+    /// let path = std::env::temp_dir().join("combinatorial-combinations-resume.ckpt");
+    /// let mut combos = Combinations::all(0..4);
+    /// combos.nth(10);
+    /// combos.save_to(&path).unwrap();
+    ///
+    /// assert!(Combinations::all(0..5).resume_from(&path).is_err());
+    /// let resumed = Combinations::all(0..4).resume_from(&path).unwrap();
+    /// assert_eq!(resumed.collect::<Vec<_>>(), combos.collect::<Vec<_>>());
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn resume_from(mut self, path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let checkpoint = Checkpoint::load(path.as_ref(), Kind::Combinations, self.elements.len())?;
+        let positions = checkpoint.positions;
+        if positions.len() != checkpoint.length
+            || (!self.all_sizes && positions.len() != self.positions.len())
+            || positions.windows(2).any(|pair| pair[0] >= pair[1])
+            || positions
+                .last()
+                .is_some_and(|last| *last >= self.elements.len())
+        {
+            return Err(invalid("checkpoint does not match the combinations"));
+        }
+        self.positions = positions;
+        self.done = checkpoint.done || self.infeasible;
+        Ok(self)
+    }
+}
+
+impl<T: Clone> Iterator for Combinations<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next combination and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let combo = self.get_current_combination();
+        self.advance();
+        combo
+    }
+
+    /// Returns the exact number of remaining combinations, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_from(self.remaining_count())
+    }
+}
+
+impl<T: Clone> RankedIterator for Combinations<T> {
+    /// Returns the total number of combinations, as by [`Combinations::total_count`].
+    fn total_count(&self) -> Option<u128> {
+        Combinations::total_count(self)
+    }
+
+    /// Returns the combination with the given rank in lexicographic order, which is the order in
+    /// which they are yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, RankedIterator};
+    ///
+    /// let combos = Combinations::of_size(0..5, 3);
+    /// assert_eq!(combos.unrank(0), Some(vec![0, 1, 2]));
+    /// assert_eq!(combos.unrank(9), Some(vec![2, 3, 4]));
+    /// assert_eq!(combos.unrank(10), None);
+    /// assert_eq!(Combinations::all(0..3).unrank(4), Some(vec![0, 1]));
+    /// ```
+    fn unrank(&self, rank: u128) -> Option<Self::Item> {
+        if rank >= Combinations::total_count(self)? {
+            return None;
+        }
+        let mut combos = self.clone();
+        combos.seek_rank(rank);
+        combos.get_current_combination()
+    }
+}
+
+impl<T: Clone, C: FromIterator<T>> Iterator for Collecting<Combinations<T>, C> {
+    type Item = C;
+
+    /// Returns the next combination and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.inner.done {
+            return None;
+        }
+        let combo = self.inner.get_current_combination();
+        self.inner.advance();
+        combo
+    }
+
+    /// Returns the exact number of remaining combinations, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Clone> Iterator for Shuffled<Combinations<T>> {
+    type Item = Vec<T>;
+
+    /// Returns the next combination in the shuffled order and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let rank = self.next_rank()?;
+        self.inner.seek_rank(rank);
+        self.inner.get_current_combination()
+    }
+
+    /// Returns the exact number of remaining combinations, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_from(Some(self.remaining()))
+    }
+}
+
+/// An iterator which generates combinations over a set of elements along with their complements,
+/// created by [`Combinations::with_complements`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Combinations;
+///
+/// let mut splits = Combinations::of_size(1..5, 2).with_complements();
+/// assert_eq!(splits.next(), Some((vec![1, 2], vec![3, 4])));
+/// assert_eq!(splits.next(), Some((vec![1, 3], vec![2, 4])));
+/// assert_eq!(splits.count(), 4);
+/// ```
+#[derive(Clone)]
+pub struct WithComplements<T> {
+    combinations: Combinations<T>,
+}
+
+/// The change from one combination to the next, as returned by
+/// [`Combinations::next_with_delta`].  Both lists hold positions in [`Combinations::elements`], in
+/// increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Combinations;
+///
+/// let mut combos = Combinations::all(0..3);
+/// combos.nth(3);
+/// let (combo, delta) = combos.next_with_delta().unwrap();
+/// assert_eq!(combo, vec![0, 1]);
+/// assert_eq!((delta.added, delta.removed), (vec![0, 1], vec![]));
+/// let (combo, delta) = combos.next_with_delta().unwrap();
+/// assert_eq!(combo, vec![0, 2]);
+/// assert_eq!((delta.added, delta.removed), (vec![2], vec![1]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CombinationDelta {
+    /// The positions of the elements which are in the new combination but not the previous one.
+    pub added: Vec<usize>,
+    /// The positions of the elements which were in the previous combination but not the new one.
+    pub removed: Vec<usize>,
+}
+
+impl<T: Clone> Iterator for WithComplements<T> {
+    type Item = (Vec<T>, Vec<T>);
+
+    /// Returns the next combination and its complement and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.combinations.done {
+            return None;
+        }
+        let combo = self.combinations.get_current_combination();
+        let complement = self.combinations.get_current_complement();
+        self.combinations.advance();
+        combo.zip(complement)
+    }
+}
+
+/// An iterator which generates combinations over a set of elements, with replacement.
+///
+/// Since elements may be repeated, combinations of a given size exist even if the size is larger
+/// than the number of unique elements, as long as there is at least one element.  When generating
+/// combinations of all sizes, only sizes up to the number of unique elements are yielded.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::CombinationsWithReplacement;
+///
+/// let mut abc_tuples = CombinationsWithReplacement::of_size(vec!['a', 'b', 'c'], 2);
+/// assert_eq!(abc_tuples.next(), Some(vec!['a', 'a']));
+/// assert_eq!(abc_tuples.next(), Some(vec!['a', 'b']));
+/// assert_eq!(abc_tuples.next(), Some(vec!['a', 'c']));
+/// assert_eq!(abc_tuples.next(), Some(vec!['b', 'b']));
+/// assert_eq!(abc_tuples.next(), Some(vec!['b', 'c']));
+/// assert_eq!(abc_tuples.next(), Some(vec!['c', 'c']));
+/// assert_eq!(abc_tuples.next(), None);
+///
+/// let mut ones_and_zeros = CombinationsWithReplacement::all(0..2);
+/// assert_eq!(ones_and_zeros.next(), Some(Vec::new()));
+/// assert_eq!(ones_and_zeros.next(), Some(vec![0]));
+/// assert_eq!(ones_and_zeros.next(), Some(vec![1]));
+/// assert_eq!(ones_and_zeros.next(), Some(vec![0, 0]));
+/// assert_eq!(ones_and_zeros.next(), Some(vec![0, 1]));
+/// assert_eq!(ones_and_zeros.next(), Some(vec![1, 1]));
+/// assert_eq!(ones_and_zeros.next(), None);
+/// ```
+#[derive(Clone)]
+pub struct CombinationsWithReplacement<T> {
+    elements: Vec<T>,
+    positions: Vec<usize>,
+    all_sizes: bool,
+    max_size: Option<usize>,
+    order: ReplacementOrder,
+    done: bool,
+}
+
+/// The order in which combinations with replacement of a given size are generated.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{CombinationsWithReplacement, ReplacementOrder};
+///
+/// let combos = |order| {
+///     CombinationsWithReplacement::of_size(0..3, 2)
+///         .order(order)
+///         .collect::<Vec<Vec<usize>>>()
+/// };
+/// assert_eq!(
+///     combos(ReplacementOrder::Lex),
+///     vec![vec![0, 0], vec![0, 1], vec![0, 2], vec![1, 1], vec![1, 2], vec![2, 2]]
+/// );
+/// assert_eq!(
+///     combos(ReplacementOrder::ReverseLex),
+///     vec![vec![2, 2], vec![1, 2], vec![1, 1], vec![0, 2], vec![0, 1], vec![0, 0]]
+/// );
+/// assert_eq!(
+///     combos(ReplacementOrder::Gray),
+///     vec![vec![0, 0], vec![0, 2], vec![0, 1], vec![1, 1], vec![1, 2], vec![2, 2]]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ReplacementOrder {
+    /// Lexicographic order of the indices of the elements.
+    #[default]
+    Lex,
+    /// Reverse lexicographic order, beginning with the combination which repeats the largest
+    /// element.
+    ReverseLex,
+    /// A minimal-change order, in which each combination differs from the previous one by
+    /// replacing a single element with another.  It begins with the same combination as
+    /// lexicographic order.
+    Gray,
+}
+
+/// Returns the number of multisets of size `k` drawn from `n` distinct elements, or `None` if the
+/// count does not fit in a `u128`.
+fn multiset_count(n: usize, k: usize) -> Option<u128> {
+    if n == 0 {
+        return Some(u128::from(k == 0));
+    }
+    combination_count(n + k - 1, k)
+}
+
+impl<T: Ord + Clone> CombinationsWithReplacement<T> {
+    /// Creates a new `CombinationsWithReplacement` iterator which will yield all combinations with
+    /// replacement of the elements in the given iterable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let mut combos = CombinationsWithReplacement::all(vec!["hello", "world"]).map(|str_vec| str_vec.join(" "));
+    /// assert_eq!(combos.next(), Some(String::from("")));
+    /// assert_eq!(combos.next(), Some(String::from("hello")));
+    /// assert_eq!(combos.next(), Some(String::from("world")));
+    /// assert_eq!(combos.next(), Some(String::from("hello hello")));
+    /// assert_eq!(combos.next(), Some(String::from("hello world")));
+    /// assert_eq!(combos.next(), Some(String::from("world world")));
+    /// assert_eq!(combos.next(), None);
+    ///
+    /// let mut combos = CombinationsWithReplacement::all(1..1);
+    /// assert_eq!(combos.next(), Some(Vec::new()));
+    /// assert_eq!(combos.next(), None);
+    /// ```
+    pub fn all(elements: impl IntoIterator<Item = T>) -> Self {
+        CombinationsWithReplacement::all_unchecked(sorted_unique(elements))
+    }
+
+    /// Creates a new `CombinationsWithReplacement` iterator which will yield all combinations with
+    /// replacement of the elements in the given iterable, of every size 0, 1, 2, and so on without
+    /// end, rather than stopping at the number of unique elements as [`all`] does.  Smaller
+    /// combinations are yielded first, so this streams every multiset breadth-first, and should be
+    /// stopped with an adaptor such as [`Iterator::find`] or [`Iterator::take_while`].  If there
+    /// are no elements, only the empty combination is yielded.
+    ///
+    /// [`all`]: CombinationsWithReplacement::all
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let coins = vec![5, 7];
+    /// let change = CombinationsWithReplacement::all_unbounded(coins)
+    ///     .find(|combo| combo.iter().sum::<i32>() == 24);
+    /// assert_eq!(change, Some(vec![5, 5, 7, 7]));
+    ///
+    /// let combos = CombinationsWithReplacement::all_unbounded(vec!['a']);
+    /// assert_eq!(combos.size_hint(), (usize::MAX, None));
+    /// let combos: Vec<String> = combos.take(4).map(String::from_iter).collect();
+    /// assert_eq!(combos, vec!["", "a", "aa", "aaa"]);
+    ///
+    /// assert_eq!(CombinationsWithReplacement::all_unbounded(Vec::<i32>::new()).count(), 1);
+    /// ```
+    pub fn all_unbounded(elements: impl IntoIterator<Item = T>) -> Self {
+        let combos = CombinationsWithReplacement::all(elements);
+        let max_size = combos.elements.is_empty().then_some(0);
+        CombinationsWithReplacement { max_size, ..combos }
+    }
+
+    /// Creates a new `CombinationsWithReplacement` iterator which will yield all combinations with
+    /// replacement of the given elements, which are used exactly as given rather than being sorted
+    /// and deduplicated first.  See [`Combinations::all_unchecked`] for the consequences if the
+    /// elements are not sorted and unique.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let combos: Vec<Vec<i32>> = CombinationsWithReplacement::all_unchecked(vec![1, 2]).collect();
+    /// assert_eq!(combos, CombinationsWithReplacement::all(vec![2, 1]).collect::<Vec<_>>());
+    /// ```
+    pub fn all_unchecked(elements: Vec<T>) -> Self {
+        let count = elements.len();
+        CombinationsWithReplacement {
+            elements,
+            positions: Vec::new(),
+            all_sizes: true,
+            max_size: Some(count),
+            order: ReplacementOrder::Lex,
+            done: false,
+        }
+    }
+
+    /// Creates a new `CombinationsWithReplacement` iterator which will yield all combinations with
+    /// replacement of the specified size from the elements in the given iterable.  With `n` unique
+    /// elements, there are `n + size - 1` choose `size` such combinations, even if `size` is
+    /// larger than `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let mut combos = CombinationsWithReplacement::of_size(1..4, 2);
+    /// assert_eq!(combos.next(), Some(vec![1, 1]));
+    /// assert_eq!(combos.next(), Some(vec![1, 2]));
+    /// assert_eq!(combos.next(), Some(vec![1, 3]));
+    /// assert_eq!(combos.next(), Some(vec![2, 2]));
+    /// assert_eq!(combos.next(), Some(vec![2, 3]));
+    /// assert_eq!(combos.next(), Some(vec![3, 3]));
+    /// assert_eq!(combos.next(), None);
+    ///
+    /// let mut combos = CombinationsWithReplacement::of_size('a'..'z', 0);
+    /// assert_eq!(combos.next(), Some(Vec::new()));
+    /// assert_eq!(combos.next(), None);
+    ///
+    /// let mut combos = CombinationsWithReplacement::of_size(vec!["foo", "bar"], 3);
+    /// assert_eq!(combos.next(), Some(vec!["bar", "bar", "bar"]));
+    /// assert_eq!(combos.count(), 3);
+    ///
+    /// let mut combos = CombinationsWithReplacement::of_size(Vec::<i32>::new(), 2);
+    /// assert_eq!(combos.next(), None);
+    /// ```
+    pub fn of_size(elements: impl IntoIterator<Item = T>, size: usize) -> Self {
+        CombinationsWithReplacement::of_size_unchecked(sorted_unique(elements), size)
+    }
+
+    /// Creates a new `CombinationsWithReplacement` iterator like
+    /// [`CombinationsWithReplacement::of_size`], but handles an oversized request as directed by
+    /// the given [`OnOversize`] policy.  Only a positive size with no elements is oversized, and
+    /// is clamped to the single empty combination.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{CombinationsWithReplacement, CombinatorialError, OnOversize};
+    ///
+    /// let combos = CombinationsWithReplacement::of_size_with(1..4, 4, OnOversize::Error);
+    /// assert_eq!(combos.unwrap().count(), 15);
+    ///
+    /// let none = Vec::<i32>::new();
+    /// let combos = CombinationsWithReplacement::of_size_with(none.clone(), 2, OnOversize::Clamp);
+    /// assert_eq!(combos.unwrap().collect::<Vec<_>>(), vec![Vec::<i32>::new()]);
+    /// assert_eq!(
+    ///     CombinationsWithReplacement::of_size_with(none, 2, OnOversize::Error).err(),
+    ///     Some(CombinatorialError::SizeTooLarge { size: 2, available: 0 })
+    /// );
+    /// ```
+    pub fn of_size_with(
+        elements: impl IntoIterator<Item = T>,
+        size: usize,
+        on_oversize: OnOversize,
+    ) -> Result<Self, CombinatorialError> {
+        let elements = sorted_unique(elements);
+        let available = elements.len();
+        let largest = if available == 0 { 0 } else { usize::MAX };
+        let size = on_oversize.resolve(size, largest, available)?;
+        Ok(CombinationsWithReplacement::of_size_unchecked(
+            elements, size,
+        ))
+    }
+
+    /// Creates a new `CombinationsWithReplacement` iterator like
+    /// [`CombinationsWithReplacement::of_size`], but which yields nothing if the size is larger
+    /// than the number of unique elements.  This matches the behavior of `of_size` in earlier
+    /// versions, for code which relies on it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// assert_eq!(CombinationsWithReplacement::of_size_bounded(1..4, 3).count(), 10);
+    ///
+    /// let combos = CombinationsWithReplacement::of_size_bounded(1..4, 4);
+    /// assert_eq!(combos.total_count(), Some(0));
+    /// assert_eq!(combos.count(), 0);
+    /// assert_eq!(CombinationsWithReplacement::of_size(1..4, 4).count(), 15);
+    /// ```
+    pub fn of_size_bounded(elements: impl IntoIterator<Item = T>, size: usize) -> Self {
+        let combos = CombinationsWithReplacement::of_size(elements, size);
+        let count = combos.elements.len();
+        CombinationsWithReplacement {
+            max_size: Some(count),
+            done: combos.done || size > count,
+            ..combos
+        }
+    }
+
+    /// Creates a new `CombinationsWithReplacement` iterator which will yield all combinations with
+    /// replacement of the specified size of the given elements, which are used exactly as given
+    /// rather than being sorted and deduplicated first.  See [`Combinations::all_unchecked`] for
+    /// the consequences if the elements are not sorted and unique.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let combos: Vec<Vec<char>> =
+    ///     CombinationsWithReplacement::of_size_unchecked(vec!['b', 'a'], 2).collect();
+    /// assert_eq!(combos, vec![vec!['b', 'b'], vec!['b', 'a'], vec!['a', 'a']]);
+    /// ```
+    pub fn of_size_unchecked(elements: Vec<T>, size: usize) -> Self {
+        let done = size > 0 && elements.is_empty();
+        CombinationsWithReplacement {
+            elements,
+            positions: vec![0; size],
+            all_sizes: false,
+            max_size: None,
+            order: ReplacementOrder::Lex,
+            done,
+        }
+    }
+
+    /// Sets the order in which combinations with replacement of each size are generated.  This
+    /// should be called before iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{CombinationsWithReplacement, ReplacementOrder};
+    ///
+    /// let mut combos = CombinationsWithReplacement::all(vec!['a', 'b']).order(ReplacementOrder::ReverseLex);
+    /// assert_eq!(combos.next(), Some(vec![]));
+    /// assert_eq!(combos.next(), Some(vec!['b']));
+    /// assert_eq!(combos.next(), Some(vec!['a']));
+    /// assert_eq!(combos.next(), Some(vec!['b', 'b']));
+    /// assert_eq!(combos.next(), Some(vec!['a', 'b']));
+    /// assert_eq!(combos.next(), Some(vec!['a', 'a']));
+    /// assert_eq!(combos.next(), None);
+    /// ```
+    pub fn order(mut self, order: ReplacementOrder) -> Self {
+        self.order = order;
+        self.reset_positions();
+        self
+    }
+
+    /// Converts the iterator into one which collects each combination directly into a container
+    /// of type `C`, such as a `String`, instead of a `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let words: Vec<String> =
+    ///     CombinationsWithReplacement::of_size("ab".chars(), 2).collecting().collect();
+    /// assert_eq!(words, vec!["aa", "ab", "bb"]);
+    /// ```
+    pub fn collecting<C: FromIterator<T>>(self) -> Collecting<Self, C> {
+        Collecting::new(self)
+    }
+
+    /// Returns the sorted, deduplicated elements over which this iterator generates combinations
+    /// with replacement.  The indices of the elements in this slice are those returned by
+    /// [`CombinationsWithReplacement::position_of`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let combos = CombinationsWithReplacement::of_size(vec![3, 1, 3, 2], 2);
+    /// assert_eq!(combos.elements(), &[1, 2, 3]);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the combination which the next call to [`next`](Iterator::next) will yield,
+    /// without advancing the iterator, or `None` if no combinations remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let mut combos = CombinationsWithReplacement::of_size(1..3, 2);
+    /// combos.next();
+    /// assert_eq!(combos.current(), Some(vec![1, 2]));
+    /// ```
+    pub fn current(&self) -> Option<Vec<T>> {
+        self.get_current_combination()
+    }
+
+    /// Consumes the iterator and returns its elements, as given by
+    /// [`CombinationsWithReplacement::elements`], along with the indices of the elements of the
+    /// next combination which would have been yielded, or `None` if no combinations remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let mut combos = CombinationsWithReplacement::of_size(vec![2, 1], 2);
+    /// assert_eq!(combos.next(), Some(vec![1, 1]));
+    /// assert_eq!(combos.into_parts(), (vec![1, 2], Some(vec![0, 1])));
+    /// ```
+    pub fn into_parts(self) -> (Vec<T>, Option<Vec<usize>>) {
+        (self.elements, (!self.done).then_some(self.positions))
+    }
 
-impl<T: Ord + Clone> CombinationsWithReplacement<T> {
-    /// Creates a new `CombinationsWithReplacement` iterator which will yield all combinations with
-    /// replacement of the elements in the given iterable.
+    /// Returns the index of the given element within the sorted, deduplicated elements over which
+    /// this iterator generates combinations, or `None` if the element is not one of them.
     ///
     /// # Examples
     ///
     /// ```
     /// use combinatorial::CombinationsWithReplacement;
     ///
-    /// let mut combos = CombinationsWithReplacement::all(vec!["hello", "world"]).map(|str_vec| str_vec.join(" "));
-    /// assert_eq!(combos.next(), Some(String::from("")));
-    /// assert_eq!(combos.next(), Some(String::from("hello")));
-    /// assert_eq!(combos.next(), Some(String::from("world")));
-    /// assert_eq!(combos.next(), Some(String::from("hello hello")));
-    /// assert_eq!(combos.next(), Some(String::from("hello world")));
-    /// assert_eq!(combos.next(), Some(String::from("world world")));
-    /// assert_eq!(combos.next(), None);
+    /// let combos = CombinationsWithReplacement::of_size(vec!['c', 'a', 'b', 'a'], 2);
+    /// assert_eq!(combos.position_of(&'a'), Some(0));
+    /// assert_eq!(combos.position_of(&'c'), Some(2));
+    /// assert_eq!(combos.position_of(&'d'), None);
+    /// ```
+    pub fn position_of(&self, element: &T) -> Option<usize> {
+        self.elements.binary_search(element).ok()
+    }
+
+    /// Returns `true` if the given candidate is a combination with replacement which this iterator
+    /// could yield, that is, if it has a valid size and consists of elements from the underlying
+    /// set in non-decreasing order.  This does not depend on how far the iterator has advanced.
+    ///
+    /// # Examples
     ///
-    /// let mut combos = CombinationsWithReplacement::all(1..1);
-    /// assert_eq!(combos.next(), Some(Vec::new()));
-    /// assert_eq!(combos.next(), None);
     /// ```
-    pub fn all(elements: impl IntoIterator<Item = T>) -> Self {
-        CombinationsWithReplacement {
-            elements: iterable_to_sorted_set(elements),
-            positions: Vec::new(),
-            all_sizes: true,
-            done: false,
-        }
+    /// use combinatorial::CombinationsWithReplacement;
+    ///
+    /// let combos = CombinationsWithReplacement::of_size(1..5, 2);
+    /// assert!(combos.is_valid_combination(&[1, 3]));
+    /// assert!(combos.is_valid_combination(&[1, 1]));
+    /// assert!(!combos.is_valid_combination(&[3, 1]));
+    /// assert!(!combos.is_valid_combination(&[1, 5]));
+    /// assert!(!combos.is_valid_combination(&[1, 2, 3]));
+    /// ```
+    pub fn is_valid_combination(&self, candidate: &[T]) -> bool {
+        let valid_size = self.all_sizes || candidate.len() == self.positions.len();
+        valid_size
+            && self.max_size.is_none_or(|max| candidate.len() <= max)
+            && candidate.windows(2).all(|pair| pair[0] <= pair[1])
+            && candidate
+                .iter()
+                .all(|element| self.elements.binary_search(element).is_ok())
     }
 
-    /// Creates a new `CombinationsWithReplacement` iterator which will yield all combinations with
-    /// replacement of the specified size from the elements in the given iterable.
+    /// Returns the total number of combinations with replacement which this iterator yields from
+    /// the beginning, regardless of how far it has advanced, or `None` if the count does not fit
+    /// in a `u128` or is infinite.
     ///
     /// # Examples
     ///
     /// ```
     /// use combinatorial::CombinationsWithReplacement;
     ///
-    /// let mut combos = CombinationsWithReplacement::of_size(1..4, 2);
-    /// assert_eq!(combos.next(), Some(vec![1, 1]));
-    /// assert_eq!(combos.next(), Some(vec![1, 2]));
-    /// assert_eq!(combos.next(), Some(vec![1, 3]));
-    /// assert_eq!(combos.next(), Some(vec![2, 2]));
-    /// assert_eq!(combos.next(), Some(vec![2, 3]));
-    /// assert_eq!(combos.next(), Some(vec![3, 3]));
-    /// assert_eq!(combos.next(), None);
+    /// assert_eq!(CombinationsWithReplacement::of_size(0..4, 2).total_count(), Some(10));
+    /// assert_eq!(CombinationsWithReplacement::all(0..2).total_count(), Some(6));
+    /// assert_eq!(CombinationsWithReplacement::all_unbounded(0..2).total_count(), None);
+    /// ```
+    pub fn total_count(&self) -> Option<u128> {
+        let n = self.elements.len();
+        let k = self.positions.len();
+        match self.max_size {
+            Some(max) if self.all_sizes => {
+                (0..=max).try_fold(0u128, |total, k| total.checked_add(multiset_count(n, k)?))
+            }
+            Some(max) if k > max => Some(0),
+            None if self.all_sizes => None,
+            _ => multiset_count(n, k),
+        }
+    }
+
+    /// Returns the total number of combinations with replacement which this iterator yields from
+    /// the beginning, as by [`CombinationsWithReplacement::total_count`], but as a big integer, so
+    /// the count is exact however large it is, or `None` if it is infinite.  This is only
+    /// available with the `bigint` feature.
     ///
-    /// let mut combos = CombinationsWithReplacement::of_size('a'..'z', 0);
-    /// assert_eq!(combos.next(), Some(Vec::new()));
-    /// assert_eq!(combos.next(), None);
+    /// # Examples
     ///
-    /// let mut combos = CombinationsWithReplacement::of_size(vec!["foo", "bar", "baz"], 4);
-    /// assert_eq!(combos.next(), None);
     /// ```
-    pub fn of_size(elements: impl IntoIterator<Item = T>, size: usize) -> Self {
-        CombinationsWithReplacement {
-            elements: iterable_to_sorted_set(elements),
-            positions: vec![0; size],
-            all_sizes: false,
-            done: false,
+    /// use combinatorial::{binomial_big, CombinationsWithReplacement};
+    ///
+    /// let combos = CombinationsWithReplacement::of_size(0..100, 100);
+    /// assert_eq!(combos.total_count(), None);
+    /// assert_eq!(combos.total_count_big(), Some(binomial_big(199, 100)));
+    /// assert_eq!(CombinationsWithReplacement::all_unbounded(0..2).total_count_big(), None);
+    /// ```
+    #[cfg(feature = "bigint")]
+    pub fn total_count_big(&self) -> Option<num_bigint::BigUint> {
+        use crate::big::multiset_count_big;
+        let n = self.elements.len();
+        let k = self.positions.len();
+        match self.max_size {
+            Some(max) if self.all_sizes => Some((0..=max).map(|k| multiset_count_big(n, k)).sum()),
+            Some(max) if k > max => Some(num_bigint::BigUint::default()),
+            None if self.all_sizes => None,
+            _ => Some(multiset_count_big(n, k)),
+        }
+    }
+
+    /// Returns the number of combinations with replacement which have not yet been yielded, or
+    /// `None` if the count does not fit in a `u128` or is infinite.
+    fn remaining_count(&self) -> Option<u128> {
+        let n = self.elements.len();
+        let k = self.positions.len();
+        if self.done {
+            return Some(0);
+        }
+        let total = multiset_count(n, k)?;
+        let before = match self.order {
+            ReplacementOrder::Lex => self.lex_rank()?,
+            ReplacementOrder::ReverseLex => total - 1 - self.lex_rank()?,
+            ReplacementOrder::Gray => self.gray_rank()?,
+        };
+        let mut remaining = total - before;
+        if self.all_sizes {
+            for size in k + 1..=self.max_size? {
+                remaining = remaining.checked_add(multiset_count(n, size)?)?;
+            }
+        }
+        Some(remaining)
+    }
+
+    /// Returns the number of combinations of the current size which precede the current one in
+    /// lexicographic order, or `None` if the count does not fit in a `u128`.
+    fn lex_rank(&self) -> Option<u128> {
+        let n = self.elements.len();
+        let k = self.positions.len();
+        let mut before = 0;
+        let mut start = 0;
+        for (index, position) in self.positions.iter().enumerate() {
+            for skipped in start..*position {
+                before += multiset_count(n - skipped, k - 1 - index)?;
+            }
+            start = *position;
+        }
+        Some(before)
+    }
+
+    /// Returns the number of times each element occurs in the current combination.
+    fn current_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.elements.len()];
+        for position in self.positions.iter() {
+            counts[*position] += 1;
         }
+        counts
+    }
+
+    /// Returns the number of combinations of the current size which precede the current one in
+    /// Gray order, or `None` if the count does not fit in a `u128`.
+    ///
+    /// In Gray order, the combinations are grouped by how many times the first element occurs,
+    /// from most to fewest, and each group lists the combinations of the remaining elements in
+    /// Gray order, forwards if the first element occurs an even number of times and backwards
+    /// otherwise.  A backwards list in turn reverses the order of its groups and of the lists in
+    /// each group.
+    fn gray_rank(&self) -> Option<u128> {
+        let n = self.elements.len();
+        let counts = self.current_counts();
+        let mut remaining = self.positions.len();
+        let mut forward = true;
+        let mut before = 0;
+        for (index, current) in counts.iter().take(n.saturating_sub(1)).enumerate() {
+            let preceding = if forward {
+                current + 1..remaining + 1
+            } else {
+                0..*current
+            };
+            for count in preceding {
+                before += multiset_count(n - 1 - index, remaining - count)?;
+            }
+            forward = forward == current.is_multiple_of(2);
+            remaining -= current;
+        }
+        Some(before)
+    }
+
+    /// Resets the positions to the first combination of the current size in the current order.
+    fn reset_positions(&mut self) {
+        let first = match self.order {
+            ReplacementOrder::ReverseLex => self.elements.len().saturating_sub(1),
+            ReplacementOrder::Lex | ReplacementOrder::Gray => 0,
+        };
+        self.positions.iter_mut().for_each(|pos| *pos = first);
     }
 
     /// Adds another position indicator to the internal positions list and resets them to point to
-    /// the first index of the elements.
+    /// the first combination of the new size.
     fn move_to_next_set_size(&mut self) -> bool {
-        if self.positions.len() >= self.elements.len() {
+        if self.max_size.is_some_and(|max| self.positions.len() >= max) {
             return false;
         }
-        self.positions.iter_mut().for_each(|pos| *pos = 0);
         self.positions.push(0);
+        self.reset_positions();
         true
     }
 
@@ -275,7 +2534,12 @@ impl<T: Ord + Clone> CombinationsWithReplacement<T> {
     /// the same size.  If the positions are successfully incremented at the current combination
     /// set size, then returns `true`.  Otherwise, returns `false`.
     fn move_to_next_position(&mut self) -> bool {
-        if self.elements.len() == 0 {
+        match self.order {
+            ReplacementOrder::Lex => {}
+            ReplacementOrder::ReverseLex => return self.move_to_previous_lex_position(),
+            ReplacementOrder::Gray => return self.move_to_next_gray_position(),
+        }
+        if self.elements.is_empty() {
             return false;
         }
         let length = self.positions.len();
@@ -295,18 +2559,94 @@ impl<T: Ord + Clone> CombinationsWithReplacement<T> {
         false
     }
 
+    /// Decrements the internal positions to correspond to the indices of the previous combination
+    /// of the same size in lexicographic order.  Returns `true` if successful, or `false` if this
+    /// was the first combination in lexicographic order.
+    fn move_to_previous_lex_position(&mut self) -> bool {
+        let last = match self.elements.len().checked_sub(1) {
+            Some(last) => last,
+            None => return false,
+        };
+        for index in (0..self.positions.len()).rev() {
+            let floor = if index == 0 {
+                0
+            } else {
+                self.positions[index - 1]
+            };
+            if self.positions[index] > floor {
+                self.positions[index] -= 1;
+                self.positions[index + 1..]
+                    .iter_mut()
+                    .for_each(|pos| *pos = last);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Changes the internal positions to correspond to the indices of the next combination of the
+    /// same size in Gray order, which replaces a single element of the current combination.
+    /// Returns `true` if successful, or `false` if this was the last combination.
+    fn move_to_next_gray_position(&mut self) -> bool {
+        let n = self.elements.len();
+        if n < 2 {
+            return false;
+        }
+        let mut counts = self.current_counts();
+        let mut remaining = Vec::with_capacity(n);
+        let mut forward = Vec::with_capacity(n);
+        let (mut left, mut direction) = (self.positions.len(), true);
+        for count in counts.iter() {
+            remaining.push(left);
+            forward.push(direction);
+            direction = direction == count.is_multiple_of(2);
+            left -= count;
+        }
+        for index in (0..n - 1).rev() {
+            if forward[index] && counts[index] > 0 {
+                counts[index] -= 1;
+            } else if !forward[index] && counts[index] < remaining[index] {
+                counts[index] += 1;
+            } else {
+                continue;
+            }
+            let mut left = remaining[index] - counts[index];
+            let mut direction = forward[index] == counts[index].is_multiple_of(2);
+            for count in counts[index + 1..n - 1].iter_mut() {
+                *count = if direction { left } else { 0 };
+                direction = direction == count.is_multiple_of(2);
+                left -= *count;
+            }
+            counts[n - 1] = left;
+            self.positions.clear();
+            for (position, count) in counts.iter().enumerate() {
+                self.positions.extend(std::iter::repeat_n(position, *count));
+            }
+            return true;
+        }
+        false
+    }
+
     /// Returns the current combination, if one exists and is valid.
-    fn get_current_combination(&mut self) -> Option<Vec<T>> {
-        if self.done || self.positions.len() > self.elements.len() {
+    fn get_current_combination<C: FromIterator<T>>(&self) -> Option<C> {
+        if self.done {
             return None;
         }
         Some(
             self.positions
                 .iter()
                 .map(|p| self.elements.get(*p).unwrap().clone())
-                .collect::<Vec<T>>(),
+                .collect(),
         )
     }
+
+    /// Advances the internal positions to the next combination, moving on to the next size if
+    /// generating combinations of all sizes, and marks the iterator as done if none remain.
+    fn advance(&mut self) {
+        if !self.move_to_next_position() && (!self.all_sizes || !self.move_to_next_set_size()) {
+            self.done = true;
+        }
+    }
 }
 
 impl<T: Ord + Clone> Iterator for CombinationsWithReplacement<T> {
@@ -318,13 +2658,35 @@ impl<T: Ord + Clone> Iterator for CombinationsWithReplacement<T> {
             return None;
         }
         let combo = self.get_current_combination();
-        if self.move_to_next_position() == false {
-            if self.all_sizes == false || self.move_to_next_set_size() == false {
-                self.done = true;
-            }
+        self.advance();
+        combo
+    }
+
+    /// Returns the exact number of remaining combinations, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_from(self.remaining_count())
+    }
+}
+
+impl<T: Ord + Clone, C: FromIterator<T>> Iterator
+    for Collecting<CombinationsWithReplacement<T>, C>
+{
+    type Item = C;
+
+    /// Returns the next combination and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.inner.done {
+            return None;
         }
+        let combo = self.inner.get_current_combination();
+        self.inner.advance();
         combo
     }
+
+    /// Returns the exact number of remaining combinations, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
 }
 
 #[cfg(test)]
@@ -332,326 +2694,937 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_combinations_iterable_to_sorted_set() {
-        assert_eq!(vec![1, 2, 3, 4], iterable_to_sorted_set(vec![1, 2, 3, 4]));
-        assert_eq!(vec![1, 2, 3, 4], iterable_to_sorted_set(1..5));
+    fn test_combinations_sorted_unique() {
+        assert_eq!(vec![1, 2, 3, 4], sorted_unique(vec![1, 2, 3, 4]));
+        assert_eq!(vec![1, 2, 3, 4], sorted_unique(1..5));
         assert_eq!(
-            vec![1, 2, 3, 4].iter().collect::<Vec<&usize>>(),
-            iterable_to_sorted_set(vec![2, 3, 1, 4].iter())
+            [1, 2, 3, 4].iter().collect::<Vec<&usize>>(),
+            sorted_unique([2, 3, 1, 4].iter())
         );
         assert_eq!(
             vec![&1, &2, &3, &4],
-            iterable_to_sorted_set(&vec![2, 1, 3, 1, 4, 2, 2, 3])
+            sorted_unique(&vec![2, 1, 3, 1, 4, 2, 2, 3])
+        );
+    }
+
+    #[test]
+    fn test_combinations_unchecked_matches_checked() {
+        for n in 0..6 {
+            let elements = sorted_unique((0..n).rev());
+            assert_eq!(
+                Combinations::all_unchecked(elements.clone()).collect::<Vec<_>>(),
+                Combinations::all((0..n).rev()).collect::<Vec<_>>()
+            );
+            for k in 0..=n + 1 {
+                assert_eq!(
+                    Combinations::of_size_unchecked(elements.clone(), k).collect::<Vec<_>>(),
+                    Combinations::of_size((0..n).rev(), k).collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    CombinationsWithReplacement::of_size_unchecked(elements.clone(), k)
+                        .collect::<Vec<_>>(),
+                    CombinationsWithReplacement::of_size((0..n).rev(), k).collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_combinations_positional() {
+        let elements = vec!['b', 'a', 'b', 'c', 'a'];
+        for k in 0..=elements.len() + 1 {
+            let expected: Vec<Vec<char>> = Combinations::of_size(0..elements.len(), k)
+                .map(|indices| indices.iter().map(|i| elements[*i]).collect())
+                .collect();
+            let combos = Combinations::of_size_positional(elements.clone(), k);
+            assert!(expected
+                .iter()
+                .all(|combo| combos.is_valid_combination(combo)));
+            assert_eq!(combos.collect::<Vec<_>>(), expected);
+        }
+        let combos = Combinations::all_positional(elements.clone());
+        assert_eq!(combos.position_of(&'a'), Some(1));
+        assert_eq!(combos.position_of(&'d'), None);
+        assert!(!combos.is_valid_combination(&['c', 'b']));
+        assert_eq!(combos.count(), 1 << elements.len());
+    }
+
+    #[test]
+    fn test_combinations_must_include_and_exclude() {
+        let include = [1, 4];
+        let exclude = [2, 7];
+        for k in 0..8 {
+            let expected: Vec<Vec<usize>> = Combinations::of_size(0..7, k)
+                .filter(|combo| include.iter().all(|element| combo.contains(element)))
+                .filter(|combo| !exclude.iter().any(|element| combo.contains(element)))
+                .collect();
+            let combos = Combinations::of_size(0..7, k)
+                .must_include(&include)
+                .must_exclude(&exclude);
+            assert!(expected
+                .iter()
+                .all(|combo| combos.is_valid_combination(combo)));
+            assert_eq!(combos.size_hint().0, expected.len());
+            assert_eq!(combos.collect::<Vec<_>>(), expected);
+        }
+        let expected: Vec<Vec<usize>> = Combinations::all(0..7)
+            .filter(|combo| include.iter().all(|element| combo.contains(element)))
+            .collect();
+        let combos = Combinations::all(0..7).must_include(&[4, 1]);
+        assert!(!combos.is_valid_combination(&[4, 1]));
+        assert!(!combos.is_valid_combination(&[1, 3]));
+        assert_eq!(combos.collect::<Vec<_>>(), expected);
+
+        let combos = Combinations::of_size_positional(vec![3, 1, 3, 2], 3).must_include(&[2, 3]);
+        assert_eq!(combos.collect::<Vec<_>>(), vec![vec![3, 3, 2]]);
+
+        // In positional mode, every position holding a given element is required or excluded.
+        let elements = vec![3, 1, 3, 2, 1];
+        let count = |combo: &Vec<i32>, x: i32| combo.iter().filter(|y| **y == x).count();
+        for k in 0..=elements.len() + 1 {
+            let positional = || Combinations::of_size_positional(elements.clone(), k);
+            let expected: Vec<Vec<i32>> = positional()
+                .filter(|combo| count(combo, 3) == 2 && count(combo, 1) == 0)
+                .collect();
+            let combos = positional().must_include(&[3]).must_exclude(&[1]);
+            assert!(expected
+                .iter()
+                .all(|combo| combos.is_valid_combination(combo)));
+            assert!(!combos.is_valid_combination(&[3, 2]));
+            assert_eq!(combos.collect::<Vec<_>>(), expected);
+        }
+        let combos = Combinations::all_positional(vec![1, 1, 2]).must_exclude(&[1]);
+        assert_eq!(combos.collect::<Vec<_>>(), vec![vec![], vec![2]]);
+        let combos = Combinations::all(0..7)
+            .must_include(&[1])
+            .must_exclude(&[1]);
+        assert_eq!(combos.count(), 0);
+    }
+
+    #[test]
+    fn test_combinations_infeasible_restrictions() {
+        let table = CombinatoricsTable::new(10);
+        let infeasible = [
+            Combinations::of_size(0..5, 2).must_include(&[9]),
+            Combinations::of_size(1..6, 2)
+                .must_include(&[2])
+                .must_exclude(&[2]),
+            Combinations::of_size(1..6, 1).must_include(&[2, 4]),
+            Combinations::all(0..5).must_include(&[9]),
+            Combinations::all(1..5).with_prefix(&[3, 1]),
+            Combinations::of_size(1..6, 2).with_prefix(&[1, 2, 3]),
+        ];
+        for combos in infeasible {
+            assert_eq!(combos.clone().count(), 0);
+            assert_eq!(combos.size_hint(), (0, Some(0)));
+            assert_eq!(combos.total_count(), Some(0));
+            #[cfg(feature = "bigint")]
+            assert_eq!(combos.total_count_big(), num_bigint::BigUint::default());
+            assert_eq!(combos.unrank_colex(0), None);
+            assert_eq!(combos.unrank_colex_with(0, &table), None);
+            assert_eq!(combos.top_k_by(|x| *x, 2), Vec::<Vec<i32>>::new());
+            assert_eq!(combos.first_where(|_| true), None);
+            assert_eq!(combos.first_where_pruned(|_| true, |_| false), None);
+            assert_eq!(combos.clone().shuffled(1).size_hint(), (0, Some(0)));
+            assert_eq!(combos.clone().shuffled(1).next(), None);
+            assert_eq!(combos.clone().starting_from(&[1, 2]).next(), None);
+        }
+        assert_eq!(Combinations::of_size(0..3, 5).first_where(|_| true), None);
+        let prefixed = Combinations::all(1..5).with_prefix(&[1, 3]);
+        assert_eq!(prefixed.clone().starting_from(&[1, 3, 4]).count(), 1);
+        let outside = std::panic::catch_unwind(|| prefixed.starting_from(&[1, 2, 3]));
+        assert!(outside.is_err());
+    }
+
+    #[test]
+    fn test_combinations_collecting_matches_vecs() {
+        let expected: Vec<String> = Combinations::all("dcba".chars())
+            .must_include(&['b'])
+            .map(|combo| combo.into_iter().collect())
+            .collect();
+        let collected = Combinations::all("dcba".chars())
+            .must_include(&['b'])
+            .collecting::<String>();
+        assert_eq!(
+            collected.size_hint(),
+            (expected.len(), Some(expected.len()))
+        );
+        assert_eq!(collected.collect::<Vec<String>>(), expected);
+        let expected: Vec<String> = CombinationsWithReplacement::all("ab".chars())
+            .take(10)
+            .map(|combo| combo.into_iter().collect())
+            .collect();
+        let collected = CombinationsWithReplacement::all("ab".chars()).collecting::<String>();
+        assert_eq!(collected.take(10).collect::<Vec<String>>(), expected);
+    }
+
+    #[test]
+    fn test_combinations_by_hash() {
+        let elements = vec![3, 1, 3, 2, 1];
+        for k in 0..5 {
+            let expected: Vec<Vec<i32>> =
+                Combinations::of_size_positional(vec![3, 1, 2], k).collect();
+            let combos = Combinations::of_size_by_hash(elements.clone(), k);
+            assert_eq!(combos.collect::<Vec<_>>(), expected);
+        }
+        assert_eq!(
+            Combinations::all_by_hash(elements.clone()).count(),
+            Combinations::all(elements).count()
         );
     }
 
+    #[test]
+    fn test_combinations_by_matches_mapped_order() {
+        for k in 0..6 {
+            let expected: Vec<Vec<i32>> = Combinations::of_size(0..5, k)
+                .map(|combo| combo.into_iter().map(|x| 4 - x).collect())
+                .collect();
+            let combos = Combinations::of_size_by(vec![2, 0, 4, 1, 3, 0], k, |a, b| b.cmp(a));
+            assert_eq!(combos.collect::<Vec<_>>(), expected);
+        }
+        assert_eq!(
+            sorted_unique_by(vec![2i32, -1, 1, -2], |a, b| a.abs().cmp(&b.abs())),
+            vec![-1, 2]
+        );
+    }
+
+    #[test]
+    fn test_combinations_current_matches_next() {
+        for order in [Order::Lex, Order::Colex] {
+            let mut combos = Combinations::all(0..5).order(order).must_include(&[2]);
+            loop {
+                let current = combos.current();
+                assert_eq!(current, combos.next());
+                if current.is_none() {
+                    break;
+                }
+            }
+        }
+        assert_eq!(Combinations::of_size(0..3, 4).current(), None);
+    }
+
     #[test]
     fn test_combinations_all() {
-        let combos = Combinations::all(vec![2, 4, 3, 1, 2, 2, 1].into_iter());
+        let combos = Combinations::all(vec![2, 4, 3, 1, 2, 2, 1]);
         assert_eq!(combos.elements, vec![1, 2, 3, 4]);
         assert_eq!(combos.positions, Vec::new());
-        assert_eq!(combos.all_sizes, true);
-        assert_eq!(combos.done, false);
+        assert!(combos.all_sizes);
+        assert!(!combos.done);
     }
 
     #[test]
     fn test_combinations_w_rep_all() {
-        let combos = CombinationsWithReplacement::all(vec![2, 4, 3, 1, 2, 2, 1].into_iter());
+        let combos = CombinationsWithReplacement::all(vec![2, 4, 3, 1, 2, 2, 1]);
         assert_eq!(combos.elements, vec![1, 2, 3, 4]);
         assert_eq!(combos.positions, Vec::new());
-        assert_eq!(combos.all_sizes, true);
-        assert_eq!(combos.done, false);
+        assert!(combos.all_sizes);
+        assert!(!combos.done);
     }
 
     #[test]
     fn test_combinations_of_size() {
-        let combos = Combinations::of_size(vec![2, 4, 3, 1, 2, 2, 1].into_iter(), 3);
+        let combos = Combinations::of_size(vec![2, 4, 3, 1, 2, 2, 1], 3);
         assert_eq!(combos.elements, vec![1, 2, 3, 4]);
         assert_eq!(combos.positions, vec![0, 1, 2]);
-        assert_eq!(combos.all_sizes, false);
-        assert_eq!(combos.done, false);
+        assert!(!combos.all_sizes);
+        assert!(!combos.done);
     }
 
     #[test]
     fn test_combinations_w_rep_of_size() {
-        let combos = CombinationsWithReplacement::of_size(vec![2, 4, 3, 1, 2, 2, 1].into_iter(), 3);
+        let combos = CombinationsWithReplacement::of_size(vec![2, 4, 3, 1, 2, 2, 1], 3);
         assert_eq!(combos.elements, vec![1, 2, 3, 4]);
         assert_eq!(combos.positions, vec![0; 3]);
-        assert_eq!(combos.all_sizes, false);
-        assert_eq!(combos.done, false);
+        assert!(!combos.all_sizes);
+        assert_eq!(combos.max_size, None);
+        assert!(!combos.done);
     }
 
     #[test]
     fn test_combinations_move_to_next_set_size() {
         let mut combos = Combinations::all(Vec::<i64>::new());
         assert_eq!(combos.positions, Vec::new());
-        assert_eq!(combos.move_to_next_set_size(), false);
+        assert!(!combos.move_to_next_set_size());
         let mut combos = Combinations::all(vec![1]);
         assert_eq!(combos.positions, Vec::new());
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0]);
-        assert_eq!(combos.move_to_next_set_size(), false);
+        assert!(!combos.move_to_next_set_size());
         let mut combos = Combinations::all(vec![1, 2, 3, 4]);
         assert_eq!(combos.positions, Vec::new());
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0]);
         combos.positions[0] = 4;
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0, 1]);
         combos.positions[0] = 5;
         combos.positions[1] = 2;
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0, 1, 2]);
         combos.positions[0] = 3;
         combos.positions[1] = 7;
         combos.positions[2] = 1;
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0, 1, 2, 3]);
         combos.positions[0] = 0;
         combos.positions[1] = 0;
         combos.positions[2] = 0;
         combos.positions[2] = 0;
-        assert_eq!(combos.move_to_next_set_size(), false);
+        assert!(!combos.move_to_next_set_size());
     }
 
     #[test]
     fn test_combinations_w_rep_move_to_next_set_size() {
         let mut combos = CombinationsWithReplacement::all(Vec::<i64>::new());
         assert_eq!(combos.positions, Vec::new());
-        assert_eq!(combos.move_to_next_set_size(), false);
+        assert!(!combos.move_to_next_set_size());
         let mut combos = CombinationsWithReplacement::all(vec![1]);
         assert_eq!(combos.positions, Vec::new());
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0]);
-        assert_eq!(combos.move_to_next_set_size(), false);
+        assert!(!combos.move_to_next_set_size());
         let mut combos = CombinationsWithReplacement::all(vec![1, 2, 3, 4]);
         assert_eq!(combos.positions, Vec::new());
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0]);
         combos.positions[0] = 4;
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0; 2]);
         combos.positions[0] = 5;
         combos.positions[1] = 2;
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0; 3]);
         combos.positions[0] = 3;
         combos.positions[1] = 7;
         combos.positions[2] = 1;
-        assert_eq!(combos.move_to_next_set_size(), true);
+        assert!(combos.move_to_next_set_size());
         assert_eq!(combos.positions, vec![0; 4]);
         combos.positions[0] = 0;
         combos.positions[1] = 0;
         combos.positions[2] = 0;
         combos.positions[2] = 0;
-        assert_eq!(combos.move_to_next_set_size(), false);
+        assert!(!combos.move_to_next_set_size());
     }
 
     #[test]
     fn test_combinations_move_to_next_position() {
         let mut combos = Combinations::of_size(Vec::<i64>::new(), 1);
         assert_eq!(combos.positions, vec![0]);
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
         let mut combos = Combinations::of_size(vec![1], 1);
         assert_eq!(combos.positions, vec![0]);
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
         let mut combos = Combinations::of_size(BTreeSet::from([1, 2, 3, 4]), 2);
         assert_eq!(combos.positions, vec![0, 1]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![2, 3]);
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
         let mut combos = Combinations::of_size("abcd".chars(), 3);
         assert_eq!(combos.positions, vec![0, 1, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 1, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 2, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 2, 3]);
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
     }
 
     #[test]
     fn test_combinations_w_rep_move_to_next_position() {
         let mut combos = CombinationsWithReplacement::of_size(Vec::<i64>::new(), 1);
         assert_eq!(combos.positions, vec![0]);
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
         let mut combos = CombinationsWithReplacement::of_size(vec![1], 1);
         assert_eq!(combos.positions, vec![0]);
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
         let mut combos = CombinationsWithReplacement::of_size(BTreeSet::from([1, 2, 3, 4]), 2);
         assert_eq!(combos.positions, vec![0, 0]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 1]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 1]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![2, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![2, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![3, 3]);
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
         let mut combos = CombinationsWithReplacement::of_size("abcd".chars(), 3);
         assert_eq!(combos.positions, vec![0, 0, 0]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 0, 1]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 0, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 0, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 1, 1]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 1, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 1, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 2, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 2, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![0, 3, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 1, 1]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 1, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 1, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 2, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 2, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![1, 3, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![2, 2, 2]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![2, 2, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![2, 3, 3]);
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.positions, vec![3, 3, 3]);
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
     }
 
     #[test]
     fn test_combinations_get_current_combination() {
         let mut combos = Combinations::of_size(vec![1, 1, 2, 3, 5, 8], 3);
         assert_eq!(combos.get_current_combination(), Some(vec![1, 2, 3]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 2, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 2, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 3, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 3, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 5, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 3, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 3, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 5, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![3, 5, 8]));
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
         combos.done = true;
-        assert_eq!(combos.get_current_combination(), None);
+        assert_eq!(combos.get_current_combination::<Vec<i32>>(), None);
     }
 
     #[test]
     fn test_combinations_w_rep_get_current_combination() {
         let mut combos = CombinationsWithReplacement::of_size(vec![1, 1, 2, 3, 5, 8], 3);
         assert_eq!(combos.get_current_combination(), Some(vec![1, 1, 1]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 1, 2]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 1, 3]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 1, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 1, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 2, 2]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 2, 3]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 2, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 2, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 3, 3]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 3, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 3, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 5, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 5, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![1, 8, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 2, 2]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 2, 3]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 2, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 2, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 3, 3]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 3, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 3, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 5, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 5, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![2, 8, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![3, 3, 3]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![3, 3, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![3, 3, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![3, 5, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![3, 5, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![3, 8, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![5, 5, 5]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![5, 5, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![5, 8, 8]));
-        assert_eq!(combos.move_to_next_position(), true);
+        assert!(combos.move_to_next_position());
         assert_eq!(combos.get_current_combination(), Some(vec![8, 8, 8]));
-        assert_eq!(combos.move_to_next_position(), false);
+        assert!(!combos.move_to_next_position());
         combos.done = true;
-        assert_eq!(combos.get_current_combination(), None);
+        assert_eq!(combos.get_current_combination::<Vec<i32>>(), None);
+    }
+
+    #[test]
+    fn test_combinations_is_valid_combination() {
+        let combos = Combinations::of_size(Vec::<i64>::new(), 0);
+        assert!(combos.is_valid_combination(&[]));
+        assert!(!combos.is_valid_combination(&[1]));
+        let combos = Combinations::all(vec![3, 1, 2]);
+        assert!(combos.is_valid_combination(&[1, 2, 3]));
+        assert!(!combos.is_valid_combination(&[1, 2, 2]));
+        for combo in Combinations::of_size(1..6, 3) {
+            assert!(Combinations::of_size(1..6, 3).is_valid_combination(&combo));
+        }
+    }
+
+    #[test]
+    fn test_combinations_size_hint_is_exact() {
+        let mut iterators: Vec<Box<dyn Iterator<Item = Vec<usize>>>> = Vec::new();
+        for n in 0..6 {
+            iterators.push(Box::new(Combinations::all(0..n)));
+            iterators.push(Box::new(CombinationsWithReplacement::all(0..n)));
+            for k in 0..=n + 1 {
+                iterators.push(Box::new(Combinations::of_size(0..n, k)));
+                iterators.push(Box::new(CombinationsWithReplacement::of_size(0..n, k)));
+            }
+        }
+        for mut iterator in iterators {
+            let mut remaining = iterator.size_hint().0;
+            assert_eq!(iterator.size_hint(), (remaining, Some(remaining)));
+            while iterator.next().is_some() {
+                remaining -= 1;
+                assert_eq!(iterator.size_hint(), (remaining, Some(remaining)));
+            }
+            assert_eq!(remaining, 0);
+        }
+        assert_eq!(
+            CombinationsWithReplacement::of_size(0..5, 3).total_count(),
+            Some(CombinationsWithReplacement::of_size(0..5, 3).count() as u128)
+        );
+    }
+
+    #[test]
+    fn test_combinations_colex_order() {
+        for n in 0..7 {
+            for k in 0..=n + 1 {
+                let mut expected: Vec<Vec<usize>> = Combinations::of_size(0..n, k).collect();
+                expected.sort_by(|a, b| a.iter().rev().cmp(b.iter().rev()));
+                let mut colex = Combinations::of_size(0..n, k).order(Order::Colex);
+                let mut remaining = expected.len();
+                assert_eq!(colex.size_hint(), (remaining, Some(remaining)));
+                let mut actual = Vec::new();
+                while let Some(combo) = colex.next() {
+                    actual.push(combo);
+                    remaining -= 1;
+                    assert_eq!(colex.size_hint(), (remaining, Some(remaining)));
+                }
+                assert_eq!(actual, expected);
+            }
+            let all: Vec<Vec<usize>> = Combinations::all(0..n).order(Order::Colex).collect();
+            let by_size: Vec<Vec<usize>> = (0..=n)
+                .flat_map(|k| Combinations::of_size(0..n, k).order(Order::Colex))
+                .collect();
+            assert_eq!(all, by_size);
+        }
+    }
+
+    #[test]
+    fn test_combinations_w_rep_larger_than_elements() {
+        for n in 0..4 {
+            for k in 0..8 {
+                let combos = CombinationsWithReplacement::of_size(0..n, k);
+                let expected = multiset_count(n, k).unwrap() as usize;
+                assert_eq!(combos.total_count(), Some(expected as u128));
+                assert_eq!(combos.size_hint(), (expected, Some(expected)));
+                let all: Vec<Vec<usize>> = combos.collect();
+                assert_eq!(all.len(), expected);
+                assert!(all.iter().all(|combo| combo.len() == k));
+                assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+                let bounded = CombinationsWithReplacement::of_size_bounded(0..n, k);
+                let bounded_count = if k > n { 0 } else { expected };
+                assert_eq!(bounded.total_count(), Some(bounded_count as u128));
+                assert_eq!(bounded.size_hint(), (bounded_count, Some(bounded_count)));
+                assert_eq!(bounded.count(), bounded_count);
+                for policy in [OnOversize::Empty, OnOversize::Clamp, OnOversize::Error] {
+                    let combos = CombinationsWithReplacement::of_size_with(0..n, k, policy);
+                    match (policy, n == 0 && k > 0) {
+                        (OnOversize::Error, true) => assert!(combos.is_err()),
+                        (OnOversize::Clamp, true) => assert_eq!(combos.unwrap().count(), 1),
+                        _ => assert_eq!(combos.unwrap().collect::<Vec<_>>(), all),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_combinations_w_rep_all_unbounded() {
+        for n in 1..4 {
+            for order in [
+                ReplacementOrder::Lex,
+                ReplacementOrder::ReverseLex,
+                ReplacementOrder::Gray,
+            ] {
+                let expected: Vec<Vec<usize>> = (0..n + 4)
+                    .flat_map(|k| CombinationsWithReplacement::of_size(0..n, k).order(order))
+                    .collect();
+                let mut combos = CombinationsWithReplacement::all_unbounded(0..n).order(order);
+                for combo in expected {
+                    assert!(combos.is_valid_combination(&combo));
+                    assert_eq!(combos.next(), Some(combo));
+                    assert_eq!(combos.size_hint(), (usize::MAX, None));
+                }
+                assert_eq!(combos.next().map(|combo| combo.len()), Some(n + 4));
+            }
+        }
+    }
+
+    #[test]
+    fn test_combinations_w_rep_orders() {
+        for n in 0..6 {
+            for k in 0..=n + 3 {
+                let lex: Vec<Vec<usize>> = CombinationsWithReplacement::of_size(0..n, k).collect();
+                let reverse: Vec<Vec<usize>> = CombinationsWithReplacement::of_size(0..n, k)
+                    .order(ReplacementOrder::ReverseLex)
+                    .collect();
+                assert_eq!(reverse, lex.iter().rev().cloned().collect::<Vec<_>>());
+                let gray: Vec<Vec<usize>> = CombinationsWithReplacement::of_size(0..n, k)
+                    .order(ReplacementOrder::Gray)
+                    .collect();
+                let mut sorted = gray.clone();
+                sorted.sort();
+                assert_eq!(sorted, lex);
+                for pair in gray.windows(2) {
+                    let removed = pair[0].iter().filter(|e| !pair[1].contains(e)).count();
+                    let mut difference: Vec<isize> = vec![0; n];
+                    pair[0].iter().for_each(|e| difference[*e] += 1);
+                    pair[1].iter().for_each(|e| difference[*e] -= 1);
+                    assert!(removed <= 1);
+                    assert_eq!(difference.iter().map(|d| d.abs()).sum::<isize>(), 2);
+                }
+            }
+            for order in [
+                ReplacementOrder::Lex,
+                ReplacementOrder::ReverseLex,
+                ReplacementOrder::Gray,
+            ] {
+                let mut combos = CombinationsWithReplacement::all(0..n).order(order);
+                let mut remaining = combos.size_hint().0;
+                let all: Vec<Vec<usize>> = (0..=n)
+                    .flat_map(|k| CombinationsWithReplacement::of_size(0..n, k).order(order))
+                    .collect();
+                assert_eq!(remaining, all.len());
+                for expected in all {
+                    assert_eq!(combos.next(), Some(expected));
+                    remaining -= 1;
+                    assert_eq!(combos.size_hint(), (remaining, Some(remaining)));
+                }
+                assert_eq!(combos.next(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_combinations_shuffled_visits_each_once() {
+        for n in 0..7 {
+            let mut lex: Vec<Vec<usize>> = Combinations::all(0..n).collect();
+            let mut shuffled = Combinations::all(0..n).shuffled(n as u64);
+            assert_eq!(shuffled.size_hint(), (lex.len(), Some(lex.len())));
+            let mut combos: Vec<Vec<usize>> = shuffled.by_ref().collect();
+            assert_eq!(shuffled.size_hint(), (0, Some(0)));
+            combos.sort();
+            lex.sort();
+            assert_eq!(combos, lex);
+            for k in 0..=n + 1 {
+                let lex: Vec<Vec<usize>> = Combinations::of_size(0..n, k).collect();
+                let mut combos: Vec<Vec<usize>> =
+                    Combinations::of_size(0..n, k).shuffled(9).collect();
+                combos.sort();
+                assert_eq!(combos, lex);
+            }
+        }
+        let mut included: Vec<Vec<usize>> = Combinations::of_size(0..6, 3)
+            .must_include(&[2])
+            .shuffled(5)
+            .collect();
+        included.sort();
+        let expected: Vec<Vec<usize>> = Combinations::of_size(0..6, 3).must_include(&[2]).collect();
+        assert_eq!(included, expected);
+        let lex: Vec<Vec<usize>> = Combinations::of_size(0..10, 4).collect();
+        let shuffled: Vec<Vec<usize>> = Combinations::of_size(0..10, 4).shuffled(0).collect();
+        assert_ne!(shuffled, lex);
+    }
+
+    #[test]
+    fn test_combinations_unrank_colex() {
+        let table = CombinatoricsTable::new(130);
+        for n in 0..9 {
+            for k in 0..=n + 1 {
+                let combos = Combinations::of_size(0..n, k);
+                let colex: Vec<Vec<usize>> = combos.clone().order(Order::Colex).collect();
+                for (rank, combo) in colex.iter().enumerate() {
+                    assert_eq!(combos.unrank_colex(rank as u128).as_ref(), Some(combo));
+                    assert_eq!(
+                        combos.unrank_colex_with(rank as u128, &table).as_ref(),
+                        Some(combo)
+                    );
+                }
+                assert_eq!(combos.unrank_colex(colex.len() as u128), None);
+                assert_eq!(combos.unrank_colex_with(colex.len() as u128, &table), None);
+            }
+            let all = Combinations::all(0..n.max(4)).must_include(&[3]);
+            let colex: Vec<Vec<usize>> = all.clone().order(Order::Colex).collect();
+            for (rank, combo) in colex.iter().enumerate() {
+                assert_eq!(all.unrank_colex(rank as u128).as_ref(), Some(combo));
+                assert_eq!(
+                    all.unrank_colex_with(rank as u128, &table).as_ref(),
+                    Some(combo)
+                );
+            }
+            assert_eq!(all.unrank_colex(colex.len() as u128), None);
+        }
+        let big = Combinations::of_size(0..130, 65);
+        let total = big.total_count().unwrap();
+        assert_eq!(big.unrank_colex(total - 1), Some((65..130).collect()));
+        assert_eq!(big.unrank_colex(1).unwrap()[64], 65);
+        for rank in [0, 1, 1 << 100, total / 3, total - 1] {
+            assert_eq!(big.unrank_colex_with(rank, &table), big.unrank_colex(rank));
+        }
+    }
+
+    #[test]
+    fn test_combinations_fill_flat() {
+        for length in [5, 7, 64] {
+            let mut combos = Combinations::all(0..5).must_include(&[2]);
+            let mut expected = Combinations::all(0..5).must_include(&[2]).peekable();
+            let mut buffer = vec![0; length];
+            loop {
+                let written = combos.fill_flat(&mut buffer);
+                if written == 0 {
+                    break;
+                }
+                let rows: Vec<Vec<i32>> = expected.by_ref().take(written).collect();
+                assert!(rows.iter().all(|row| row.len() == rows[0].len()));
+                assert!(expected.peek().is_none_or(|next| {
+                    next.len() != rows[0].len() || (written + 1) * next.len() > length
+                }));
+                assert_eq!(buffer[..rows.concat().len()], rows.concat());
+            }
+            assert_eq!(expected.next(), None);
+        }
+        let mut empty = Combinations::all(0..3);
+        assert_eq!(empty.fill_flat(&mut []), 1);
+        assert_eq!(empty.fill_flat(&mut []), 0);
+        assert_eq!(empty.next(), Some(vec![0]));
+    }
+
+    #[test]
+    fn test_combinations_for_each_ref() {
+        for size in 0..8 {
+            let mut visited = Vec::new();
+            Combinations::of_size(0..6, size).for_each_ref(|combo| visited.push(combo.to_vec()));
+            assert_eq!(
+                visited,
+                Combinations::of_size(0..6, size).collect::<Vec<_>>()
+            );
+            let mut visited = Vec::new();
+            Combinations::of_size(0..6, size)
+                .order(Order::Colex)
+                .for_each_ref(|combo| visited.push(combo.to_vec()));
+            assert_eq!(
+                visited,
+                Combinations::of_size(0..6, size)
+                    .order(Order::Colex)
+                    .collect::<Vec<_>>()
+            );
+        }
+        let mut visited = Vec::new();
+        let mut combos = Combinations::all(vec!['a', 'b', 'c', 'd']);
+        combos.next();
+        combos.next();
+        combos.for_each_ref(|combo| visited.push(combo.to_vec()));
+        let expected: Vec<Vec<char>> = Combinations::all(vec!['a', 'b', 'c', 'd'])
+            .skip(2)
+            .collect();
+        assert_eq!(visited, expected);
+    }
+
+    #[test]
+    fn test_combinations_with_complements() {
+        let elements = vec![5, 1, 4, 2, 3];
+        let pairs: Vec<(Vec<i32>, Vec<i32>)> = Combinations::all(elements.clone())
+            .with_complements()
+            .collect();
+        let combos: Vec<Vec<i32>> = Combinations::all(elements).collect();
+        assert_eq!(pairs.len(), combos.len());
+        for ((subset, complement), combo) in pairs.iter().zip(combos.iter()) {
+            assert_eq!(subset, combo);
+            let expected: Vec<i32> = (1..6).filter(|e| !combo.contains(e)).collect();
+            assert_eq!(complement, &expected);
+        }
+        assert_eq!(
+            Combinations::of_size(1..3, 3).with_complements().next(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_combinations_next_with_delta() {
+        for order in [Order::Lex, Order::Colex] {
+            let mut combos = Combinations::all(0..6usize).order(order);
+            let expected: Vec<Vec<usize>> = combos.clone().collect();
+            let mut previous: Vec<usize> = Vec::new();
+            for combo in expected {
+                let (next, delta) = combos.next_with_delta().unwrap();
+                assert_eq!(next, combo);
+                let added: Vec<usize> = combo
+                    .iter()
+                    .copied()
+                    .filter(|e| !previous.contains(e))
+                    .collect();
+                let removed: Vec<usize> = previous
+                    .iter()
+                    .copied()
+                    .filter(|e| !combo.contains(e))
+                    .collect();
+                assert_eq!(delta, CombinationDelta { added, removed });
+                previous = combo;
+            }
+            assert_eq!(combos.next_with_delta(), None);
+        }
+        let mut combos = Combinations::of_size(0..5, 2);
+        combos.next_with_delta();
+        combos.nth(2);
+        let (combo, delta) = combos.next_with_delta().unwrap();
+        assert_eq!(combo, vec![1, 2]);
+        assert_eq!((delta.added, delta.removed), (vec![2], vec![0]));
+    }
+
+    #[test]
+    fn test_combinations_first_where_matches_find() {
+        let variants = [
+            Combinations::of_size(1..12, 4),
+            Combinations::all(1..10),
+            Combinations::of_size(1..12, 4).must_include(&[5]),
+            Combinations::all(1..10).must_include(&[2, 7]),
+            Combinations::of_size(1..5, 6),
+        ];
+        for combos in variants {
+            for target in 0..50 {
+                let pred = |c: &[i32]| c.iter().sum::<i32>() == target && c.len().is_multiple_of(2);
+                let expected = combos.clone().find(|c| pred(c));
+                assert_eq!(combos.first_where(pred), expected);
+                let pruned = combos.first_where_pruned(pred, |c| c.iter().sum::<i32>() > target);
+                assert_eq!(pruned, expected);
+            }
+        }
+        let mut combos = Combinations::of_size(0..6, 3).order(Order::Colex);
+        combos.nth(4);
+        assert_eq!(combos.first_where(|c| c[0] == 1), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_combinations_starting_from_matches_suffix() {
+        let variants = [
+            Combinations::of_size(1..8, 3),
+            Combinations::all(1..6),
+            Combinations::of_size(1..8, 3).order(Order::Colex),
+            Combinations::all(1..7).must_include(&[2, 5]),
+            Combinations::all_positional(vec![3, 1, 3, 2]),
+        ];
+        for combos in variants {
+            let all: Vec<Vec<i32>> = combos.clone().collect();
+            for start in all.iter() {
+                // Repeated elements in positional mode start from the first such combination.
+                let index = all.iter().position(|combo| combo == start).unwrap();
+                let generated: Vec<Vec<i32>> = combos.clone().starting_from(start).collect();
+                assert_eq!(generated, all[index..]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_combinations_with_prefix_matches_filter() {
+        let variants = [
+            Combinations::of_size(1..8, 3),
+            Combinations::all(1..7),
+            Combinations::of_size(1..8, 4).order(Order::Colex),
+            Combinations::all_positional(vec![1, 4, 2, 6, 3]),
+        ];
+        let prefixes: [&[i32]; 7] = [&[], &[1], &[3], &[1, 3], &[3, 1], &[2, 4, 5], &[9]];
+        for combos in variants {
+            for prefix in prefixes {
+                let expected: Vec<Vec<i32>> = combos
+                    .clone()
+                    .filter(|combo| combo.starts_with(prefix))
+                    .collect();
+                let generated: Vec<Vec<i32>> = combos.clone().with_prefix(prefix).collect();
+                assert_eq!(generated, expected);
+            }
+        }
+        let combos = Combinations::all_positional(vec![1, 2, 1]).with_prefix(&[2]);
+        assert_eq!(combos.collect::<Vec<_>>(), vec![vec![2], vec![2, 1]]);
+    }
+
+    #[test]
+    fn test_combinations_w_rep_is_valid_combination() {
+        let combos = CombinationsWithReplacement::all(vec![3, 1, 2]);
+        assert!(combos.is_valid_combination(&[]));
+        assert!(combos.is_valid_combination(&[1, 1, 3]));
+        assert!(!combos.is_valid_combination(&[1, 1, 1, 1]));
+        assert!(!combos.is_valid_combination(&[2, 1]));
+        for combo in CombinationsWithReplacement::of_size(1..6, 3) {
+            assert!(CombinationsWithReplacement::of_size(1..6, 3).is_valid_combination(&combo));
+        }
     }
 }
@@ -0,0 +1,281 @@
+use crate::sorted_unique;
+
+/// An iterator which generates the combinations of a set of elements which contain no two
+/// elements of any of a given list of conflicting pairs.
+///
+/// Viewing the pairs as the edges of a conflict graph on the elements, these are the independent
+/// sets of the graph.  Elements are added to a combination in increasing order, and an element is
+/// never considered while a conflicting element is in the combination, so no combination
+/// extending a conflicting pair is ever searched, rather than every combination being generated
+/// and filtered.  The elements are sorted and deduplicated, and combinations are yielded in the
+/// same order as by [`Combinations`](crate::Combinations): lexicographically within each size,
+/// with smaller combinations first when generating every size.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::ConflictFreeCombinations;
+///
+/// // Teams of three from five people, where Ann and Bob refuse to work together, as do Bob and
+/// // Cat.
+/// let conflicts = [("Ann", "Bob"), ("Bob", "Cat")];
+/// let people = ["Ann", "Bob", "Cat", "Dan", "Eve"];
+/// let teams: Vec<Vec<&str>> = ConflictFreeCombinations::of_size(people, 3, &conflicts).collect();
+/// assert_eq!(teams.len(), 5);
+/// assert_eq!(teams[0], vec!["Ann", "Cat", "Dan"]);
+/// assert!(teams.iter().all(|team| !team.contains(&"Bob") || !team.contains(&"Cat")));
+/// ```
+#[derive(Clone)]
+pub struct ConflictFreeCombinations<T> {
+    elements: Vec<T>,
+    /// The indices of the elements which conflict with each element.
+    conflicts: Vec<Vec<usize>>,
+    /// The number of chosen elements which conflict with each element.
+    blocked: Vec<usize>,
+    positions: Vec<usize>,
+    length: usize,
+    last_length: usize,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord + Clone> ConflictFreeCombinations<T> {
+    /// Creates a new `ConflictFreeCombinations` iterator which will yield every combination of
+    /// the elements in the given iterable, of every size, which contains no conflicting pair.
+    ///
+    /// Pairs which include an element not in the set are ignored, as are pairs of an element
+    /// with itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::ConflictFreeCombinations;
+    ///
+    /// // The independent sets of a path 1 - 2 - 3 - 4.
+    /// let sets: Vec<Vec<i32>> =
+    ///     ConflictFreeCombinations::all(1..=4, &[(1, 2), (2, 3), (3, 4)]).collect();
+    /// assert_eq!(
+    ///     sets,
+    ///     vec![
+    ///         vec![],
+    ///         vec![1],
+    ///         vec![2],
+    ///         vec![3],
+    ///         vec![4],
+    ///         vec![1, 3],
+    ///         vec![1, 4],
+    ///         vec![2, 4],
+    ///     ]
+    /// );
+    /// ```
+    pub fn all(elements: impl IntoIterator<Item = T>, conflicts: &[(T, T)]) -> Self {
+        let elements = sorted_unique(elements);
+        let count = elements.len();
+        ConflictFreeCombinations::new(elements, conflicts, 0, count)
+    }
+
+    /// Creates a new `ConflictFreeCombinations` iterator which will yield every combination of
+    /// the given size of the elements in the given iterable which contains no conflicting pair.
+    ///
+    /// Pairs which include an element not in the set are ignored, as are pairs of an element
+    /// with itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::ConflictFreeCombinations;
+    ///
+    /// // Non-attacking placements of three rooks on the squares of a 3x3 board.
+    /// let squares: Vec<(usize, usize)> =
+    ///     (0..3).flat_map(|row| (0..3).map(move |col| (row, col))).collect();
+    /// let mut attacks = Vec::new();
+    /// for a in squares.iter() {
+    ///     for b in squares.iter() {
+    ///         if a < b && (a.0 == b.0 || a.1 == b.1) {
+    ///             attacks.push((*a, *b));
+    ///         }
+    ///     }
+    /// }
+    /// assert_eq!(ConflictFreeCombinations::of_size(squares, 3, &attacks).count(), 6);
+    /// ```
+    pub fn of_size(
+        elements: impl IntoIterator<Item = T>,
+        size: usize,
+        conflicts: &[(T, T)],
+    ) -> Self {
+        let elements = sorted_unique(elements);
+        ConflictFreeCombinations::new(elements, conflicts, size, size)
+    }
+
+    /// Creates a new `ConflictFreeCombinations` iterator over the given sorted, deduplicated
+    /// elements, which will yield combinations with sizes from `first_length` through
+    /// `last_length`.
+    fn new(elements: Vec<T>, pairs: &[(T, T)], first_length: usize, last_length: usize) -> Self {
+        let count = elements.len();
+        let mut conflicts = vec![Vec::new(); count];
+        for (a, b) in pairs {
+            if let (Ok(a), Ok(b)) = (elements.binary_search(a), elements.binary_search(b)) {
+                if a != b && !conflicts[a].contains(&b) {
+                    conflicts[a].push(b);
+                    conflicts[b].push(a);
+                }
+            }
+        }
+        ConflictFreeCombinations {
+            elements,
+            conflicts,
+            blocked: vec![0; count],
+            positions: Vec::with_capacity(last_length.min(count)),
+            length: first_length,
+            last_length,
+            started: false,
+            done: first_length > count,
+        }
+    }
+
+    /// Returns the sorted, deduplicated elements from which this iterator chooses combinations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::ConflictFreeCombinations;
+    ///
+    /// let combos = ConflictFreeCombinations::all(vec![3, 1, 3, 2], &[(1, 2)]);
+    /// assert_eq!(combos.elements(), &[1, 2, 3]);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Adds the element with the given index to the combination.
+    fn place(&mut self, index: usize) {
+        for other in self.conflicts[index].iter() {
+            self.blocked[*other] += 1;
+        }
+        self.positions.push(index);
+    }
+
+    /// Removes and returns the index of the last element added to the combination, if any.
+    fn unplace(&mut self) -> Option<usize> {
+        let index = self.positions.pop()?;
+        for other in self.conflicts[index].iter() {
+            self.blocked[*other] -= 1;
+        }
+        Some(index)
+    }
+
+    /// Returns the index of the first element which may be added to the combination, skipping
+    /// every element with index not greater than `previous`, if given, or than the last element
+    /// added otherwise.  Elements too late to leave room for the rest of the combination are not
+    /// considered.
+    fn next_candidate(&self, previous: Option<usize>) -> Option<usize> {
+        let start = match (previous, self.positions.last().copied()) {
+            (Some(index), _) | (None, Some(index)) => index + 1,
+            (None, None) => 0,
+        };
+        let needed = self.length - self.positions.len();
+        let end = (self.elements.len() + 1).checked_sub(needed)?;
+        (start..end).find(|index| self.blocked[*index] == 0)
+    }
+
+    /// Completes the combination with the lexicographically smallest conflict-free elements,
+    /// backtracking as necessary.  If `previous` is given, the search resumes by trying elements
+    /// after `previous` in the next position.  Returns `true` if the combination was completed, or
+    /// `false` if no further combinations of the current size exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            if previous.is_none() && self.positions.len() == self.length {
+                return true;
+            }
+            match self.next_candidate(previous) {
+                Some(index) => {
+                    self.place(index);
+                    previous = None;
+                }
+                None => match self.unplace() {
+                    Some(index) => previous = Some(index),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Moves to the next combination, moving on to the next size if generating combinations of
+    /// several sizes.  Returns `false` and marks the iterator as done if no combinations remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let mut found = if self.started {
+            match self.unplace() {
+                Some(index) => self.fill(Some(index)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.fill(None)
+        };
+        while !found && self.length < self.last_length {
+            self.length += 1;
+            found = self.fill(None);
+        }
+        if !found {
+            self.done = true;
+        }
+        found
+    }
+}
+
+impl<T: Ord + Clone> Iterator for ConflictFreeCombinations<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next combination and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        Some(
+            self.positions
+                .iter()
+                .map(|p| self.elements[*p].clone())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_conflict_free_combinations_match_filtered_combinations() {
+        let conflicts = [
+            (0, 3),
+            (1, 2),
+            (2, 5),
+            (4, 6),
+            (6, 0),
+            (2, 2),
+            (3, 9),
+            (3, 0),
+        ];
+        let allowed = |combo: &Vec<i32>| {
+            conflicts
+                .iter()
+                .all(|(a, b)| a == b || !combo.contains(a) || !combo.contains(b))
+        };
+        for size in 0..=8 {
+            let expected: Vec<Vec<i32>> =
+                Combinations::of_size(0..7, size).filter(allowed).collect();
+            let generated: Vec<Vec<i32>> =
+                ConflictFreeCombinations::of_size(0..7, size, &conflicts).collect();
+            assert_eq!(generated, expected);
+        }
+        let expected: Vec<Vec<i32>> = Combinations::all(0..7).filter(allowed).collect();
+        let generated: Vec<Vec<i32>> = ConflictFreeCombinations::all(0..7, &conflicts).collect();
+        assert_eq!(generated, expected);
+        let empty: Vec<Vec<i32>> = ConflictFreeCombinations::all(Vec::new(), &[]).collect();
+        assert_eq!(empty, vec![Vec::<i32>::new()]);
+    }
+}
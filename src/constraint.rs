@@ -0,0 +1,177 @@
+use std::ops::RangeInclusive;
+
+/// A declarative constraint on the sequences yielded by a generator, given to builders such as
+/// [`Permutations::with_constraint`] and [`MaskedSequences::with_constraint`].
+///
+/// Constraints are checked each time the generator extends a partial sequence by one element, so
+/// a branch of the search is pruned as soon as it breaks a constraint, and no sequence extending
+/// it is ever generated.  Several constraints may be given, in which case every sequence yielded
+/// satisfies all of them.
+///
+/// [`Permutations::with_constraint`]: crate::Permutations::with_constraint
+/// [`MaskedSequences::with_constraint`]: crate::MaskedSequences::with_constraint
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{Constraint, MaskedSequences};
+///
+/// // Rolls of three dice totalling 10 to 12, never rolling the same number twice in a row.
+/// let rolls = MaskedSequences::repeated(1..=6, 3)
+///     .with_constraint(Constraint::NoAdjacentEqual)
+///     .with_constraint(Constraint::sum_in_range(10..=12));
+/// let expected = MaskedSequences::repeated(1..=6, 3)
+///     .filter(|roll| roll[0] != roll[1] && roll[1] != roll[2])
+///     .filter(|roll| (10..=12).contains(&roll.iter().sum::<i32>()));
+/// assert_eq!(rolls.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone)]
+pub enum Constraint<T> {
+    /// No two adjacent elements of the sequence are equal.
+    NoAdjacentEqual,
+    /// The sum of the weights of the elements of the sequence lies in the given range.  A partial
+    /// sequence is pruned once the smallest and largest weights among the candidates for the
+    /// remaining positions show that the sum can no longer reach the range.
+    SumInRange {
+        /// The weight of each element.
+        weight: fn(&T) -> i64,
+        /// The range in which the sum of the weights must lie.
+        range: RangeInclusive<i64>,
+    },
+    /// The given function returns `true` for the sequence.  The function is called with every
+    /// partial sequence as it is built, beginning with the empty sequence, and returning `false`
+    /// prunes every sequence which extends it, so it must only return `false` for a partial
+    /// sequence if no completion of it could be accepted.
+    Custom(fn(&[&T]) -> bool),
+}
+
+impl<T: Copy + Into<i64>> Constraint<T> {
+    /// Returns a [`Constraint::SumInRange`] which requires the sum of the elements themselves to
+    /// lie in the given range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Constraint, Permutations};
+    ///
+    /// let sum_to_four = Constraint::sum_in_range(0..=4);
+    /// let perms = Permutations::of_length(1..6, 2).with_constraint(sum_to_four);
+    /// assert_eq!(perms.collect::<Vec<_>>(), vec![vec![1, 2], vec![1, 3], vec![2, 1], vec![3, 1]]);
+    /// ```
+    pub fn sum_in_range(range: RangeInclusive<i64>) -> Self {
+        Constraint::SumInRange {
+            weight: |element| (*element).into(),
+            range,
+        }
+    }
+}
+
+/// The constraints given to a generator, along with the equality of its elements, which is
+/// captured when the first constraint is added so that checking them needs no bounds on `T`.
+#[derive(Debug, Clone)]
+pub(crate) struct Constraints<T> {
+    constraints: Vec<Constraint<T>>,
+    equal: fn(&T, &T) -> bool,
+}
+
+impl<T> Constraints<T> {
+    /// Returns an empty set of constraints, which every sequence satisfies.
+    pub(crate) fn new() -> Self {
+        Constraints {
+            constraints: Vec::new(),
+            equal: |_, _| false,
+        }
+    }
+
+    /// Adds the given constraint.
+    pub(crate) fn push(&mut self, constraint: Constraint<T>)
+    where
+        T: PartialEq,
+    {
+        self.equal = T::eq;
+        self.constraints.push(constraint);
+    }
+
+    /// Returns `true` if there are no constraints.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    /// Returns `true` if the given partial sequence, whose remaining positions will each be
+    /// filled from the given candidates, may still be completed to a sequence which satisfies
+    /// every constraint.  Every shorter prefix of the sequence is assumed to have been allowed.
+    pub(crate) fn allow<'a>(
+        &self,
+        prefix: &[&T],
+        rest: impl Iterator<Item = &'a [T]> + Clone,
+    ) -> bool
+    where
+        T: 'a,
+    {
+        self.constraints.iter().all(|constraint| match constraint {
+            Constraint::NoAdjacentEqual => match prefix {
+                [.., previous, last] => !(self.equal)(previous, last),
+                _ => true,
+            },
+            Constraint::SumInRange { weight, range } => {
+                let sum = prefix
+                    .iter()
+                    .fold(0i64, |sum, e| sum.saturating_add(weight(e)));
+                let (low, high) = rest.clone().fold((sum, sum), |(low, high), candidates| {
+                    let weights = candidates.iter().map(weight);
+                    let lightest = weights.clone().min().unwrap_or(0);
+                    let heaviest = weights.max().unwrap_or(0);
+                    (low.saturating_add(lightest), high.saturating_add(heaviest))
+                });
+                low <= *range.end() && high >= *range.start()
+            }
+            Constraint::Custom(allows) => allows(prefix),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MaskedSequences, Permutations};
+
+    type Filter = fn(&Vec<i32>) -> bool;
+
+    #[test]
+    fn test_constraint_matches_filter() {
+        let constraints: [(Constraint<i32>, Filter); 3] = [
+            (Constraint::NoAdjacentEqual, |seq| {
+                seq.windows(2).all(|pair| pair[0] != pair[1])
+            }),
+            (Constraint::sum_in_range(6..=9), |seq| {
+                (6..=9).contains(&seq.iter().sum::<i32>())
+            }),
+            (
+                Constraint::Custom(|prefix| prefix.first().is_none_or(|first| **first < 3)),
+                |seq| seq.first().is_none_or(|first| *first < 3),
+            ),
+        ];
+        for (constraint, accepts) in &constraints {
+            let sequences = MaskedSequences::repeated(0..5, 3).with_constraint(constraint.clone());
+            let expected = MaskedSequences::repeated(0..5, 3).filter(accepts);
+            assert_eq!(sequences.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+            let perms = Permutations::all(0..5).with_constraint(constraint.clone());
+            let expected = Permutations::all(0..5).filter(accepts);
+            assert_eq!(perms.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+        }
+        let accepts_all = |seq: &Vec<i32>| constraints.iter().all(|(_, accepts)| accepts(seq));
+        let (sequences, perms) = constraints.iter().fold(
+            (MaskedSequences::repeated(0..5, 4), Permutations::all(0..5)),
+            |(sequences, perms), (constraint, _)| {
+                (
+                    sequences.with_constraint(constraint.clone()),
+                    perms.with_constraint(constraint.clone()),
+                )
+            },
+        );
+        let expected = MaskedSequences::repeated(0..5, 4).filter(accepts_all);
+        assert_eq!(sequences.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+        let expected = Permutations::all(0..5).filter(accepts_all);
+        assert_eq!(perms.collect::<Vec<_>>(), expected.collect::<Vec<_>>());
+    }
+}
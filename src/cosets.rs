@@ -0,0 +1,163 @@
+use crate::{Permutation, PermutationGroup, SymmetricGroup};
+
+/// An iterator which generates one representative of each coset, or double coset, of subgroups
+/// of the symmetric group `S_n`.
+///
+/// The permutations of `S_n` are generated in lexicographic order, as by [`SymmetricGroup`], and
+/// a permutation is yielded only if it is the lexicographically smallest element of its coset,
+/// which is checked on the fly by multiplying it by every element of the subgroups.  No record
+/// of previously yielded representatives is kept, so memory use depends only on the sizes of the
+/// subgroups, not on the number of cosets, although every permutation of `S_n` is still visited.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{Cosets, Permutation, PermutationGroup};
+///
+/// // The subgroup swapping the points 0 and 1 has three cosets on each side in S_3.
+/// let swap = Permutation::from_images(vec![1, 0]).unwrap();
+/// let subgroup = PermutationGroup::generated_by(vec![swap]);
+///
+/// let left: Vec<Vec<usize>> = Cosets::left(&subgroup, 3).map(|g| g.into_images()).collect();
+/// assert_eq!(left, vec![vec![0, 1, 2], vec![0, 2, 1], vec![1, 2, 0]]);
+///
+/// let right: Vec<Vec<usize>> = Cosets::right(&subgroup, 3).map(|g| g.into_images()).collect();
+/// assert_eq!(right, vec![vec![0, 1, 2], vec![0, 2, 1], vec![2, 0, 1]]);
+/// ```
+pub struct Cosets {
+    candidates: SymmetricGroup,
+    /// The elements by which each candidate is multiplied on the left.
+    left_elements: Vec<Permutation>,
+    /// The elements by which each candidate is multiplied on the right.
+    right_elements: Vec<Permutation>,
+}
+
+impl Cosets {
+    /// Creates a new `Cosets` iterator which will yield the smallest element of each left coset
+    /// `gH` of the given subgroup `H` in `S_n`.  There are `n! / |H|` such cosets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subgroup moves a point not less than `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Cosets, Permutation, PermutationGroup};
+    ///
+    /// let rotation = Permutation::from_images(vec![1, 2, 3, 0]).unwrap();
+    /// let rotations = PermutationGroup::generated_by(vec![rotation]);
+    /// assert_eq!(Cosets::left(&rotations, 4).count(), 6);
+    /// assert_eq!(Cosets::left(&rotations, 5).count(), 30);
+    /// ```
+    pub fn left(subgroup: &PermutationGroup, n: usize) -> Self {
+        Cosets::new(&PermutationGroup::generated_by(Vec::new()), subgroup, n)
+    }
+
+    /// Creates a new `Cosets` iterator which will yield the smallest element of each right coset
+    /// `Hg` of the given subgroup `H` in `S_n`.  There are `n! / |H|` such cosets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subgroup moves a point not less than `n`.
+    pub fn right(subgroup: &PermutationGroup, n: usize) -> Self {
+        Cosets::new(subgroup, &PermutationGroup::generated_by(Vec::new()), n)
+    }
+
+    /// Creates a new `Cosets` iterator which will yield the smallest element of each double coset
+    /// `HgK` of the given subgroups `H` and `K` in `S_n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either subgroup moves a point not less than `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Cosets, Permutation, PermutationGroup};
+    ///
+    /// let swap = Permutation::from_images(vec![1, 0]).unwrap();
+    /// let subgroup = PermutationGroup::generated_by(vec![swap]);
+    /// let double: Vec<Vec<usize>> = Cosets::double(&subgroup, &subgroup, 3)
+    ///     .map(|g| g.into_images())
+    ///     .collect();
+    /// assert_eq!(double, vec![vec![0, 1, 2], vec![0, 2, 1]]);
+    /// ```
+    pub fn double(left: &PermutationGroup, right: &PermutationGroup, n: usize) -> Self {
+        Cosets::new(left, right, n)
+    }
+
+    /// Creates a new `Cosets` iterator over the double cosets of the given subgroups, of which
+    /// the left and right cosets are the special cases where one subgroup is trivial.
+    fn new(left: &PermutationGroup, right: &PermutationGroup, n: usize) -> Self {
+        assert!(
+            left.degree() <= n && right.degree() <= n,
+            "the subgroups must act on the points 0..{n}"
+        );
+        Cosets {
+            candidates: SymmetricGroup::new(n),
+            left_elements: left.elements().collect(),
+            right_elements: right.elements().collect(),
+        }
+    }
+
+    /// Returns `true` if no product `l g r` of the given candidate `g` with elements `l` and `r`
+    /// of the subgroups is lexicographically smaller than the candidate.
+    fn is_canonical(&self, candidate: &Permutation) -> bool {
+        let images = candidate.images();
+        self.left_elements.iter().all(|l| {
+            self.right_elements.iter().all(|r| {
+                let product = (0..images.len()).map(|x| l.image(images[r.image(x)]));
+                product.ge(images.iter().copied())
+            })
+        })
+    }
+}
+
+impl Iterator for Cosets {
+    type Item = Permutation;
+
+    /// Returns the next coset representative and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let candidate = self.candidates.next()?;
+            if self.is_canonical(&candidate) {
+                return Some(candidate);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn perm(images: &[usize]) -> Permutation {
+        Permutation::from_images(images.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_cosets_partition_symmetric_group() {
+        let h = PermutationGroup::generated_by(vec![perm(&[1, 2, 0]), perm(&[1, 0])]);
+        let k = PermutationGroup::generated_by(vec![perm(&[0, 1, 2, 4, 3])]);
+        let expected: BTreeSet<Permutation> = SymmetricGroup::new(5).collect();
+        let identity = PermutationGroup::generated_by(Vec::new());
+        for (left, right, count) in [(&identity, &h, 20), (&h, &identity, 20), (&h, &k, 13)] {
+            let mut covered = BTreeSet::new();
+            let mut representatives = 0;
+            for g in Cosets::double(left, right, 5) {
+                representatives += 1;
+                for l in left.elements() {
+                    for r in right.elements() {
+                        let product = l.compose(&g).compose(&r).with_degree(5).unwrap();
+                        assert!(product >= g);
+                        covered.insert(product);
+                    }
+                }
+            }
+            assert_eq!(representatives, count);
+            assert_eq!(covered, expected);
+        }
+    }
+}
@@ -0,0 +1,332 @@
+/// An iterator which generates every pair of an item from one generator and an item from another,
+/// created by [`cross`].
+///
+/// The pairs are yielded in lexicographic order: every pairing of the first item of the first
+/// generator is yielded before any pairing of its second item, and so on.  The second generator
+/// is cloned afresh for each item of the first, so it is never collected into memory, and the
+/// size hints of the two generators are multiplied so that the total count of the product is
+/// known up front.  When the second generator knows its exact length, [`Iterator::nth`] skips
+/// whole rows of the product at once, advancing the first generator directly rather than
+/// generating every skipped pair.  When both generators are [`RankedIterator`]s, so is the
+/// product, so its total count is known and any pair can be looked up by its rank.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{cross, Combinations, Permutations};
+///
+/// let mut pairs = cross(Combinations::of_size(1..4, 2), Permutations::all(vec!['x', 'y']));
+/// assert_eq!(pairs.size_hint(), (15, Some(15)));
+/// assert_eq!(pairs.next(), Some((vec![1, 2], vec![])));
+/// assert_eq!(pairs.next(), Some((vec![1, 2], vec!['x'])));
+/// assert_eq!(pairs.nth(6), Some((vec![1, 3], vec!['x', 'y'])));
+/// assert_eq!(pairs.count(), 6);
+/// ```
+pub struct Cross<A: Iterator, B> {
+    first: A,
+    second: B,
+    current: Option<(A::Item, B)>,
+}
+
+/// Returns an iterator over every pair of an item from the first generator with an item from the
+/// second, as in a nested loop.  The second generator must be `Clone`, since it is restarted for
+/// every item of the first.  Products of more than two generators can be formed by nesting calls
+/// to `cross`.  This is also available as a method, [`CombinatorialExt::cross`].
+///
+/// [`CombinatorialExt::cross`]: crate::CombinatorialExt::cross
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{cross, Combinations, Pairings};
+///
+/// let pairs: Vec<(Vec<char>, usize)> =
+///     cross(Combinations::of_size("abc".chars(), 2), 0..2).collect();
+/// assert_eq!(
+///     pairs,
+///     vec![
+///         (vec!['a', 'b'], 0),
+///         (vec!['a', 'b'], 1),
+///         (vec!['a', 'c'], 0),
+///         (vec!['a', 'c'], 1),
+///         (vec!['b', 'c'], 0),
+///         (vec!['b', 'c'], 1),
+///     ]
+/// );
+///
+/// let triples = cross(cross(0..3, Pairings::of(1..5)), Combinations::all(0..4));
+/// assert_eq!(triples.count(), 3 * 3 * 16);
+/// ```
+pub fn cross<A, B>(first: A, second: B) -> Cross<A::IntoIter, B::IntoIter>
+where
+    A: IntoIterator,
+    B: IntoIterator,
+    B::IntoIter: Clone,
+{
+    Cross {
+        first: first.into_iter(),
+        second: second.into_iter(),
+        current: None,
+    }
+}
+
+impl<A: Iterator, B> Cross<A, B> {
+    /// Returns the two generators from which the pairs are formed, discarding the progress through
+    /// the current item of the first generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::cross;
+    ///
+    /// let mut pairs = cross(0..3, 0..2);
+    /// pairs.next();
+    /// let (first, second) = pairs.into_parts();
+    /// assert_eq!(first.collect::<Vec<_>>(), vec![1, 2]);
+    /// assert_eq!(second.collect::<Vec<_>>(), vec![0, 1]);
+    /// ```
+    pub fn into_parts(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Clone for Cross<A, B>
+where
+    A: Iterator + Clone,
+    A::Item: Clone,
+    B: Clone,
+{
+    fn clone(&self) -> Self {
+        Cross {
+            first: self.first.clone(),
+            second: self.second.clone(),
+            current: self.current.clone(),
+        }
+    }
+}
+
+/// Returns the exact length given by a size hint, if it is exact.
+fn exact_len(hint: (usize, Option<usize>)) -> Option<usize> {
+    match hint {
+        (lower, Some(upper)) if lower == upper => Some(lower),
+        _ => None,
+    }
+}
+
+impl<A, B> Iterator for Cross<A, B>
+where
+    A: Iterator,
+    A::Item: Clone,
+    B: Iterator + Clone,
+{
+    type Item = (A::Item, B::Item);
+
+    /// Returns the next pair and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((item, rest)) = &mut self.current {
+                if let Some(other) = rest.next() {
+                    return Some((item.clone(), other));
+                }
+            }
+            self.current = None;
+            let item = self.first.next()?;
+            self.current = Some((item, self.second.clone()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (first_lower, first_upper) = self.first.size_hint();
+        let (second_lower, second_upper) = self.second.size_hint();
+        let (rest_lower, rest_upper) = match &self.current {
+            Some((_, rest)) => rest.size_hint(),
+            None => (0, Some(0)),
+        };
+        let lower = first_lower
+            .saturating_mul(second_lower)
+            .saturating_add(rest_lower);
+        let upper = match (first_upper, second_upper, rest_upper) {
+            (Some(0), _, rest) | (_, Some(0), rest) => rest,
+            (Some(first), Some(second), Some(rest)) => first
+                .checked_mul(second)
+                .and_then(|product| product.checked_add(rest)),
+            _ => None,
+        };
+        (lower, upper)
+    }
+
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
+        if let Some((_, rest)) = &mut self.current {
+            match exact_len(rest.size_hint()) {
+                Some(len) if n >= len => {
+                    n -= len;
+                    self.current = None;
+                }
+                _ => {
+                    for _ in 0..n {
+                        self.next()?;
+                    }
+                    return self.next();
+                }
+            }
+        }
+        if let Some(len) = exact_len(self.second.size_hint()) {
+            if len == 0 {
+                return None;
+            }
+            let item = self.first.nth(n / len)?;
+            let mut rest = self.second.clone();
+            let other = rest.nth(n % len)?;
+            self.current = Some((item.clone(), rest));
+            return Some((item, other));
+        }
+        for _ in 0..n {
+            self.next()?;
+        }
+        self.next()
+    }
+}
+
+/// A generator which knows how many items it yields from the beginning and can look up any of
+/// them by its rank, without generating the items before it.  This is implemented by
+/// [`Combinations`](crate::Combinations) and [`Permutations`](crate::Permutations), and by the
+/// [`Cross`] product of any two ranked generators.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{CombinatorialExt, Combinations, Permutations, RankedIterator};
+///
+/// let pairs = Combinations::of_size(0..20, 10).cross(Permutations::of_length(0..12, 12));
+/// assert_eq!(pairs.total_count(), Some(184_756 * 479_001_600));
+/// let last = pairs.unrank(184_756 * 479_001_600 - 1).unwrap();
+/// assert_eq!(last, ((10..20).collect(), (0..12).rev().collect()));
+/// ```
+pub trait RankedIterator: Iterator {
+    /// Returns the total number of items which this generator yields from the beginning,
+    /// regardless of how far it has advanced, or `None` if the count is not known or does not fit
+    /// in a `u128`.
+    fn total_count(&self) -> Option<u128>;
+
+    /// Returns the item with the given rank, counting from zero in the order in which this
+    /// generator yields its items from the beginning, regardless of how far it has advanced.
+    /// Returns `None` if the rank is not less than the total count, or the count is not known.
+    fn unrank(&self, rank: u128) -> Option<Self::Item>;
+}
+
+impl<A, B> RankedIterator for Cross<A, B>
+where
+    A: RankedIterator,
+    A::Item: Clone,
+    B: RankedIterator + Clone,
+{
+    /// Returns the product of the total counts of the two generators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{cross, Combinations, Permutations, RankedIterator};
+    ///
+    /// let pairs = cross(Combinations::of_size(0..5, 2), Permutations::of_length(0..4, 2));
+    /// assert_eq!(pairs.total_count(), Some(10 * 12));
+    /// let pairs = cross(Combinations::all(0..100), Combinations::all(0..100));
+    /// assert_eq!(pairs.total_count(), None);
+    /// ```
+    fn total_count(&self) -> Option<u128> {
+        self.first
+            .total_count()?
+            .checked_mul(self.second.total_count()?)
+    }
+
+    /// Returns the pair with the given rank, made up of the items of the two generators whose
+    /// ranks are the quotient and remainder of the rank by the total count of the second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{cross, Combinations, Permutations, RankedIterator};
+    ///
+    /// let pairs = cross(Combinations::of_size(1..4, 2), Permutations::all(vec!['x', 'y']));
+    /// assert_eq!(pairs.unrank(0), Some((vec![1, 2], vec![])));
+    /// assert_eq!(pairs.unrank(8), Some((vec![1, 3], vec!['x', 'y'])));
+    /// assert_eq!(pairs.unrank(15), None);
+    /// ```
+    fn unrank(&self, rank: u128) -> Option<Self::Item> {
+        let count = self.second.total_count()?;
+        if count == 0 {
+            return None;
+        }
+        let item = self.first.unrank(rank / count)?;
+        Some((item, self.second.unrank(rank % count)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Combinations, CombinatorialExt, Permutations};
+
+    #[test]
+    fn test_cross_matches_nested_loops() {
+        let expected: Vec<(Vec<i32>, Vec<i32>)> = Combinations::all(0..3)
+            .flat_map(|combo| Permutations::all(5..8).map(move |perm| (combo.clone(), perm)))
+            .collect();
+        let pairs = cross(Combinations::all(0..3), Permutations::all(5..8));
+        assert_eq!(pairs.size_hint(), (expected.len(), Some(expected.len())));
+        assert_eq!(pairs.collect::<Vec<_>>(), expected);
+        for n in 0..=expected.len() {
+            let mut pairs = cross(Combinations::all(0..3), Permutations::all(5..8));
+            pairs.next();
+            assert_eq!(pairs.nth(n), expected.get(n + 1).cloned());
+            assert_eq!(
+                pairs.size_hint().1,
+                Some(expected.len().saturating_sub(n + 2))
+            );
+            assert_eq!(
+                pairs.collect::<Vec<_>>(),
+                expected[(n + 2).min(expected.len())..]
+            );
+        }
+        assert_eq!(cross(0..3, 0..0).next(), None);
+        assert_eq!(cross(0..3, 0..0).nth(2), None);
+        assert_eq!(cross(0..0, 0..3).size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_cross_ranked() {
+        let combos = Combinations::all(1..5).must_include(&[2]);
+        let perms = Permutations::of_length(0..4, 2);
+        let pairs = combos.clone().cross(perms.clone());
+        let triples = pairs.clone().cross(Combinations::of_size(0..4, 3));
+        let expected: Vec<_> = pairs.clone().collect();
+        assert_eq!(pairs.total_count(), Some(expected.len() as u128));
+        for (rank, pair) in expected.iter().enumerate() {
+            assert_eq!(pairs.unrank(rank as u128).as_ref(), Some(pair));
+        }
+        assert_eq!(pairs.unrank(expected.len() as u128), None);
+        let expected: Vec<_> = triples.clone().collect();
+        assert_eq!(triples.total_count(), Some(expected.len() as u128));
+        for (rank, triple) in expected.iter().enumerate() {
+            assert_eq!(triples.unrank(rank as u128).as_ref(), Some(triple));
+        }
+
+        // Ranks count from the beginning, however far the product has advanced.
+        let mut pairs = combos.cross(perms);
+        pairs.nth(20);
+        assert_eq!(pairs.total_count(), Some(expected.len() as u128 / 4));
+        assert_eq!(pairs.unrank(0), Some((vec![2], vec![0, 1])));
+
+        let pinned = Permutations::of_length(0..4, 2).with_fixed(&[(0, 1)]);
+        assert_eq!(
+            cross(Combinations::all(0..2), pinned.clone()).total_count(),
+            None
+        );
+        assert_eq!(cross(Combinations::all(0..2), pinned).unrank(0), None);
+        let empty = Combinations::of_size(0..2, 3);
+        assert_eq!(
+            cross(Combinations::all(0..2), empty.clone()).total_count(),
+            Some(0)
+        );
+        assert_eq!(cross(Combinations::all(0..2), empty).unrank(0), None);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{sorted_unique, Combinations, WithComplements};
+
+/// An iterator which generates ordered pairs of disjoint combinations of a set of elements, with
+/// specified sizes `k` and `m`.
+///
+/// Each first combination is generated together with its complement, and the second combinations
+/// are then drawn from that complement, so no pairs are generated only to be rejected.  Pairs are
+/// yielded in lexicographic order of their first combinations, then of their second combinations.
+/// There are `C(n, k) * C(n - k, m)` pairs of `n` elements.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::DisjointPairs;
+///
+/// let mut pairs = DisjointPairs::new(vec!['a', 'b', 'c'], 1, 1);
+/// assert_eq!(pairs.next(), Some((vec!['a'], vec!['b'])));
+/// assert_eq!(pairs.next(), Some((vec!['a'], vec!['c'])));
+/// assert_eq!(pairs.next(), Some((vec!['b'], vec!['a'])));
+/// assert_eq!(pairs.count(), 3);
+///
+/// // A committee of four from ten people, and a subcommittee of two from the rest.
+/// assert_eq!(DisjointPairs::new(0..10, 4, 2).count(), 210 * 15);
+/// ```
+#[derive(Clone)]
+pub struct DisjointPairs<T> {
+    elements: Vec<T>,
+    firsts: WithComplements<T>,
+    current: Option<(Vec<T>, Combinations<T>)>,
+    second_size: usize,
+}
+
+/// The indices of the elements of the two combinations in a pair.
+type PairIndices = (Vec<usize>, Vec<usize>);
+
+impl<T: Ord + Clone> DisjointPairs<T> {
+    /// Creates a new `DisjointPairs` iterator which will yield every pair of disjoint
+    /// combinations of the elements in the given iterable, where the first has size `k` and the
+    /// second has size `m`.  If `k + m` is greater than the number of unique elements, then no
+    /// pairs are yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::DisjointPairs;
+    ///
+    /// let mut pairs = DisjointPairs::new(1..4, 2, 0);
+    /// assert_eq!(pairs.next(), Some((vec![1, 2], vec![])));
+    /// assert_eq!(pairs.count(), 2);
+    ///
+    /// assert_eq!(DisjointPairs::new(1..4, 2, 2).next(), None);
+    /// ```
+    pub fn new(elements: impl IntoIterator<Item = T>, k: usize, m: usize) -> Self {
+        let elements = sorted_unique(elements);
+        DisjointPairs {
+            firsts: Combinations::of_size_unchecked(elements.clone(), k).with_complements(),
+            elements,
+            current: None,
+            second_size: m,
+        }
+    }
+
+    /// Returns the sorted, deduplicated elements from which both combinations of each pair are
+    /// drawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::DisjointPairs;
+    ///
+    /// assert_eq!(DisjointPairs::new(vec![5, 1, 5, 3], 1, 1).elements(), &[1, 3, 5]);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the pair which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if none remain.  Since the next pair is only found
+    /// when the iterator advances, this searches for it using a copy of the iterator, so it costs
+    /// about as much as a call to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::DisjointPairs;
+    ///
+    /// let mut pairs = DisjointPairs::new(1..4, 1, 1);
+    /// assert_eq!(pairs.next(), Some((vec![1], vec![2])));
+    /// assert_eq!(pairs.current(), Some((vec![1], vec![3])));
+    /// assert_eq!(pairs.next(), Some((vec![1], vec![3])));
+    /// ```
+    pub fn current(&self) -> Option<(Vec<T>, Vec<T>)> {
+        self.clone().next()
+    }
+
+    /// Consumes the iterator and returns its elements, as given by [`DisjointPairs::elements`],
+    /// along with the indices of the elements of both combinations in the next pair which would
+    /// have been yielded, or `None` if no pairs remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::DisjointPairs;
+    ///
+    /// let mut pairs = DisjointPairs::new(vec!['c', 'b', 'a'], 1, 2);
+    /// assert_eq!(pairs.next(), Some((vec!['a'], vec!['b', 'c'])));
+    /// assert_eq!(pairs.into_parts(), (vec!['a', 'b', 'c'], Some((vec![1], vec![0, 2]))));
+    /// ```
+    pub fn into_parts(mut self) -> (Vec<T>, Option<PairIndices>) {
+        let next = self.next().map(|(first, second)| {
+            let indices = |combo: Vec<T>| -> Vec<usize> {
+                let index = |element: &T| self.elements.binary_search(element).unwrap();
+                combo.iter().map(index).collect()
+            };
+            (indices(first), indices(second))
+        });
+        (self.elements, next)
+    }
+}
+
+impl<T: Ord + Clone> Iterator for DisjointPairs<T> {
+    type Item = (Vec<T>, Vec<T>);
+
+    /// Returns the next pair of combinations and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((first, seconds)) = &mut self.current {
+                if let Some(second) = seconds.next() {
+                    return Some((first.clone(), second));
+                }
+            }
+            let (first, complement) = self.firsts.next()?;
+            let seconds = Combinations::of_size_unchecked(complement, self.second_size);
+            self.current = Some((first, seconds));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combination_count;
+
+    #[test]
+    fn test_disjoint_pairs_match_filtered_product() {
+        for n in 0..7 {
+            for k in 0..=n {
+                for m in 0..=n + 1 - k {
+                    let mut expected = Vec::new();
+                    for first in Combinations::of_size(0..n, k) {
+                        for second in Combinations::of_size(0..n, m) {
+                            if second.iter().all(|element| !first.contains(element)) {
+                                expected.push((first.clone(), second));
+                            }
+                        }
+                    }
+                    let pairs: Vec<(Vec<usize>, Vec<usize>)> =
+                        DisjointPairs::new(0..n, k, m).collect();
+                    assert_eq!(pairs, expected);
+                    let count =
+                        combination_count(n, k).unwrap() * combination_count(n - k, m).unwrap_or(0);
+                    assert_eq!(pairs.len() as u128, count);
+                }
+            }
+        }
+        let mut items = DisjointPairs::new(0..5, 2, 1);
+        while let Some(current) = items.current() {
+            assert_eq!(items.next(), Some(current));
+        }
+        assert_eq!(items.next(), None);
+    }
+}
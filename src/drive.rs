@@ -0,0 +1,162 @@
+use std::time::{Duration, Instant};
+
+/// A handle to an enumeration which is being driven in bounded time slices.
+///
+/// A `Drive` is created by [`drive`], which runs the first time slice immediately.  Between slices,
+/// the handle holds the partially consumed generator, so the caller is free to do other work (such
+/// as rendering a frame) before calling [`Drive::resume`] to run the next slice.
+pub struct Drive<I, F> {
+    generator: I,
+    time_slice: Duration,
+    callback: F,
+    processed: usize,
+    finished: bool,
+}
+
+/// Passes items from the given generator to the given callback until either the generator is
+/// exhausted or the given time slice has elapsed, then returns a [`Drive`] handle which can be
+/// used to resume the enumeration in later time slices.
+///
+/// At least one item is processed in every time slice, so the enumeration always makes progress
+/// even if the time slice is very short.  The elapsed time is checked after every item, so a slice
+/// may overrun by the time taken to generate and process a single item.
+///
+/// Time slices are measured using [`Instant`], which is not available on
+/// `wasm32-unknown-unknown`, so this panics there.  In the browser, a generator can instead be
+/// driven by taking a fixed number of items per frame with [`Iterator::by_ref`] and
+/// [`Iterator::take`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{drive, Combinations};
+/// use std::time::Duration;
+///
+/// let mut sizes = Vec::new();
+/// let mut handle = drive(
+///     Combinations::all(0..10),
+///     Duration::from_millis(1),
+///     |combo| sizes.push(combo.len()),
+/// );
+/// while !handle.is_finished() {
+///     // Do other work between slices, then continue where the last slice stopped.
+///     handle.resume();
+/// }
+/// assert_eq!(handle.processed(), 1024);
+/// assert_eq!(sizes.len(), 1024);
+/// ```
+pub fn drive<I, F>(generator: I, time_slice: Duration, callback: F) -> Drive<I::IntoIter, F>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item),
+{
+    let mut handle = Drive {
+        generator: generator.into_iter(),
+        time_slice,
+        callback,
+        processed: 0,
+        finished: false,
+    };
+    handle.resume();
+    handle
+}
+
+impl<I, F> Drive<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item),
+{
+    /// Runs the enumeration for another time slice, returning the number of items which were
+    /// processed during the slice.  Once the generator is exhausted, this returns zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::drive;
+    /// use std::time::Duration;
+    ///
+    /// let mut total = 0;
+    /// let mut handle = drive(1..=3, Duration::from_secs(1), |n| total += n);
+    /// assert!(handle.is_finished());
+    /// assert_eq!(handle.resume(), 0);
+    /// assert_eq!(total, 6);
+    /// ```
+    pub fn resume(&mut self) -> usize {
+        if self.finished {
+            return 0;
+        }
+        let start = Instant::now();
+        let mut count = 0;
+        loop {
+            match self.generator.next() {
+                Some(item) => (self.callback)(item),
+                None => {
+                    self.finished = true;
+                    break;
+                }
+            }
+            count += 1;
+            if start.elapsed() >= self.time_slice {
+                break;
+            }
+        }
+        self.processed += count;
+        count
+    }
+
+    /// Returns `true` if the generator has been exhausted.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the total number of items which have been processed over all time slices so far.
+    pub fn processed(&self) -> usize {
+        self.processed
+    }
+
+    /// Returns the duration of each time slice.
+    pub fn time_slice(&self) -> Duration {
+        self.time_slice
+    }
+
+    /// Sets the duration of subsequent time slices, for example to adapt to a changing frame
+    /// budget.
+    pub fn set_time_slice(&mut self, time_slice: Duration) {
+        self.time_slice = time_slice;
+    }
+
+    /// Consumes the handle and returns the partially consumed generator and the callback.
+    pub fn into_inner(self) -> (I, F) {
+        (self.generator, self.callback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drive_processes_at_least_one_item_per_slice() {
+        let mut seen = Vec::new();
+        let mut handle = drive(0..5, Duration::ZERO, |n| seen.push(n));
+        assert_eq!(handle.processed(), 1);
+        assert!(!handle.is_finished());
+        assert_eq!(handle.resume(), 1);
+        assert_eq!(handle.resume(), 1);
+        assert_eq!(handle.resume(), 1);
+        assert_eq!(handle.resume(), 1);
+        assert!(!handle.is_finished());
+        assert_eq!(handle.resume(), 0);
+        assert!(handle.is_finished());
+        assert_eq!(handle.processed(), 5);
+        assert_eq!(seen, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_drive_into_inner() {
+        let mut handle = drive(0..10, Duration::ZERO, |_| ());
+        handle.resume();
+        let (rest, _) = handle.into_inner();
+        assert_eq!(rest.collect::<Vec<i32>>(), vec![2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+}
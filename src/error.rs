@@ -0,0 +1,135 @@
+use std::fmt;
+
+/// An invalid configuration rejected by a fallible constructor, such as
+/// [`Combinations::try_of_size`](crate::Combinations::try_of_size), which would otherwise produce
+/// an iterator that silently yields nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CombinatorialError {
+    /// The input contained no elements.
+    EmptyInput,
+    /// The requested size is larger than the number of unique elements available.
+    SizeTooLarge { size: usize, available: usize },
+}
+
+impl fmt::Display for CombinatorialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CombinatorialError::EmptyInput => write!(f, "no elements were given"),
+            CombinatorialError::SizeTooLarge { size, available } => write!(
+                f,
+                "requested size {} is larger than the {} unique elements available",
+                size, available
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CombinatorialError {}
+
+/// What a constructor such as [`Combinations::of_size_with`](crate::Combinations::of_size_with)
+/// does when asked for items of a size which no item can have, such as combinations of more
+/// elements than are available.
+///
+/// A size is only oversized if the generator has no items of that size at all.  Combinations with
+/// replacement may repeat elements, so they exist for every size as long as there is at least one
+/// element, and are never oversized otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{Combinations, CombinatorialError, OnOversize};
+///
+/// let elements = vec!['a', 'b', 'c'];
+/// let empty = Combinations::of_size_with(elements.clone(), 5, OnOversize::Empty).unwrap();
+/// assert_eq!(empty.count(), 0);
+///
+/// let clamped = Combinations::of_size_with(elements.clone(), 5, OnOversize::Clamp).unwrap();
+/// assert_eq!(clamped.collect::<Vec<_>>(), vec![vec!['a', 'b', 'c']]);
+///
+/// assert_eq!(
+///     Combinations::of_size_with(elements, 5, OnOversize::Error).err(),
+///     Some(CombinatorialError::SizeTooLarge { size: 5, available: 3 })
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OnOversize {
+    /// Yield nothing, as the constructors which take no policy do.
+    #[default]
+    Empty,
+    /// Reduce the size to the largest size which items can have.
+    Clamp,
+    /// Return [`CombinatorialError::SizeTooLarge`] instead of a generator.
+    Error,
+}
+
+impl OnOversize {
+    /// Returns the size which a generator over `available` unique elements should use when asked
+    /// for items of the given size, where `largest` is the largest size which its items can have.
+    pub(crate) fn resolve(
+        self,
+        size: usize,
+        largest: usize,
+        available: usize,
+    ) -> Result<usize, CombinatorialError> {
+        match self {
+            _ if size <= largest => Ok(size),
+            OnOversize::Empty => Ok(size),
+            OnOversize::Clamp => Ok(largest),
+            OnOversize::Error => Err(CombinatorialError::SizeTooLarge { size, available }),
+        }
+    }
+}
+
+/// Checks that a generator over the given number of unique elements can yield items of the given
+/// size.
+pub(crate) fn check_size(size: usize, available: usize) -> Result<(), CombinatorialError> {
+    if available == 0 {
+        Err(CombinatorialError::EmptyInput)
+    } else if size > available {
+        Err(CombinatorialError::SizeTooLarge { size, available })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_check_size() {
+        assert_eq!(check_size(0, 1), Ok(()));
+        assert_eq!(check_size(3, 3), Ok(()));
+        assert_eq!(check_size(0, 0), Err(CombinatorialError::EmptyInput));
+        let error = check_size(4, 3).unwrap_err();
+        assert_eq!(
+            error,
+            CombinatorialError::SizeTooLarge {
+                size: 4,
+                available: 3
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "requested size 4 is larger than the 3 unique elements available"
+        );
+    }
+
+    #[test]
+    fn test_error_on_oversize_resolve() {
+        for policy in [OnOversize::Empty, OnOversize::Clamp, OnOversize::Error] {
+            assert_eq!(policy.resolve(2, 3, 3), Ok(2));
+            assert_eq!(policy.resolve(0, 0, 0), Ok(0));
+        }
+        assert_eq!(OnOversize::Empty.resolve(5, 3, 3), Ok(5));
+        assert_eq!(OnOversize::Clamp.resolve(5, 3, 3), Ok(3));
+        assert_eq!(
+            OnOversize::Error.resolve(5, 0, 0),
+            Err(CombinatorialError::SizeTooLarge {
+                size: 5,
+                available: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::mem::size_of;
+use std::time::Duration;
+
+use crate::{combination_count, log_binomial, log_factorial, permutation_count};
+
+/// An estimate of the work needed to enumerate every item of a generator: how many items there
+/// are, and how much memory each one takes, from which the time the enumeration would take at a
+/// measured rate follows.
+///
+/// Checking the estimate before launching an enumeration catches those which could never finish.
+/// The number of items is kept both exactly, when it fits in a `u128`, and as a natural
+/// logarithm, so even astronomically large counts can be compared against a budget.  Estimates
+/// can be made from the sizes of a problem, with [`EstimatedWork::for_combinations`] and
+/// [`EstimatedWork::for_permutations`], or from a generator itself, with
+/// [`Combinations::estimated_work`] and [`Permutations::estimated_work`].
+///
+/// [`Combinations::estimated_work`]: crate::Combinations::estimated_work
+/// [`Permutations::estimated_work`]: crate::Permutations::estimated_work
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::EstimatedWork;
+/// use std::time::Duration;
+///
+/// let rate = 50_000_000.0;
+/// let hour = Duration::from_secs(3600);
+/// assert!(EstimatedWork::for_combinations(40, 10).is_feasible(hour, rate));
+/// assert!(!EstimatedWork::for_combinations(60, 30).is_feasible(hour, rate));
+///
+/// let work = EstimatedWork::for_permutations(12, 12);
+/// assert_eq!(work.items(), Some(479_001_600));
+/// assert_eq!(work.eta(rate).map(|eta| eta.as_secs()), Some(9));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EstimatedWork {
+    items: Option<u128>,
+    log_items: f64,
+    bytes_per_item: usize,
+}
+
+impl EstimatedWork {
+    /// Returns an estimate of the work needed to enumerate the combinations of size `k` of `n`
+    /// elements, each collected into a `Vec<usize>` of indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::EstimatedWork;
+    /// use std::mem::size_of;
+    ///
+    /// let work = EstimatedWork::for_combinations(100, 50);
+    /// assert_eq!(work.items(), Some(100891344545564193334812497256));
+    /// assert_eq!(work.bytes_per_item(), size_of::<Vec<usize>>() + 50 * size_of::<usize>());
+    /// assert_eq!(EstimatedWork::for_combinations(1000, 500).items(), None);
+    /// ```
+    pub fn for_combinations(n: usize, k: usize) -> Self {
+        EstimatedWork::of_vecs::<usize>(combination_count(n, k), log_binomial(n, k), k)
+    }
+
+    /// Returns an estimate of the work needed to enumerate the permutations of length `k` of `n`
+    /// elements, each collected into a `Vec<usize>` of indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::EstimatedWork;
+    ///
+    /// let work = EstimatedWork::for_permutations(10, 3);
+    /// assert_eq!(work.items(), Some(720));
+    /// assert!((work.log_items() - 720f64.ln()).abs() < 1e-9);
+    /// ```
+    pub fn for_permutations(n: usize, k: usize) -> Self {
+        let log_items = if k > n {
+            f64::NEG_INFINITY
+        } else {
+            log_factorial(n) - log_factorial(n - k)
+        };
+        EstimatedWork::of_vecs::<usize>(permutation_count(n, k), log_items, k)
+    }
+
+    /// Returns an estimate for the given number of items, each a `Vec<T>` of at most the given
+    /// length.
+    pub(crate) fn of_vecs<T>(items: Option<u128>, log_items: f64, length: usize) -> Self {
+        EstimatedWork {
+            items,
+            log_items,
+            bytes_per_item: size_of::<Vec<T>>() + length * size_of::<T>(),
+        }
+    }
+
+    /// Returns the exact number of items, or `None` if it does not fit in a `u128` or is not
+    /// known exactly, as when a generator is constrained.
+    pub fn items(&self) -> Option<u128> {
+        self.items
+    }
+
+    /// Returns the natural logarithm of the number of items, or of an upper bound on the number
+    /// if it is not known exactly.  This is negative infinity if there are no items.
+    pub fn log_items(&self) -> f64 {
+        self.log_items
+    }
+
+    /// Returns the number of bytes taken by the largest item, including the heap allocation of
+    /// its elements.
+    pub fn bytes_per_item(&self) -> usize {
+        self.bytes_per_item
+    }
+
+    /// Returns the time needed to enumerate every item at the given number of items per second,
+    /// as measured by timing a sample of the enumeration, or `None` if the time is too long to be
+    /// represented by a `Duration`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::EstimatedWork;
+    /// use std::time::Duration;
+    ///
+    /// let work = EstimatedWork::for_combinations(30, 15);
+    /// assert_eq!(work.eta(155_117_520.0), Some(Duration::from_secs(1)));
+    /// assert_eq!(EstimatedWork::for_combinations(200, 100).eta(1e12), None);
+    /// ```
+    pub fn eta(&self, items_per_second: f64) -> Option<Duration> {
+        let seconds = match self.items {
+            Some(items) => items as f64 / items_per_second,
+            None => (self.log_items - items_per_second.ln()).exp(),
+        };
+        Duration::try_from_secs_f64(seconds).ok()
+    }
+
+    /// Returns `true` if every item can be enumerated within the given budget of time, at the
+    /// given number of items per second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    /// use std::time::Duration;
+    ///
+    /// let minute = Duration::from_secs(60);
+    /// assert!(Permutations::of_length(0..10, 10).estimated_work().is_feasible(minute, 1e7));
+    /// assert!(!Permutations::of_length(0..20, 20).estimated_work().is_feasible(minute, 1e9));
+    /// ```
+    pub fn is_feasible(&self, budget: Duration, items_per_second: f64) -> bool {
+        self.eta(items_per_second).is_some_and(|eta| eta <= budget)
+    }
+}
+
+/// Returns the natural logarithm of the sum of the numbers whose natural logarithms are given,
+/// without computing the numbers themselves.
+pub(crate) fn log_sum(logs: impl Iterator<Item = f64> + Clone) -> f64 {
+    let largest = logs.clone().fold(f64::NEG_INFINITY, f64::max);
+    if largest == f64::NEG_INFINITY {
+        return largest;
+    }
+    largest + logs.map(|log| (log - largest).exp()).sum::<f64>().ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Combinations, Permutations};
+
+    #[test]
+    fn test_estimate_matches_generator_counts() {
+        let close = |log: f64, count: u128| {
+            let exact = (count as f64).ln();
+            log == exact || (log - exact).abs() < 1e-9
+        };
+        for n in 0..8 {
+            for k in 0..=n + 1 {
+                let work = Combinations::of_size(0..n as u32, k).estimated_work();
+                let count = Combinations::of_size(0..n, k).count() as u128;
+                assert_eq!(work.items(), Some(count));
+                assert!(close(work.log_items(), count));
+                assert_eq!(work.bytes_per_item(), size_of::<Vec<u32>>() + 4 * k);
+                let indices = EstimatedWork::for_combinations(n, k);
+                assert_eq!(indices.items(), work.items());
+                assert_eq!(indices.log_items(), work.log_items());
+
+                let work = Permutations::of_length(0..n, k).estimated_work();
+                let count = Permutations::of_length(0..n, k).count() as u128;
+                assert_eq!(work, EstimatedWork::for_permutations(n, k));
+                assert!(close(work.log_items(), count));
+            }
+            let work = Combinations::all(0..n).estimated_work();
+            assert!(close(work.log_items(), work.items().unwrap()));
+            let work = Permutations::all(0..n).estimated_work();
+            let count = Permutations::all(0..n).count() as u128;
+            assert_eq!(work.items(), Some(count));
+            assert!(close(work.log_items(), count));
+        }
+        let pinned = Permutations::of_length(0..5, 5)
+            .with_fixed(&[(0, 0)])
+            .estimated_work();
+        assert_eq!(pinned.items(), None);
+        assert!(close(pinned.log_items(), 120));
+    }
+}
@@ -0,0 +1,329 @@
+/// An iterator which generates every exact cover of a set of columns by a collection of rows,
+/// using Knuth's Algorithm X with dancing links.
+///
+/// Each row covers some subset of the columns, and an exact cover is a set of rows which covers
+/// every column exactly once.  Each cover is yielded as the sorted indices of its rows.  The
+/// search always branches on the column covered by the fewest remaining rows, and the rows and
+/// columns are kept in doubly-linked lists so that removing and restoring them as the search
+/// advances and backtracks takes constant time per link.
+///
+/// Many problems can be phrased as exact cover, including Sudoku, polyomino tilings, and the
+/// n-queens problem, as well as set partitions.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::ExactCovers;
+///
+/// // The example from Knuth's "Dancing Links" paper.
+/// let rows = vec![
+///     vec![2, 4, 5],
+///     vec![0, 3, 6],
+///     vec![1, 2, 5],
+///     vec![0, 3],
+///     vec![1, 6],
+///     vec![3, 4, 6],
+/// ];
+/// let mut covers = ExactCovers::new(7, &rows);
+/// assert_eq!(covers.next(), Some(vec![0, 3, 4]));
+/// assert_eq!(covers.next(), None);
+/// ```
+pub struct ExactCovers {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    row: Vec<usize>,
+    size: Vec<usize>,
+    solution: Vec<usize>,
+    started: bool,
+    done: bool,
+}
+
+/// The index of the root node, which links the headers of the uncovered columns.
+const ROOT: usize = 0;
+
+impl ExactCovers {
+    /// Creates a new `ExactCovers` iterator over the given number of columns, where each row is
+    /// given as a list of the indices of the columns which it covers.  Repeated indices within a
+    /// row are ignored, and empty rows are never part of a cover.  If any index is not less than
+    /// the number of columns, then no covers are yielded.  With no columns, the empty cover is
+    /// yielded once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::ExactCovers;
+    ///
+    /// let rows = vec![vec![0, 1], vec![1, 2], vec![2], vec![0]];
+    /// let covers: Vec<Vec<usize>> = ExactCovers::new(3, &rows).collect();
+    /// assert_eq!(covers, vec![vec![0, 2], vec![1, 3]]);
+    ///
+    /// assert_eq!(ExactCovers::new(2, &[vec![0, 2]]).next(), None);
+    /// assert_eq!(ExactCovers::new(0, &[]).next(), Some(vec![]));
+    /// ```
+    pub fn new(columns: usize, rows: &[Vec<usize>]) -> Self {
+        let nodes = 1 + columns + rows.iter().map(|row| row.len()).sum::<usize>();
+        let mut covers = ExactCovers {
+            left: Vec::with_capacity(nodes),
+            right: Vec::with_capacity(nodes),
+            up: Vec::with_capacity(nodes),
+            down: Vec::with_capacity(nodes),
+            column: Vec::with_capacity(nodes),
+            row: Vec::with_capacity(nodes),
+            size: vec![0; columns + 1],
+            solution: Vec::new(),
+            started: false,
+            done: false,
+        };
+        for header in 0..=columns {
+            covers
+                .left
+                .push(if header == 0 { columns } else { header - 1 });
+            covers
+                .right
+                .push(if header == columns { 0 } else { header + 1 });
+            covers.up.push(header);
+            covers.down.push(header);
+            covers.column.push(header);
+            covers.row.push(usize::MAX);
+        }
+        for (index, row) in rows.iter().enumerate() {
+            let mut row = row.clone();
+            row.sort_unstable();
+            row.dedup();
+            if row.last().is_some_and(|last| *last >= columns) {
+                covers.done = true;
+                continue;
+            }
+            let first = covers.column.len();
+            for (offset, column) in row.iter().enumerate() {
+                let node = first + offset;
+                let header = column + 1;
+                covers.left.push(if offset == 0 {
+                    first + row.len() - 1
+                } else {
+                    node - 1
+                });
+                covers.right.push(if offset == row.len() - 1 {
+                    first
+                } else {
+                    node + 1
+                });
+                covers.up.push(covers.up[header]);
+                covers.down.push(header);
+                let above = covers.up[header];
+                covers.down[above] = node;
+                covers.up[header] = node;
+                covers.column.push(header);
+                covers.row.push(index);
+                covers.size[header] += 1;
+            }
+        }
+        covers
+    }
+
+    /// Creates a new `ExactCovers` iterator from a binary matrix, in which each row covers the
+    /// columns where it is `true`.  The number of columns is the length of the longest row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::ExactCovers;
+    ///
+    /// let matrix = vec![
+    ///     vec![true, false, true],
+    ///     vec![false, true, false],
+    ///     vec![true, true, false],
+    /// ];
+    /// assert_eq!(ExactCovers::from_matrix(&matrix).collect::<Vec<_>>(), vec![vec![0, 1]]);
+    /// ```
+    pub fn from_matrix(matrix: &[Vec<bool>]) -> Self {
+        let columns = matrix.iter().map(|row| row.len()).max().unwrap_or(0);
+        let rows: Vec<Vec<usize>> = matrix
+            .iter()
+            .map(|row| (0..row.len()).filter(|column| row[*column]).collect())
+            .collect();
+        ExactCovers::new(columns, &rows)
+    }
+
+    /// Removes the given column from the header list, and removes every row which covers it from
+    /// the other columns which that row covers.
+    fn cover(&mut self, header: usize) {
+        self.right[self.left[header]] = self.right[header];
+        self.left[self.right[header]] = self.left[header];
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    /// Restores the given column and the rows which cover it, exactly undoing [`Self::cover`].
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                self.size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        self.right[self.left[header]] = header;
+        self.left[self.right[header]] = header;
+    }
+
+    /// Adds the row containing the given node to the solution, covering every other column which
+    /// that row covers.
+    fn choose(&mut self, row: usize) {
+        self.solution.push(row);
+        let mut node = self.right[row];
+        while node != row {
+            self.cover(self.column[node]);
+            node = self.right[node];
+        }
+    }
+
+    /// Removes and returns the last node added to the solution, if any, uncovering the other
+    /// columns which its row covers.
+    fn unchoose(&mut self) -> Option<usize> {
+        let row = self.solution.pop()?;
+        let mut node = self.left[row];
+        while node != row {
+            self.uncover(self.column[node]);
+            node = self.left[node];
+        }
+        Some(row)
+    }
+
+    /// Returns the header of the uncovered column which is covered by the fewest remaining rows,
+    /// or `None` if every column has been covered.
+    fn smallest_column(&self) -> Option<usize> {
+        let mut best = None;
+        let mut header = self.right[ROOT];
+        while header != ROOT {
+            if best.is_none_or(|best| self.size[header] < self.size[best]) {
+                best = Some(header);
+            }
+            header = self.right[header];
+        }
+        best
+    }
+
+    /// Replaces the most recent choice with the next row covering the same column, backtracking
+    /// further as necessary.  Returns `false` if no alternatives remain.
+    fn advance(&mut self) -> bool {
+        while let Some(row) = self.unchoose() {
+            let header = self.column[row];
+            let next = self.down[row];
+            if next != header {
+                self.choose(next);
+                return true;
+            }
+            self.uncover(header);
+        }
+        false
+    }
+}
+
+impl Iterator for ExactCovers {
+    type Item = Vec<usize>;
+
+    /// Returns the next exact cover and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut found = if self.started {
+            self.advance()
+        } else {
+            self.started = true;
+            true
+        };
+        while found {
+            let header = match self.smallest_column() {
+                Some(header) => header,
+                None => {
+                    let mut rows: Vec<usize> =
+                        self.solution.iter().map(|node| self.row[*node]).collect();
+                    rows.sort_unstable();
+                    return Some(rows);
+                }
+            };
+            self.cover(header);
+            let row = self.down[header];
+            if row == header {
+                self.uncover(header);
+                found = self.advance();
+            } else {
+                self.choose(row);
+            }
+        }
+        self.done = true;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bell_number, Combinations};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_exact_cover_set_partitions() {
+        for n in 0..7 {
+            let rows: Vec<Vec<usize>> = Combinations::all(0..n).skip(1).collect();
+            let covers: Vec<Vec<usize>> = ExactCovers::new(n, &rows).collect();
+            assert_eq!(covers.len(), bell_number::<usize>(n));
+            let unique: BTreeSet<Vec<usize>> = covers.iter().cloned().collect();
+            assert_eq!(unique.len(), covers.len());
+            for cover in covers {
+                let mut covered: Vec<usize> = cover
+                    .iter()
+                    .flat_map(|row| rows[*row].iter().copied())
+                    .collect();
+                covered.sort_unstable();
+                assert_eq!(covered, (0..n).collect::<Vec<usize>>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_exact_cover_queens() {
+        // Rows and columns must each be covered exactly once, while diagonals are covered at most
+        // once, which is modelled by adding a row covering each diagonal alone.
+        let counts: Vec<usize> = (1..8)
+            .map(|n| {
+                let diagonals = 2 * n - 1;
+                let columns = 2 * n + 2 * diagonals;
+                let mut rows = Vec::new();
+                for r in 0..n {
+                    for c in 0..n {
+                        rows.push(vec![
+                            r,
+                            n + c,
+                            2 * n + r + c,
+                            2 * n + diagonals + r + n - 1 - c,
+                        ]);
+                    }
+                }
+                for diagonal in 2 * n..columns {
+                    rows.push(vec![diagonal]);
+                }
+                ExactCovers::new(columns, &rows).count()
+            })
+            .collect();
+        assert_eq!(counts, vec![1, 0, 0, 2, 10, 4, 40]);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{cross, Combinations, CombinationsWithReplacement, Cross, Permutations};
+
+/// An extension trait, implemented for everything which can be iterated over items which are
+/// `Ord` and `Clone`, for building generators fluently without naming their types.
+///
+/// Each method is a shorthand for one of the inherent constructors or for [`cross`], which remain
+/// available.  The items are sorted and deduplicated as usual.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::CombinatorialExt;
+///
+/// assert_eq!(vec![1, 2, 3].powerset().count(), 8);
+/// assert_eq!("abcd".chars().combinations_of(2).count(), 6);
+/// assert_eq!([3, 1, 2].all_permutations().next(), Some(vec![1, 2, 3]));
+/// ```
+pub trait CombinatorialExt: IntoIterator + Sized
+where
+    Self::Item: Ord + Clone,
+{
+    /// Returns a [`Combinations`] iterator over every subset of the unique items, as by
+    /// [`Combinations::all`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinatorialExt;
+    ///
+    /// let subsets: Vec<Vec<char>> = vec!['b', 'a'].powerset().collect();
+    /// assert_eq!(subsets, vec![vec![], vec!['a'], vec!['b'], vec!['a', 'b']]);
+    /// ```
+    fn powerset(self) -> Combinations<Self::Item> {
+        Combinations::all(self)
+    }
+
+    /// Returns a [`Combinations`] iterator over the combinations of `k` of the unique items, as by
+    /// [`Combinations::of_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinatorialExt;
+    ///
+    /// let combos: Vec<Vec<i32>> = (1..4).combinations_of(2).collect();
+    /// assert_eq!(combos, vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+    /// ```
+    fn combinations_of(self, k: usize) -> Combinations<Self::Item> {
+        Combinations::of_size(self, k)
+    }
+
+    /// Returns a [`CombinationsWithReplacement`] iterator over the combinations of `k` of the
+    /// unique items in which items may be repeated, as by [`CombinationsWithReplacement::of_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinatorialExt;
+    ///
+    /// let combos: Vec<Vec<i32>> = (1..3).combinations_with_replacement_of(2).collect();
+    /// assert_eq!(combos, vec![vec![1, 1], vec![1, 2], vec![2, 2]]);
+    /// ```
+    fn combinations_with_replacement_of(self, k: usize) -> CombinationsWithReplacement<Self::Item> {
+        CombinationsWithReplacement::of_size(self, k)
+    }
+
+    /// Returns a [`Permutations`] iterator over the permutations of `k` of the unique items, as by
+    /// [`Permutations::of_length`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinatorialExt;
+    ///
+    /// assert_eq!((0..5).permutations_of(2).count(), 20);
+    /// ```
+    fn permutations_of(self, k: usize) -> Permutations<Self::Item> {
+        Permutations::of_length(self, k)
+    }
+
+    /// Returns a [`Permutations`] iterator over the arrangements of all of the unique items, in
+    /// lexicographic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinatorialExt;
+    ///
+    /// let mut perms = vec!['b', 'a', 'c'].all_permutations();
+    /// assert_eq!(perms.next(), Some(vec!['a', 'b', 'c']));
+    /// assert_eq!(perms.next(), Some(vec!['a', 'c', 'b']));
+    /// assert_eq!(perms.count(), 4);
+    /// ```
+    fn all_permutations(self) -> Permutations<Self::Item> {
+        let elements = crate::sorted_unique(self);
+        let length = elements.len();
+        Permutations::of_length_unchecked(elements, length)
+    }
+
+    /// Returns a [`Cross`] iterator over every pair of an item of this generator with an item of
+    /// the other, as by [`cross`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{CombinatorialExt, Combinations, Permutations, RankedIterator};
+    ///
+    /// let combos = Combinations::of_size(0..4, 2);
+    /// let pairs = combos.cross(Permutations::of_length("ab".chars(), 2));
+    /// assert_eq!(pairs.total_count(), Some(12));
+    /// assert_eq!(pairs.unrank(3), Some((vec![0, 2], vec!['b', 'a'])));
+    /// assert_eq!(pairs.count(), 12);
+    /// ```
+    fn cross<B>(self, other: B) -> Cross<Self::IntoIter, B::IntoIter>
+    where
+        B: IntoIterator,
+        B::IntoIter: Clone,
+    {
+        cross(self, other)
+    }
+}
+
+impl<I> CombinatorialExt for I
+where
+    I: IntoIterator,
+    I::Item: Ord + Clone,
+{
+}
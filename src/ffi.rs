@@ -0,0 +1,244 @@
+//! A C interface to the core generators, for reuse from C and C++.
+//!
+//! This module is only available with the `ffi` feature.  The generators exposed here work with
+//! indices only: a generator over `n` elements writes arrays of indices into `0..n`, which the
+//! caller can use to look up its own elements, so no generic types cross the boundary.  Each
+//! generator is an opaque handle which is created by a `_new` function, advanced by the matching
+//! `_next` function, and must be released by the matching `_free` function.
+//!
+//! To link against the crate from C, build it as a static or dynamic library, for example with
+//! `cargo rustc --release --features ffi --crate-type staticlib`, and declare the functions in a
+//! header:
+//!
+//! ```c
+//! #include <stdbool.h>
+//! #include <stddef.h>
+//!
+//! typedef struct CombinationGenerator CombinationGenerator;
+//!
+//! CombinationGenerator *combinatorial_combinations_new(size_t n, size_t k);
+//! bool combinatorial_combinations_next(CombinationGenerator *generator, size_t *out);
+//! void combinatorial_combinations_free(CombinationGenerator *generator);
+//! ```
+
+use crate::{Combinations, Permutations};
+
+/// An opaque handle to a generator of the combinations of the indices `0..n` with a given size.
+pub struct CombinationGenerator {
+    inner: Combinations<usize>,
+}
+
+/// An opaque handle to a generator of the permutations of the indices `0..n` with a given length.
+pub struct PermutationGenerator {
+    inner: Permutations<usize>,
+}
+
+/// Creates a generator which will write every combination of `k` of the indices `0..n`, in
+/// lexicographic order.  The returned handle must be released with
+/// [`combinatorial_combinations_free`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::ffi::*;
+///
+/// let generator = combinatorial_combinations_new(4, 2);
+/// let mut out = [0usize; 2];
+/// let mut count = 0;
+/// while unsafe { combinatorial_combinations_next(generator, out.as_mut_ptr()) } {
+///     count += 1;
+/// }
+/// assert_eq!(count, 6);
+/// assert_eq!(out, [2, 3]);
+/// unsafe { combinatorial_combinations_free(generator) };
+/// ```
+#[no_mangle]
+pub extern "C" fn combinatorial_combinations_new(n: usize, k: usize) -> *mut CombinationGenerator {
+    let inner = Combinations::of_size_unchecked((0..n).collect(), k);
+    Box::into_raw(Box::new(CombinationGenerator { inner }))
+}
+
+/// Writes the next combination to `out` and advances the generator, returning `true`, or returns
+/// `false` without writing anything once every combination has been written or if `generator` is
+/// null.
+///
+/// # Safety
+///
+/// `generator` must be null or a handle returned by [`combinatorial_combinations_new`] which has
+/// not been freed, and `out` must point to space for at least `k` indices, where `k` is the size
+/// given when the generator was created.  If `k` is zero, `out` may be null.
+#[no_mangle]
+pub unsafe extern "C" fn combinatorial_combinations_next(
+    generator: *mut CombinationGenerator,
+    out: *mut usize,
+) -> bool {
+    match generator
+        .as_mut()
+        .and_then(|generator| generator.inner.next())
+    {
+        Some(combo) => {
+            write_indices(&combo, out);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Releases a generator created by [`combinatorial_combinations_new`].  Passing a null pointer
+/// does nothing.
+///
+/// # Safety
+///
+/// `generator` must be null or a handle returned by [`combinatorial_combinations_new`] which has
+/// not already been freed.  It must not be used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn combinatorial_combinations_free(generator: *mut CombinationGenerator) {
+    if !generator.is_null() {
+        drop(Box::from_raw(generator));
+    }
+}
+
+/// Creates a generator which will write every permutation of `k` of the indices `0..n`, in
+/// lexicographic order.  The returned handle must be released with
+/// [`combinatorial_permutations_free`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::ffi::*;
+///
+/// let generator = combinatorial_permutations_new(3, 3);
+/// let mut out = [0usize; 3];
+/// assert!(unsafe { combinatorial_permutations_next(generator, out.as_mut_ptr()) });
+/// assert_eq!(out, [0, 1, 2]);
+/// assert!(unsafe { combinatorial_permutations_next(generator, out.as_mut_ptr()) });
+/// assert_eq!(out, [0, 2, 1]);
+/// unsafe { combinatorial_permutations_free(generator) };
+/// ```
+#[no_mangle]
+pub extern "C" fn combinatorial_permutations_new(n: usize, k: usize) -> *mut PermutationGenerator {
+    let inner = Permutations::of_length_unchecked((0..n).collect(), k);
+    Box::into_raw(Box::new(PermutationGenerator { inner }))
+}
+
+/// Writes the next permutation to `out` and advances the generator, returning `true`, or returns
+/// `false` without writing anything once every permutation has been written or if `generator` is
+/// null.
+///
+/// # Safety
+///
+/// `generator` must be null or a handle returned by [`combinatorial_permutations_new`] which has
+/// not been freed, and `out` must point to space for at least `k` indices, where `k` is the
+/// length given when the generator was created.  If `k` is zero, `out` may be null.
+#[no_mangle]
+pub unsafe extern "C" fn combinatorial_permutations_next(
+    generator: *mut PermutationGenerator,
+    out: *mut usize,
+) -> bool {
+    match generator
+        .as_mut()
+        .and_then(|generator| generator.inner.next())
+    {
+        Some(perm) => {
+            write_indices(&perm, out);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Releases a generator created by [`combinatorial_permutations_new`].  Passing a null pointer
+/// does nothing.
+///
+/// # Safety
+///
+/// `generator` must be null or a handle returned by [`combinatorial_permutations_new`] which has
+/// not already been freed.  It must not be used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn combinatorial_permutations_free(generator: *mut PermutationGenerator) {
+    if !generator.is_null() {
+        drop(Box::from_raw(generator));
+    }
+}
+
+/// Copies the given indices to `out`, which is not touched if there are none, so that it may be
+/// null for generators of empty items.
+///
+/// # Safety
+///
+/// If `indices` is not empty, `out` must point to space for at least `indices.len()` indices.
+unsafe fn write_indices(indices: &[usize], out: *mut usize) {
+    if !indices.is_empty() {
+        std::ptr::copy_nonoverlapping(indices.as_ptr(), out, indices.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    /// Drains the given generator through its `_next` function, collecting every item written,
+    /// and checks that it stays exhausted without touching the output afterwards.
+    fn drain<G>(
+        generator: *mut G,
+        k: usize,
+        next: unsafe extern "C" fn(*mut G, *mut usize) -> bool,
+    ) -> Vec<Vec<usize>> {
+        let mut items = Vec::new();
+        let mut out = vec![usize::MAX; k];
+        while unsafe { next(generator, out.as_mut_ptr()) } {
+            items.push(out.clone());
+        }
+        out.fill(usize::MAX);
+        assert!(!unsafe { next(generator, out.as_mut_ptr()) });
+        assert!(out.iter().all(|index| *index == usize::MAX));
+        items
+    }
+
+    #[test]
+    fn test_ffi_null_zero_length_and_exhaustion() {
+        let mut out = [usize::MAX; 2];
+        unsafe {
+            assert!(!combinatorial_combinations_next(
+                ptr::null_mut(),
+                out.as_mut_ptr()
+            ));
+            assert!(!combinatorial_permutations_next(
+                ptr::null_mut(),
+                out.as_mut_ptr()
+            ));
+            combinatorial_combinations_free(ptr::null_mut());
+            combinatorial_permutations_free(ptr::null_mut());
+        }
+        assert_eq!(out, [usize::MAX; 2]);
+
+        for n in 0..5 {
+            // An empty item is written through a null pointer, as there is nothing to write.
+            let generator = combinatorial_combinations_new(n, 0);
+            unsafe {
+                assert!(combinatorial_combinations_next(generator, ptr::null_mut()));
+                assert!(!combinatorial_combinations_next(generator, ptr::null_mut()));
+                combinatorial_combinations_free(generator);
+            }
+            let generator = combinatorial_permutations_new(n, 0);
+            unsafe {
+                assert!(combinatorial_permutations_next(generator, ptr::null_mut()));
+                assert!(!combinatorial_permutations_next(generator, ptr::null_mut()));
+                combinatorial_permutations_free(generator);
+            }
+
+            for k in 0..=n + 1 {
+                let generator = combinatorial_combinations_new(n, k);
+                let combos = drain(generator, k, combinatorial_combinations_next);
+                unsafe { combinatorial_combinations_free(generator) };
+                assert_eq!(combos, Combinations::of_size(0..n, k).collect::<Vec<_>>());
+
+                let generator = combinatorial_permutations_new(n, k);
+                let perms = drain(generator, k, combinatorial_permutations_next);
+                unsafe { combinatorial_permutations_free(generator) };
+                assert_eq!(perms, Permutations::of_length(0..n, k).collect::<Vec<_>>());
+            }
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use crate::Permutation;
+
+/// An iterator which generates the involutions of the points `0..n`, which are the permutations
+/// equal to their own inverse, so that applying one twice gives the identity.
+///
+/// Every involution splits the points into fixed points and swapped pairs, and is built directly
+/// by deciding, for the smallest point not yet placed, whether it is fixed or which larger point
+/// it is swapped with, so only the involutions are visited rather than all `n!` permutations.
+/// Involutions are yielded in lexicographic order of their images.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Involutions;
+///
+/// let images: Vec<Vec<usize>> = Involutions::new(3).map(|p| p.into_images()).collect();
+/// assert_eq!(
+///     images,
+///     vec![vec![0, 1, 2], vec![0, 2, 1], vec![1, 0, 2], vec![2, 1, 0]]
+/// );
+/// assert_eq!(Involutions::new(6).count(), 76);
+/// assert!(Involutions::new(6).all(|p| p.compose(&p).is_identity()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Involutions {
+    images: Vec<Option<usize>>,
+    /// The pairs of points placed so far, in the order they were placed, with fixed points
+    /// paired with themselves.
+    pairs: Vec<(usize, usize)>,
+    started: bool,
+    done: bool,
+}
+
+impl Involutions {
+    /// Creates a new `Involutions` iterator which will yield every involution of the points
+    /// `0..n`.
+    pub fn new(n: usize) -> Self {
+        Involutions {
+            images: vec![None; n],
+            pairs: Vec::with_capacity(n),
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Returns the degree of the involutions, which is the number of points they act on.
+    pub fn degree(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Returns the smallest point which has not yet been placed, if any.
+    fn first_unplaced(&self) -> Option<usize> {
+        self.images.iter().position(|image| image.is_none())
+    }
+
+    /// Pairs the smallest unplaced point with the given point, which fixes it if they are equal.
+    fn place(&mut self, partner: usize) {
+        let first = self.first_unplaced().unwrap();
+        self.images[first] = Some(partner);
+        self.images[partner] = Some(first);
+        self.pairs.push((first, partner));
+    }
+
+    /// Removes the last pair and returns the partner of its first point, if any.
+    fn unplace(&mut self) -> Option<usize> {
+        let (first, partner) = self.pairs.pop()?;
+        self.images[first] = None;
+        self.images[partner] = None;
+        Some(partner)
+    }
+
+    /// Completes the involution with the lexicographically smallest images, backtracking as
+    /// necessary.  If `previous` is given, the search resumes by trying partners after `previous`
+    /// for the smallest unplaced point.  Returns `true` if the involution was completed, or
+    /// `false` if no further involutions exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            let first = match self.first_unplaced() {
+                Some(first) => first,
+                None => return true,
+            };
+            let start = previous.map_or(first, |partner| partner + 1);
+            match (start..self.images.len()).find(|partner| self.images[*partner].is_none()) {
+                Some(partner) => {
+                    self.place(partner);
+                    previous = None;
+                }
+                None => match self.unplace() {
+                    Some(partner) => previous = Some(partner),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Moves to the next involution.  Returns `false` and marks the iterator as done if no
+    /// involutions remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let found = if self.started {
+            match self.unplace() {
+                Some(partner) => self.fill(Some(partner)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.fill(None)
+        };
+        if !found {
+            self.done = true;
+        }
+        found
+    }
+}
+
+impl Iterator for Involutions {
+    type Item = Permutation;
+
+    /// Returns the next involution and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        Permutation::from_images(self.images.iter().map(|image| image.unwrap()).collect())
+    }
+}
+
+/// An iterator which generates the permutations of the points `0..n` with exactly a given number
+/// of fixed points.  With no fixed points, these are the derangements.
+///
+/// The images of the points are chosen in order, and an image is only tried if the permutation
+/// can still be completed with exactly the required number of fixed points, so the search never
+/// reaches a dead end and only the matching permutations are visited, rather than all `n!`
+/// permutations.  Permutations are yielded in lexicographic order of their images.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{subfactorial, FixedPointPermutations};
+///
+/// let images: Vec<Vec<usize>> = FixedPointPermutations::new(3, 1)
+///     .map(|p| p.into_images())
+///     .collect();
+/// assert_eq!(images, vec![vec![0, 2, 1], vec![1, 0, 2], vec![2, 1, 0]]);
+///
+/// assert_eq!(FixedPointPermutations::new(7, 0).count(), subfactorial(7));
+/// assert_eq!(FixedPointPermutations::new(7, 6).count(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedPointPermutations {
+    degree: usize,
+    fixed_points: usize,
+    images: Vec<usize>,
+    used: Vec<bool>,
+    fixed: usize,
+    started: bool,
+    done: bool,
+}
+
+impl FixedPointPermutations {
+    /// Creates a new `FixedPointPermutations` iterator which will yield every permutation of the
+    /// points `0..n` which fixes exactly `fixed_points` of them.
+    pub fn new(n: usize, fixed_points: usize) -> Self {
+        FixedPointPermutations {
+            degree: n,
+            fixed_points,
+            images: Vec::with_capacity(n),
+            used: vec![false; n],
+            fixed: 0,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Returns the degree of the permutations, which is the number of points they act on.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns the number of fixed points of every permutation yielded.
+    pub fn fixed_points(&self) -> usize {
+        self.fixed_points
+    }
+
+    /// Returns `true` if the permutation can be completed with exactly the required number of
+    /// fixed points after giving the next point the given image.
+    fn can_complete_with(&self, image: usize) -> bool {
+        let point = self.images.len();
+        let fixed = self.fixed + usize::from(image == point);
+        let Some(needed) = self.fixed_points.checked_sub(fixed) else {
+            return false;
+        };
+        // The later points whose own image is still free are the only ones which can be fixed.
+        let remaining = self.degree - point - 1;
+        let fixable = (point + 1..self.degree)
+            .filter(|p| !self.used[*p] && *p != image)
+            .count();
+        // The points which are not fixed can be deranged among the free images unless exactly one
+        // is left over, and its own image is the only one free.
+        needed <= fixable && !(remaining - needed == 1 && fixable - needed == 1)
+    }
+
+    /// Gives the next point the given image.
+    fn place(&mut self, image: usize) {
+        self.fixed += usize::from(image == self.images.len());
+        self.used[image] = true;
+        self.images.push(image);
+    }
+
+    /// Removes the image of the last point placed and returns it, if any.
+    fn unplace(&mut self) -> Option<usize> {
+        let image = self.images.pop()?;
+        self.used[image] = false;
+        self.fixed -= usize::from(image == self.images.len());
+        Some(image)
+    }
+
+    /// Completes the permutation with the lexicographically smallest images, backtracking as
+    /// necessary.  If `previous` is given, the search resumes by trying images after `previous`
+    /// for the last point placed.  Returns `true` if the permutation was completed, or `false` if
+    /// no further permutations exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            if self.images.len() == self.degree {
+                return true;
+            }
+            let start = previous.map_or(0, |image| image + 1);
+            match (start..self.degree)
+                .find(|image| !self.used[*image] && self.can_complete_with(*image))
+            {
+                Some(image) => {
+                    self.place(image);
+                    previous = None;
+                }
+                None => match self.unplace() {
+                    Some(image) => previous = Some(image),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Moves to the next permutation.  Returns `false` and marks the iterator as done if no
+    /// permutations remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let found = if self.started {
+            match self.unplace() {
+                Some(image) => self.fill(Some(image)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            // The empty permutation has no points left to place, so check its fixed points here.
+            (self.degree > 0 || self.fixed_points == 0) && self.fill(None)
+        };
+        if !found {
+            self.done = true;
+        }
+        found
+    }
+}
+
+impl Iterator for FixedPointPermutations {
+    type Item = Permutation;
+
+    /// Returns the next permutation and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        Permutation::from_images(self.images.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SymmetricGroup;
+
+    #[test]
+    fn test_fixed_points_match_filtered_symmetric_group() {
+        for n in 0..8 {
+            let expected: Vec<Permutation> = SymmetricGroup::new(n)
+                .filter(|p| p.compose(p).is_identity())
+                .collect();
+            assert_eq!(Involutions::new(n).collect::<Vec<_>>(), expected);
+            for fixed_points in 0..=n + 1 {
+                let expected: Vec<Permutation> = SymmetricGroup::new(n)
+                    .filter(|p| p.cycle_type().iter().filter(|l| **l == 1).count() == fixed_points)
+                    .collect();
+                let generated: Vec<Permutation> =
+                    FixedPointPermutations::new(n, fixed_points).collect();
+                assert_eq!(generated, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Enumeration of the cliques and independent sets of a simple undirected graph.
+//!
+//! A [`Graph`] is built from a list of edges or from adjacency lists over the vertices `0..n`,
+//! and its cliques and independent sets are yielded as sorted lists of vertices.  The adjacency
+//! test is pushed into the search itself, so a branch is abandoned as soon as it includes a
+//! non-adjacent pair, rather than generating every subset with [`powerset`](crate::powerset) and
+//! filtering afterwards.
+//!
+//! # Examples
+//!
+//! ```
+//! use combinatorial::graph::Graph;
+//! use combinatorial::powerset;
+//!
+//! // A square with one diagonal.
+//! let graph = Graph::new(4, [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2)]);
+//! let cliques: Vec<Vec<usize>> = graph.cliques().collect();
+//! let filtered: Vec<Vec<usize>> = powerset(0..4).filter(|set| graph.is_clique(set)).collect();
+//! assert_eq!(cliques, filtered);
+//! assert_eq!(graph.independent_sets().filter(|set| set.len() == 2).count(), 1);
+//! ```
+
+use crate::Backtracker;
+
+/// A simple undirected graph on the vertices `0..n`, stored as an adjacency matrix.
+///
+/// Self-loops and repeated edges are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::graph::Graph;
+///
+/// let path = Graph::new(3, [(0, 1), (1, 2)]);
+/// assert!(path.is_adjacent(1, 0));
+/// assert!(!path.is_adjacent(0, 2));
+/// assert!(path.complement().is_adjacent(0, 2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    adjacent: Vec<Vec<bool>>,
+}
+
+impl Graph {
+    /// Creates a graph with `n` vertices and the given edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either end of an edge is not less than `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::graph::Graph;
+    ///
+    /// let triangle = Graph::new(3, [(0, 1), (1, 2), (2, 0)]);
+    /// assert_eq!(triangle.vertex_count(), 3);
+    /// assert!(triangle.is_clique(&[0, 1, 2]));
+    /// ```
+    pub fn new(n: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let mut adjacent = vec![vec![false; n]; n];
+        for (a, b) in edges {
+            assert!(a < n && b < n, "edge ({a}, {b}) has an end outside 0..{n}");
+            if a != b {
+                adjacent[a][b] = true;
+                adjacent[b][a] = true;
+            }
+        }
+        Graph { adjacent }
+    }
+
+    /// Creates a graph from adjacency lists, in which the `i`th list holds the neighbours of vertex
+    /// `i`.  An edge listed in only one direction is still added in both.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a neighbour is not less than the number of lists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::graph::Graph;
+    ///
+    /// let star = Graph::from_adjacency_lists(&[vec![1, 2, 3], vec![], vec![], vec![]]);
+    /// assert_eq!(star, Graph::new(4, [(0, 1), (0, 2), (0, 3)]));
+    /// ```
+    pub fn from_adjacency_lists(lists: &[Vec<usize>]) -> Self {
+        let edges = lists
+            .iter()
+            .enumerate()
+            .flat_map(|(a, neighbours)| neighbours.iter().map(move |b| (a, *b)));
+        Graph::new(lists.len(), edges)
+    }
+
+    /// Returns the number of vertices.
+    pub fn vertex_count(&self) -> usize {
+        self.adjacent.len()
+    }
+
+    /// Returns `true` if there is an edge between the given vertices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either vertex is not less than the number of vertices.
+    pub fn is_adjacent(&self, a: usize, b: usize) -> bool {
+        self.adjacent[a][b]
+    }
+
+    /// Returns the complement of the graph, in which two distinct vertices are adjacent exactly
+    /// when they are not adjacent in this graph.
+    pub fn complement(&self) -> Graph {
+        let n = self.vertex_count();
+        let adjacent = (0..n)
+            .map(|a| (0..n).map(|b| a != b && !self.adjacent[a][b]).collect())
+            .collect();
+        Graph { adjacent }
+    }
+
+    /// Returns `true` if every pair of distinct vertices in the given set is adjacent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::graph::Graph;
+    ///
+    /// let path = Graph::new(3, [(0, 1), (1, 2)]);
+    /// assert!(path.is_clique(&[1, 2]));
+    /// assert!(!path.is_clique(&[0, 1, 2]));
+    /// ```
+    pub fn is_clique(&self, vertices: &[usize]) -> bool {
+        vertices.iter().enumerate().all(|(i, a)| {
+            vertices[i + 1..]
+                .iter()
+                .all(|b| a == b || self.adjacent[*a][*b])
+        })
+    }
+
+    /// Returns `true` if no pair of vertices in the given set is adjacent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::graph::Graph;
+    ///
+    /// let path = Graph::new(3, [(0, 1), (1, 2)]);
+    /// assert!(path.is_independent(&[0, 2]));
+    /// assert!(!path.is_independent(&[0, 1]));
+    /// ```
+    pub fn is_independent(&self, vertices: &[usize]) -> bool {
+        vertices
+            .iter()
+            .enumerate()
+            .all(|(i, a)| vertices[i + 1..].iter().all(|b| !self.adjacent[*a][*b]))
+    }
+
+    /// Returns an iterator over the cliques with exactly `size` vertices, in lexicographic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::graph::Graph;
+    ///
+    /// let graph = Graph::new(5, [(0, 1), (0, 2), (1, 2), (2, 3), (3, 4), (2, 4)]);
+    /// let triangles: Vec<Vec<usize>> = graph.cliques_of_size(3).collect();
+    /// assert_eq!(triangles, vec![vec![0, 1, 2], vec![2, 3, 4]]);
+    /// ```
+    pub fn cliques_of_size(&self, size: usize) -> impl Iterator<Item = Vec<usize>> + '_ {
+        self.sets_of_size(size, true)
+    }
+
+    /// Returns an iterator over the independent sets with exactly `size` vertices, in
+    /// lexicographic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::graph::Graph;
+    ///
+    /// let path = Graph::new(4, [(0, 1), (1, 2), (2, 3)]);
+    /// let pairs: Vec<Vec<usize>> = path.independent_sets_of_size(2).collect();
+    /// assert_eq!(pairs, vec![vec![0, 2], vec![0, 3], vec![1, 3]]);
+    /// ```
+    pub fn independent_sets_of_size(&self, size: usize) -> impl Iterator<Item = Vec<usize>> + '_ {
+        self.sets_of_size(size, false)
+    }
+
+    /// Returns an iterator over every clique, including the empty set, in the order used by
+    /// [`Combinations::all`](crate::Combinations::all): smaller cliques first, and cliques of each
+    /// size in lexicographic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::graph::Graph;
+    ///
+    /// let path = Graph::new(3, [(0, 1), (1, 2)]);
+    /// let cliques: Vec<Vec<usize>> = path.cliques().collect();
+    /// assert_eq!(cliques, vec![vec![], vec![0], vec![1], vec![2], vec![0, 1], vec![1, 2]]);
+    /// ```
+    pub fn cliques(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        self.sets_of_every_size(true)
+    }
+
+    /// Returns an iterator over every independent set, including the empty set, in the order used
+    /// by [`Combinations::all`](crate::Combinations::all): smaller sets first, and sets of each
+    /// size in lexicographic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::graph::Graph;
+    ///
+    /// let path = Graph::new(3, [(0, 1), (1, 2)]);
+    /// let sets: Vec<Vec<usize>> = path.independent_sets().collect();
+    /// assert_eq!(sets, vec![vec![], vec![0], vec![1], vec![2], vec![0, 2]]);
+    /// ```
+    pub fn independent_sets(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        self.sets_of_every_size(false)
+    }
+
+    /// Returns an iterator over the maximal cliques, which are the cliques not contained in any
+    /// larger clique, found with the Bron–Kerbosch algorithm.
+    ///
+    /// Each clique is sorted, but the cliques are yielded in the order in which the search finds
+    /// them rather than in lexicographic order.  Since every clique is contained in a maximal
+    /// clique, this is usually far faster than [`Graph::cliques`] for finding large cliques.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::graph::Graph;
+    ///
+    /// let graph = Graph::new(5, [(0, 1), (0, 2), (1, 2), (2, 3), (3, 4)]);
+    /// let mut maximal: Vec<Vec<usize>> = graph.maximal_cliques().collect();
+    /// maximal.sort();
+    /// assert_eq!(maximal, vec![vec![0, 1, 2], vec![2, 3], vec![3, 4]]);
+    /// ```
+    pub fn maximal_cliques(&self) -> MaximalCliques<'_> {
+        MaximalCliques {
+            graph: self,
+            stack: Vec::new(),
+            started: false,
+        }
+    }
+
+    /// Returns an iterator over the sets of the given size in which every pair of vertices is
+    /// adjacent, if `adjacent` is `true`, or in which no pair is adjacent otherwise.
+    fn sets_of_size(&self, size: usize, adjacent: bool) -> impl Iterator<Item = Vec<usize>> + '_ {
+        let n = self.vertex_count();
+        Backtracker::new(size, move |chosen: &[usize]| {
+            let start = chosen.last().map_or(0, |last| last + 1);
+            // Leave enough later vertices to complete the set.
+            let end = (n + 1 + chosen.len()).saturating_sub(size);
+            (start..end)
+                .filter(|v| chosen.iter().all(|u| self.adjacent[*u][*v] == adjacent))
+                .collect::<Vec<usize>>()
+        })
+    }
+
+    /// Returns an iterator over the sets of every size which are cliques, if `adjacent` is
+    /// `true`, or independent sets otherwise, stopping at the first size with none, since no
+    /// larger set can then qualify.
+    fn sets_of_every_size(&self, adjacent: bool) -> impl Iterator<Item = Vec<usize>> + '_ {
+        (0..=self.vertex_count())
+            .map_while(move |size| {
+                let mut sets = self.sets_of_size(size, adjacent).peekable();
+                sets.peek().is_some().then_some(sets)
+            })
+            .flatten()
+    }
+}
+
+/// An iterator which generates the maximal cliques of a [`Graph`], created by
+/// [`Graph::maximal_cliques`].
+///
+/// The search is the Bron–Kerbosch algorithm with pivoting, run with an explicit stack so that
+/// cliques are found one at a time.
+pub struct MaximalCliques<'a> {
+    graph: &'a Graph,
+    stack: Vec<Frame>,
+    started: bool,
+}
+
+/// A level of the Bron–Kerbosch search.
+struct Frame {
+    /// The vertices of the clique being extended.
+    clique: Vec<usize>,
+    /// The vertices which may still be added to the clique.
+    candidates: Vec<usize>,
+    /// The vertices which could be added to the clique, but whose cliques have been found already.
+    excluded: Vec<usize>,
+    /// The candidates which remain to be branched on, which are those not adjacent to the pivot.
+    branches: Vec<usize>,
+}
+
+impl MaximalCliques<'_> {
+    /// Creates the search level which extends the given clique.
+    fn frame(&self, clique: Vec<usize>, candidates: Vec<usize>, excluded: Vec<usize>) -> Frame {
+        let adjacent = &self.graph.adjacent;
+        // Branching only on the candidates which are not adjacent to a pivot still finds every
+        // maximal clique, since any clique containing none of them could be extended by the pivot.
+        let pivot = candidates
+            .iter()
+            .chain(excluded.iter())
+            .max_by_key(|u| candidates.iter().filter(|v| adjacent[**u][**v]).count());
+        let mut branches: Vec<usize> = candidates
+            .iter()
+            .filter(|v| pivot.is_none_or(|u| !adjacent[*u][**v]))
+            .copied()
+            .collect();
+        branches.reverse();
+        Frame {
+            clique,
+            candidates,
+            excluded,
+            branches,
+        }
+    }
+}
+
+impl Iterator for MaximalCliques<'_> {
+    type Item = Vec<usize>;
+
+    /// Returns the next maximal clique and advances the search.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            let n = self.graph.vertex_count();
+            if n == 0 {
+                return Some(Vec::new());
+            }
+            let root = self.frame(Vec::new(), (0..n).collect(), Vec::new());
+            self.stack.push(root);
+        }
+        let adjacent = &self.graph.adjacent;
+        while let Some(frame) = self.stack.last_mut() {
+            let Some(v) = frame.branches.pop() else {
+                self.stack.pop();
+                continue;
+            };
+            let mut clique = frame.clique.clone();
+            clique.push(v);
+            let candidates: Vec<usize> = frame
+                .candidates
+                .iter()
+                .filter(|u| adjacent[v][**u])
+                .copied()
+                .collect();
+            let excluded: Vec<usize> = frame
+                .excluded
+                .iter()
+                .filter(|u| adjacent[v][**u])
+                .copied()
+                .collect();
+            frame.candidates.retain(|u| *u != v);
+            frame.excluded.push(v);
+            if candidates.is_empty() {
+                if excluded.is_empty() {
+                    clique.sort();
+                    return Some(clique);
+                }
+            } else {
+                let next = self.frame(clique, candidates, excluded);
+                self.stack.push(next);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::powerset;
+
+    /// Returns a pseudo-random graph with `n` vertices, using a simple linear congruential
+    /// generator so that the tests are reproducible.
+    fn random_graph(n: usize, seed: u64) -> Graph {
+        let mut state = seed;
+        let mut edges = Vec::new();
+        for a in 0..n {
+            for b in a + 1..n {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                if state >> 63 == 1 {
+                    edges.push((a, b));
+                }
+            }
+        }
+        Graph::new(n, edges)
+    }
+
+    #[test]
+    fn test_graph_matches_filtered_powerset() {
+        for n in 0..9 {
+            for seed in 0..5 {
+                let graph = random_graph(n, seed);
+                let cliques: Vec<Vec<usize>> =
+                    powerset(0..n).filter(|set| graph.is_clique(set)).collect();
+                assert_eq!(graph.cliques().collect::<Vec<_>>(), cliques);
+                let independent: Vec<Vec<usize>> = powerset(0..n)
+                    .filter(|set| graph.is_independent(set))
+                    .collect();
+                assert_eq!(graph.independent_sets().collect::<Vec<_>>(), independent);
+                assert_eq!(
+                    graph.complement().cliques().collect::<Vec<_>>(),
+                    independent
+                );
+
+                let mut maximal: Vec<Vec<usize>> = cliques
+                    .iter()
+                    .filter(|clique| {
+                        (0..n).all(|v| {
+                            clique.contains(&v) || !clique.iter().all(|u| graph.is_adjacent(*u, v))
+                        })
+                    })
+                    .cloned()
+                    .collect();
+                let mut found: Vec<Vec<usize>> = graph.maximal_cliques().collect();
+                maximal.sort();
+                found.sort();
+                assert_eq!(found, maximal);
+            }
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use crate::Permutation;
+
+/// A group of permutations, represented by the subgroup generated by a set of permutations.
+///
+/// On construction, the group is processed with the incremental Schreier-Sims algorithm,
+/// which builds a table of coset representatives for the chain of subgroups
+/// `G = G_(n-1) >= ... >= G_0 >= {e}`, where `G_k` consists of the elements of the group which fix
+/// every point greater than `k`.  The table allows the order of the group to be computed and
+/// membership to be tested in polynomial time, and every element of the group to be enumerated
+/// exactly once without storing the whole group.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{Permutation, PermutationGroup};
+///
+/// // The symmetries of a square with vertices 0, 1, 2, 3 in order.
+/// let rotation = Permutation::from_images(vec![1, 2, 3, 0]).unwrap();
+/// let reflection = Permutation::from_images(vec![3, 2, 1, 0]).unwrap();
+/// let group = PermutationGroup::generated_by(vec![rotation, reflection]);
+/// assert_eq!(group.order(), Some(8));
+/// assert!(group.contains(&Permutation::from_images(vec![0, 3, 2, 1]).unwrap()));
+/// assert!(!group.contains(&Permutation::from_images(vec![1, 0, 2, 3]).unwrap()));
+/// assert_eq!(group.elements().count(), 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PermutationGroup {
+    degree: usize,
+    generators: Vec<Permutation>,
+    /// For each level `k`, the strong generators which fix every point greater than `k`, and so
+    /// generate `G_k`.
+    level_generators: Vec<Vec<Permutation>>,
+    /// For each level `k`, the representative at index `j` (if any) is an element of `G_k` which
+    /// maps `k` to `j`.
+    representatives: Vec<Vec<Option<Permutation>>>,
+}
+
+impl PermutationGroup {
+    /// Creates the group generated by the given permutations.  The degree of the group is the
+    /// largest degree of the generators, and generators of smaller degree are extended to fix the
+    /// remaining points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Permutation, PermutationGroup};
+    ///
+    /// let cycle = Permutation::from_images(vec![1, 2, 3, 4, 0]).unwrap();
+    /// let swap = Permutation::from_images(vec![1, 0]).unwrap();
+    /// assert_eq!(PermutationGroup::generated_by(vec![cycle.clone()]).order(), Some(5));
+    /// assert_eq!(PermutationGroup::generated_by(vec![cycle, swap]).order(), Some(120));
+    ///
+    /// let trivial = PermutationGroup::generated_by(Vec::new());
+    /// assert_eq!(trivial.order(), Some(1));
+    /// ```
+    pub fn generated_by(generators: impl IntoIterator<Item = Permutation>) -> Self {
+        let generators: Vec<Permutation> = generators.into_iter().collect();
+        let degree = generators.iter().map(|g| g.degree()).max().unwrap_or(0);
+        let generators: Vec<Permutation> = generators
+            .iter()
+            .filter_map(|g| g.with_degree(degree))
+            .collect();
+        let mut group = PermutationGroup {
+            degree,
+            generators: generators.clone(),
+            level_generators: vec![Vec::new(); degree],
+            representatives: (0..degree)
+                .map(|k| {
+                    let mut reps = vec![None; k + 1];
+                    reps[k] = Some(Permutation::identity(degree));
+                    reps
+                })
+                .collect(),
+        };
+        if degree > 0 {
+            for generator in generators {
+                if let Some((level, residue)) = group.sift(degree - 1, generator) {
+                    group.add_generator(level, residue);
+                }
+            }
+        }
+        group
+    }
+
+    /// Returns the degree of the group, which is the number of points it acts on.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns the generators from which the group was created.
+    pub fn generators(&self) -> &[Permutation] {
+        &self.generators
+    }
+
+    /// Returns the number of elements in the group, or `None` if it does not fit in a `u128`.
+    /// The order of any group of degree at most 34 fits, but the symmetric groups of larger
+    /// degree, and some of their subgroups, do not.
+    pub fn order(&self) -> Option<u128> {
+        self.representatives.iter().try_fold(1u128, |order, reps| {
+            order.checked_mul(reps.iter().filter(|rep| rep.is_some()).count() as u128)
+        })
+    }
+
+    /// Returns `true` if the given permutation is an element of the group.  A permutation of
+    /// different degree is treated as fixing every point beyond its degree.
+    pub fn contains(&self, permutation: &Permutation) -> bool {
+        match permutation.with_degree(self.degree) {
+            Some(permutation) => {
+                self.degree == 0 || self.sift(self.degree - 1, permutation).is_none()
+            }
+            None => false,
+        }
+    }
+
+    /// Returns an iterator over every element of the group, each yielded exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Permutation, PermutationGroup};
+    ///
+    /// let group = PermutationGroup::generated_by(vec![Permutation::from_images(vec![1, 2, 0]).unwrap()]);
+    /// let mut elements: Vec<Vec<usize>> = group.elements().map(|p| p.into_images()).collect();
+    /// elements.sort();
+    /// assert_eq!(elements, vec![vec![0, 1, 2], vec![1, 2, 0], vec![2, 0, 1]]);
+    /// ```
+    pub fn elements(&self) -> GroupElements<'_> {
+        GroupElements {
+            levels: self
+                .representatives
+                .iter()
+                .map(|reps| reps.iter().flatten().collect())
+                .collect(),
+            indices: vec![0; self.degree],
+            degree: self.degree,
+            done: false,
+        }
+    }
+
+    /// Returns one representative of each left coset `gH` of the given subgroup `H` in this group.
+    /// The given group must be a subgroup of this group.
+    ///
+    /// The cosets are found by a breadth-first search from `H` itself, whose representative is the
+    /// identity, multiplying each representative found so far by the generators of this group.
+    /// This takes a number of membership tests quadratic in the index `[G:H]` rather than
+    /// enumerating the whole group, and stops as soon as `[G:H]` cosets have been found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Permutation, PermutationGroup};
+    ///
+    /// let cycle = Permutation::from_images(vec![1, 2, 3, 0]).unwrap();
+    /// let swap = Permutation::from_images(vec![1, 0, 2, 3]).unwrap();
+    /// let symmetric = PermutationGroup::generated_by(vec![cycle.clone(), swap]);
+    /// let cyclic = PermutationGroup::generated_by(vec![cycle]);
+    /// let representatives = symmetric.coset_representatives(&cyclic);
+    /// assert_eq!(representatives.len(), 6);
+    /// assert_eq!(representatives[0], Permutation::identity(4));
+    /// ```
+    pub fn coset_representatives(&self, subgroup: &PermutationGroup) -> Vec<Permutation> {
+        let index = self
+            .order()
+            .zip(subgroup.order())
+            .map(|(order, subgroup_order)| order / subgroup_order);
+        let mut representatives = vec![Permutation::identity(self.degree)];
+        let mut next = 0;
+        while next < representatives.len() {
+            if index.is_some_and(|index| representatives.len() as u128 >= index) {
+                break;
+            }
+            for generator in &self.generators {
+                let candidate = generator.compose(&representatives[next]);
+                if !representatives
+                    .iter()
+                    .any(|rep| subgroup.contains(&rep.inverse().compose(&candidate)))
+                {
+                    representatives.push(candidate);
+                }
+            }
+            next += 1;
+        }
+        representatives
+    }
+
+    /// Reduces the given permutation, which must fix every point greater than `level`, by the
+    /// coset representatives at `level` and below.  Returns `None` if it reduces to the identity,
+    /// meaning the permutation is in the group, or otherwise returns the level at which no
+    /// representative was found along with the partially reduced permutation.
+    fn sift(&self, level: usize, mut permutation: Permutation) -> Option<(usize, Permutation)> {
+        for k in (0..=level).rev() {
+            let image = permutation.image(k);
+            match &self.representatives[k][image] {
+                Some(rep) => permutation = rep.inverse().compose(&permutation),
+                None => return Some((k, permutation)),
+            }
+        }
+        None
+    }
+
+    /// Adds a new strong generator which fixes every point greater than `level`.  Since it belongs
+    /// to `G_k` for every `k >= level`, the representatives of each of those levels are extended by
+    /// applying it to every existing representative.
+    fn add_generator(&mut self, level: usize, generator: Permutation) {
+        for k in level..self.degree {
+            self.level_generators[k].push(generator.clone());
+            let reps: Vec<Permutation> =
+                self.representatives[k].iter().flatten().cloned().collect();
+            for rep in reps {
+                self.extend(k, generator.compose(&rep));
+            }
+        }
+    }
+
+    /// Records the given element of `G_level` as a coset representative if its coset is new, and
+    /// otherwise sifts the resulting Schreier generator into the lower levels.
+    fn extend(&mut self, level: usize, permutation: Permutation) {
+        let image = permutation.image(level);
+        match &self.representatives[level][image] {
+            Some(rep) => {
+                let schreier = rep.inverse().compose(&permutation);
+                if level > 0 {
+                    if let Some((k, residue)) = self.sift(level - 1, schreier) {
+                        self.add_generator(k, residue);
+                    }
+                }
+            }
+            None => {
+                self.representatives[level][image] = Some(permutation.clone());
+                let generators = self.level_generators[level].clone();
+                for generator in generators {
+                    self.extend(level, generator.compose(&permutation));
+                }
+            }
+        }
+    }
+}
+
+/// An iterator over the elements of a [`PermutationGroup`].
+///
+/// Each element is the product of one coset representative from each level of the group, so the
+/// elements are enumerated by counting through the representatives like an odometer.
+pub struct GroupElements<'a> {
+    levels: Vec<Vec<&'a Permutation>>,
+    indices: Vec<usize>,
+    degree: usize,
+    done: bool,
+}
+
+impl Iterator for GroupElements<'_> {
+    type Item = Permutation;
+
+    /// Returns the next element of the group and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let element = self
+            .levels
+            .iter()
+            .zip(self.indices.iter())
+            .rev()
+            .fold(Permutation::identity(self.degree), |product, (reps, i)| {
+                product.compose(reps[*i])
+            });
+        self.done = true;
+        for (reps, index) in self.levels.iter().zip(self.indices.iter_mut()) {
+            if *index + 1 < reps.len() {
+                *index += 1;
+                self.done = false;
+                break;
+            }
+            *index = 0;
+        }
+        Some(element)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// Computes the closure of the given generators by breadth-first search.
+    fn brute_force_closure(generators: &[Permutation], degree: usize) -> BTreeSet<Permutation> {
+        let mut elements = BTreeSet::from([Permutation::identity(degree)]);
+        let mut frontier = vec![Permutation::identity(degree)];
+        while let Some(element) = frontier.pop() {
+            for generator in generators {
+                let product = generator.with_degree(degree).unwrap().compose(&element);
+                if elements.insert(product.clone()) {
+                    frontier.push(product);
+                }
+            }
+        }
+        elements
+    }
+
+    fn perm(images: &[usize]) -> Permutation {
+        Permutation::from_images(images.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_group_matches_brute_force() {
+        let generator_sets = vec![
+            vec![perm(&[1, 0, 2, 3, 4, 5])],
+            vec![perm(&[1, 2, 0, 4, 5, 3]), perm(&[3, 4, 5, 0, 1, 2])],
+            vec![perm(&[1, 2, 3, 4, 5, 0]), perm(&[5, 4, 3, 2, 1, 0])],
+            vec![perm(&[1, 0, 3, 2, 5, 4]), perm(&[2, 3, 0, 1, 4, 5])],
+            vec![perm(&[1, 2, 0, 3, 4, 5]), perm(&[0, 1, 3, 4, 5, 2])],
+            vec![perm(&[1, 2, 3, 4, 5, 0]), perm(&[1, 0, 2, 3, 4, 5])],
+            vec![
+                perm(&[2, 0, 1, 3, 4, 5]),
+                perm(&[0, 1, 2, 4, 5, 3]),
+                perm(&[0, 4, 2, 3, 1, 5]),
+            ],
+        ];
+        for generators in generator_sets {
+            let group = PermutationGroup::generated_by(generators.clone());
+            let expected = brute_force_closure(&generators, 6);
+            assert_eq!(group.order(), Some(expected.len() as u128));
+            let elements: BTreeSet<Permutation> = group.elements().collect();
+            assert_eq!(elements, expected);
+            for images in crate::verify::cartesian_power(&[0, 1, 2, 3, 4, 5], 6) {
+                if let Some(p) = Permutation::from_images(images) {
+                    assert_eq!(group.contains(&p), expected.contains(&p));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_group_coset_representatives() {
+        let symmetric =
+            PermutationGroup::generated_by(vec![perm(&[1, 2, 3, 0]), perm(&[1, 0, 2, 3])]);
+        let klein = PermutationGroup::generated_by(vec![perm(&[1, 0, 3, 2]), perm(&[2, 3, 0, 1])]);
+        let reps = symmetric.coset_representatives(&klein);
+        assert_eq!(reps.len(), 6);
+        for (i, a) in reps.iter().enumerate() {
+            for b in reps[i + 1..].iter() {
+                assert!(!klein.contains(&a.inverse().compose(b)));
+            }
+        }
+        for element in symmetric.elements() {
+            assert!(reps
+                .iter()
+                .any(|rep| klein.contains(&rep.inverse().compose(&element))));
+        }
+        let trivial = PermutationGroup::generated_by(vec![Permutation::identity(4)]);
+        assert_eq!(symmetric.coset_representatives(&trivial).len(), 24);
+        assert_eq!(symmetric.coset_representatives(&symmetric).len(), 1);
+
+        // The index of S_11 in S_12 is small, so this must not enumerate all 12! elements.
+        let symmetric = |n: usize| {
+            let cycle = Permutation::from_images((1..n).chain([0]).collect()).unwrap();
+            PermutationGroup::generated_by(vec![cycle, perm(&[1, 0])])
+        };
+        let (large, small) = (symmetric(12), symmetric(11));
+        let reps = large.coset_representatives(&small);
+        assert_eq!(reps.len(), 12);
+        let images: BTreeSet<usize> = reps.iter().map(|rep| rep.image(11)).collect();
+        assert_eq!(images.len(), 12);
+    }
+
+    #[test]
+    fn test_group_degree_zero_and_contains_larger_degree() {
+        let trivial = PermutationGroup::generated_by(Vec::new());
+        assert_eq!(trivial.degree(), 0);
+        assert_eq!(trivial.elements().count(), 1);
+        assert!(trivial.contains(&Permutation::identity(3)));
+        assert!(!trivial.contains(&perm(&[1, 0])));
+    }
+
+    #[test]
+    fn test_group_order_overflow() {
+        let symmetric = |n: usize| {
+            let cycle = Permutation::from_images((1..n).chain([0]).collect()).unwrap();
+            PermutationGroup::generated_by(vec![cycle, perm(&[1, 0])])
+        };
+        assert_eq!(symmetric(34).order(), crate::permutation_count(34, 34));
+        assert_eq!(symmetric(35).order(), None);
+    }
+}
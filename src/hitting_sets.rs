@@ -0,0 +1,159 @@
+use crate::{sorted_unique, MonotoneFrontier};
+
+/// Returns an iterator over the minimal hitting sets of the given family of sets, which are the
+/// minimal sets sharing at least one element with every set in the family, drawn from the
+/// elements of the family.  The hitting sets are yielded smallest first, and in lexicographic
+/// order among sets of the same size, each with its elements in increasing order.
+///
+/// Hitting every set is a monotone property, so the hitting sets are found with a
+/// [`MonotoneFrontier`], which never extends a partial set containing a hitting set already
+/// found, or one which cannot hit every set even with every later element added.  If the family
+/// is empty, the only minimal hitting set is the empty set, and if it contains an empty set, there
+/// are none.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::minimal_hitting_sets;
+///
+/// // Each failing test covers some lines, and each minimal hitting set is a smallest group of
+/// // lines which might explain every failure.
+/// let failures = vec![vec![10, 11], vec![11, 12], vec![10, 13]];
+/// let explanations: Vec<Vec<u32>> = minimal_hitting_sets(&failures).collect();
+/// assert_eq!(explanations, vec![vec![10, 11], vec![10, 12], vec![11, 13]]);
+///
+/// assert_eq!(minimal_hitting_sets::<u32>(&[]).collect::<Vec<_>>(), vec![Vec::<u32>::new()]);
+/// assert_eq!(minimal_hitting_sets::<u32>(&[vec![1], vec![]]).next(), None);
+/// ```
+pub fn minimal_hitting_sets<T: Ord + Clone>(
+    family: &[Vec<T>],
+) -> MonotoneFrontier<T, impl FnMut(&[T]) -> bool> {
+    let family: Vec<Vec<T>> = family
+        .iter()
+        .map(|set| sorted_unique(set.clone()))
+        .collect();
+    let elements: Vec<T> = family.iter().flatten().cloned().collect();
+    MonotoneFrontier::minimal_satisfying(elements, move |subset: &[T]| {
+        family.iter().all(|set| {
+            set.iter()
+                .any(|element| subset.binary_search(element).is_ok())
+        })
+    })
+}
+
+/// Returns the indices of a smallest collection of the given sets whose union contains every
+/// element of the universe, in increasing order, or `None` if some element of the universe is in
+/// none of the sets.
+///
+/// A collection of sets covers the universe exactly when it hits, for every element of the
+/// universe, the collection of sets containing that element, so the smallest cover is the first
+/// of the [`minimal_hitting_sets`] of those collections.  The search takes exponential time in
+/// the worst case, so this suits small instances; [`greedy_set_cover`] gives a cover which may be
+/// larger, but quickly.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{greedy_set_cover, minimum_set_cover};
+///
+/// let sets = vec![vec![1, 2, 4, 5], vec![1, 2, 3], vec![4, 5, 6]];
+/// assert_eq!(minimum_set_cover(1..=6, &sets), Some(vec![1, 2]));
+/// assert_eq!(greedy_set_cover(1..=6, &sets), Some(vec![0, 1, 2]));
+/// assert_eq!(minimum_set_cover(1..=7, &sets), None);
+/// ```
+pub fn minimum_set_cover<T: Ord + Clone>(
+    universe: impl IntoIterator<Item = T>,
+    sets: &[Vec<T>],
+) -> Option<Vec<usize>> {
+    let containing: Vec<Vec<usize>> = sorted_unique(universe)
+        .iter()
+        .map(|element| {
+            (0..sets.len())
+                .filter(|i| sets[*i].contains(element))
+                .collect()
+        })
+        .collect();
+    minimal_hitting_sets(&containing).next()
+}
+
+/// Returns the indices of a collection of the given sets whose union contains every element of
+/// the universe, in the order in which they were chosen, or `None` if some element of the
+/// universe is in none of the sets.
+///
+/// The sets are chosen greedily, each time taking the set which contains the most elements not
+/// yet covered, preferring earlier sets among equals.  The cover found is at most about `ln m`
+/// times larger than the smallest, where `m` is the size of the largest set, and is found in
+/// polynomial time; [`minimum_set_cover`] finds a smallest cover.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::greedy_set_cover;
+///
+/// let sets = vec![vec!['a', 'b'], vec!['b', 'c', 'd'], vec!['d', 'e'], vec!['a', 'e']];
+/// assert_eq!(greedy_set_cover("abcde".chars(), &sets), Some(vec![1, 3]));
+/// assert_eq!(greedy_set_cover("".chars(), &sets), Some(vec![]));
+/// ```
+pub fn greedy_set_cover<T: Ord + Clone>(
+    universe: impl IntoIterator<Item = T>,
+    sets: &[Vec<T>],
+) -> Option<Vec<usize>> {
+    let mut uncovered = sorted_unique(universe);
+    let mut chosen = Vec::new();
+    while !uncovered.is_empty() {
+        let newly_covered = |set: &Vec<T>| {
+            let covered = uncovered.iter().filter(|element| set.contains(element));
+            covered.count()
+        };
+        let (best, count) = sets
+            .iter()
+            .map(newly_covered)
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, count)| *count)?;
+        if count == 0 {
+            return None;
+        }
+        uncovered.retain(|element| !sets[best].contains(element));
+        chosen.push(best);
+    }
+    Some(chosen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_hitting_sets_and_covers_match_exhaustive_search() {
+        let sets: Vec<Vec<u8>> = vec![
+            vec![0, 1, 2],
+            vec![2, 3],
+            vec![3, 4, 5, 6],
+            vec![0, 6],
+            vec![1, 4],
+            vec![5, 7],
+            vec![2, 7],
+        ];
+        let hits = |subset: &[u8]| {
+            sets.iter()
+                .all(|set| set.iter().any(|x| subset.contains(x)))
+        };
+        let without = |s: &[u8], x: u8| s.iter().copied().filter(|y| *y != x).collect::<Vec<u8>>();
+        let expected: Vec<Vec<u8>> = Combinations::all(0..8)
+            .filter(|s| hits(s) && s.iter().all(|x| !hits(&without(s, *x))))
+            .collect();
+        assert_eq!(minimal_hitting_sets(&sets).collect::<Vec<_>>(), expected);
+
+        let covers = |indices: &Vec<usize>| {
+            (0..8).all(|x| indices.iter().any(|index| sets[*index].contains(&x)))
+        };
+        let smallest = Combinations::all(0..sets.len()).find(covers);
+        assert_eq!(minimum_set_cover(0..8, &sets), smallest);
+        let greedy = greedy_set_cover(0..8, &sets).unwrap();
+        assert!(covers(&greedy) && greedy.len() >= smallest.unwrap().len());
+        assert_eq!(minimum_set_cover(0..9, &sets), None);
+        assert_eq!(greedy_set_cover(0..9, &sets), None);
+    }
+}
@@ -0,0 +1,113 @@
+//! Extension methods which let any iterator build this crate's generators in the style of
+//! `itertools`.
+//!
+//! Importing [`CombinatorialIterator`] adds methods such as
+//! [`combinations_lex`](CombinatorialIterator::combinations_lex) to every iterator, so code written
+//! against `itertools` can switch to these generators by renaming a method call.  Unlike the
+//! `itertools` adapters, which pick items by their positions in the input, these methods build the
+//! generators in the usual way, sorting and deduplicating the items first, so the results are
+//! yielded in lexicographic order with no repeated items.  For the positional behaviour, use
+//! constructors such as [`Combinations::of_size_positional`] directly.
+//!
+//! This module also converts between this crate's types and plain vectors: a vector converts into
+//! a [`Combinations`] or [`Permutations`] generator over all of its unique items, as by
+//! [`Combinations::all`] and [`Permutations::all`], and a [`Permutation`](crate::Permutation)
+//! converts to and from the vector of its images.
+//!
+//! For building generators from any iterable, rather than only from iterators, see
+//! [`CombinatorialExt`](crate::CombinatorialExt).
+//!
+//! # Examples
+//!
+//! ```
+//! use combinatorial::interop::CombinatorialIterator;
+//!
+//! let combos: Vec<Vec<i32>> = vec![3, 1, 2, 1].into_iter().combinations_lex(2).collect();
+//! assert_eq!(combos, vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+//! ```
+
+use crate::{Combinations, Permutations};
+
+/// An extension trait for iterators, implemented for every iterator over items which are `Ord`
+/// and `Clone`, which builds generators over the items of the iterator.
+pub trait CombinatorialIterator: Iterator + Sized
+where
+    Self::Item: Ord + Clone,
+{
+    /// Returns a [`Combinations`] iterator which yields every combination of `k` of the unique
+    /// items of this iterator, as by [`Combinations::of_size`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::interop::CombinatorialIterator;
+    ///
+    /// let mut combos = "cab".chars().combinations_lex(2);
+    /// assert_eq!(combos.next(), Some(vec!['a', 'b']));
+    /// assert_eq!(combos.next(), Some(vec!['a', 'c']));
+    /// assert_eq!(combos.next(), Some(vec!['b', 'c']));
+    /// assert_eq!(combos.next(), None);
+    /// ```
+    fn combinations_lex(self, k: usize) -> Combinations<Self::Item> {
+        Combinations::of_size(self, k)
+    }
+
+    /// Returns a [`Permutations`] iterator which yields every permutation of `k` of the unique
+    /// items of this iterator, as by [`Permutations::of_length`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::interop::CombinatorialIterator;
+    ///
+    /// let mut perms = (1..=3).permutations_lex(2);
+    /// assert_eq!(perms.next(), Some(vec![1, 2]));
+    /// assert_eq!(perms.next(), Some(vec![1, 3]));
+    /// assert_eq!(perms.next(), Some(vec![2, 1]));
+    /// assert_eq!(perms.count(), 3);
+    /// ```
+    fn permutations_lex(self, k: usize) -> Permutations<Self::Item> {
+        Permutations::of_length(self, k)
+    }
+}
+
+impl<I> CombinatorialIterator for I
+where
+    I: Iterator,
+    I::Item: Ord + Clone,
+{
+}
+
+/// Converts a vector into a generator of every combination of its unique items, of every size, as
+/// by [`Combinations::all`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Combinations;
+///
+/// let combos = Combinations::from(vec![2, 1, 2]);
+/// assert_eq!(combos.collect::<Vec<_>>(), vec![vec![], vec![1], vec![2], vec![1, 2]]);
+/// ```
+impl<T: Ord + Clone> From<Vec<T>> for Combinations<T> {
+    fn from(elements: Vec<T>) -> Self {
+        Combinations::all(elements)
+    }
+}
+
+/// Converts a vector into a generator of every permutation of its unique items, of every length,
+/// as by [`Permutations::all`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Permutations;
+///
+/// let perms: Permutations<char> = vec!['b', 'a'].into();
+/// assert_eq!(perms.count(), 1 + 2 + 2);
+/// ```
+impl<T: Ord + Clone> From<Vec<T>> for Permutations<T> {
+    fn from(elements: Vec<T>) -> Self {
+        Permutations::all(elements)
+    }
+}
@@ -0,0 +1,260 @@
+/// An iterator which generates all Latin squares of a given order.
+///
+/// A Latin square of order `n` is an `n` by `n` grid filled with the values `0..n` such that each
+/// value occurs exactly once in every row and exactly once in every column.  Squares are yielded as
+/// vectors of rows, in lexicographic order of their cells read row by row.  Rather than filtering
+/// permutations, the squares are built cell by cell using backtracking, so no work is spent on
+/// partial grids which cannot be completed.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::LatinSquares;
+///
+/// let mut squares = LatinSquares::of_order(2);
+/// assert_eq!(squares.next(), Some(vec![vec![0, 1], vec![1, 0]]));
+/// assert_eq!(squares.next(), Some(vec![vec![1, 0], vec![0, 1]]));
+/// assert_eq!(squares.next(), None);
+///
+/// assert_eq!(LatinSquares::of_order(4).count(), 576);
+/// ```
+#[derive(Clone)]
+pub struct LatinSquares {
+    order: usize,
+    cells: Vec<usize>,
+    used_in_row: Vec<bool>,
+    used_in_col: Vec<bool>,
+    started: bool,
+    done: bool,
+}
+
+impl LatinSquares {
+    /// Creates a new `LatinSquares` iterator which will yield all Latin squares of the given
+    /// order.  The square of order zero is the empty square, which is yielded exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::LatinSquares;
+    ///
+    /// let mut squares = LatinSquares::of_order(3);
+    /// assert_eq!(
+    ///     squares.next(),
+    ///     Some(vec![vec![0, 1, 2], vec![1, 2, 0], vec![2, 0, 1]])
+    /// );
+    /// assert_eq!(squares.count(), 11);
+    ///
+    /// let mut squares = LatinSquares::of_order(0);
+    /// assert_eq!(squares.next(), Some(Vec::new()));
+    /// assert_eq!(squares.next(), None);
+    /// ```
+    pub fn of_order(order: usize) -> Self {
+        LatinSquares {
+            order,
+            cells: Vec::with_capacity(order * order),
+            used_in_row: vec![false; order * order],
+            used_in_col: vec![false; order * order],
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Returns the square which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if none remain.  Since the next square is only found
+    /// when the iterator advances, this searches for it using a copy of the iterator, so it costs
+    /// about as much as a call to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::LatinSquares;
+    ///
+    /// let mut squares = LatinSquares::of_order(2);
+    /// assert_eq!(squares.current(), Some(vec![vec![0, 1], vec![1, 0]]));
+    /// assert_eq!(squares.next(), Some(vec![vec![0, 1], vec![1, 0]]));
+    /// assert_eq!(squares.current(), Some(vec![vec![1, 0], vec![0, 1]]));
+    /// ```
+    pub fn current(&self) -> Option<Vec<Vec<usize>>> {
+        self.clone().next()
+    }
+
+    /// Returns a pseudo-randomly generated Latin square of the given order, using the given random
+    /// number generator.
+    ///
+    /// The square is built by backtracking over the cells in row-major order, trying the values for
+    /// each cell in a random order.  Every Latin square of the given order can be produced, but
+    /// the distribution is not uniform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::LatinSquares;
+    ///
+    /// let square = LatinSquares::random(6, &mut rand::thread_rng());
+    /// for i in 0..6 {
+    ///     let mut row = square[i].clone();
+    ///     let mut col: Vec<usize> = square.iter().map(|row| row[i]).collect();
+    ///     row.sort();
+    ///     col.sort();
+    ///     assert_eq!(row, (0..6).collect::<Vec<usize>>());
+    ///     assert_eq!(col, (0..6).collect::<Vec<usize>>());
+    /// }
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn random<R: rand::Rng + ?Sized>(order: usize, rng: &mut R) -> Vec<Vec<usize>> {
+        use rand::seq::SliceRandom;
+
+        let mut squares = LatinSquares::of_order(order);
+        let mut candidates: Vec<Vec<usize>> = Vec::with_capacity(order * order);
+        while squares.cells.len() < order * order {
+            let cell = squares.cells.len();
+            if candidates.len() == cell {
+                let mut values: Vec<usize> = (0..order).collect();
+                values.shuffle(rng);
+                candidates.push(values);
+            }
+            let value = loop {
+                match candidates[cell].pop() {
+                    Some(value) if squares.is_available(cell, value) => break Some(value),
+                    Some(_) => continue,
+                    None => break None,
+                }
+            };
+            match value {
+                Some(value) => squares.place(value),
+                None => {
+                    candidates.pop();
+                    squares.unplace();
+                }
+            }
+        }
+        squares.current_square()
+    }
+
+    /// Returns `true` if the given value does not yet occur in the row or column of the given cell.
+    fn is_available(&self, cell: usize, value: usize) -> bool {
+        let (row, col) = (cell / self.order, cell % self.order);
+        !self.used_in_row[row * self.order + value] && !self.used_in_col[col * self.order + value]
+    }
+
+    /// Places the given value in the next empty cell.
+    fn place(&mut self, value: usize) {
+        let cell = self.cells.len();
+        let (row, col) = (cell / self.order, cell % self.order);
+        self.used_in_row[row * self.order + value] = true;
+        self.used_in_col[col * self.order + value] = true;
+        self.cells.push(value);
+    }
+
+    /// Removes the value from the most recently filled cell and returns it, if any cell is filled.
+    fn unplace(&mut self) -> Option<usize> {
+        let value = self.cells.pop()?;
+        let cell = self.cells.len();
+        let (row, col) = (cell / self.order, cell % self.order);
+        self.used_in_row[row * self.order + value] = false;
+        self.used_in_col[col * self.order + value] = false;
+        Some(value)
+    }
+
+    /// Fills the remaining cells with the lexicographically smallest valid values, backtracking
+    /// as necessary.  If `previous` is given, the search resumes by trying values greater than
+    /// `previous` in the next empty cell.  Returns `true` if the grid was completely filled, or
+    /// `false` if no further Latin squares exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            let cell = self.cells.len();
+            if previous.is_none() && cell == self.order * self.order {
+                return true;
+            }
+            let start = previous.map_or(0, |value| value + 1);
+            match (start..self.order).find(|value| self.is_available(cell, *value)) {
+                Some(value) => {
+                    self.place(value);
+                    previous = None;
+                }
+                None => match self.unplace() {
+                    Some(value) => previous = Some(value),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Returns the current contents of the grid as a vector of rows.
+    fn current_square(&self) -> Vec<Vec<usize>> {
+        self.cells
+            .chunks(self.order.max(1))
+            .map(|row| row.to_vec())
+            .collect()
+    }
+}
+
+impl Iterator for LatinSquares {
+    type Item = Vec<Vec<usize>>;
+
+    /// Returns the next Latin square and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let found = if self.started {
+            match self.unplace() {
+                Some(value) => self.fill(Some(value)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.fill(None)
+        };
+        if !found {
+            self.done = true;
+            return None;
+        }
+        Some(self.current_square())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latin_squares_counts() {
+        assert_eq!(LatinSquares::of_order(0).count(), 1);
+        assert_eq!(LatinSquares::of_order(1).count(), 1);
+        assert_eq!(LatinSquares::of_order(2).count(), 2);
+        assert_eq!(LatinSquares::of_order(3).count(), 12);
+        assert_eq!(LatinSquares::of_order(4).count(), 576);
+        let mut items = LatinSquares::of_order(3);
+        while let Some(current) = items.current() {
+            assert_eq!(items.next(), Some(current));
+        }
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn test_latin_squares_valid_and_ordered() {
+        let squares: Vec<Vec<Vec<usize>>> = LatinSquares::of_order(4).collect();
+        assert!(squares.windows(2).all(|pair| pair[0] < pair[1]));
+        for square in squares {
+            for i in 0..4 {
+                let mut row = square[i].clone();
+                let mut col: Vec<usize> = square.iter().map(|row| row[i]).collect();
+                row.sort();
+                col.sort();
+                assert_eq!(row, vec![0, 1, 2, 3]);
+                assert_eq!(col, vec![0, 1, 2, 3]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_latin_squares_fill() {
+        let mut squares = LatinSquares::of_order(3);
+        assert!(squares.fill(None));
+        assert_eq!(squares.cells, vec![0, 1, 2, 1, 2, 0, 2, 0, 1]);
+        assert_eq!(squares.unplace(), Some(1));
+        assert!(squares.fill(Some(1)));
+        assert_eq!(squares.cells, vec![0, 1, 2, 2, 0, 1, 1, 2, 0]);
+    }
+}
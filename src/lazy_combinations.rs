@@ -0,0 +1,199 @@
+/// An iterator which generates combinations of a given size from a stream of elements, pulling
+/// elements from the stream only as they are needed.
+///
+/// Unlike [`Combinations`](crate::Combinations), which collects, sorts, and deduplicates its
+/// input up front, this takes the elements in positional mode, exactly as they arrive, and yields
+/// combinations in colexicographic order of their positions: every combination of the first `m`
+/// elements is yielded before the `m + 1`th element is read.  This means it can begin yielding
+/// combinations immediately, even from very large or infinite streams, and it never holds more
+/// elements than it has actually used.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::LazyCombinations;
+///
+/// let mut pairs = LazyCombinations::of_size(1.., 2);
+/// assert_eq!(pairs.next(), Some(vec![1, 2]));
+/// assert_eq!(pairs.next(), Some(vec![1, 3]));
+/// assert_eq!(pairs.next(), Some(vec![2, 3]));
+/// assert_eq!(pairs.next(), Some(vec![1, 4]));
+/// assert_eq!(pairs.buffered(), 4);
+/// ```
+#[derive(Clone)]
+pub struct LazyCombinations<I: Iterator> {
+    source: I,
+    buffer: Vec<I::Item>,
+    positions: Vec<usize>,
+    exhausted: bool,
+    started: bool,
+    done: bool,
+}
+
+impl<I: Iterator> LazyCombinations<I> {
+    /// Creates a new `LazyCombinations` iterator which will yield all combinations with the
+    /// specified size of the elements produced by the given iterable.  If it produces fewer
+    /// elements than the size, then no combinations are yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::LazyCombinations;
+    ///
+    /// let combos: Vec<Vec<char>> = LazyCombinations::of_size("abc".chars(), 2).collect();
+    /// assert_eq!(combos, vec![vec!['a', 'b'], vec!['a', 'c'], vec!['b', 'c']]);
+    ///
+    /// assert_eq!(LazyCombinations::of_size(0..3, 4).next(), None);
+    /// ```
+    pub fn of_size(elements: impl IntoIterator<IntoIter = I>, size: usize) -> Self {
+        LazyCombinations {
+            source: elements.into_iter(),
+            buffer: Vec::with_capacity(size),
+            positions: (0..size).collect(),
+            exhausted: false,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Returns the number of elements which have been read from the stream so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::LazyCombinations;
+    ///
+    /// let mut combos = LazyCombinations::of_size(0..1000, 3);
+    /// assert_eq!(combos.buffered(), 0);
+    /// combos.next();
+    /// assert_eq!(combos.buffered(), 3);
+    /// ```
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the combination which the next call to [`next`](Iterator::next) will yield,
+    /// without advancing the iterator, or `None` if none remain.  The next combination may need
+    /// an element which has not been read yet, so this searches for it using a copy of the
+    /// iterator, including its source, and is only available for sources which can be cloned.
+    /// Any elements read by the copy are read again from the original source when the iterator
+    /// advances.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::LazyCombinations;
+    ///
+    /// let mut pairs = LazyCombinations::of_size(1.., 2);
+    /// assert_eq!(pairs.next(), Some(vec![1, 2]));
+    /// assert_eq!(pairs.current(), Some(vec![1, 3]));
+    /// assert_eq!(pairs.buffered(), 2);
+    /// assert_eq!(pairs.next(), Some(vec![1, 3]));
+    /// ```
+    pub fn current(&self) -> Option<Vec<I::Item>>
+    where
+        I: Clone,
+        I::Item: Clone,
+    {
+        self.clone().next()
+    }
+
+    /// Reads elements from the stream until the buffer holds at least `count` of them.  Returns
+    /// `false` if the stream ends first.
+    fn fill_to(&mut self, count: usize) -> bool {
+        while self.buffer.len() < count {
+            if self.exhausted {
+                return false;
+            }
+            match self.source.next() {
+                Some(element) => self.buffer.push(element),
+                None => self.exhausted = true,
+            }
+        }
+        true
+    }
+
+    /// Increments the internal positions to correspond to the indices of the next combination in
+    /// colexicographic order, reading another element from the stream when the last position
+    /// needs to move past the end of the buffer.  Returns `false` if this was the last
+    /// combination.
+    fn move_to_next_position(&mut self) -> bool {
+        let length = self.positions.len();
+        for index in 0..length {
+            let next = self.positions[index] + 1;
+            let available = match self.positions.get(index + 1) {
+                Some(limit) => next < *limit,
+                None => self.fill_to(next + 1),
+            };
+            if available {
+                self.positions[index] = next;
+                for (earlier, position) in self.positions[..index].iter_mut().enumerate() {
+                    *position = earlier;
+                }
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<I: Iterator> Iterator for LazyCombinations<I>
+where
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    /// Returns the next combination and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let found = if self.started {
+            self.move_to_next_position()
+        } else {
+            self.started = true;
+            self.fill_to(self.positions.len())
+        };
+        if !found {
+            self.done = true;
+            return None;
+        }
+        Some(
+            self.positions
+                .iter()
+                .map(|p| self.buffer[*p].clone())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Combinations, Order};
+
+    #[test]
+    fn test_lazy_combinations_match_colex_positional() {
+        let elements = vec![3, 1, 4, 1, 5, 9, 2];
+        for k in 0..=elements.len() + 1 {
+            let expected: Vec<Vec<i32>> = Combinations::of_size_positional(elements.clone(), k)
+                .order(Order::Colex)
+                .collect();
+            let combos: Vec<Vec<i32>> = LazyCombinations::of_size(elements.clone(), k).collect();
+            assert_eq!(combos, expected);
+        }
+        let mut items = LazyCombinations::of_size(0..6, 3);
+        while let Some(current) = items.current() {
+            assert_eq!(items.next(), Some(current));
+        }
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn test_lazy_combinations_read_only_what_is_needed() {
+        let mut combos = LazyCombinations::of_size(0.., 3);
+        let first: Vec<Vec<usize>> = combos.by_ref().take(10).collect();
+        assert_eq!(first.last(), Some(&vec![2, 3, 4]));
+        assert_eq!(combos.buffered(), 5);
+    }
+}
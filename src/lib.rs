@@ -1,10 +1,136 @@
 //! Combinatorial tools, functions, and generators.
 
+use num_traits::Num;
+
+mod analysis;
+mod approximate;
+mod assignments;
+mod backtrack;
+mod batch;
+#[cfg(feature = "bigint")]
+mod big;
+mod binary_trees;
+mod bipartitions;
+mod block_designs;
+mod branch_and_bound;
+mod cancel;
+mod canonical;
+#[cfg(feature = "std")]
+mod checkpoint;
+mod circular_permutations;
+mod collecting;
 mod combinations;
-pub use combinations::{Combinations, CombinationsWithReplacement};
+mod conflict_free;
+mod constraint;
+mod cosets;
+mod cross;
+mod disjoint_pairs;
+mod drive;
+mod error;
+mod estimate;
+mod exact_cover;
+mod ext;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fixed_points;
+pub mod graph;
+mod group;
+mod hitting_sets;
+pub mod interop;
+mod latin_squares;
+mod lazy_combinations;
+mod masked_sequences;
+mod masks;
+mod modular;
+mod monotone;
+mod orbits;
+mod pairings;
+mod permutation;
+mod permutations;
+mod polya;
+mod round_robin;
+mod shared;
+mod shuffled;
+mod sized_partitions;
+mod small_combinations;
+mod statistics;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+mod subset_sums;
+mod swap_walk;
+mod symmetric_group;
+mod table;
+mod top_k;
+mod topological_orders;
+pub mod verify;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod windows;
+mod words;
+mod young_tableaux;
+pub use analysis::longest_increasing_subsequence;
+pub use approximate::{log_binomial, log_factorial};
+pub use assignments::Assignments;
+pub use backtrack::Backtracker;
+pub use batch::BatchedIterator;
+#[cfg(feature = "bigint")]
+pub use big::{bell_big, binomial_big, catalan_big, factorial_big};
+pub use binary_trees::{BinaryTree, BinaryTrees};
+pub use bipartitions::Bipartitions;
+pub use block_designs::{steiner_triple_system, BlockDesigns};
+pub use branch_and_bound::{maximize, subsets_of, SubsetTree};
+pub use cancel::{cancellable, Cancellable};
+pub use canonical::{CanonicalIterator, CanonicalizeUnder, SymmetryAction};
+pub use circular_permutations::CircularPermutations;
+pub use collecting::Collecting;
+pub use combinations::{
+    sorted_unique, CombinationDelta, Combinations, CombinationsWithReplacement, Order,
+    ReplacementOrder, WithComplements,
+};
+pub use conflict_free::ConflictFreeCombinations;
+pub use constraint::Constraint;
+pub use cosets::Cosets;
+pub use cross::{cross, Cross, RankedIterator};
+pub use disjoint_pairs::DisjointPairs;
+pub use drive::{drive, Drive};
+pub use error::{CombinatorialError, OnOversize};
+pub use estimate::EstimatedWork;
+pub use exact_cover::ExactCovers;
+pub use ext::CombinatorialExt;
+pub use fixed_points::{FixedPointPermutations, Involutions};
+pub use group::{GroupElements, PermutationGroup};
+pub use hitting_sets::{greedy_set_cover, minimal_hitting_sets, minimum_set_cover};
+pub use latin_squares::LatinSquares;
+pub use lazy_combinations::LazyCombinations;
+pub use masked_sequences::MaskedSequences;
+pub use masks::{combination_to_mask, mask_to_combination, CombinationMasks};
+pub use modular::{binomial_mod, binomial_mod_prime, factorial_mod};
+pub use monotone::MonotoneFrontier;
+pub use orbits::Orbits;
+pub use pairings::Pairings;
+pub use permutation::{apply_permutation, inverse_permutation, Permutation};
+pub use permutations::Permutations;
+pub use polya::{count_distinct_colorings, CycleIndex};
+pub use round_robin::RoundRobin;
+pub use shared::SharedCombinations;
+pub use shuffled::Shuffled;
+pub use sized_partitions::SizedPartitions;
+pub use small_combinations::{pairs, triples, Pairs, Triples, Tuple, TupleCombinations};
+pub use statistics::{eulerian, mahonian, q_factorial, PermutationStatistic, WithStatistic};
+pub use subset_sums::{subset_sums, SubsetSums};
+pub use swap_walk::{SwapOrder, SwapWalk};
+pub use symmetric_group::{AlternatingGroup, SymmetricGroup};
+pub use table::CombinatoricsTable;
+pub use topological_orders::TopologicalOrders;
+pub use windows::{circular_windows, contiguous_combinations, Windows};
+pub use words::Words;
+pub use young_tableaux::{young_tableaux_count, YoungTableaux};
 
 /// Returns the `n`th triangle number.
 ///
+/// This works for any numeric type, including the primitive integers and big integer types such
+/// as `num_bigint::BigUint`.
+///
 /// # Examples
 ///
 /// ```
@@ -17,18 +143,117 @@ pub use combinations::{Combinations, CombinationsWithReplacement};
 /// let nums: Vec<usize> = (3..8).map(triangle_number).collect();
 /// assert_eq!(nums, vec![6, 10, 15, 21, 28]);
 ///
-/// assert_eq!(triangle_number(1000), (0..=1000).sum());
+/// assert_eq!(triangle_number(1000), (0..=1000).sum::<u32>());
 ///
 /// for n in 1..=1000 {
 ///     assert_eq!(triangle_number(n), n + triangle_number(n - 1));
 /// }
+///
+/// assert_eq!(triangle_number(1u128 << 63), 1 << 125 | 1 << 62);
+/// ```
+pub fn triangle_number<T: Num + Clone>(n: T) -> T {
+    let two = T::one() + T::one();
+    n.clone() * (n + T::one()) / two
+}
+
+/// Returns the `n`th pentagonal number, `n * (3n - 1) / 2`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::pentagonal_number;
+///
+/// let nums: Vec<u32> = (0..8).map(pentagonal_number).collect();
+/// assert_eq!(nums, vec![0, 1, 5, 12, 22, 35, 51, 70]);
+/// ```
+pub fn pentagonal_number<T: Num + Clone>(n: T) -> T {
+    let five = T::one() + T::one() + T::one() + T::one() + T::one();
+    polygonal_number(five, n)
+}
+
+/// Returns the `n`th `s`-gonal number, which is the number of dots in a regular polygon with `s`
+/// sides and `n` dots along each side, when the polygons for `1..=n` are nested at a common
+/// corner.  Triangle numbers and square numbers are the 3-gonal and 4-gonal numbers.  The number
+/// of sides `s` must be at least 2.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{pentagonal_number, polygonal_number, triangle_number};
+///
+/// let hexagonal: Vec<u32> = (0..6).map(|n| polygonal_number(6, n)).collect();
+/// assert_eq!(hexagonal, vec![0, 1, 6, 15, 28, 45]);
+///
+/// for n in 0..100u64 {
+///     assert_eq!(polygonal_number(3, n), triangle_number(n));
+///     assert_eq!(polygonal_number(4, n), n * n);
+///     assert_eq!(polygonal_number(5, n), pentagonal_number(n));
+/// }
 /// ```
-pub fn triangle_number(n: usize) -> usize {
-    n * (n + 1) / 2
+pub fn polygonal_number<T: Num + Clone>(s: T, n: T) -> T {
+    if n.is_zero() {
+        return n;
+    }
+    let two = T::one() + T::one();
+    n.clone() * ((s - two.clone()) * (n - T::one()) + two.clone()) / two
+}
+
+/// Returns the `n`th Fibonacci number, where the sequence begins with 0 and 1 and each later
+/// number is the sum of the previous two.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::fibonacci;
+///
+/// let nums: Vec<u32> = (0..12).map(fibonacci).collect();
+/// assert_eq!(nums, vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89]);
+///
+/// assert_eq!(fibonacci(186u128), 332825110087067562321196029789634457848);
+/// ```
+pub fn fibonacci<T: Num + Clone + PartialOrd>(n: T) -> T {
+    fibonacci_like(T::zero(), T::one(), n)
+}
+
+/// Returns the `n`th Lucas number, where the sequence begins with 2 and 1 and each later number
+/// is the sum of the previous two.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{fibonacci, lucas};
+///
+/// let nums: Vec<u32> = (0..10).map(lucas).collect();
+/// assert_eq!(nums, vec![2, 1, 3, 4, 7, 11, 18, 29, 47, 76]);
+///
+/// for n in 1..50u64 {
+///     assert_eq!(lucas(n), fibonacci(n - 1) + fibonacci(n + 1));
+/// }
+/// ```
+pub fn lucas<T: Num + Clone + PartialOrd>(n: T) -> T {
+    fibonacci_like(T::one() + T::one(), T::one(), n)
+}
+
+/// Returns the `n`th number of the sequence which begins with `first` and `second` and in which
+/// each later number is the sum of the previous two.
+fn fibonacci_like<T: Num + Clone + PartialOrd>(first: T, second: T, n: T) -> T {
+    if n.is_zero() {
+        return first;
+    }
+    let (mut current, mut next) = (first, second);
+    let mut i = T::one();
+    while i < n {
+        (current, next) = (next.clone(), current + next);
+        i = i + T::one();
+    }
+    next
 }
 
 /// Returns `n` factorial.
 ///
+/// This works for any numeric type, including the primitive integers and big integer types such
+/// as `num_bigint::BigUint`.
+///
 /// # Examples
 ///
 /// ```
@@ -39,14 +264,316 @@ pub fn triangle_number(n: usize) -> usize {
 /// assert_eq!(factorial(2), 2);
 /// assert_eq!(factorial(3), 6);
 ///
-/// assert_eq!(factorial(10), (1..=10).product());
+/// assert_eq!(factorial(10), (1..=10).product::<u32>());
 ///
 /// for n in 1..10 {
 ///     assert_eq!(factorial(n), n * factorial(n - 1));
 /// }
+///
+/// assert_eq!(factorial(25u128), 15511210043330985984000000);
+/// ```
+pub fn factorial<T: Num + Clone + PartialOrd>(n: T) -> T {
+    let mut result = T::one();
+    let mut i = T::one();
+    while i <= n {
+        result = result * i.clone();
+        i = i + T::one();
+    }
+    result
+}
+
+/// Returns the subfactorial of `n`, written `!n`, which is the number of derangements of `n`
+/// elements: the permutations which leave no element in its original position.
+///
+/// This works for any numeric type, including the primitive integers and big integer types such
+/// as `num_bigint::BigUint`.  See [`checked_subfactorial`] for a version which detects overflow.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{subfactorial, Permutations};
+///
+/// let nums: Vec<u32> = (0..8).map(subfactorial).collect();
+/// assert_eq!(nums, vec![1, 0, 1, 2, 9, 44, 265, 1854]);
+///
+/// let diagonal: Vec<(usize, usize)> = (0..6).map(|i| (i, i)).collect();
+/// let derangements = Permutations::of_length(0..6, 6).avoiding(&diagonal);
+/// assert_eq!(derangements.count(), subfactorial(6));
+/// ```
+pub fn subfactorial<T: Num + Clone + PartialOrd>(n: T) -> T {
+    let mut previous = T::one();
+    let mut current = T::zero();
+    if n.is_zero() {
+        return previous;
+    }
+    let mut i = T::one();
+    while i < n {
+        let next = i.clone() * (current.clone() + previous);
+        previous = current;
+        current = next;
+        i = i + T::one();
+    }
+    current
+}
+
+/// Returns the subfactorial of `n`, which is the number of derangements of `n` elements, or
+/// `None` if it does not fit in a `u128`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{checked_subfactorial, subfactorial};
+///
+/// assert_eq!(checked_subfactorial(10), Some(subfactorial(10)));
+/// assert_eq!(checked_subfactorial(34), Some(108610077126170304674801654684367969729));
+/// assert_eq!(checked_subfactorial(35), None);
+/// ```
+pub fn checked_subfactorial(n: usize) -> Option<u128> {
+    let (mut previous, mut current): (u128, u128) = (1, 0);
+    if n == 0 {
+        return Some(previous);
+    }
+    for i in 1..n {
+        let next = (current.checked_add(previous)?).checked_mul(i as u128)?;
+        previous = current;
+        current = next;
+    }
+    Some(current)
+}
+
+/// Returns the binomial coefficient `n` choose `k`, which is the number of combinations of size `k`
+/// of `n` elements.  Returns zero if `k` is greater than `n`.
+///
+/// Intermediate values are kept as small as possible, so the result is computed without overflow
+/// whenever `n` times the result fits in the numeric type.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{binomial, Combinations};
+///
+/// assert_eq!(binomial(5, 2), 10);
+/// assert_eq!(binomial(5, 0), 1);
+/// assert_eq!(binomial(5, 6), 0);
+/// assert_eq!(binomial(60u64, 30), 118264581564861424);
+///
+/// for k in 0..=8 {
+///     assert_eq!(binomial(8, k), Combinations::of_size(0..8, k).count());
+/// }
+/// ```
+pub fn binomial<T: Num + Clone + PartialOrd>(n: T, k: T) -> T {
+    if k > n {
+        return T::zero();
+    }
+    let complement = n.clone() - k.clone();
+    let k = if complement < k { complement } else { k };
+    let mut result = T::one();
+    let mut i = T::zero();
+    while i < k {
+        i = i + T::one();
+        result = result * (n.clone() + T::one() - i.clone()) / i.clone();
+    }
+    result
+}
+
+/// Returns the number of permutations of length `k` of `n` distinct elements, which is
+/// `n! / (n - k)!`, or `None` if the count does not fit in a `u128`.  Returns zero if `k` is
+/// greater than `n`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{permutation_count, Permutations};
+///
+/// assert_eq!(permutation_count(5, 2), Some(20));
+/// assert_eq!(permutation_count(5, 6), Some(0));
+/// assert_eq!(permutation_count(34, 34), Some(295232799039604140847618609643520000000));
+/// assert_eq!(permutation_count(35, 35), None);
+///
+/// assert_eq!(Permutations::of_length(0..6, 3).total_count(), permutation_count(6, 3));
+/// ```
+pub fn permutation_count(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    (n - k + 1..=n).try_fold(1u128, |count, factor| count.checked_mul(factor as u128))
+}
+
+/// Returns the number of combinations of size `k` of `n` distinct elements, which is `n` choose
+/// `k`, or `None` if the count does not fit in a `u128`.  Returns zero if `k` is greater than
+/// `n`.
+///
+/// Unlike computing the count from factorials, this only fails if the result itself is too large.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{combination_count, Combinations};
+///
+/// assert_eq!(combination_count(5, 2), Some(10));
+/// assert_eq!(combination_count(5, 6), Some(0));
+/// assert_eq!(combination_count(100, 50), Some(100891344545564193334812497256));
+/// assert_eq!(combination_count(200, 100), None);
+///
+/// assert_eq!(Combinations::of_size(0..6, 3).total_count(), combination_count(6, 3));
+/// ```
+pub fn combination_count(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let mut count: u128 = 1;
+    for i in 0..k.min(n - k) {
+        let numerator = (n - i) as u128;
+        let denominator = (i + 1) as u128;
+        let divisor = gcd(count, denominator);
+        count = (count / divisor).checked_mul(numerator / (denominator / divisor))?;
+    }
+    Some(count)
+}
+
+/// Returns the greatest common divisor of `a` and `b`.
+pub(crate) fn gcd<T: Num + Clone>(mut a: T, mut b: T) -> T {
+    while !b.is_zero() {
+        (a, b) = (b.clone(), a % b);
+    }
+    a
+}
+
+/// Returns the `n`th Bell number, which is the number of ways to partition a set of `n` elements
+/// into nonempty blocks, computed with the Bell triangle.
+///
+/// The result may be any numeric type, including big integer types such as
+/// `num_bigint::BigUint`.  See [`checked_bell_number`] for a version which detects overflow.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::bell_number;
+///
+/// let nums: Vec<u32> = (0..10).map(bell_number).collect();
+/// assert_eq!(nums, vec![1, 1, 2, 5, 15, 52, 203, 877, 4140, 21147]);
+///
+/// assert_eq!(bell_number::<u64>(25), 4638590332229999353);
+/// ```
+pub fn bell_number<T: Num + Clone>(n: usize) -> T {
+    match n {
+        0 => T::one(),
+        _ => BellTriangle::new().nth(n - 1).unwrap().pop().unwrap(),
+    }
+}
+
+/// Returns the `n`th Bell number, which is the number of ways to partition a set of `n` elements
+/// into nonempty blocks, or `None` if it does not fit in a `u128`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{bell_number, checked_bell_number};
+///
+/// assert_eq!(checked_bell_number(20), Some(bell_number(20)));
+/// assert!(checked_bell_number(42).is_some());
+/// assert_eq!(checked_bell_number(43), None);
+/// ```
+pub fn checked_bell_number(n: usize) -> Option<u128> {
+    if n == 0 {
+        return Some(1);
+    }
+    let mut row: Vec<u128> = vec![1];
+    for _ in 1..n {
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(*row.last().unwrap());
+        for above in row.iter() {
+            next.push(next.last().unwrap().checked_add(*above)?);
+        }
+        row = next;
+    }
+    row.last().copied()
+}
+
+/// An iterator which generates the rows of the Bell triangle.
+///
+/// Row `n` has `n + 1` entries.  The first row is `[1]`, and each later row begins with the last
+/// entry of the previous row, with each following entry the sum of the entry to its left and the
+/// entry above that one.  The first entry of row `n` is the `n`th Bell number, and the last entry
+/// is the `n + 1`th.  The iterator never ends, so the numeric type must be large enough for the
+/// rows which are consumed.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::BellTriangle;
+///
+/// let rows: Vec<Vec<u32>> = BellTriangle::new().take(4).collect();
+/// assert_eq!(rows, vec![vec![1], vec![1, 2], vec![2, 3, 5], vec![5, 7, 10, 15]]);
+/// ```
+pub struct BellTriangle<T> {
+    row: Vec<T>,
+}
+
+impl<T: Num + Clone> BellTriangle<T> {
+    /// Creates a new `BellTriangle` iterator which will yield the rows of the Bell triangle,
+    /// beginning with `[1]`.
+    pub fn new() -> Self {
+        BellTriangle { row: Vec::new() }
+    }
+}
+
+impl<T: Num + Clone> Default for BellTriangle<T> {
+    fn default() -> Self {
+        BellTriangle::new()
+    }
+}
+
+impl<T: Num + Clone> Iterator for BellTriangle<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next row of the triangle and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut next = Vec::with_capacity(self.row.len() + 1);
+        next.push(self.row.last().cloned().unwrap_or_else(T::one));
+        for above in self.row.iter() {
+            let left = next.last().unwrap().clone();
+            next.push(left + above.clone());
+        }
+        self.row = next;
+        Some(self.row.clone())
+    }
+}
+
+/// Returns the `n`th Catalan number.
+///
+/// The `n`th Catalan number counts, among many other things, the full binary trees with `n + 1`
+/// leaves and the balanced strings of `n` pairs of parentheses.
+///
+/// # Panics
+///
+/// Panics if the Catalan number does not fit in a `usize`, which on 64-bit targets is when `n` is
+/// greater than 36.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::catalan;
+///
+/// let nums: Vec<usize> = (0..10).map(catalan).collect();
+/// assert_eq!(nums, vec![1, 1, 2, 5, 14, 42, 132, 429, 1430, 4862]);
+///
+/// for n in 1..20 {
+///     assert_eq!(catalan(n), (0..n).map(|i| catalan(i) * catalan(n - 1 - i)).sum());
+/// }
 /// ```
-pub fn factorial(n: usize) -> usize {
-    (1..=n).product()
+pub fn catalan(n: usize) -> usize {
+    // Each step multiplies before dividing to stay exact, so the product is taken in u128 to
+    // avoid overflowing when the result itself still fits.
+    let mut c: u128 = 1;
+    for i in 0..n as u128 {
+        c = c * (4 * i + 2) / (i + 2);
+        assert!(
+            c <= usize::MAX as u128,
+            "the Catalan number {n} does not fit in a usize"
+        );
+    }
+    c as usize
 }
 
 /// Returns the powerset of the elements in the given iterable.
@@ -101,7 +628,10 @@ mod tests {
         let mut combos = CombinationsWithReplacement::of_size(vec![1, 2, 3], 0);
         assert_eq!(combos.next(), Some(Vec::new()));
         assert_eq!(combos.next(), None);
-        let mut combos = CombinationsWithReplacement::of_size(vec![1, 2, 3], 4);
+        let combos = CombinationsWithReplacement::of_size(vec![1, 2, 3], 4);
+        assert_eq!(combos.count(), 15);
+        let mut combos: CombinationsWithReplacement<u64> =
+            CombinationsWithReplacement::of_size(Vec::new(), 1);
         assert_eq!(combos.next(), None);
         let mut combos: CombinationsWithReplacement<u64> =
             CombinationsWithReplacement::all(Vec::new());
@@ -125,4 +655,99 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_permutation_and_combination_counts() {
+        for n in 0..30 {
+            for k in 0..=n + 1 {
+                assert_eq!(
+                    combination_count(n, k),
+                    Some(binomial(n as u128, k as u128))
+                );
+                assert_eq!(
+                    permutation_count(n, k),
+                    Some(binomial(n as u128, k as u128) * factorial(k as u128))
+                );
+            }
+        }
+        assert_eq!(combination_count(130, 1), Some(130));
+        assert_eq!(combination_count(130, 129), Some(130));
+        assert_eq!(combination_count(300, 150), None);
+    }
+
+    #[test]
+    fn test_subfactorial_recurrence() {
+        for n in 1..30u128 {
+            let sign = if n % 2 == 0 { 1 } else { -1 };
+            assert_eq!(
+                subfactorial(n) as i128,
+                n as i128 * subfactorial(n - 1) as i128 + sign
+            );
+            assert_eq!(checked_subfactorial(n as usize), Some(subfactorial(n)));
+        }
+    }
+
+    #[test]
+    fn test_bell_numbers() {
+        let mut rows = BellTriangle::<u128>::new();
+        let mut previous_last = 1;
+        for n in 0..30 {
+            let row = rows.next().unwrap();
+            assert_eq!(row.len(), n + 1);
+            assert_eq!(row[0], previous_last);
+            assert_eq!(Some(row[0]), checked_bell_number(n));
+            assert_eq!(row[0], bell_number(n));
+            previous_last = *row.last().unwrap();
+        }
+        // B(n + 1) is the sum of binomial(n, k) * B(k).
+        for n in 0..20u128 {
+            let sum: u128 = (0..=n)
+                .map(|k| binomial(n, k) * bell_number::<u128>(k as usize))
+                .sum();
+            assert_eq!(bell_number::<u128>(n as usize + 1), sum);
+        }
+        let big: num_bigint::BigUint = bell_number(60);
+        assert_eq!(
+            big.to_string(),
+            "976939307467007552986994066961675455550246347757474482558637"
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_catalan_near_usize_limit() {
+        for n in 0..36 {
+            let next = catalan(n + 1) as u128 * (n as u128 + 2);
+            assert_eq!(next, catalan(n) as u128 * (4 * n as u128 + 2));
+        }
+        assert_eq!(catalan(34), 812944042149730764);
+        assert_eq!(catalan(36), 11959798385860453492);
+        assert!(std::panic::catch_unwind(|| catalan(37)).is_err());
+    }
+
+    #[test]
+    fn test_counting_functions_generic() {
+        use num_bigint::BigUint;
+
+        for n in 0u32..13 {
+            assert_eq!(factorial(n) as u64, factorial(n as u64));
+            assert_eq!(triangle_number(n) as u128, triangle_number(n as u128));
+            for k in 0..=n + 1 {
+                assert_eq!(binomial(n, k) as usize, binomial(n as usize, k as usize));
+            }
+        }
+        let big = factorial(BigUint::from(40u32));
+        assert_eq!(
+            big.to_string(),
+            "815915283247897734345611269596115894272000000000"
+        );
+        assert_eq!(
+            binomial(BigUint::from(100u32), BigUint::from(50u32)).to_string(),
+            "100891344545564193334812497256"
+        );
+        assert_eq!(
+            triangle_number(BigUint::from(u64::MAX)),
+            BigUint::from(u64::MAX) * (BigUint::from(u64::MAX) + 1u32) / 2u32
+        );
+    }
 }
@@ -0,0 +1,367 @@
+use crate::collecting::Collecting;
+use crate::combinations::size_hint_from;
+use crate::constraint::{Constraint, Constraints};
+use crate::sorted_unique;
+
+/// An iterator which generates every sequence in which each position holds one of its own set of
+/// candidates, like the masks of a password cracker such as hashcat.
+///
+/// Each position's candidates are sorted and deduplicated, and the sequences are yielded in
+/// lexicographic order, as the Cartesian product of the candidate sets.  Giving every position
+/// the same candidates, as [`MaskedSequences::repeated`] does, yields every sequence of a fixed
+/// length over an alphabet.  The sequences may be restricted to those which never repeat an
+/// element, with [`MaskedSequences::distinct`], and to those which include at least one element
+/// of each of a number of classes, with [`MaskedSequences::must_include`], as well as by any
+/// [`Constraint`], with [`MaskedSequences::with_constraint`].  Every constraint is checked as each
+/// position is filled, so branches which cannot satisfy them are never searched.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::MaskedSequences;
+///
+/// let upper = 'A'..='Z';
+/// let lower: Vec<char> = ('a'..='z').collect();
+/// let digits: Vec<char> = ('0'..='9').collect();
+/// let mask = vec![upper.collect(), lower.clone(), lower, digits.clone(), digits];
+/// let mut passwords = MaskedSequences::new(mask).collecting::<String>();
+/// assert_eq!(passwords.size_hint().0, 26 * 26 * 26 * 10 * 10);
+/// assert_eq!(passwords.next(), Some(String::from("Aaa00")));
+/// assert_eq!(passwords.next(), Some(String::from("Aaa01")));
+/// ```
+#[derive(Clone)]
+pub struct MaskedSequences<T> {
+    mask: Vec<Vec<T>>,
+    classes: Vec<Vec<T>>,
+    distinct: bool,
+    constraints: Constraints<T>,
+    indices: Vec<usize>,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord + Clone> MaskedSequences<T> {
+    /// Creates a new `MaskedSequences` iterator which will yield every sequence whose element in
+    /// each position is one of the candidates given for that position.  If any position has no
+    /// candidates, nothing is yielded, and if there are no positions, a single empty sequence is
+    /// yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::MaskedSequences;
+    ///
+    /// let sequences: Vec<Vec<i32>> = MaskedSequences::new(vec![vec![2, 1], vec![7]]).collect();
+    /// assert_eq!(sequences, vec![vec![1, 7], vec![2, 7]]);
+    ///
+    /// assert_eq!(MaskedSequences::new(vec![vec![1, 2], vec![]]).next(), None);
+    /// assert_eq!(MaskedSequences::<i32>::new(Vec::<Vec<i32>>::new()).count(), 1);
+    /// ```
+    pub fn new<I: IntoIterator<Item = T>>(mask: impl IntoIterator<Item = I>) -> Self {
+        let mask: Vec<Vec<T>> = mask.into_iter().map(sorted_unique).collect();
+        let done = mask.iter().any(|candidates| candidates.is_empty());
+        MaskedSequences {
+            indices: Vec::with_capacity(mask.len()),
+            mask,
+            classes: Vec::new(),
+            distinct: false,
+            constraints: Constraints::new(),
+            started: false,
+            done,
+        }
+    }
+
+    /// Creates a new `MaskedSequences` iterator which will yield every sequence of the given
+    /// length over the given alphabet, that is, the Cartesian power of the alphabet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::MaskedSequences;
+    ///
+    /// let sequences = MaskedSequences::repeated("ba".chars(), 2).collecting::<String>();
+    /// assert_eq!(sequences.collect::<Vec<_>>(), vec!["aa", "ab", "ba", "bb"]);
+    /// ```
+    pub fn repeated(alphabet: impl IntoIterator<Item = T>, length: usize) -> Self {
+        let alphabet = sorted_unique(alphabet);
+        MaskedSequences::new(vec![alphabet; length])
+    }
+
+    /// Restricts the iterator to sequences which include at least one element of the given class.
+    /// This may be called several times to require an element of each of several classes, and a
+    /// single element may count towards more than one class.  If the class is empty, nothing is
+    /// yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::MaskedSequences;
+    ///
+    /// let pins: Vec<String> = MaskedSequences::repeated('0'..='9', 4)
+    ///     .must_include(['7'])
+    ///     .must_include(['0', '2', '4', '6', '8'])
+    ///     .collecting()
+    ///     .collect();
+    /// assert_eq!(pins[0], "0007");
+    /// assert!(pins.iter().all(|pin| pin.contains('7')));
+    /// assert_eq!(pins.len(), 10_000 - 9 * 9 * 9 * 9 - 5 * 5 * 5 * 5 + 4 * 4 * 4 * 4);
+    /// ```
+    pub fn must_include(mut self, class: impl IntoIterator<Item = T>) -> Self {
+        self.classes.push(sorted_unique(class));
+        self
+    }
+
+    /// Restricts the iterator to sequences in which no element appears more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::MaskedSequences;
+    ///
+    /// let sequences: Vec<Vec<i32>> = MaskedSequences::new(vec![vec![1, 2], vec![1, 2, 3]])
+    ///     .distinct()
+    ///     .collect();
+    /// assert_eq!(sequences, vec![vec![1, 2], vec![1, 3], vec![2, 1], vec![2, 3]]);
+    /// ```
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Restricts the iterator to sequences which satisfy the given [`Constraint`].  This may be
+    /// called several times to apply several constraints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Constraint, MaskedSequences};
+    ///
+    /// let sequences: Vec<String> = MaskedSequences::repeated("ab".chars(), 4)
+    ///     .with_constraint(Constraint::NoAdjacentEqual)
+    ///     .collecting()
+    ///     .collect();
+    /// assert_eq!(sequences, vec!["abab", "baba"]);
+    /// ```
+    pub fn with_constraint(mut self, constraint: Constraint<T>) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Converts the iterator into one which collects each sequence directly into a container of
+    /// type `C`, such as a `String`, instead of a `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::MaskedSequences;
+    ///
+    /// let mut words = MaskedSequences::new(["ab".chars(), "c".chars()]).collecting::<String>();
+    /// assert_eq!(words.next(), Some(String::from("ac")));
+    /// assert_eq!(words.next(), Some(String::from("bc")));
+    /// assert_eq!(words.next(), None);
+    /// ```
+    pub fn collecting<C: FromIterator<T>>(self) -> Collecting<Self, C> {
+        Collecting::new(self)
+    }
+
+    /// Returns the element chosen for each filled position.
+    fn chosen(&self) -> impl Iterator<Item = &T> + '_ {
+        self.indices
+            .iter()
+            .zip(&self.mask)
+            .map(|(index, candidates)| &candidates[*index])
+    }
+
+    /// Returns the index of the first candidate which may be placed in the next position,
+    /// skipping every candidate with index not greater than `previous`, if given.
+    fn next_candidate(&self, previous: Option<usize>) -> Option<usize> {
+        let candidates = &self.mask[self.indices.len()];
+        let start = previous.map_or(0, |previous| previous + 1);
+        (start..candidates.len()).find(|index| {
+            !self.distinct || self.chosen().all(|element| *element != candidates[*index])
+        })
+    }
+
+    /// Returns `true` if every class which the filled positions do not yet include has an
+    /// element among the candidates of some later position, and the filled positions may still
+    /// be completed to a sequence which satisfies every constraint.
+    fn can_complete(&self) -> bool {
+        let rest = &self.mask[self.indices.len()..];
+        let includes_classes = self.classes.iter().all(|class| {
+            let contains = |element: &T| class.binary_search(element).is_ok();
+            self.chosen().any(contains)
+                || rest
+                    .iter()
+                    .any(|candidates| candidates.iter().any(contains))
+        });
+        includes_classes
+            && (self.constraints.is_empty() || {
+                let prefix: Vec<&T> = self.chosen().collect();
+                self.constraints
+                    .allow(&prefix, rest.iter().map(Vec::as_slice))
+            })
+    }
+
+    /// Completes the sequence with the lexicographically smallest valid candidates, backtracking
+    /// as necessary.  If `previous` is given, the search resumes by trying candidates after
+    /// `previous` in the next position.  Returns `true` if the sequence was completed, or `false`
+    /// if no further sequences exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            if previous.is_none() && self.indices.len() == self.mask.len() {
+                return true;
+            }
+            match self.next_candidate(previous) {
+                Some(index) => {
+                    self.indices.push(index);
+                    previous = None;
+                    if !self.can_complete() {
+                        previous = self.indices.pop();
+                    }
+                }
+                None => match self.indices.pop() {
+                    Some(index) => previous = Some(index),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Moves the internal indices to the next sequence.  Returns `false` and marks the iterator
+    /// as done if no sequences remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let found = if self.started {
+            match self.indices.pop() {
+                Some(index) => self.fill(Some(index)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.can_complete() && self.fill(None)
+        };
+        if !found {
+            self.done = true;
+        }
+        found
+    }
+
+    /// Returns the number of sequences which have not yet been yielded, ignoring the
+    /// constraints, or `None` if the count does not fit in a `u128`.
+    fn unconstrained_remaining_count(&self) -> Option<u128> {
+        if self.done {
+            return Some(0);
+        }
+        let mut total = 1u128;
+        let mut rank = 0u128;
+        for (position, candidates) in self.mask.iter().enumerate() {
+            let size = candidates.len() as u128;
+            total = total.checked_mul(size)?;
+            let index = self.indices.get(position).copied().unwrap_or(0) as u128;
+            rank = rank.checked_mul(size)?.checked_add(index)?;
+        }
+        Some(total - rank - u128::from(self.started))
+    }
+}
+
+impl<T: Ord + Clone> Iterator for MaskedSequences<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next sequence and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        Some(self.chosen().cloned().collect())
+    }
+
+    /// Returns the exact number of remaining sequences, if it fits in a `usize`, or an upper
+    /// bound if the sequences are constrained.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let bound = size_hint_from(self.unconstrained_remaining_count());
+        if self.classes.is_empty() && !self.distinct && self.constraints.is_empty() {
+            bound
+        } else {
+            (0, bound.1)
+        }
+    }
+}
+
+impl<T: Ord + Clone, C: FromIterator<T>> Iterator for Collecting<MaskedSequences<T>, C> {
+    type Item = C;
+
+    /// Returns the next sequence and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.inner.advance() {
+            return None;
+        }
+        Some(self.inner.chosen().cloned().collect())
+    }
+
+    /// Returns the exact number of remaining sequences, if it fits in a `usize`, or an upper
+    /// bound if the sequences are constrained.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::cartesian_power;
+
+    #[test]
+    fn test_masked_sequences_match_filtered_product() {
+        let mask = vec![vec![0, 1, 2], vec![1, 3], vec![0, 2, 3, 4], vec![2]];
+        let product: Vec<Vec<i32>> = cartesian_power(&[0, 1, 2, 3, 4], 4)
+            .into_iter()
+            .filter(|seq| {
+                seq.iter()
+                    .zip(&mask)
+                    .all(|(e, options)| options.contains(e))
+            })
+            .collect();
+        let mut sequences = MaskedSequences::new(mask.clone());
+        let mut remaining = product.len();
+        for expected in &product {
+            assert_eq!(sequences.size_hint(), (remaining, Some(remaining)));
+            assert_eq!(sequences.next().as_ref(), Some(expected));
+            remaining -= 1;
+        }
+        assert_eq!(sequences.size_hint(), (0, Some(0)));
+        assert_eq!(sequences.next(), None);
+
+        let is_distinct = |seq: &Vec<i32>| {
+            seq.iter()
+                .all(|e| seq.iter().filter(|f| e == *f).count() == 1)
+        };
+        let includes = |seq: &Vec<i32>, class: &[i32]| seq.iter().any(|e| class.contains(e));
+        let expected: Vec<Vec<i32>> = product
+            .iter()
+            .filter(|seq| is_distinct(seq) && includes(seq, &[0, 1]) && includes(seq, &[4]))
+            .cloned()
+            .collect();
+        let constrained = MaskedSequences::new(mask)
+            .distinct()
+            .must_include([1, 0])
+            .must_include([4]);
+        assert!(constrained.size_hint().1 >= Some(expected.len()));
+        assert_eq!(constrained.collect::<Vec<_>>(), expected);
+
+        for n in 0..4 {
+            let letters: Vec<usize> = (0..3).collect();
+            let repeated: Vec<Vec<usize>> = MaskedSequences::repeated(0..3, n).collect();
+            assert_eq!(repeated, cartesian_power(&letters, n));
+        }
+        assert_eq!(
+            MaskedSequences::repeated(0..3, 2).must_include([]).next(),
+            None
+        );
+        assert_eq!(
+            MaskedSequences::repeated(0..3, 0).must_include([1]).next(),
+            None
+        );
+    }
+}
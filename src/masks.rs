@@ -0,0 +1,238 @@
+use crate::combinations::size_hint_from;
+use crate::{combination_count, Order};
+
+/// Returns the bitmask of the given combination of indices, with bit `i` set for each index `i`.
+///
+/// # Panics
+///
+/// Panics if any index is 128 or greater.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{combination_to_mask, mask_to_combination};
+///
+/// assert_eq!(combination_to_mask(&[0, 2, 3]), 0b1101);
+/// assert_eq!(mask_to_combination(0b1101), vec![0, 2, 3]);
+/// assert_eq!(combination_to_mask(&[]), 0);
+/// ```
+pub fn combination_to_mask(combination: &[usize]) -> u128 {
+    combination.iter().fold(0, |mask, index| {
+        assert!(*index < 128, "index {index} does not fit in a u128 mask");
+        mask | 1 << index
+    })
+}
+
+/// Returns the indices of the set bits of the given bitmask, in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::mask_to_combination;
+///
+/// assert_eq!(mask_to_combination(0b1010), vec![1, 3]);
+/// assert_eq!(mask_to_combination(1 << 127).len(), 1);
+/// ```
+pub fn mask_to_combination(mut mask: u128) -> Vec<usize> {
+    let mut combination = Vec::with_capacity(mask.count_ones() as usize);
+    while mask != 0 {
+        combination.push(mask.trailing_zeros() as usize);
+        mask &= mask - 1;
+    }
+    combination
+}
+
+/// Returns the mask with the lowest `bits` bits set.
+fn low_bits(bits: usize) -> u128 {
+    if bits >= 128 {
+        u128::MAX
+    } else {
+        (1 << bits) - 1
+    }
+}
+
+/// An iterator which generates the combinations of the indices `0..n` as bitmasks, in the same
+/// order as [`Combinations`](crate::Combinations) generates them as vectors.
+///
+/// Bit `i` of each mask is set if index `i` is in the combination, as by
+/// [`combination_to_mask`], so the `r`th mask yielded here is the mask of the `r`th combination
+/// yielded by the corresponding `Combinations` iterator over `0..n`.  Each mask is found from the
+/// previous one with a few bitwise operations, without building the combination itself.  Masks
+/// hold at most 128 bits, so nothing is yielded if `n` is greater than 128.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{mask_to_combination, CombinationMasks, Combinations};
+///
+/// let masks: Vec<u128> = CombinationMasks::of_size(4, 2).collect();
+/// assert_eq!(masks, vec![0b0011, 0b0101, 0b1001, 0b0110, 0b1010, 0b1100]);
+///
+/// let combos: Vec<Vec<usize>> = masks.into_iter().map(mask_to_combination).collect();
+/// assert_eq!(combos, Combinations::of_size(0..4, 2).collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CombinationMasks {
+    n: usize,
+    mask: u128,
+    all_sizes: bool,
+    order: Order,
+    remaining: Option<u128>,
+}
+
+impl CombinationMasks {
+    /// Creates a new `CombinationMasks` iterator which will yield the masks of every combination of
+    /// `k` of the indices `0..n`, in lexicographic order of the indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationMasks;
+    ///
+    /// assert_eq!(CombinationMasks::of_size(5, 3).count(), 10);
+    /// assert_eq!(CombinationMasks::of_size(128, 127).count(), 128);
+    /// assert_eq!(CombinationMasks::of_size(3, 4).next(), None);
+    /// ```
+    pub fn of_size(n: usize, k: usize) -> Self {
+        CombinationMasks {
+            n,
+            mask: low_bits(k),
+            all_sizes: false,
+            order: Order::Lex,
+            remaining: if n > 128 {
+                Some(0)
+            } else {
+                combination_count(n, k)
+            },
+        }
+    }
+
+    /// Creates a new `CombinationMasks` iterator which will yield the masks of every subset of the
+    /// indices `0..n`, with smaller subsets first, as by [`Combinations::all`].
+    ///
+    /// [`Combinations::all`]: crate::Combinations::all
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinationMasks;
+    ///
+    /// let masks: Vec<u128> = CombinationMasks::all(3).collect();
+    /// assert_eq!(masks, vec![0b000, 0b001, 0b010, 0b100, 0b011, 0b101, 0b110, 0b111]);
+    /// ```
+    pub fn all(n: usize) -> Self {
+        CombinationMasks {
+            all_sizes: true,
+            remaining: match n {
+                0..128 => Some(1 << n),
+                128 => None,
+                _ => Some(0),
+            },
+            ..CombinationMasks::of_size(n, 0)
+        }
+    }
+
+    /// Sets the order in which the masks of each size are generated.  In colexicographic order,
+    /// the masks of each size are yielded in increasing numeric order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{CombinationMasks, Order};
+    ///
+    /// let masks: Vec<u128> = CombinationMasks::of_size(4, 2).order(Order::Colex).collect();
+    /// assert_eq!(masks, vec![0b0011, 0b0101, 0b0110, 0b1001, 0b1010, 0b1100]);
+    /// ```
+    pub fn order(mut self, order: Order) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Returns the mask which follows the current one among those of the same size, if any.
+    fn next_of_same_size(&self) -> Option<u128> {
+        let mask = self.mask;
+        if mask == 0 {
+            return None;
+        }
+        match self.order {
+            Order::Lex => {
+                // Move the highest set bit below the run of set bits at the top up by one, and
+                // gather the run just above it.
+                let clear = !mask & low_bits(self.n);
+                let highest_clear = clear.checked_ilog2()? as usize;
+                let run = self.n - 1 - highest_clear;
+                let rest = mask & low_bits(highest_clear);
+                let moved = rest.checked_ilog2()? as usize;
+                Some((rest & !(1 << moved)) | low_bits(run + 1) << (moved + 1))
+            }
+            Order::Colex => {
+                // Gosper's hack for the next larger number with the same number of set bits.
+                let lowest = mask & mask.wrapping_neg();
+                let ripple = mask.checked_add(lowest)?;
+                let next = (((ripple ^ mask) >> 2) / lowest) | ripple;
+                (next <= low_bits(self.n)).then_some(next)
+            }
+        }
+    }
+}
+
+impl Iterator for CombinationMasks {
+    type Item = u128;
+
+    /// Returns the next mask and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        let mask = self.mask;
+        self.remaining = self.remaining.map(|remaining| remaining - 1);
+        match self.next_of_same_size() {
+            Some(next) => self.mask = next,
+            None if self.all_sizes => self.mask = low_bits(mask.count_ones() as usize + 1),
+            None => {}
+        }
+        Some(mask)
+    }
+
+    /// Returns the exact number of remaining masks, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_from(self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_masks_match_combinations() {
+        for n in 0..9 {
+            for order in [Order::Lex, Order::Colex] {
+                for k in 0..=n + 1 {
+                    let expected: Vec<u128> = Combinations::of_size(0..n, k)
+                        .order(order)
+                        .map(|combo| combination_to_mask(&combo))
+                        .collect();
+                    let masks = CombinationMasks::of_size(n, k).order(order);
+                    assert_eq!(masks.size_hint(), (expected.len(), Some(expected.len())));
+                    assert_eq!(masks.collect::<Vec<_>>(), expected);
+                }
+                let expected: Vec<u128> = Combinations::all(0..n)
+                    .order(order)
+                    .map(|combo| combination_to_mask(&combo))
+                    .collect();
+                let masks: Vec<u128> = CombinationMasks::all(n).order(order).collect();
+                assert_eq!(masks, expected);
+            }
+        }
+        for order in [Order::Lex, Order::Colex] {
+            let masks: Vec<u128> = CombinationMasks::of_size(128, 2).order(order).collect();
+            assert_eq!(masks.len(), 128 * 127 / 2);
+            assert!(masks.iter().all(|mask| mask.count_ones() == 2));
+            assert_eq!(masks.last(), Some(&(3 << 126)));
+        }
+        assert_eq!(CombinationMasks::all(129).next(), None);
+        assert_eq!(CombinationMasks::all(128).size_hint(), (usize::MAX, None));
+    }
+}
@@ -0,0 +1,202 @@
+/// Returns `n` factorial modulo `m`.
+///
+/// This takes time linear in the smaller of `n` and `m`, since `n!` is divisible by `m` whenever
+/// `n` is at least `m`.
+///
+/// # Panics
+///
+/// Panics if `m` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{factorial, factorial_mod};
+///
+/// assert_eq!(factorial_mod(10, 1_000), factorial(10u64) % 1_000);
+/// assert_eq!(factorial_mod(100_000, 1_000_000_007), 457_992_974);
+/// assert_eq!(factorial_mod(u64::MAX, 97), 0);
+/// ```
+pub fn factorial_mod(n: u64, m: u64) -> u64 {
+    if n >= m {
+        return 0;
+    }
+    (2..=n).fold(1 % m, |product, i| mul_mod(product, i, m))
+}
+
+/// Returns the binomial coefficient `n` choose `k` modulo `m`, which may be any positive
+/// modulus, prime or not.  Returns zero if `k` is greater than `n`.
+///
+/// The coefficient is computed as the product of `(n - i) / (i + 1)` for each `i` below `k`, or
+/// below `n - k` if that is smaller.  The factors of each denominator which `m` shares are
+/// cancelled against those of the numerators by counting them, and the rest are divided out with
+/// modular inverses, so the time taken is linear in `k` rather than in `n`.  For a prime modulus
+/// and large `k`, [`binomial_mod_prime`] is faster.
+///
+/// # Panics
+///
+/// Panics if `m` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{binomial_mod, combination_count};
+///
+/// assert_eq!(binomial_mod(10, 3, 7), 120 % 7);
+/// let exact = combination_count(100, 50).unwrap();
+/// assert_eq!(binomial_mod(100, 50, 1 << 40), (exact % (1 << 40)) as u64);
+/// assert_eq!(binomial_mod(1_000_000_000_000, 4, 1_000_000_007), 438_425_861);
+/// assert_eq!(binomial_mod(5, 6, 7), 0);
+/// ```
+pub fn binomial_mod(n: u64, k: u64, m: u64) -> u64 {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    // Every factor of a denominator is at most `k`, so only the primes of `m` up to `k` can
+    // cancel.  Their powers are counted separately and multiplied in at the end.
+    let primes = small_prime_factors(m, k);
+    let mut exponents = vec![0u64; primes.len()];
+    let mut product = 1 % m;
+    for i in 0..k {
+        let mut numerator = n - i;
+        let mut denominator = i + 1;
+        for (prime, exponent) in primes.iter().zip(exponents.iter_mut()) {
+            while numerator.is_multiple_of(*prime) {
+                numerator /= prime;
+                *exponent += 1;
+            }
+            while denominator.is_multiple_of(*prime) {
+                denominator /= prime;
+                *exponent -= 1;
+            }
+        }
+        let inverse = inverse_mod(denominator % m, m).unwrap();
+        product = mul_mod(mul_mod(product, numerator % m, m), inverse, m);
+    }
+    for (prime, exponent) in primes.iter().zip(exponents) {
+        product = mul_mod(product, pow_mod(*prime, exponent, m), m);
+    }
+    product
+}
+
+/// Returns the binomial coefficient `n` choose `k` modulo the prime `p`, using Lucas's theorem.
+/// Returns zero if `k` is greater than `n`.
+///
+/// By Lucas's theorem, the coefficient modulo `p` is the product of the coefficients of the
+/// corresponding digits of `n` and `k` written in base `p`, so the time taken is at most linear
+/// in `p` for each digit, however large `n` and `k` are.  The result is unspecified if `p` is not
+/// prime; use [`binomial_mod`] for other moduli.
+///
+/// # Panics
+///
+/// Panics if `p` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{binomial_mod, binomial_mod_prime};
+///
+/// assert_eq!(binomial_mod_prime(10, 3, 7), 120 % 7);
+/// assert_eq!(binomial_mod_prime(1 << 62, 1 << 61, 1_000_003), 197_035);
+/// assert_eq!(binomial_mod_prime(1 << 62, 1 << 61, 3), 0);
+/// assert_eq!(
+///     binomial_mod_prime(123_456_789, 12_345, 1_000_000_007),
+///     binomial_mod(123_456_789, 12_345, 1_000_000_007),
+/// );
+/// ```
+pub fn binomial_mod_prime(mut n: u64, mut k: u64, p: u64) -> u64 {
+    let mut product = 1 % p;
+    while k > 0 {
+        let (n_digit, k_digit) = (n % p, k % p);
+        if k_digit > n_digit {
+            return 0;
+        }
+        product = mul_mod(product, binomial_mod(n_digit, k_digit, p), p);
+        n /= p;
+        k /= p;
+    }
+    product
+}
+
+/// Returns `a * b` modulo `m`.
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// Returns `base` to the power `exponent` modulo `m`.
+fn pow_mod(mut base: u64, mut exponent: u64, m: u64) -> u64 {
+    let mut power = 1 % m;
+    base %= m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            power = mul_mod(power, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    power
+}
+
+/// Returns the inverse of `a` modulo `m`, or `None` if `a` and `m` are not coprime.
+fn inverse_mod(a: u64, m: u64) -> Option<u64> {
+    let (mut r0, mut r1) = (m as i128, a as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let quotient = r0 / r1;
+        (r0, r1) = (r1, r0 - quotient * r1);
+        (t0, t1) = (t1, t0 - quotient * t1);
+    }
+    (r0 == 1 || m == 1).then(|| t0.rem_euclid(m as i128) as u64)
+}
+
+/// Returns the distinct prime factors of `m` which are at most `limit`, in increasing order.
+fn small_prime_factors(mut m: u64, limit: u64) -> Vec<u64> {
+    let mut primes = Vec::new();
+    let mut divisor = 2;
+    while divisor <= limit && divisor.saturating_mul(divisor) <= m {
+        if m.is_multiple_of(divisor) {
+            primes.push(divisor);
+            while m.is_multiple_of(divisor) {
+                m /= divisor;
+            }
+        }
+        divisor += 1;
+    }
+    if m > 1 && m <= limit {
+        primes.push(m);
+    }
+    primes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{combination_count, permutation_count};
+
+    #[test]
+    fn test_modular_matches_exact_counts() {
+        for m in 1..60u64 {
+            for n in 0..35u64 {
+                let exact = permutation_count(n as usize, n as usize).unwrap();
+                assert_eq!(factorial_mod(n, m) as u128, exact % m as u128);
+                for k in 0..=n + 1 {
+                    let exact = combination_count(n as usize, k as usize).unwrap();
+                    assert_eq!(binomial_mod(n, k, m) as u128, exact % m as u128);
+                }
+            }
+        }
+        for p in [2, 3, 5, 7, 11, 13] {
+            for n in 0..120u64 {
+                for k in 0..=n + 1 {
+                    let exact = combination_count(n as usize, k as usize).unwrap();
+                    assert_eq!(binomial_mod_prime(n, k, p) as u128, exact % p as u128);
+                }
+            }
+        }
+        let big = (1 << 61) - 1;
+        assert_eq!(
+            binomial_mod(u64::MAX, 3, big),
+            binomial_mod_prime(u64::MAX, 3, big)
+        );
+    }
+}
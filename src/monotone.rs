@@ -0,0 +1,330 @@
+use crate::sorted_unique;
+
+/// An iterator which generates the frontier of a monotone predicate over the subsets of a set of
+/// elements: either the minimal subsets which satisfy it, or the maximal subsets which do not.
+///
+/// The predicate must be monotone, so that every superset of a satisfying subset also satisfies
+/// it.  Minimal satisfying subsets are searched for by size, smallest first, building each subset
+/// one element at a time in increasing order.  A partial subset is abandoned as soon as it
+/// contains a minimal subset already found, since every subset extending it then satisfies the
+/// predicate without being minimal, or as soon as adding every element which may still follow it
+/// does not satisfy the predicate, since then no subset extending it does.  This makes it
+/// suitable for finding all of the minimal failure-inducing sets, or the minimal hitting sets,
+/// where filtering the whole powerset is out of reach.  The maximal unsatisfying subsets are
+/// found in the same way, as the complements of the minimal satisfying subsets of the dual
+/// predicate, which holds for a subset when the original predicate does not hold for its
+/// complement.
+///
+/// The elements are sorted and deduplicated, and each subset is yielded with its elements in
+/// increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::MonotoneFrontier;
+///
+/// // A build fails whenever it includes both 'a' and 'c', or 'd'.
+/// let fails = |changes: &[char]| {
+///     changes.contains(&'d') || changes.contains(&'a') && changes.contains(&'c')
+/// };
+/// let causes: Vec<Vec<char>> =
+///     MonotoneFrontier::minimal_satisfying("abcd".chars(), fails).collect();
+/// assert_eq!(causes, vec![vec!['d'], vec!['a', 'c']]);
+///
+/// let safe: Vec<Vec<char>> =
+///     MonotoneFrontier::maximal_unsatisfying("abcd".chars(), fails).collect();
+/// assert_eq!(safe, vec![vec!['b', 'c'], vec!['a', 'b']]);
+/// ```
+pub struct MonotoneFrontier<T, F> {
+    elements: Vec<T>,
+    predicate: F,
+    /// Whether the search is for the maximal unsatisfying subsets, through the dual predicate.
+    dual: bool,
+    /// The indices of the minimal subsets found so far which contain each element.
+    containing: Vec<Vec<usize>>,
+    /// The size of each minimal subset found so far.
+    found_sizes: Vec<usize>,
+    /// The number of elements of each minimal subset found so far in the current subset.
+    hits: Vec<usize>,
+    /// The number of minimal subsets found so far which the current subset contains.
+    covered: usize,
+    positions: Vec<usize>,
+    length: usize,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord + Clone, F: FnMut(&[T]) -> bool> MonotoneFrontier<T, F> {
+    /// Creates a new `MonotoneFrontier` iterator which will yield the minimal subsets of the
+    /// elements in the given iterable which satisfy the given monotone predicate, smallest first,
+    /// and in lexicographic order among subsets of the same size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::MonotoneFrontier;
+    ///
+    /// // The minimal subsets of 1..=6 adding up to at least 10.
+    /// let large = |subset: &[u32]| subset.iter().sum::<u32>() >= 10;
+    /// let minimal: Vec<Vec<u32>> = MonotoneFrontier::minimal_satisfying(1..=6, large).collect();
+    /// assert_eq!(minimal[..3], [vec![4, 6], vec![5, 6], vec![1, 3, 6]]);
+    /// assert_eq!(minimal.len(), 9);
+    /// ```
+    pub fn minimal_satisfying(elements: impl IntoIterator<Item = T>, predicate: F) -> Self {
+        MonotoneFrontier::new(sorted_unique(elements), predicate, false)
+    }
+
+    /// Creates a new `MonotoneFrontier` iterator which will yield the maximal subsets of the
+    /// elements in the given iterable which do not satisfy the given monotone predicate, largest
+    /// first, and in lexicographic order of the elements they leave out among subsets of the same
+    /// size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::MonotoneFrontier;
+    ///
+    /// // The maximal subsets of 1..=5 adding up to less than 8.
+    /// let large = |subset: &[u32]| subset.iter().sum::<u32>() >= 8;
+    /// let maximal: Vec<Vec<u32>> = MonotoneFrontier::maximal_unsatisfying(1..=5, large).collect();
+    /// assert_eq!(maximal, vec![vec![1, 2, 4], vec![1, 2, 3], vec![3, 4], vec![2, 5], vec![1, 5]]);
+    /// ```
+    pub fn maximal_unsatisfying(elements: impl IntoIterator<Item = T>, predicate: F) -> Self {
+        MonotoneFrontier::new(sorted_unique(elements), predicate, true)
+    }
+
+    /// Creates a new `MonotoneFrontier` iterator over the given sorted, deduplicated elements,
+    /// searching through the dual predicate if `dual` is `true`.
+    fn new(elements: Vec<T>, predicate: F, dual: bool) -> Self {
+        let count = elements.len();
+        MonotoneFrontier {
+            elements,
+            predicate,
+            dual,
+            containing: vec![Vec::new(); count],
+            found_sizes: Vec::new(),
+            hits: Vec::new(),
+            covered: 0,
+            positions: Vec::with_capacity(count),
+            length: 0,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Returns the sorted, deduplicated elements whose subsets are searched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::MonotoneFrontier;
+    ///
+    /// let frontier = MonotoneFrontier::minimal_satisfying(vec![3, 1, 3], |s: &[i32]| s.len() > 1);
+    /// assert_eq!(frontier.elements(), &[1, 3]);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns `true` if the searched predicate, which is the dual predicate when searching for
+    /// maximal unsatisfying subsets, holds for the chosen elements together with every element
+    /// from `rest` onwards.
+    fn holds(&mut self, rest: usize) -> bool {
+        let mut chosen = vec![false; self.elements.len()];
+        for index in self
+            .positions
+            .iter()
+            .copied()
+            .chain(rest..self.elements.len())
+        {
+            chosen[index] = true;
+        }
+        let subset: Vec<T> = self.subset(&chosen);
+        (self.predicate)(&subset) != self.dual
+    }
+
+    /// Returns the subset of the elements with the given membership, or its complement when
+    /// searching through the dual predicate.
+    fn subset(&self, chosen: &[bool]) -> Vec<T> {
+        self.elements
+            .iter()
+            .zip(chosen)
+            .filter(|(_, chosen)| **chosen != self.dual)
+            .map(|(element, _)| element.clone())
+            .collect()
+    }
+
+    /// Records the current subset as a minimal satisfying subset.
+    fn record(&mut self) {
+        let id = self.found_sizes.len();
+        self.found_sizes.push(self.positions.len());
+        self.hits.push(self.positions.len());
+        for position in self.positions.iter() {
+            self.containing[*position].push(id);
+        }
+        self.covered += 1;
+    }
+
+    /// Adds the element with the given index to the current subset.
+    fn place(&mut self, index: usize) {
+        for id in self.containing[index].iter() {
+            self.hits[*id] += 1;
+            if self.hits[*id] == self.found_sizes[*id] {
+                self.covered += 1;
+            }
+        }
+        self.positions.push(index);
+    }
+
+    /// Removes and returns the index of the last element added to the current subset, if any.
+    fn unplace(&mut self) -> Option<usize> {
+        let index = self.positions.pop()?;
+        for id in self.containing[index].iter() {
+            if self.hits[*id] == self.found_sizes[*id] {
+                self.covered -= 1;
+            }
+            self.hits[*id] -= 1;
+        }
+        Some(index)
+    }
+
+    /// Returns the index of the first element which may be added to the current subset, skipping
+    /// every element with index not greater than `previous`, if given, or than the last element
+    /// added otherwise.  Elements too late to leave room for the rest of the subset are not
+    /// considered.
+    fn next_candidate(&self, previous: Option<usize>) -> Option<usize> {
+        let start = match (previous, self.positions.last().copied()) {
+            (Some(index), _) | (None, Some(index)) => index + 1,
+            (None, None) => 0,
+        };
+        let needed = self.length - self.positions.len();
+        let end = (self.elements.len() + 1).checked_sub(needed)?;
+        (start < end).then_some(start)
+    }
+
+    /// Completes the current subset to the next minimal satisfying subset of the current size,
+    /// backtracking as necessary.  If `previous` is given, the search resumes by trying elements
+    /// after `previous` in the next position.  Returns `true` if a minimal subset was found, or
+    /// `false` if none remain of the current size.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            if previous.is_none() && self.positions.len() == self.length {
+                // Any satisfying subset containing no minimal subset found so far is minimal,
+                // since every smaller satisfying subset would contain one.
+                if self.covered == 0 && self.holds(self.elements.len()) {
+                    self.record();
+                    return true;
+                }
+                match self.unplace() {
+                    Some(index) => previous = Some(index),
+                    None => return false,
+                }
+                continue;
+            }
+            match self.next_candidate(previous) {
+                Some(index) => {
+                    self.place(index);
+                    previous = None;
+                    let partial = self.positions.len() < self.length;
+                    if self.covered > 0 || partial && !self.holds(index + 1) {
+                        previous = self.unplace();
+                    }
+                }
+                None => match self.unplace() {
+                    Some(index) => previous = Some(index),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Moves to the next minimal subset, moving on to larger sizes as necessary.  Returns `false`
+    /// and marks the iterator as done if none remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let mut found = if self.started {
+            match self.unplace() {
+                Some(index) => self.fill(Some(index)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            // If even the whole set does not satisfy the predicate, then no subset does.
+            self.holds(0) && self.fill(None)
+        };
+        // Once the empty subset is found to be minimal, every other subset contains it.
+        while !found && self.length < self.elements.len() && self.covered == 0 {
+            self.length += 1;
+            found = self.fill(None);
+        }
+        if !found {
+            self.done = true;
+        }
+        found
+    }
+}
+
+impl<T: Ord + Clone, F: FnMut(&[T]) -> bool> Iterator for MonotoneFrontier<T, F> {
+    type Item = Vec<T>;
+
+    /// Returns the next subset on the frontier and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        let mut chosen = vec![false; self.elements.len()];
+        for index in self.positions.iter() {
+            chosen[*index] = true;
+        }
+        Some(self.subset(&chosen))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_monotone_frontier_matches_exhaustive_search() {
+        let families: [&[&[u8]]; 5] = [
+            &[&[0, 3], &[1, 2, 4], &[2, 3], &[5], &[0, 1, 4]],
+            &[&[1, 2, 3, 4, 5, 6]],
+            &[&[]],
+            &[],
+            &[&[0], &[1], &[2], &[3], &[4], &[5], &[6]],
+        ];
+        for family in families {
+            let holds = |subset: &[u8]| {
+                family
+                    .iter()
+                    .any(|set| set.iter().all(|x| subset.contains(x)))
+            };
+            let all: Vec<Vec<u8>> = Combinations::all(0..7).collect();
+            let without =
+                |s: &[u8], x: u8| s.iter().copied().filter(|y| *y != x).collect::<Vec<u8>>();
+            let with = |s: &[u8], x: u8| [s, &[x]].concat();
+            let complement = |s: &Vec<u8>| (0..7).filter(|x| !s.contains(x)).collect::<Vec<u8>>();
+            let minimal: Vec<Vec<u8>> = all
+                .iter()
+                .filter(|s| holds(s) && s.iter().all(|x| !holds(&without(s, *x))))
+                .cloned()
+                .collect();
+            let mut left_out: Vec<Vec<u8>> = all
+                .iter()
+                .filter(|s| !holds(s) && complement(s).iter().all(|x| holds(&with(s, *x))))
+                .map(complement)
+                .collect();
+            left_out.sort_by(|a, b| (a.len(), a).cmp(&(b.len(), b)));
+            let maximal: Vec<Vec<u8>> = left_out.iter().map(complement).collect();
+            let generated: Vec<Vec<u8>> =
+                MonotoneFrontier::minimal_satisfying(0..7, holds).collect();
+            assert_eq!(generated, minimal);
+            let generated: Vec<Vec<u8>> =
+                MonotoneFrontier::maximal_unsatisfying(0..7, holds).collect();
+            assert_eq!(generated, maximal);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{Combinations, Permutation, PermutationGroup};
+
+/// An iterator which generates one canonical representative of each orbit of subsets or sequences
+/// under a group of permutations acting on positions.
+///
+/// Candidates are generated in lexicographic order, and a candidate is yielded only if it is the
+/// lexicographically smallest element of its orbit, which is checked on the fly by applying every
+/// element of the group to it.  No record of previously yielded representatives is kept, so
+/// memory use depends only on the size of the group, not on the number of orbits.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{Orbits, Permutation, PermutationGroup};
+///
+/// // Necklaces of four beads in two colors, up to rotation.
+/// let rotation = Permutation::from_images(vec![1, 2, 3, 0]).unwrap();
+/// let rotations = PermutationGroup::generated_by(vec![rotation]);
+/// let necklaces: Vec<Vec<usize>> = Orbits::of_sequences(&rotations, 2).collect();
+/// assert_eq!(
+///     necklaces,
+///     vec![
+///         vec![0, 0, 0, 0],
+///         vec![0, 0, 0, 1],
+///         vec![0, 0, 1, 1],
+///         vec![0, 1, 0, 1],
+///         vec![0, 1, 1, 1],
+///         vec![1, 1, 1, 1],
+///     ]
+/// );
+/// ```
+pub struct Orbits {
+    group_elements: Vec<Permutation>,
+    candidates: Candidates,
+}
+
+/// The candidates from which orbit representatives are selected.
+enum Candidates {
+    Subsets(Combinations<usize>),
+    Sequences {
+        current: Option<Vec<usize>>,
+        num_colors: usize,
+    },
+}
+
+impl Candidates {
+    /// Returns the next candidate in lexicographic order.
+    fn next(&mut self) -> Option<Vec<usize>> {
+        match self {
+            Candidates::Subsets(combos) => combos.next(),
+            Candidates::Sequences {
+                current,
+                num_colors,
+            } => {
+                let sequence = current.take()?;
+                let mut successor = sequence.clone();
+                for color in successor.iter_mut().rev() {
+                    if *color + 1 < *num_colors {
+                        *color += 1;
+                        *current = Some(successor);
+                        break;
+                    }
+                    *color = 0;
+                }
+                Some(sequence)
+            }
+        }
+    }
+}
+
+impl Orbits {
+    /// Creates a new `Orbits` iterator which will yield one representative of each orbit of the
+    /// subsets of the given size of the points acted on by the given group.  Each representative
+    /// is a sorted list of points, and is the lexicographically smallest subset in its orbit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Orbits, Permutation, PermutationGroup};
+    ///
+    /// // Pairs of vertices of a square, up to rotation and reflection: edges and diagonals.
+    /// let rotation = Permutation::from_images(vec![1, 2, 3, 0]).unwrap();
+    /// let reflection = Permutation::from_images(vec![3, 2, 1, 0]).unwrap();
+    /// let symmetries = PermutationGroup::generated_by(vec![rotation, reflection]);
+    /// let mut pairs = Orbits::of_subsets(&symmetries, 2);
+    /// assert_eq!(pairs.next(), Some(vec![0, 1]));
+    /// assert_eq!(pairs.next(), Some(vec![0, 2]));
+    /// assert_eq!(pairs.next(), None);
+    /// ```
+    pub fn of_subsets(group: &PermutationGroup, size: usize) -> Self {
+        Orbits {
+            group_elements: group.elements().collect(),
+            candidates: Candidates::Subsets(Combinations::of_size(0..group.degree(), size)),
+        }
+    }
+
+    /// Creates a new `Orbits` iterator which will yield one representative of each orbit of the
+    /// sequences which assign one of `num_colors` colors to each point acted on by the given
+    /// group.  Each representative is the lexicographically smallest sequence in its orbit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Orbits, Permutation, PermutationGroup};
+    ///
+    /// // Colorings of the vertices of a triangle with three colors, up to rotation.
+    /// let rotation = Permutation::from_images(vec![1, 2, 0]).unwrap();
+    /// let rotations = PermutationGroup::generated_by(vec![rotation]);
+    /// assert_eq!(Orbits::of_sequences(&rotations, 3).count(), 11);
+    /// ```
+    pub fn of_sequences(group: &PermutationGroup, num_colors: usize) -> Self {
+        let degree = group.degree();
+        Orbits {
+            group_elements: group.elements().collect(),
+            candidates: Candidates::Sequences {
+                current: (num_colors > 0 || degree == 0).then(|| vec![0; degree]),
+                num_colors,
+            },
+        }
+    }
+
+    /// Returns `true` if no element of the group maps the given candidate to a lexicographically
+    /// smaller one.
+    fn is_canonical(&self, candidate: &[usize]) -> bool {
+        match self.candidates {
+            Candidates::Subsets(_) => self.group_elements.iter().all(|element| {
+                let mut image: Vec<usize> = candidate.iter().map(|p| element.image(*p)).collect();
+                image.sort_unstable();
+                image.as_slice() >= candidate
+            }),
+            Candidates::Sequences { .. } => self.group_elements.iter().all(|element| {
+                let image = (0..candidate.len()).map(|p| candidate[element.image(p)]);
+                image.cmp(candidate.iter().copied()) != std::cmp::Ordering::Less
+            }),
+        }
+    }
+}
+
+impl Iterator for Orbits {
+    type Item = Vec<usize>;
+
+    /// Returns the next orbit representative and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let candidate = self.candidates.next()?;
+            if self.is_canonical(&candidate) {
+                return Some(candidate);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn perm(images: &[usize]) -> Permutation {
+        Permutation::from_images(images.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_orbits_of_sequences_partition_all_sequences() {
+        let group = PermutationGroup::generated_by(vec![
+            perm(&[1, 2, 3, 4, 5, 0]),
+            perm(&[5, 4, 3, 2, 1, 0]),
+        ]);
+        let reps: Vec<Vec<usize>> = Orbits::of_sequences(&group, 3).collect();
+        assert_eq!(reps.len(), 92);
+        let mut covered = BTreeSet::new();
+        for rep in reps.iter() {
+            for element in group.elements() {
+                let image: Vec<usize> = (0..6).map(|p| rep[element.image(p)]).collect();
+                covered.insert(image);
+            }
+        }
+        assert_eq!(covered.len(), 729);
+    }
+
+    #[test]
+    fn test_orbits_of_subsets() {
+        let trivial = PermutationGroup::generated_by(vec![Permutation::identity(5)]);
+        assert_eq!(Orbits::of_subsets(&trivial, 2).count(), 10);
+        let symmetric = PermutationGroup::generated_by(vec![perm(&[1, 2, 3, 4, 0]), perm(&[1, 0])]);
+        for size in 0..=5 {
+            assert_eq!(
+                Orbits::of_subsets(&symmetric, size).collect::<Vec<Vec<usize>>>(),
+                vec![(0..size).collect::<Vec<usize>>()]
+            );
+        }
+        assert_eq!(Orbits::of_subsets(&symmetric, 6).next(), None);
+    }
+
+    #[test]
+    fn test_orbits_of_sequences_edge_cases() {
+        let group = PermutationGroup::generated_by(vec![perm(&[1, 0])]);
+        assert_eq!(Orbits::of_sequences(&group, 0).next(), None);
+        assert_eq!(
+            Orbits::of_sequences(&group, 1).collect::<Vec<Vec<usize>>>(),
+            vec![vec![0, 0]]
+        );
+        let empty = PermutationGroup::generated_by(Vec::new());
+        assert_eq!(
+            Orbits::of_sequences(&empty, 0).collect::<Vec<Vec<usize>>>(),
+            vec![Vec::<usize>::new()]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use crate::sorted_unique;
+
+/// An iterator which generates the perfect matchings of a set of elements, that is, the ways to
+/// split the set into pairs.
+///
+/// A set of `2n` elements has `(2n - 1)!! = 1 * 3 * 5 * ... * (2n - 1)` perfect matchings.  Each
+/// matching is yielded as a list of pairs, where the first element of each pair is smaller than
+/// the second and the pairs are sorted by their first elements.  Matchings are yielded in
+/// lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Pairings;
+///
+/// let mut pairings = Pairings::of(vec!['a', 'b', 'c', 'd']);
+/// assert_eq!(pairings.next(), Some(vec![('a', 'b'), ('c', 'd')]));
+/// assert_eq!(pairings.next(), Some(vec![('a', 'c'), ('b', 'd')]));
+/// assert_eq!(pairings.next(), Some(vec![('a', 'd'), ('b', 'c')]));
+/// assert_eq!(pairings.next(), None);
+///
+/// assert_eq!(Pairings::of(0..10).count(), 9 * 7 * 5 * 3);
+/// ```
+#[derive(Clone)]
+pub struct Pairings<T> {
+    elements: Vec<T>,
+    pairs: Vec<(usize, usize)>,
+    matched: Vec<bool>,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord + Clone> Pairings<T> {
+    /// Creates a new `Pairings` iterator which will yield every perfect matching of the elements
+    /// in the given iterable.  If there is an odd number of unique elements, then no matchings
+    /// are yielded.  An empty set has a single, empty matching.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Pairings;
+    ///
+    /// assert_eq!(Pairings::of(1..4).next(), None);
+    ///
+    /// let mut pairings = Pairings::of(Vec::<usize>::new());
+    /// assert_eq!(pairings.next(), Some(Vec::new()));
+    /// assert_eq!(pairings.next(), None);
+    /// ```
+    pub fn of(elements: impl IntoIterator<Item = T>) -> Self {
+        let elements = sorted_unique(elements);
+        let count = elements.len();
+        Pairings {
+            elements,
+            pairs: Vec::with_capacity(count / 2),
+            matched: vec![false; count],
+            started: false,
+            done: count % 2 == 1,
+        }
+    }
+
+    /// Returns the sorted, deduplicated elements which this iterator matches into pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Pairings;
+    ///
+    /// assert_eq!(Pairings::of(vec![4, 2, 4, 1]).elements(), &[1, 2, 4]);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the matching which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if none remain.  Since the next matching is only found
+    /// when the iterator advances, this searches for it using a copy of the iterator, so it costs
+    /// about as much as a call to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Pairings;
+    ///
+    /// let mut pairings = Pairings::of(1..=4);
+    /// assert_eq!(pairings.next(), Some(vec![(1, 2), (3, 4)]));
+    /// assert_eq!(pairings.current(), Some(vec![(1, 3), (2, 4)]));
+    /// assert_eq!(pairings.next(), Some(vec![(1, 3), (2, 4)]));
+    /// ```
+    pub fn current(&self) -> Option<Vec<(T, T)>> {
+        self.clone().next()
+    }
+
+    /// Consumes the iterator and returns its elements, as given by [`Pairings::elements`], along
+    /// with the pairs of indices of the next matching which would have been yielded, or `None` if
+    /// no matchings remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Pairings;
+    ///
+    /// let mut pairings = Pairings::of(vec!['d', 'c', 'b', 'a']);
+    /// assert_eq!(pairings.next(), Some(vec![('a', 'b'), ('c', 'd')]));
+    /// assert_eq!(pairings.into_parts(), (vec!['a', 'b', 'c', 'd'], Some(vec![(0, 2), (1, 3)])));
+    /// ```
+    pub fn into_parts(mut self) -> (Vec<T>, Option<Vec<(usize, usize)>>) {
+        let next = self.advance().then_some(self.pairs);
+        (self.elements, next)
+    }
+
+    /// Returns the total number of matchings which this iterator yields from the beginning,
+    /// regardless of how far it has advanced, as a big integer, so the count is exact however
+    /// large it is.  This is only available with the `bigint` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Pairings;
+    /// use num_bigint::BigUint;
+    ///
+    /// assert_eq!(Pairings::of(0..10).total_count_big(), BigUint::from(945u16));
+    /// assert_eq!(Pairings::of(0..9).total_count_big(), BigUint::from(0u8));
+    /// assert!(Pairings::of(0..80).total_count_big() > BigUint::from(u128::MAX));
+    /// ```
+    #[cfg(feature = "bigint")]
+    pub fn total_count_big(&self) -> num_bigint::BigUint {
+        let n = self.elements.len();
+        if n % 2 == 1 {
+            return num_bigint::BigUint::default();
+        }
+        (1..n).step_by(2).map(num_bigint::BigUint::from).product()
+    }
+
+    /// Returns the index of the smallest element which has not yet been matched, if any.
+    fn first_unmatched(&self) -> Option<usize> {
+        self.matched.iter().position(|matched| !matched)
+    }
+
+    /// Matches the smallest unmatched element with the element at the given index.
+    fn place(&mut self, partner: usize) {
+        let first = self.first_unmatched().unwrap();
+        self.matched[first] = true;
+        self.matched[partner] = true;
+        self.pairs.push((first, partner));
+    }
+
+    /// Removes the last pair and returns the index of the partner of its first element, if any.
+    fn unplace(&mut self) -> Option<usize> {
+        let (first, partner) = self.pairs.pop()?;
+        self.matched[first] = false;
+        self.matched[partner] = false;
+        Some(partner)
+    }
+
+    /// Completes the matching with the lexicographically smallest pairs, backtracking as
+    /// necessary.  If `previous` is given, the search resumes by trying partners after `previous`
+    /// for the smallest unmatched element.  Returns `true` if the matching was completed, or
+    /// `false` if no further matchings exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            let first = match self.first_unmatched() {
+                Some(first) => first,
+                None => return true,
+            };
+            let start = previous.map_or(first + 1, |partner| partner + 1);
+            match (start..self.elements.len()).find(|partner| !self.matched[*partner]) {
+                Some(partner) => {
+                    self.place(partner);
+                    previous = None;
+                }
+                None => match self.unplace() {
+                    Some(partner) => previous = Some(partner),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Moves to the next matching.  Returns `false` and marks the iterator as done if no
+    /// matchings remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let found = if self.started {
+            match self.unplace() {
+                Some(partner) => self.fill(Some(partner)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.fill(None)
+        };
+        if !found {
+            self.done = true;
+        }
+        found
+    }
+}
+
+impl<T: Ord + Clone> Iterator for Pairings<T> {
+    type Item = Vec<(T, T)>;
+
+    /// Returns the next matching and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        Some(
+            self.pairs
+                .iter()
+                .map(|(a, b)| (self.elements[*a].clone(), self.elements[*b].clone()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_pairings_counts_and_validity() {
+        let mut expected = 1;
+        for n in 0..7 {
+            let matchings: Vec<Vec<(usize, usize)>> = Pairings::of(0..2 * n).collect();
+            assert_eq!(matchings.len(), expected);
+            expected *= 2 * n + 1;
+            for matching in matchings.iter() {
+                let mut covered: Vec<usize> = matching.iter().flat_map(|(a, b)| [*a, *b]).collect();
+                covered.sort_unstable();
+                assert_eq!(covered, (0..2 * n).collect::<Vec<usize>>());
+                assert!(matching.iter().all(|(a, b)| a < b));
+            }
+            let unique: BTreeSet<Vec<(usize, usize)>> = matchings.iter().cloned().collect();
+            assert_eq!(unique.into_iter().collect::<Vec<_>>(), matchings);
+            assert_eq!(Pairings::of(0..2 * n + 1).count(), 0);
+        }
+    }
+}
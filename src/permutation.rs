@@ -0,0 +1,545 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A permutation of the points `0..n`, stored as the list of images of each point.
+///
+/// Points at or beyond the degree of a permutation are treated as fixed, so permutations of
+/// different degrees can be composed and applied freely.  Composition follows the usual
+/// right-to-left convention: `p.compose(&q)` maps each point `x` to `p(q(x))`.  Likewise,
+/// permutations which differ only in their degrees compare equal and hash alike, and are ordered
+/// by their images with any trailing fixed points removed.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Permutation;
+///
+/// let p = Permutation::from_images(vec![1, 2, 0]).unwrap();
+/// let q = Permutation::from_cycles(3, &[&[0, 1]]).unwrap();
+/// assert_eq!(p.image(0), 1);
+/// assert_eq!(p.compose(&q).images(), &[2, 1, 0]);
+/// assert_eq!(p.inverse().images(), &[2, 0, 1]);
+/// assert!(p.compose(&p.inverse()).is_identity());
+/// assert_eq!(p.to_string(), "(0 1 2)");
+/// assert_eq!(Permutation::identity(2), Permutation::identity(3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Permutation {
+    images: Vec<usize>,
+}
+
+impl Permutation {
+    /// Returns the identity permutation of the given degree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutation;
+    ///
+    /// assert_eq!(Permutation::identity(3).images(), &[0, 1, 2]);
+    /// ```
+    pub fn identity(degree: usize) -> Self {
+        Permutation {
+            images: (0..degree).collect(),
+        }
+    }
+
+    /// Creates a permutation from the images of the points `0..n`, where `n` is the length of the
+    /// given vector.  Returns `None` if the images are not a rearrangement of `0..n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutation;
+    ///
+    /// assert!(Permutation::from_images(vec![2, 0, 1]).is_some());
+    /// assert!(Permutation::from_images(vec![2, 0, 0]).is_none());
+    /// assert!(Permutation::from_images(vec![3, 0, 1]).is_none());
+    /// ```
+    pub fn from_images(images: Vec<usize>) -> Option<Self> {
+        Permutation::try_from(images).ok()
+    }
+
+    /// Creates a permutation of the given degree from a list of disjoint cycles, where each cycle
+    /// maps each of its points to the next and the last back to the first.  Returns `None` if a
+    /// point occurs more than once or is not less than the degree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutation;
+    ///
+    /// let p = Permutation::from_cycles(5, &[&[0, 2], &[1, 3, 4]]).unwrap();
+    /// assert_eq!(p.images(), &[2, 3, 0, 4, 1]);
+    /// assert!(Permutation::from_cycles(3, &[&[0, 1], &[1, 2]]).is_none());
+    /// ```
+    pub fn from_cycles(degree: usize, cycles: &[&[usize]]) -> Option<Self> {
+        let mut images: Vec<usize> = (0..degree).collect();
+        let mut seen = vec![false; degree];
+        for cycle in cycles {
+            for (index, point) in cycle.iter().enumerate() {
+                if *point >= degree || seen[*point] {
+                    return None;
+                }
+                seen[*point] = true;
+                images[*point] = cycle[(index + 1) % cycle.len()];
+            }
+        }
+        Some(Permutation { images })
+    }
+
+    /// Returns the number of points on which the permutation is defined.
+    pub fn degree(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Returns the image of the given point.  Points not less than the degree are fixed.
+    pub fn image(&self, point: usize) -> usize {
+        self.images.get(point).copied().unwrap_or(point)
+    }
+
+    /// Returns the images of the points `0..n`, where `n` is the degree.
+    pub fn images(&self) -> &[usize] {
+        &self.images
+    }
+
+    /// Consumes the permutation and returns the images of the points `0..n`.
+    pub fn into_images(self) -> Vec<usize> {
+        self.images
+    }
+
+    /// Returns the composition of `self` after `other`, which maps each point `x` to
+    /// `self(other(x))`.  The degree of the result is the larger of the two degrees.
+    pub fn compose(&self, other: &Permutation) -> Permutation {
+        let degree = self.degree().max(other.degree());
+        Permutation {
+            images: (0..degree)
+                .map(|point| self.image(other.image(point)))
+                .collect(),
+        }
+    }
+
+    /// Returns the inverse permutation.
+    pub fn inverse(&self) -> Permutation {
+        let mut images = vec![0; self.degree()];
+        for (point, image) in self.images.iter().enumerate() {
+            images[*image] = point;
+        }
+        Permutation { images }
+    }
+
+    /// Returns `true` if the permutation fixes every point.
+    pub fn is_identity(&self) -> bool {
+        self.images
+            .iter()
+            .enumerate()
+            .all(|(point, image)| point == *image)
+    }
+
+    /// Returns `true` if the permutation is even, meaning that it is the product of an even number
+    /// of transpositions.  A permutation is even exactly when its degree and its number of cycles,
+    /// including fixed points, have the same parity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutation;
+    ///
+    /// assert!(Permutation::from_images(vec![1, 2, 0]).unwrap().is_even());
+    /// assert!(!Permutation::from_images(vec![1, 0, 2]).unwrap().is_even());
+    /// assert!(Permutation::identity(0).is_even());
+    /// ```
+    pub fn is_even(&self) -> bool {
+        (self.degree() - self.cycles().len()).is_multiple_of(2)
+    }
+
+    /// Returns a copy of the permutation with the given degree, which must be at least the degree
+    /// of the largest point the permutation moves.  Returns `None` otherwise.
+    pub(crate) fn with_degree(&self, degree: usize) -> Option<Permutation> {
+        if self.images[degree.min(self.degree())..]
+            .iter()
+            .enumerate()
+            .any(|(offset, image)| *image != degree + offset)
+        {
+            return None;
+        }
+        Some(Permutation {
+            images: (0..degree).map(|point| self.image(point)).collect(),
+        })
+    }
+
+    /// Returns the images with any trailing fixed points removed, which are the same for every
+    /// degree at which the permutation can be written.
+    fn trimmed_images(&self) -> &[usize] {
+        let moved = self
+            .images
+            .iter()
+            .enumerate()
+            .rposition(|(point, image)| point != *image);
+        &self.images[..moved.map_or(0, |point| point + 1)]
+    }
+
+    /// Returns the cycles of the permutation, including fixed points as cycles of length one.
+    /// Each cycle begins with its smallest point, and the cycles are ordered by their first point.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutation;
+    ///
+    /// let p = Permutation::from_images(vec![2, 1, 3, 0]).unwrap();
+    /// assert_eq!(p.cycles(), vec![vec![0, 2, 3], vec![1]]);
+    /// ```
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.degree()];
+        let mut cycles = Vec::new();
+        for start in 0..self.degree() {
+            if seen[start] {
+                continue;
+            }
+            let mut cycle = Vec::new();
+            let mut point = start;
+            while !seen[point] {
+                seen[point] = true;
+                cycle.push(point);
+                point = self.images[point];
+            }
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// Returns the cycle type of the permutation: the lengths of its cycles, including fixed
+    /// points, in non-increasing order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutation;
+    ///
+    /// let p = Permutation::from_images(vec![2, 1, 3, 0, 5, 4]).unwrap();
+    /// assert_eq!(p.cycle_type(), vec![3, 2, 1]);
+    /// ```
+    pub fn cycle_type(&self) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self.cycles().iter().map(|cycle| cycle.len()).collect();
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        lengths
+    }
+}
+
+impl PartialEq for Permutation {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed_images() == other.trimmed_images()
+    }
+}
+
+impl Eq for Permutation {}
+
+impl Hash for Permutation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.trimmed_images().hash(state);
+    }
+}
+
+impl PartialOrd for Permutation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Permutation {
+    /// Compares the images with any trailing fixed points removed.  Among permutations of the same
+    /// degree, this is the lexicographic order of their images.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.trimmed_images().cmp(other.trimmed_images())
+    }
+}
+
+impl fmt::Display for Permutation {
+    /// Formats the permutation in cycle notation, omitting fixed points.  The identity is written
+    /// as `()`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cycles: Vec<Vec<usize>> = self
+            .cycles()
+            .into_iter()
+            .filter(|cycle| cycle.len() > 1)
+            .collect();
+        if cycles.is_empty() {
+            return write!(f, "()");
+        }
+        for cycle in cycles {
+            let points: Vec<String> = cycle.iter().map(|point| point.to_string()).collect();
+            write!(f, "({})", points.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts the images of the points `0..n` into a permutation, as by
+/// [`Permutation::from_images`], giving back the images if they are not a rearrangement of
+/// `0..n`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Permutation;
+///
+/// let p = Permutation::try_from(vec![1, 2, 0]).unwrap();
+/// assert_eq!(p.image(0), 1);
+/// assert_eq!(Permutation::try_from(vec![1, 1]), Err(vec![1, 1]));
+/// ```
+impl TryFrom<Vec<usize>> for Permutation {
+    type Error = Vec<usize>;
+
+    fn try_from(images: Vec<usize>) -> Result<Self, Self::Error> {
+        let mut seen = vec![false; images.len()];
+        for image in images.iter() {
+            if *image >= images.len() || seen[*image] {
+                return Err(images);
+            }
+            seen[*image] = true;
+        }
+        Ok(Permutation { images })
+    }
+}
+
+/// Converts a permutation into the images of its points, as by [`Permutation::into_images`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Permutation;
+///
+/// let images: Vec<usize> = Permutation::identity(3).into();
+/// assert_eq!(images, vec![0, 1, 2]);
+/// ```
+impl From<Permutation> for Vec<usize> {
+    fn from(permutation: Permutation) -> Self {
+        permutation.into_images()
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+impl quickcheck::Arbitrary for Permutation {
+    /// Returns a permutation drawn uniformly at random from those of a degree no greater than the
+    /// size of the generator, using a Fisher-Yates shuffle.
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let points: Vec<usize> = (0..=g.size()).collect();
+        let degree = *g.choose(&points).unwrap();
+        let mut images: Vec<usize> = (0..degree).collect();
+        for i in (1..degree).rev() {
+            images.swap(i, *g.choose(&points[..=i]).unwrap());
+        }
+        Permutation { images }
+    }
+
+    /// Shrinks the permutation by removing its largest point, joining the points on either side
+    /// of it in its cycle.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let mut images = self.images.clone();
+        match images.pop() {
+            Some(last) => {
+                if let Some(preimage) = images.iter().position(|image| *image == images.len()) {
+                    images[preimage] = last;
+                }
+                Box::new(std::iter::once(Permutation { images }))
+            }
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+/// Rearranges the given slice in place by the given index permutation, so that afterwards the
+/// item at each index `i` is the one which was previously at index `perm[i]`.
+///
+/// This is the arrangement yielded by [`Permutations`](crate::Permutations) for the positions
+/// `perm`, so a permutation of indices can be generated once and then applied to any number of
+/// slices.  The items are moved by following the cycles of the permutation, with each cycle
+/// started from its smallest index, so only constant extra memory is used.  Finding the start of
+/// each cycle takes time proportional to the length of the cycle, so this takes `O(n^2)` time in
+/// the worst case, but `O(n log n)` on average for a random permutation.
+///
+/// # Panics
+///
+/// Panics if `perm` and `data` have different lengths or any index is out of range.  Some other
+/// invalid permutations, which repeat an index, are also detected, but the contents of `data`
+/// are unspecified for any that are not.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{apply_permutation, Permutations};
+///
+/// let mut letters = ['a', 'b', 'c', 'd'];
+/// apply_permutation(&[2, 0, 3, 1], &mut letters);
+/// assert_eq!(letters, ['c', 'a', 'd', 'b']);
+///
+/// for perm in Permutations::of_length(0..4, 4) {
+///     let mut letters = ['a', 'b', 'c', 'd'];
+///     apply_permutation(&perm, &mut letters);
+///     let expected: Vec<char> = perm.iter().map(|i| (b'a' + *i as u8) as char).collect();
+///     assert_eq!(letters.to_vec(), expected);
+/// }
+/// ```
+pub fn apply_permutation<T>(perm: &[usize], data: &mut [T]) {
+    let n = data.len();
+    assert_eq!(
+        perm.len(),
+        n,
+        "the permutation must have one index for each item"
+    );
+    for start in 0..n {
+        // Walk the cycle through `start` until returning to it, or reaching a smaller index
+        // which shows the cycle has already been applied.  A walk longer than `n` steps can
+        // only happen if `perm` repeats an index.
+        let mut index = perm[start];
+        let mut steps = 0;
+        while index > start {
+            index = perm[index];
+            steps += 1;
+            assert!(steps <= n, "the indices must be a permutation of 0..{n}");
+        }
+        if index != start {
+            continue;
+        }
+        let mut current = start;
+        while perm[current] != start {
+            data.swap(current, perm[current]);
+            current = perm[current];
+        }
+    }
+}
+
+/// Returns the inverse of the given index permutation, which maps each index `perm[i]` back to
+/// `i`.  Applying a permutation and then its inverse with [`apply_permutation`] leaves a slice
+/// unchanged.
+///
+/// # Panics
+///
+/// Panics if `perm` is not a rearrangement of `0..n`, where `n` is its length.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{apply_permutation, inverse_permutation};
+///
+/// let perm = [2, 0, 3, 1];
+/// assert_eq!(inverse_permutation(&perm), vec![1, 3, 0, 2]);
+///
+/// let mut numbers = [10, 20, 30, 40];
+/// apply_permutation(&perm, &mut numbers);
+/// apply_permutation(&inverse_permutation(&perm), &mut numbers);
+/// assert_eq!(numbers, [10, 20, 30, 40]);
+/// ```
+pub fn inverse_permutation(perm: &[usize]) -> Vec<usize> {
+    let n = perm.len();
+    let mut inverse = vec![usize::MAX; n];
+    for (index, image) in perm.iter().enumerate() {
+        assert!(
+            *image < n && inverse[*image] == usize::MAX,
+            "the indices must be a permutation of 0..{n}"
+        );
+        inverse[*image] = index;
+    }
+    inverse
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permutation_compose_different_degrees() {
+        let p = Permutation::from_images(vec![1, 0]).unwrap();
+        let q = Permutation::from_images(vec![0, 2, 1]).unwrap();
+        assert_eq!(p.compose(&q).images(), &[1, 2, 0]);
+        assert_eq!(q.compose(&p).images(), &[2, 0, 1]);
+        assert_eq!(p.image(5), 5);
+    }
+
+    #[test]
+    fn test_permutation_with_degree() {
+        let p = Permutation::from_images(vec![1, 0, 2, 3]).unwrap();
+        assert_eq!(p.with_degree(2).unwrap().images(), &[1, 0]);
+        assert_eq!(p.with_degree(6).unwrap().images(), &[1, 0, 2, 3, 4, 5]);
+        assert_eq!(p.with_degree(1), None);
+    }
+
+    #[test]
+    fn test_permutation_equality_ignores_degree() {
+        use std::collections::HashSet;
+
+        let p = Permutation::from_images(vec![1, 0]).unwrap();
+        let q = p.with_degree(5).unwrap();
+        assert_eq!(p, q);
+        assert_eq!(Permutation::identity(0), Permutation::identity(4));
+        let set: HashSet<Permutation> = [p.clone(), q, Permutation::identity(3)].into();
+        assert_eq!(set.len(), 2);
+        let mut perms: Vec<Permutation> = crate::Permutations::of_length(0..4, 4)
+            .map(|images| Permutation::from_images(images).unwrap())
+            .collect();
+        let sorted = perms.clone();
+        perms.reverse();
+        perms.sort();
+        assert_eq!(perms, sorted);
+        assert!(p < Permutation::from_images(vec![2, 1, 0]).unwrap());
+    }
+
+    #[test]
+    fn test_permutation_cycles_round_trip() {
+        let p = Permutation::from_images(vec![4, 0, 3, 2, 1, 5]).unwrap();
+        let cycles = p.cycles();
+        assert_eq!(cycles, vec![vec![0, 4, 1], vec![2, 3], vec![5]]);
+        let cycle_refs: Vec<&[usize]> = cycles.iter().map(|c| c.as_slice()).collect();
+        assert_eq!(Permutation::from_cycles(6, &cycle_refs), Some(p.clone()));
+        assert_eq!(p.to_string(), "(0 4 1)(2 3)");
+        assert_eq!(Permutation::identity(4).to_string(), "()");
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn test_permutation_arbitrary() {
+        use quickcheck::{Arbitrary, Gen};
+
+        let mut g = Gen::new(4);
+        let mut seen = std::collections::BTreeSet::new();
+        for _ in 0..2000 {
+            let p = Permutation::arbitrary(&mut g);
+            assert!(p.degree() <= 4);
+            for shrunk in p.shrink() {
+                assert_eq!(shrunk.degree(), p.degree() - 1);
+                assert!(Permutation::from_images(shrunk.images().to_vec()).is_some());
+            }
+            seen.insert(p.into_images());
+        }
+        assert_eq!(seen.len(), 1 + 1 + 2 + 6 + 24);
+    }
+
+    #[test]
+    fn test_permutation_apply_and_inverse() {
+        use crate::Permutations;
+
+        for n in 0..7 {
+            for perm in Permutations::of_length(0..n, n) {
+                let mut data: Vec<usize> = (0..n).map(|i| i * 10).collect();
+                apply_permutation(&perm, &mut data);
+                let expected: Vec<usize> = perm.iter().map(|i| i * 10).collect();
+                assert_eq!(data, expected);
+                let inverse = inverse_permutation(&perm);
+                assert_eq!(
+                    inverse,
+                    Permutation::from_images(perm.clone())
+                        .unwrap()
+                        .inverse()
+                        .into_images()
+                );
+                apply_permutation(&inverse, &mut data);
+                assert_eq!(data, (0..n).map(|i| i * 10).collect::<Vec<usize>>());
+            }
+        }
+        let repeated = std::panic::catch_unwind(|| apply_permutation(&[1, 2, 1], &mut [0, 0, 0]));
+        assert!(repeated.is_err());
+        assert!(std::panic::catch_unwind(|| inverse_permutation(&[0, 0])).is_err());
+    }
+}
@@ -0,0 +1,1733 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::ops::{Bound, RangeBounds};
+
+#[cfg(feature = "std")]
+use crate::checkpoint::{invalid, Checkpoint, Kind};
+use crate::collecting::Collecting;
+use crate::combinations::{size_hint_from, sorted_unique_by};
+use crate::constraint::{Constraint, Constraints};
+use crate::cross::RankedIterator;
+use crate::error::{check_size, CombinatorialError, OnOversize};
+use crate::estimate::{log_sum, EstimatedWork};
+use crate::shuffled::Shuffled;
+use crate::statistics::{PermutationStatistic, WithStatistic};
+use crate::{log_factorial, permutation_count, sorted_unique};
+
+/// An iterator which generates permutations over a set of elements.
+///
+/// The elements are sorted and deduplicated, and permutations are yielded in lexicographic order
+/// of the positions of their elements in the sorted set.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Permutations;
+///
+/// let mut abc_perms = Permutations::of_length(vec!['a', 'b', 'c'], 2);
+/// assert_eq!(abc_perms.next(), Some(vec!['a', 'b']));
+/// assert_eq!(abc_perms.next(), Some(vec!['a', 'c']));
+/// assert_eq!(abc_perms.next(), Some(vec!['b', 'a']));
+/// assert_eq!(abc_perms.next(), Some(vec!['b', 'c']));
+/// assert_eq!(abc_perms.next(), Some(vec!['c', 'a']));
+/// assert_eq!(abc_perms.next(), Some(vec!['c', 'b']));
+/// assert_eq!(abc_perms.next(), None);
+///
+/// let ones_and_zeros: Vec<Vec<usize>> = Permutations::all(0..2).collect();
+/// assert_eq!(ones_and_zeros, vec![Vec::new(), vec![0], vec![1], vec![0, 1], vec![1, 0]]);
+/// ```
+#[derive(Clone)]
+pub struct Permutations<T> {
+    elements: Vec<T>,
+    pinned: Vec<Option<usize>>,
+    forbidden: BTreeSet<(usize, usize)>,
+    reserved: Vec<bool>,
+    constraints: Constraints<T>,
+    /// The required cycle lengths, in non-increasing order, if any.
+    cycle_type: Option<Vec<usize>>,
+    /// The indices of the first permutation which is not yielded, if iteration stops early.
+    end: Option<Vec<usize>>,
+    positions: Vec<usize>,
+    used: Vec<bool>,
+    length: usize,
+    first_length: usize,
+    last_length: usize,
+    started: bool,
+    done: bool,
+    /// Whether the pinned positions conflict, so that nothing is yielded however the iterator is
+    /// moved.
+    infeasible: bool,
+}
+
+impl<T: Ord + Clone> Permutations<T> {
+    /// Creates a new `Permutations` iterator which will yield all permutations of every length of
+    /// the elements in the given iterable, beginning with the empty permutation.
+    ///
+    /// The elements are sorted and deduplicated first, so each distinct element appears at most
+    /// once in any permutation, and no permutation is yielded twice.  With `n` distinct elements,
+    /// the permutations of each length `k` from 0 through `n` are yielded in turn, shortest first,
+    /// for a total of the sum of `n! / (n - k)!` over every `k`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// assert_eq!(Permutations::all(1..4).count(), 1 + 3 + 6 + 6);
+    ///
+    /// let perms: Vec<Vec<i32>> = Permutations::all(vec![2, 1, 2]).collect();
+    /// assert_eq!(perms, vec![vec![], vec![1], vec![2], vec![1, 2], vec![2, 1]]);
+    ///
+    /// let empty: Vec<Vec<i32>> = Permutations::all(Vec::new()).collect();
+    /// assert_eq!(empty, vec![Vec::<i32>::new()]);
+    /// ```
+    pub fn all(elements: impl IntoIterator<Item = T>) -> Self {
+        Permutations::all_unchecked(sorted_unique(elements))
+    }
+
+    /// Creates a new `Permutations` iterator which will yield all permutations with the specified
+    /// length of the elements in the given iterable.
+    ///
+    /// The elements are sorted and deduplicated first, so each distinct element appears at most
+    /// once in any permutation, and repeated elements never produce repeated permutations.  To
+    /// treat repeated elements as distinct, use [`Permutations::of_length_unchecked`].  A length of
+    /// zero yields a single empty permutation, and a length greater than the number of distinct
+    /// elements yields nothing; use [`Permutations::try_of_length`] to get an error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let mut perms = Permutations::of_length(1..3, 2);
+    /// assert_eq!(perms.next(), Some(vec![1, 2]));
+    /// assert_eq!(perms.next(), Some(vec![2, 1]));
+    /// assert_eq!(perms.next(), None);
+    ///
+    /// let mut perms = Permutations::of_length(vec!["foo", "bar"], 3);
+    /// assert_eq!(perms.next(), None);
+    ///
+    /// let perms: Vec<Vec<char>> = Permutations::of_length("aab".chars(), 2).collect();
+    /// assert_eq!(perms, vec![vec!['a', 'b'], vec!['b', 'a']]);
+    ///
+    /// assert_eq!(Permutations::of_length(1..4, 0).collect::<Vec<_>>(), vec![Vec::<i32>::new()]);
+    /// ```
+    pub fn of_length(elements: impl IntoIterator<Item = T>, length: usize) -> Self {
+        Permutations::of_length_unchecked(sorted_unique(elements), length)
+    }
+
+    /// Creates a new `Permutations` iterator which will yield all permutations of the elements in
+    /// the given iterable with each length in the given range, shortest first.
+    ///
+    /// The elements are sorted and deduplicated as by [`Permutations::of_length`], and lengths
+    /// greater than the number of distinct elements are skipped, so an unbounded range yields
+    /// permutations up to the full length, and a range with no possible lengths yields nothing.
+    /// [`Permutations::all`] is equivalent to a range of `0..`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let perms: Vec<Vec<i32>> = Permutations::of_length_range(1..4, 1..=2).collect();
+    /// assert_eq!(perms[..3], [vec![1], vec![2], vec![3]]);
+    /// assert_eq!(perms.len(), 3 + 6);
+    ///
+    /// assert_eq!(Permutations::of_length_range(1..4, 2..).count(), 6 + 6);
+    /// assert_eq!(Permutations::of_length_range(1..4, 2..10).count(), 6 + 6);
+    /// assert_eq!(Permutations::of_length_range(1..4, 4..10).next(), None);
+    /// assert_eq!(Permutations::of_length_range(1..4, 2..2).next(), None);
+    /// ```
+    pub fn of_length_range(
+        elements: impl IntoIterator<Item = T>,
+        lengths: impl RangeBounds<usize>,
+    ) -> Self {
+        let elements = sorted_unique(elements);
+        let count = elements.len();
+        let first = match lengths.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let last = match lengths.end_bound() {
+            Bound::Included(end) => Some(*end),
+            Bound::Excluded(end) => end.checked_sub(1),
+            Bound::Unbounded => Some(count),
+        };
+        match last {
+            Some(last) => Permutations::new(elements, first, last.min(count)),
+            None => Permutations::new(elements, 1, 0),
+        }
+    }
+
+    /// Creates a new `Permutations` iterator like [`Permutations::of_length`], but returns an
+    /// error instead of an iterator which yields nothing if the given iterable is empty or the
+    /// length is larger than the number of unique elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{CombinatorialError, Permutations};
+    ///
+    /// assert_eq!(Permutations::try_of_length(1..4, 2).unwrap().count(), 6);
+    /// assert_eq!(
+    ///     Permutations::try_of_length(vec!["foo", "bar"], 3).err(),
+    ///     Some(CombinatorialError::SizeTooLarge { size: 3, available: 2 })
+    /// );
+    /// ```
+    pub fn try_of_length(
+        elements: impl IntoIterator<Item = T>,
+        length: usize,
+    ) -> Result<Self, CombinatorialError> {
+        let perms = Permutations::of_length(elements, length);
+        check_size(length, perms.elements.len())?;
+        Ok(perms)
+    }
+
+    /// Creates a new `Permutations` iterator like [`Permutations::of_length`], but handles a
+    /// length larger than the number of unique elements as directed by the given [`OnOversize`]
+    /// policy: yielding nothing, yielding the permutations of every element, or returning an
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{CombinatorialError, OnOversize, Permutations};
+    ///
+    /// let perms = Permutations::of_length_with(1..4, 5, OnOversize::Clamp).unwrap();
+    /// assert_eq!(perms.count(), 6);
+    /// assert_eq!(Permutations::of_length_with(1..4, 5, OnOversize::Empty).unwrap().count(), 0);
+    /// assert_eq!(
+    ///     Permutations::of_length_with(1..4, 5, OnOversize::Error).err(),
+    ///     Some(CombinatorialError::SizeTooLarge { size: 5, available: 3 })
+    /// );
+    /// ```
+    pub fn of_length_with(
+        elements: impl IntoIterator<Item = T>,
+        length: usize,
+        on_oversize: OnOversize,
+    ) -> Result<Self, CombinatorialError> {
+        let elements = sorted_unique(elements);
+        let available = elements.len();
+        let length = on_oversize.resolve(length, available, available)?;
+        Ok(Permutations::of_length_unchecked(elements, length))
+    }
+
+    /// Restricts the iterator to permutations in which each of the given positions holds the
+    /// given element.  The pinned positions are never searched, so the remaining permutations are
+    /// generated directly rather than by filtering.
+    ///
+    /// If an element is not one of the elements being permuted, or the constraints pin two
+    /// different elements to the same position or the same element to two different positions,
+    /// then no permutations are yielded.  When generating permutations of several lengths, only
+    /// lengths long enough to include every pinned position are yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let mut perms = Permutations::of_length(1..5, 4).with_fixed(&[(0, 3), (2, 1)]);
+    /// assert_eq!(perms.next(), Some(vec![3, 2, 1, 4]));
+    /// assert_eq!(perms.next(), Some(vec![3, 4, 1, 2]));
+    /// assert_eq!(perms.next(), None);
+    ///
+    /// let perms = Permutations::all(1..4).with_fixed(&[(1, 2)]);
+    /// assert_eq!(perms.collect::<Vec<Vec<i32>>>(), vec![
+    ///     vec![1, 2],
+    ///     vec![3, 2],
+    ///     vec![1, 2, 3],
+    ///     vec![3, 2, 1],
+    /// ]);
+    ///
+    /// assert_eq!(Permutations::of_length(1..5, 4).with_fixed(&[(0, 1), (1, 1)]).next(), None);
+    /// assert_eq!(Permutations::of_length(1..5, 4).with_fixed(&[(0, 7)]).next(), None);
+    /// ```
+    pub fn with_fixed(mut self, positions: &[(usize, T)]) -> Self {
+        for (position, element) in positions {
+            let index = match self.elements.binary_search(element) {
+                Ok(index) => index,
+                Err(_) => {
+                    self.mark_infeasible();
+                    continue;
+                }
+            };
+            if self.pinned.len() <= *position {
+                self.pinned.resize(position + 1, None);
+            }
+            match self.pinned[*position] {
+                Some(existing) if existing == index => {}
+                None if !self.reserved[index] => {
+                    self.pinned[*position] = Some(index);
+                    self.reserved[index] = true;
+                }
+                _ => self.mark_infeasible(),
+            }
+        }
+        self
+    }
+
+    /// Restricts the iterator to permutations which begin with the given elements, by pinning
+    /// each of them to its position as by [`Permutations::with_fixed`], so only the choices of
+    /// the remaining positions are searched.  When generating permutations of several lengths,
+    /// only lengths at least as long as the prefix are yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let perms: Vec<Vec<i32>> = Permutations::of_length(1..5, 3).with_prefix(&[4, 2]).collect();
+    /// assert_eq!(perms, vec![vec![4, 2, 1], vec![4, 2, 3]]);
+    ///
+    /// assert_eq!(Permutations::all(1..5).with_prefix(&[3]).count(), 1 + 3 + 6 + 6);
+    /// assert_eq!(Permutations::all(1..5).with_prefix(&[3, 3]).next(), None);
+    /// ```
+    pub fn with_prefix(self, prefix: &[T]) -> Self {
+        let pinned: Vec<(usize, T)> = prefix.iter().cloned().enumerate().collect();
+        self.with_fixed(&pinned)
+    }
+
+    /// Restricts the iterator to permutations which satisfy the given [`Constraint`].  The
+    /// constraint is checked as each position is filled, so no permutation extending a partial
+    /// permutation which breaks it is ever searched.  This may be called several times to apply
+    /// several constraints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Constraint, Permutations};
+    ///
+    /// // Orderings of 1 to 6 in which consecutive numbers differ by at least two.
+    /// let spread = Constraint::Custom(|prefix: &[&i32]| match prefix {
+    ///     [.., previous, last] => previous.abs_diff(**last) >= 2,
+    ///     _ => true,
+    /// });
+    /// let spread = Permutations::of_length(1..=6, 6).with_constraint(spread);
+    /// assert_eq!(spread.count(), 90);
+    /// ```
+    pub fn with_constraint(mut self, constraint: Constraint<T>) -> Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Restricts the iterator to permutations of every element whose cycle type is the given
+    /// partition of the number of elements.  Each permutation is read as the map sending each
+    /// position `i` to the index of the element placed there, and is yielded only if the lengths
+    /// of the cycles of that map, including fixed points as cycles of length one, are exactly the
+    /// given lengths in some order.  The permutations with a given cycle type form a conjugacy
+    /// class of the symmetric group.
+    ///
+    /// The cycles are followed as each position is filled, so a partial permutation is abandoned
+    /// as soon as it closes a cycle of a length which is not wanted, or leaves a chain of
+    /// positions too long for any wanted cycle.  Permutations shorter than the number of
+    /// elements have no cycle type, so none of them are yielded, nor is anything yielded if the
+    /// lengths do not sum to the number of elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let perms = Permutations::of_length(0..4, 4).with_cycle_type(&[2, 2]);
+    /// let perms: Vec<Vec<usize>> = perms.collect();
+    /// assert_eq!(perms, vec![vec![1, 0, 3, 2], vec![2, 3, 0, 1], vec![3, 2, 1, 0]]);
+    ///
+    /// // The 3-cycles of five elements: 5! / (3 * 2!).
+    /// let three_cycles = Permutations::of_length(0..5, 5).with_cycle_type(&[1, 3, 1]);
+    /// assert_eq!(three_cycles.count(), 20);
+    /// assert_eq!(Permutations::of_length(0..5, 5).with_cycle_type(&[3, 3]).next(), None);
+    /// ```
+    pub fn with_cycle_type(mut self, cycle_type: &[usize]) -> Self {
+        let mut lengths = cycle_type.to_vec();
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        self.cycle_type = Some(lengths);
+        self
+    }
+
+    /// Moves the iterator to the first permutation which it yields from the beginning that is not
+    /// less than the given sequence, so that iteration begins there rather than at the first
+    /// permutation.  Permutations are compared in the order in which they are yielded: by length
+    /// first, when generating several lengths, and then lexicographically by their elements.  A
+    /// sequence shorter than the shortest permutations is compared with them as a prefix, coming
+    /// before every permutation which begins with it.  The sequence need not be a permutation
+    /// which the iterator yields, so work can be split between workers by value, such as giving
+    /// one worker the permutations from `['b', 'a']` onwards.  This should be called after any
+    /// constraints are added.
+    ///
+    /// The elements are compared with [`Ord`], so this requires that the iterator was created by
+    /// a constructor which sorts them in that order, such as [`Permutations::of_length`].  If the
+    /// pinned positions conflict, the iterator stays empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sequence places an element in a position pinned by
+    /// [`Permutations::with_fixed`] or [`Permutations::with_prefix`] other than the one pinned
+    /// there.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let mut perms = Permutations::of_length(1..5, 3).starting_from(&[2, 4, 1]);
+    /// assert_eq!(perms.next(), Some(vec![2, 4, 1]));
+    /// assert_eq!(perms.next(), Some(vec![2, 4, 3]));
+    /// assert_eq!(perms.next(), Some(vec![3, 1, 2]));
+    ///
+    /// // A sequence which is not itself a permutation starts from the next one after it.
+    /// let perms = Permutations::of_length("abcd".chars(), 4).starting_from(&['b', 'd', 'e']);
+    /// assert_eq!(perms.current(), Some(vec!['c', 'a', 'b', 'd']));
+    /// let perms = Permutations::of_length("abcd".chars(), 4).starting_from(&['b', 'd']);
+    /// assert_eq!(perms.current(), Some(vec!['b', 'd', 'a', 'c']));
+    ///
+    /// let perms = Permutations::all(1..4).starting_from(&[3, 2]);
+    /// assert_eq!(perms.count(), 1 + 6);
+    /// ```
+    pub fn starting_from(mut self, start: &[T]) -> Self {
+        if self.infeasible {
+            return self;
+        }
+        assert!(
+            start.iter().enumerate().all(|(position, element)| {
+                let pinned = self.pinned.get(position).copied().flatten();
+                pinned.is_none_or(|index| self.elements[index] == *element)
+            }),
+            "the starting sequence must agree with the pinned positions"
+        );
+        self.positions.clear();
+        self.used.iter_mut().for_each(|used| *used = false);
+        self.started = false;
+        self.done = false;
+        self.length = start.len().max(self.first_length);
+        if self.length > self.last_length {
+            self.done = true;
+            return self;
+        }
+        let mut found = self.pinned.len() <= self.length
+            && self.satisfies_constraints(&self.positions, self.length);
+        if found {
+            // Follow the sequence for as long as it can be matched exactly, then move on to the
+            // first valid element greater than the next one in the sequence.
+            found = 'search: {
+                for element in start {
+                    let bound = self.elements.partition_point(|other| other < element);
+                    let exact = self.elements.get(bound) == Some(element);
+                    if exact && self.next_candidate(bound.checked_sub(1)) == Some(bound) {
+                        self.place(bound);
+                        if self.satisfies_constraints(&self.positions, self.length) {
+                            continue;
+                        }
+                        self.unplace();
+                    }
+                    let previous = if exact {
+                        Some(bound)
+                    } else {
+                        bound.checked_sub(1)
+                    };
+                    break 'search match previous {
+                        Some(previous) => self.fill(Some(previous)),
+                        None => self.fill(None),
+                    };
+                }
+                // The whole sequence matched, and one shorter than the permutations is completed
+                // as a prefix.
+                self.fill(None)
+            };
+        }
+        while !found && self.length < self.last_length {
+            self.length += 1;
+            found = self.fill(None);
+        }
+        // The permutation found is completed again, and yielded, by the first call to `next`.
+        self.done = !found;
+        self
+    }
+
+    /// Restricts the iterator to the permutations which are not less than `start` and are less
+    /// than `end`, compared as by [`Permutations::starting_from`], and moves it to the first of
+    /// them.  Sequences shorter than the permutations are compared as prefixes, so the window from
+    /// `['b', 'a']` to `['c', 'a']` covers every permutation beginning with `b, a` up to, but not
+    /// including, those beginning with `c, a`.  Since the end is excluded, consecutive windows
+    /// split an enumeration between workers with no permutation handled twice.  This should be
+    /// called after any constraints are added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either sequence disagrees with the pinned positions, as by
+    /// [`Permutations::starting_from`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let window = Permutations::of_length("abc".chars(), 3).between(&['a', 'c'], &['c']);
+    /// let window: Vec<String> = window.map(|perm| perm.into_iter().collect()).collect();
+    /// assert_eq!(window, vec!["acb", "bac", "bca"]);
+    ///
+    /// // Split the permutations of four elements between three workers.
+    /// let bounds: [&[char]; 4] = [&[], &['b', 'c'], &['c', 'd', 'a'], &['e']];
+    /// let mut total = 0;
+    /// for worker in bounds.windows(2) {
+    ///     let perms = Permutations::of_length("abcd".chars(), 4).between(worker[0], worker[1]);
+    ///     total += perms.count();
+    /// }
+    /// assert_eq!(total, 24);
+    /// ```
+    pub fn between(mut self, start: &[T], end: &[T]) -> Self {
+        let stop = self.clone().starting_from(end);
+        self.end = (!stop.done).then_some(stop.positions);
+        self.starting_from(start)
+    }
+
+    /// Returns the permutation which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if none remain.  Since the next permutation is only found
+    /// when the iterator advances, this searches for it using a copy of the iterator, so it costs
+    /// about as much as a call to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let mut perms = Permutations::of_length(1..4, 3);
+    /// assert_eq!(perms.current(), Some(vec![1, 2, 3]));
+    /// assert_eq!(perms.next(), Some(vec![1, 2, 3]));
+    /// assert_eq!(perms.current(), Some(vec![1, 3, 2]));
+    /// ```
+    pub fn current(&self) -> Option<Vec<T>> {
+        self.clone().next()
+    }
+
+    /// Writes the next permutation into the given buffer, replacing its previous contents, and
+    /// advances the internal iterator.  Returns `false`, leaving the buffer untouched, if there
+    /// are no more permutations.
+    ///
+    /// Unlike [`next`](Iterator::next), this does not allocate a new vector for every
+    /// permutation, so reusing one buffer across calls avoids an allocation per item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let mut perms = Permutations::of_length(1..4, 3);
+    /// let mut buffer = Vec::new();
+    /// let mut count = 0;
+    /// while perms.next_into(&mut buffer) {
+    ///     assert_eq!(buffer.len(), 3);
+    ///     count += 1;
+    /// }
+    /// assert_eq!(count, 6);
+    /// assert_eq!(buffer, vec![3, 2, 1]);
+    /// ```
+    pub fn next_into(&mut self, buffer: &mut Vec<T>) -> bool {
+        if !self.advance() {
+            return false;
+        }
+        buffer.clear();
+        buffer.extend(self.positions.iter().map(|p| self.elements[*p].clone()));
+        true
+    }
+}
+
+impl<T> Permutations<T> {
+    /// Creates a new `Permutations` iterator which will yield all permutations of every length of
+    /// the elements in the given iterable, sorted and deduplicated using the given comparison
+    /// function instead of `Ord`, so that permutations are yielded in lexicographic order with
+    /// respect to it.  Elements which compare as equal are deduplicated, keeping the first of them
+    /// in the input.
+    ///
+    /// Since [`Permutations::with_fixed`] locates elements using `Ord`, it should not be combined
+    /// with a custom ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let perms: Vec<Vec<i32>> = Permutations::all_by(1..3, |a, b| b.cmp(a)).collect();
+    /// assert_eq!(perms, vec![vec![], vec![2], vec![1], vec![2, 1], vec![1, 2]]);
+    /// ```
+    pub fn all_by(
+        elements: impl IntoIterator<Item = T>,
+        compare: impl FnMut(&T, &T) -> Ordering,
+    ) -> Self {
+        Permutations::all_unchecked(sorted_unique_by(elements, compare))
+    }
+
+    /// Creates a new `Permutations` iterator which will yield all permutations with the specified
+    /// length of the elements in the given iterable, sorted and deduplicated using the given
+    /// comparison function instead of `Ord`.  See [`Permutations::all_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let words = vec!["b", "A", "a"];
+    /// let perms = Permutations::of_length_by(words, 2, |a, b| {
+    ///     a.to_lowercase().cmp(&b.to_lowercase())
+    /// });
+    /// assert_eq!(perms.collect::<Vec<Vec<&str>>>(), vec![vec!["A", "b"], vec!["b", "A"]]);
+    /// ```
+    pub fn of_length_by(
+        elements: impl IntoIterator<Item = T>,
+        length: usize,
+        compare: impl FnMut(&T, &T) -> Ordering,
+    ) -> Self {
+        Permutations::of_length_unchecked(sorted_unique_by(elements, compare), length)
+    }
+
+    /// Creates a new `Permutations` iterator which will yield all permutations of every length of
+    /// the elements in the given iterable, sorted and deduplicated by the keys extracted by the
+    /// given function, like [`slice::sort_by_key`].  See [`Permutations::all_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let perms: Vec<Vec<&str>> = Permutations::all_by_key(vec!["ccc", "a", "bb"], |s| s.len())
+    ///     .filter(|perm| perm.len() == 2)
+    ///     .collect();
+    /// assert_eq!(perms[0], vec!["a", "bb"]);
+    /// assert_eq!(perms.len(), 6);
+    /// ```
+    pub fn all_by_key<K: Ord>(
+        elements: impl IntoIterator<Item = T>,
+        mut key: impl FnMut(&T) -> K,
+    ) -> Self {
+        Permutations::all_by(elements, |a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Creates a new `Permutations` iterator which will yield all permutations with the specified
+    /// length of the elements in the given iterable, sorted and deduplicated by the keys
+    /// extracted by the given function.  See [`Permutations::all_by`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let mut perms = Permutations::of_length_by_key(vec![(2, 'x'), (1, 'y')], 2, |pair| pair.0);
+    /// assert_eq!(perms.next(), Some(vec![(1, 'y'), (2, 'x')]));
+    /// assert_eq!(perms.next(), Some(vec![(2, 'x'), (1, 'y')]));
+    /// assert_eq!(perms.next(), None);
+    /// ```
+    pub fn of_length_by_key<K: Ord>(
+        elements: impl IntoIterator<Item = T>,
+        length: usize,
+        mut key: impl FnMut(&T) -> K,
+    ) -> Self {
+        Permutations::of_length_by(elements, length, |a, b| key(a).cmp(&key(b)))
+    }
+
+    /// Creates a new `Permutations` iterator which will yield all permutations of every length of
+    /// the given elements, which are used exactly as given rather than being sorted and
+    /// deduplicated first.
+    ///
+    /// The permutations are generated over the positions of the elements, in lexicographic order
+    /// of those positions, so this is equivalent to [`Permutations::all`] if the elements are
+    /// sorted and unique.  Otherwise, repeated elements are treated as distinct, and the
+    /// constraints given to [`Permutations::with_fixed`], which locate elements by binary search,
+    /// may not be applied correctly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let perms: Vec<Vec<char>> = Permutations::all_unchecked(vec!['b', 'a']).collect();
+    /// assert_eq!(perms, vec![vec![], vec!['b'], vec!['a'], vec!['b', 'a'], vec!['a', 'b']]);
+    /// ```
+    pub fn all_unchecked(elements: Vec<T>) -> Self {
+        let count = elements.len();
+        Permutations::new(elements, 0, count)
+    }
+
+    /// Creates a new `Permutations` iterator which will yield all permutations with the specified
+    /// length of the given elements, which are used exactly as given rather than being sorted and
+    /// deduplicated first.  See [`Permutations::all_unchecked`] for the consequences if the
+    /// elements are not sorted and unique.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let perms: Vec<Vec<i32>> = Permutations::of_length_unchecked(vec![1, 2, 3], 3).collect();
+    /// assert_eq!(perms, Permutations::of_length(vec![3, 1, 2, 1], 3).collect::<Vec<_>>());
+    /// ```
+    pub fn of_length_unchecked(elements: Vec<T>, length: usize) -> Self {
+        Permutations::new(elements, length, length)
+    }
+
+    /// Creates a new `Permutations` iterator over the given sorted, deduplicated elements, which
+    /// will yield permutations with lengths from `first_length` through `last_length`.
+    fn new(elements: Vec<T>, first_length: usize, last_length: usize) -> Self {
+        let count = elements.len();
+        Permutations {
+            elements,
+            pinned: Vec::new(),
+            forbidden: BTreeSet::new(),
+            reserved: vec![false; count],
+            constraints: Constraints::new(),
+            cycle_type: None,
+            end: None,
+            positions: Vec::with_capacity(last_length.min(count)),
+            used: vec![false; count],
+            length: first_length,
+            first_length,
+            last_length,
+            started: false,
+            done: first_length > last_length || first_length > count,
+            infeasible: false,
+        }
+    }
+
+    /// Restricts the iterator to permutations which never place the element with index `j` in
+    /// position `i` for any forbidden pair `(i, j)`, where elements are indexed by their position
+    /// in the sorted set of elements.  Forbidden placements are rejected as soon as they are
+    /// considered, so no permutation extending them is ever searched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// // Derangements, in which no element remains in its original position.
+    /// let derangements = Permutations::of_length(0..3, 3).avoiding(&[(0, 0), (1, 1), (2, 2)]);
+    /// assert_eq!(derangements.collect::<Vec<Vec<usize>>>(), vec![vec![1, 2, 0], vec![2, 0, 1]]);
+    ///
+    /// // The problème des ménages: seatings of five husbands in the gaps between their wives
+    /// // seated around a table, so that no husband sits next to his wife.
+    /// let forbidden: Vec<(usize, usize)> = (0..5).flat_map(|i| [(i, i), (i, (i + 1) % 5)]).collect();
+    /// assert_eq!(Permutations::of_length(0..5, 5).avoiding(&forbidden).count(), 13);
+    /// ```
+    pub fn avoiding(mut self, forbidden: &[(usize, usize)]) -> Self {
+        self.forbidden.extend(forbidden.iter().copied());
+        self
+    }
+
+    /// Returns the sorted, deduplicated elements over which this iterator generates
+    /// permutations.  The indices of the elements in this slice are those used by
+    /// [`Permutations::avoiding`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let perms = Permutations::of_length(vec!['b', 'c', 'a', 'b'], 2);
+    /// assert_eq!(perms.elements(), &['a', 'b', 'c']);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Consumes the iterator and returns its elements, as given by [`Permutations::elements`],
+    /// along with the indices of the elements of the next permutation which would have been
+    /// yielded, or `None` if no permutations remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let mut perms = Permutations::of_length(vec!['c', 'b', 'a'], 3);
+    /// assert_eq!(perms.next(), Some(vec!['a', 'b', 'c']));
+    /// assert_eq!(perms.into_parts(), (vec!['a', 'b', 'c'], Some(vec![0, 2, 1])));
+    ///
+    /// assert_eq!(Permutations::of_length(0..2, 3).into_parts(), (vec![0, 1], None));
+    /// ```
+    pub fn into_parts(mut self) -> (Vec<T>, Option<Vec<usize>>) {
+        let next = self.advance().then_some(self.positions);
+        (self.elements, next)
+    }
+
+    /// Converts the iterator into one which collects each permutation directly into a container
+    /// of type `C`, such as a `String`, instead of a `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let words: Vec<String> = Permutations::of_length("abc".chars(), 2).collecting().collect();
+    /// assert_eq!(words, vec!["ab", "ac", "ba", "bc", "ca", "cb"]);
+    /// ```
+    pub fn collecting<C: FromIterator<T>>(self) -> Collecting<Self, C> {
+        Collecting::new(self)
+    }
+
+    /// Converts the iterator into one which yields each permutation along with the value of the
+    /// given statistic, computed from the indices of its elements in
+    /// [`Permutations::elements`].  The statistic is updated from the previous permutation
+    /// rather than computed from scratch for each.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{PermutationStatistic, Permutations};
+    ///
+    /// // The major index and the number of inversions are equidistributed.
+    /// let distribution = |statistic| {
+    ///     let mut counts = [0; 11];
+    ///     for (_, value) in Permutations::of_length(0..5, 5).with_statistic(statistic) {
+    ///         counts[value] += 1;
+    ///     }
+    ///     counts
+    /// };
+    /// let by_major = distribution(PermutationStatistic::MajorIndex);
+    /// assert_eq!(by_major, distribution(PermutationStatistic::Inversions));
+    /// assert_eq!(by_major, [1, 4, 9, 15, 20, 22, 20, 15, 9, 4, 1]);
+    /// ```
+    pub fn with_statistic(self, statistic: PermutationStatistic) -> WithStatistic<T> {
+        WithStatistic::new(self, statistic)
+    }
+
+    /// Converts the iterator into one which yields every permutation exactly once, beginning
+    /// again from the first permutation, but in a pseudo-random order determined by the seed.
+    /// Nothing is yielded if the number of permutations, ignoring any pinned or forbidden
+    /// positions, does not fit in a `u128`.
+    ///
+    /// Pinned and forbidden positions are respected by skipping the permutations which break
+    /// them, so each step may take time proportional to the number of permutations skipped.  See
+    /// [`Shuffled`] for how the order is chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let mut shuffled: Vec<Vec<char>> = Permutations::of_length("abc".chars(), 3)
+    ///     .shuffled(3)
+    ///     .collect();
+    /// assert_eq!(shuffled.len(), 6);
+    /// shuffled.sort();
+    /// assert_eq!(shuffled, Permutations::of_length("abc".chars(), 3).collect::<Vec<_>>());
+    ///
+    /// let diagonal = [(0, 0), (1, 1), (2, 2), (3, 3)];
+    /// let derangements = Permutations::of_length(0..4, 4).avoiding(&diagonal);
+    /// assert_eq!(derangements.shuffled(3).count(), 9);
+    /// ```
+    pub fn shuffled(self, seed: u64) -> Shuffled<Self> {
+        let total = match self.infeasible {
+            true => Some(0),
+            false => self.unconstrained_count_from(self.first_length),
+        };
+        Shuffled::new(self, total, seed)
+    }
+
+    /// Records that the pinned positions conflict, so that nothing is yielded or counted.
+    fn mark_infeasible(&mut self) {
+        self.infeasible = true;
+        self.done = true;
+    }
+
+    /// Returns `true` if the element with the given index may be placed in the given position.
+    fn is_allowed(&self, position: usize, index: usize) -> bool {
+        !self.forbidden.contains(&(position, index))
+    }
+
+    /// Returns `true` if any positions are pinned or forbidden, any constraints or cycle type are
+    /// given, or iteration stops early, so that some permutations may be skipped.
+    fn is_constrained(&self) -> bool {
+        !self.pinned.is_empty()
+            || !self.forbidden.is_empty()
+            || !self.constraints.is_empty()
+            || self.cycle_type.is_some()
+            || self.end.is_some()
+    }
+
+    /// Returns `true` if the partial permutation with the given indices may still be completed
+    /// to a permutation of the given length which satisfies every constraint and has the
+    /// required cycle type, if any.
+    fn satisfies_constraints(&self, positions: &[usize], length: usize) -> bool {
+        if !self.may_have_cycle_type(positions, length) {
+            return false;
+        }
+        if self.constraints.is_empty() {
+            return true;
+        }
+        let prefix: Vec<&T> = positions.iter().map(|p| &self.elements[*p]).collect();
+        let rest = std::iter::repeat_n(self.elements.as_slice(), length - positions.len());
+        self.constraints.allow(&prefix, rest)
+    }
+
+    /// Returns `true` if the partial permutation with the given indices may still be completed
+    /// to a permutation of the given length with the required cycle type, if any.
+    ///
+    /// The partial map from positions to indices splits into closed cycles and open chains, and
+    /// any completion only joins the chains into further cycles.  So the closed cycles must have
+    /// wanted lengths, and the remaining wanted lengths must be no more numerous than the chains
+    /// and at least as long as the longest chain.
+    fn may_have_cycle_type(&self, positions: &[usize], length: usize) -> bool {
+        let Some(cycle_type) = &self.cycle_type else {
+            return true;
+        };
+        let n = self.elements.len();
+        if length != n || cycle_type.iter().sum::<usize>() != n {
+            return false;
+        }
+        let mut remaining = cycle_type.clone();
+        let mut seen = vec![false; n];
+        let mut hit = vec![false; n];
+        for index in positions {
+            hit[*index] = true;
+        }
+        // Every point which is not the image of another begins a chain.
+        let mut chains = 0;
+        let mut longest_chain = 0;
+        for start in (0..n).filter(|point| !hit[*point]) {
+            let mut point = start;
+            let mut chain = 1;
+            seen[point] = true;
+            while let Some(next) = positions.get(point) {
+                point = *next;
+                seen[point] = true;
+                chain += 1;
+            }
+            chains += 1;
+            longest_chain = longest_chain.max(chain);
+        }
+        // The other points lie on closed cycles.
+        for start in 0..n {
+            if seen[start] {
+                continue;
+            }
+            let mut point = start;
+            let mut cycle = 0;
+            while !seen[point] {
+                seen[point] = true;
+                point = positions[point];
+                cycle += 1;
+            }
+            match remaining.iter().position(|wanted| *wanted == cycle) {
+                Some(index) => remaining.remove(index),
+                None => return false,
+            };
+        }
+        remaining.len() <= chains && remaining.first().is_none_or(|l| *l >= longest_chain)
+    }
+
+    /// Returns the total number of permutations which this iterator yields from the beginning,
+    /// regardless of how far it has advanced, or `None` if the count does not fit in a `u128`.
+    /// Also returns `None` if any positions are pinned or forbidden, or any constraints or cycle
+    /// type are given, since the count is then not known without generating the permutations,
+    /// unless the pinned positions conflict, in which case the count is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// assert_eq!(Permutations::of_length(0..10, 3).total_count(), Some(720));
+    /// assert_eq!(Permutations::all(0..3).total_count(), Some(16));
+    /// assert_eq!(Permutations::of_length(0..3, 3).with_fixed(&[(0, 1)]).total_count(), None);
+    /// assert_eq!(Permutations::of_length(0..3, 3).with_fixed(&[(0, 7)]).total_count(), Some(0));
+    /// ```
+    pub fn total_count(&self) -> Option<u128> {
+        if self.infeasible {
+            return Some(0);
+        }
+        if self.is_constrained() {
+            return None;
+        }
+        self.unconstrained_count_from(self.first_length)
+    }
+
+    /// Returns an estimate of the work needed to enumerate every permutation which this iterator
+    /// yields from the beginning, from which the time the enumeration would take can be checked
+    /// before starting it.  If any positions are pinned or forbidden, or any constraints are
+    /// given, the number of items is not known, and the estimate is an upper bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let work = Permutations::of_length(0..20, 20).estimated_work();
+    /// assert_eq!(work.items(), Some(2432902008176640000));
+    /// assert!(work.eta(1e9).unwrap().as_secs() > 75 * 365 * 86400);
+    /// ```
+    pub fn estimated_work(&self) -> EstimatedWork {
+        let n = self.elements.len();
+        let lengths = self.first_length..=self.last_length.min(n);
+        let log_items = log_sum(lengths.map(|k| log_factorial(n) - log_factorial(n - k)));
+        EstimatedWork::of_vecs::<T>(self.total_count(), log_items, self.last_length)
+    }
+
+    /// Returns the total number of permutations which this iterator yields from the beginning, as
+    /// by [`Permutations::total_count`], but as a big integer, so the count is exact however large
+    /// it is.  Returns `None` if any positions are pinned or forbidden, or any constraints are
+    /// given, unless the pinned positions conflict.  This is only available with the `bigint`
+    /// feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{factorial_big, Permutations};
+    ///
+    /// let perms = Permutations::of_length(0..50, 50);
+    /// assert_eq!(perms.total_count(), None);
+    /// assert_eq!(perms.total_count_big(), Some(factorial_big(50)));
+    /// ```
+    #[cfg(feature = "bigint")]
+    pub fn total_count_big(&self) -> Option<num_bigint::BigUint> {
+        if self.infeasible {
+            return Some(num_bigint::BigUint::default());
+        }
+        if self.is_constrained() {
+            return None;
+        }
+        let n = self.elements.len();
+        let lengths = self.first_length..=self.last_length;
+        Some(
+            lengths
+                .map(|k| crate::big::permutation_count_big(n, k))
+                .sum(),
+        )
+    }
+
+    /// Returns the number of permutations with lengths from `length` up to the last length which
+    /// the iterator yields, ignoring any pinned or forbidden positions.
+    fn unconstrained_count_from(&self, length: usize) -> Option<u128> {
+        let n = self.elements.len();
+        (length..=self.last_length).try_fold(0u128, |total, k| {
+            total.checked_add(permutation_count(n, k)?)
+        })
+    }
+
+    /// Returns the indices of the elements of the permutation with the given rank, counting from
+    /// zero in lexicographic order among every permutation which the iterator yields from the
+    /// beginning when nothing is pinned or forbidden, or `None` if that permutation breaks a pinned
+    /// or forbidden position or a constraint.  The rank must be less than the unconstrained total
+    /// count.
+    fn positions_at_rank(&self, mut rank: u128) -> Option<Vec<usize>> {
+        let n = self.elements.len();
+        let mut length = self.first_length;
+        while let Some(count) = permutation_count(n, length).filter(|count| rank >= *count) {
+            rank -= count;
+            length += 1;
+        }
+        if self.pinned.len() > length || !self.satisfies_constraints(&[], length) {
+            return None;
+        }
+        let mut available: Vec<usize> = (0..n).collect();
+        let mut positions = Vec::with_capacity(length);
+        for position in 0..length {
+            let block = permutation_count(n - 1 - position, length - 1 - position).unwrap();
+            let index = available.remove((rank / block) as usize);
+            rank %= block;
+            let valid = match self.pinned.get(position).copied().flatten() {
+                Some(pinned) => index == pinned,
+                None => !self.reserved[index],
+            };
+            if !valid || !self.is_allowed(position, index) {
+                return None;
+            }
+            positions.push(index);
+            if !self.satisfies_constraints(&positions, length) {
+                return None;
+            }
+        }
+        Some(positions)
+    }
+
+    /// Returns the number of permutations which have not yet been yielded, ignoring any pinned or
+    /// forbidden positions, or `None` if the count does not fit in a `u128`.  This is exact if
+    /// there are no constraints, and an upper bound otherwise.
+    fn unconstrained_remaining_count(&self) -> Option<u128> {
+        if self.done {
+            return Some(0);
+        }
+        if !self.started {
+            return self.unconstrained_count_from(self.length);
+        }
+        let n = self.elements.len();
+        let k = self.length;
+        let mut used = vec![false; n];
+        let mut through = 1;
+        for (index, position) in self.positions.iter().enumerate() {
+            let smaller_unused = used[..*position].iter().filter(|used| !**used).count() as u128;
+            through += smaller_unused * permutation_count(n - 1 - index, k - 1 - index)?;
+            used[*position] = true;
+        }
+        (permutation_count(n, k)? - through).checked_add(self.unconstrained_count_from(k + 1)?)
+    }
+
+    /// Moves the internal positions to the next permutation, moving on to the next length if
+    /// generating permutations of several lengths.  Returns `false` and marks the iterator as done
+    /// if no permutations remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let mut found = if self.started {
+            match self.unplace() {
+                Some(index) => self.fill(Some(index)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.fill(None)
+        };
+        while !found && self.length < self.last_length {
+            self.length += 1;
+            found = self.fill(None);
+        }
+        if let Some(end) = &self.end {
+            found &= (self.positions.len(), &self.positions) < (end.len(), end);
+        }
+        if !found {
+            self.done = true;
+        }
+        found
+    }
+
+    /// Places the element with the given index in the next position.
+    fn place(&mut self, index: usize) {
+        self.used[index] = true;
+        self.positions.push(index);
+    }
+
+    /// Removes and returns the index of the element in the last filled position, if any.
+    fn unplace(&mut self) -> Option<usize> {
+        let index = self.positions.pop()?;
+        self.used[index] = false;
+        Some(index)
+    }
+
+    /// Returns the index of the first element which may be placed in the next position, skipping
+    /// every element with index not greater than `previous`, if given.
+    fn next_candidate(&self, previous: Option<usize>) -> Option<usize> {
+        let position = self.positions.len();
+        if let Some(index) = self.pinned.get(position).copied().flatten() {
+            let after_previous = previous.is_none_or(|previous| index > previous);
+            return (after_previous && self.is_allowed(position, index)).then_some(index);
+        }
+        let start = previous.map_or(0, |index| index + 1);
+        (start..self.elements.len()).find(|index| {
+            !self.used[*index] && !self.reserved[*index] && self.is_allowed(position, *index)
+        })
+    }
+
+    /// Completes the permutation with the lexicographically smallest valid elements, backtracking
+    /// as necessary.  If `previous` is given, the search resumes by trying elements after
+    /// `previous` in the next position.  Returns `true` if the permutation was completed, or
+    /// `false` if no further permutations of the current length exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        if self.pinned.len() > self.length {
+            return false;
+        }
+        if previous.is_none() && !self.satisfies_constraints(&self.positions, self.length) {
+            return false;
+        }
+        loop {
+            if previous.is_none() && self.positions.len() == self.length {
+                return true;
+            }
+            match self.next_candidate(previous) {
+                Some(index) => {
+                    self.place(index);
+                    previous = None;
+                    if !self.satisfies_constraints(&self.positions, self.length) {
+                        previous = self.unplace();
+                    }
+                }
+                None => match self.unplace() {
+                    Some(index) => previous = Some(index),
+                    None => return false,
+                },
+            }
+        }
+    }
+}
+
+impl<'a, T> Permutations<&'a T> {
+    /// Creates a new `Permutations` iterator which will yield every full-length permutation of
+    /// references to the elements of the given slice, so elements are never cloned and need not
+    /// implement `Clone` or `Ord`.
+    ///
+    /// The elements are permuted by their positions in the slice, so the permutations are yielded
+    /// in lexicographic order of those positions, and equal elements are treated as distinct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Big([u64; 64]);
+    ///
+    /// let items = [Big([1; 64]), Big([2; 64]), Big([3; 64])];
+    /// let mut perms = Permutations::of_slice(&items);
+    /// assert_eq!(perms.next(), Some(vec![&items[0], &items[1], &items[2]]));
+    /// assert_eq!(perms.next(), Some(vec![&items[0], &items[2], &items[1]]));
+    /// assert_eq!(perms.count(), 4);
+    /// ```
+    pub fn of_slice(elements: &'a [T]) -> Self {
+        Permutations::of_length_unchecked(elements.iter().collect(), elements.len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Permutations<T> {
+    /// Saves the progress of the iterator to a checkpoint file at the given path, so that a
+    /// long-running enumeration can later be continued with [`Permutations::resume_from`].  The
+    /// elements and constraints themselves are not saved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let path = std::env::temp_dir().join("combinatorial-permutations-save.ckpt");
+    /// let mut perms = Permutations::of_length(0..6, 4);
+    /// perms.nth(100);
+    /// perms.save_to(&path).unwrap();
+    ///
+    /// let mut resumed = Permutations::of_length(0..6, 4).resume_from(&path).unwrap();
+    /// assert_eq!(resumed.next(), perms.next());
+    /// assert_eq!(resumed.count(), 360 - 102);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn save_to(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        Checkpoint {
+            kind: Kind::Permutations,
+            started: self.started,
+            done: self.done,
+            elements: self.elements.len(),
+            length: self.length,
+            positions: self.positions.clone(),
+        }
+        .save(path.as_ref())
+    }
+
+    /// Restores the progress saved by [`Permutations::save_to`] to this iterator, which must have
+    /// been created with the same elements, constraints, and options as the one which saved it.
+    /// Returns an error if the file cannot be read, or if it was not saved by a compatible
+    /// iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let path = std::env::temp_dir().join("combinatorial-permutations-resume.ckpt");
+    /// let mut perms = Permutations::all(0..4);
+    /// perms.nth(20);
+    /// perms.save_to(&path).unwrap();
+    ///
+    /// assert!(Permutations::of_length(0..4, 2).resume_from(&path).is_err());
+    /// let resumed = Permutations::all(0..4).resume_from(&path).unwrap();
+    /// assert_eq!(resumed.collect::<Vec<_>>(), perms.collect::<Vec<_>>());
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn resume_from(mut self, path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let checkpoint = Checkpoint::load(path.as_ref(), Kind::Permutations, self.elements.len())?;
+        let mut used = vec![false; self.elements.len()];
+        for index in &checkpoint.positions {
+            match used.get_mut(*index) {
+                Some(slot @ false) => *slot = true,
+                _ => return Err(invalid("checkpoint does not match the permutations")),
+            }
+        }
+        if checkpoint.positions.len() > checkpoint.length
+            || checkpoint.length > self.elements.len()
+            || !(self.first_length..=self.last_length).contains(&checkpoint.length)
+        {
+            return Err(invalid("checkpoint does not match the permutations"));
+        }
+        self.positions = checkpoint.positions;
+        self.used = used;
+        self.length = checkpoint.length;
+        self.started = checkpoint.started;
+        self.done = checkpoint.done || self.infeasible;
+        Ok(self)
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next permutation and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        Some(
+            self.positions
+                .iter()
+                .map(|p| self.elements[*p].clone())
+                .collect(),
+        )
+    }
+
+    /// Returns the exact number of remaining permutations, if it fits in a `usize`.  If any
+    /// positions are pinned or forbidden, or any constraints are given, returns only an upper
+    /// bound.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let bound = size_hint_from(self.unconstrained_remaining_count());
+        if !self.is_constrained() {
+            bound
+        } else {
+            (0, bound.1)
+        }
+    }
+}
+
+impl<T: Clone> RankedIterator for Permutations<T> {
+    /// Returns the total number of permutations, as by [`Permutations::total_count`], so it is
+    /// `None` if any positions are pinned or forbidden or any constraints are given.
+    fn total_count(&self) -> Option<u128> {
+        Permutations::total_count(self)
+    }
+
+    /// Returns the permutation with the given rank in lexicographic order, which is the order in
+    /// which they are yielded.  Returns `None` if the total count is not known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Permutations, RankedIterator};
+    ///
+    /// let perms = Permutations::of_length(0..4, 2);
+    /// assert_eq!(perms.unrank(0), Some(vec![0, 1]));
+    /// assert_eq!(perms.unrank(11), Some(vec![3, 2]));
+    /// assert_eq!(perms.unrank(12), None);
+    /// assert_eq!(Permutations::all(0..3).unrank(4), Some(vec![0, 1]));
+    /// assert_eq!(perms.with_fixed(&[(0, 1)]).unrank(0), None);
+    /// ```
+    fn unrank(&self, rank: u128) -> Option<Self::Item> {
+        if rank >= Permutations::total_count(self)? {
+            return None;
+        }
+        let positions = self.positions_at_rank(rank)?;
+        Some(
+            positions
+                .iter()
+                .map(|p| self.elements[*p].clone())
+                .collect(),
+        )
+    }
+}
+
+impl<T: Clone> Iterator for Shuffled<Permutations<T>> {
+    type Item = Vec<T>;
+
+    /// Returns the next permutation in the shuffled order and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rank = self.next_rank()?;
+            if let Some(positions) = self.inner.positions_at_rank(rank) {
+                let elements = &self.inner.elements;
+                return Some(positions.iter().map(|p| elements[*p].clone()).collect());
+            }
+        }
+    }
+
+    /// Returns the exact number of remaining permutations, if it fits in a `usize`.  If any
+    /// positions are pinned or forbidden, or any constraints are given, returns only an upper
+    /// bound.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let bound = size_hint_from(Some(self.remaining()));
+        if !self.inner.is_constrained() {
+            bound
+        } else {
+            (0, bound.1)
+        }
+    }
+}
+
+impl<T: Clone, C: FromIterator<T>> Iterator for Collecting<Permutations<T>, C> {
+    type Item = C;
+
+    /// Returns the next permutation and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.inner.advance() {
+            return None;
+        }
+        let Permutations {
+            elements,
+            positions,
+            ..
+        } = &self.inner;
+        Some(positions.iter().map(|p| elements[*p].clone()).collect())
+    }
+
+    /// Returns the exact number of remaining permutations, if it fits in a `usize`, or an upper
+    /// bound if any positions are pinned or forbidden, or any constraints are given.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T: Clone> Iterator for WithStatistic<T> {
+    type Item = (Vec<T>, usize);
+
+    /// Returns the next permutation and its statistic and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.permutations.advance() {
+            return None;
+        }
+        let positions = std::mem::take(&mut self.permutations.positions);
+        let value = self.statistic_of(&positions);
+        let Permutations { elements, .. } = &self.permutations;
+        let permutation = positions.iter().map(|p| elements[*p].clone()).collect();
+        self.permutations.positions = positions;
+        Some((permutation, value))
+    }
+
+    /// Returns the exact number of remaining permutations, if it fits in a `usize`, or an upper
+    /// bound if any positions are pinned or forbidden, or any constraints are given.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.permutations.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factorial;
+
+    #[test]
+    fn test_permutations_counts() {
+        for n in 0..7 {
+            assert_eq!(Permutations::of_length(0..n, n).count(), factorial(n));
+            for length in 0..=n + 1 {
+                let expected = if length > n {
+                    0
+                } else {
+                    factorial(n) / factorial(n - length)
+                };
+                assert_eq!(Permutations::of_length(0..n, length).count(), expected);
+            }
+        }
+        let perms: Vec<Vec<usize>> = Permutations::of_length(0..5, 5).collect();
+        let mut sorted = perms.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(perms, sorted);
+    }
+
+    #[test]
+    fn test_permutations_of_length_range_matches_lengths() {
+        for n in 0..5 {
+            for first in 0..=n + 1 {
+                for last in 0..=n + 1 {
+                    let expected: Vec<Vec<usize>> = (first..=last)
+                        .flat_map(|length| Permutations::of_length(0..n, length))
+                        .collect();
+                    let perms = Permutations::of_length_range(0..n, first..=last);
+                    let total = perms.total_count();
+                    assert_eq!(total, Some(expected.len() as u128));
+                    assert_eq!(perms.size_hint(), (expected.len(), Some(expected.len())));
+                    let mut shuffled: Vec<Vec<usize>> = perms.clone().shuffled(7).collect();
+                    let mut sorted = expected.clone();
+                    shuffled.sort();
+                    sorted.sort();
+                    assert_eq!(shuffled, sorted);
+                    assert_eq!(perms.collect::<Vec<_>>(), expected);
+                }
+            }
+            let all: Vec<Vec<usize>> = Permutations::all(0..n).collect();
+            assert_eq!(
+                Permutations::of_length_range(0..n, ..).collect::<Vec<_>>(),
+                all
+            );
+        }
+        assert_eq!(Permutations::of_length_range(0..3, ..0).next(), None);
+        let repeated = Permutations::of_length_range(vec![1, 1, 2], 1..);
+        assert_eq!(repeated.count(), 2 + 2);
+    }
+
+    #[test]
+    fn test_permutations_with_fixed_matches_filter() {
+        let pins = [(1, 4), (3, 0), (4, 2)];
+        for length in 0..=6 {
+            let filtered: Vec<Vec<usize>> = Permutations::of_length(0..6, length)
+                .filter(|perm| pins.iter().all(|(p, e)| perm.get(*p) == Some(e)))
+                .collect();
+            let pinned: Vec<Vec<usize>> = Permutations::of_length(0..6, length)
+                .with_fixed(&pins)
+                .collect();
+            assert_eq!(pinned, filtered);
+        }
+        let filtered: Vec<Vec<usize>> = Permutations::all(0..6)
+            .filter(|perm| pins.iter().all(|(p, e)| perm.get(*p) == Some(e)))
+            .collect();
+        let pinned: Vec<Vec<usize>> = Permutations::all(0..6).with_fixed(&pins).collect();
+        assert_eq!(pinned, filtered);
+        let repeated: Vec<Vec<usize>> = Permutations::of_length(0..3, 3)
+            .with_fixed(&[(0, 2), (0, 2)])
+            .collect();
+        assert_eq!(repeated, vec![vec![2, 0, 1], vec![2, 1, 0]]);
+    }
+
+    #[test]
+    fn test_permutations_infeasible_pins() {
+        let infeasible = [
+            Permutations::of_length(1..5, 4).with_fixed(&[(0, 7)]),
+            Permutations::of_length(1..5, 4).with_fixed(&[(0, 1), (1, 1)]),
+            Permutations::of_length(1..5, 4).with_fixed(&[(0, 1), (0, 2)]),
+            Permutations::all(1..5).with_prefix(&[3, 3]),
+        ];
+        for perms in infeasible {
+            assert_eq!(perms.clone().count(), 0);
+            assert_eq!(perms.size_hint(), (0, Some(0)));
+            assert_eq!(perms.total_count(), Some(0));
+            #[cfg(feature = "bigint")]
+            assert_eq!(
+                perms.total_count_big(),
+                Some(num_bigint::BigUint::default())
+            );
+            assert_eq!(perms.clone().shuffled(1).next(), None);
+            assert_eq!(perms.clone().starting_from(&[1]).next(), None);
+            assert_eq!(perms.clone().between(&[1], &[4]).next(), None);
+        }
+        let pinned = Permutations::of_length(1..5, 4).with_fixed(&[(1, 3)]);
+        assert_eq!(pinned.clone().starting_from(&[2, 3]).count(), 4);
+        let disagreeing = std::panic::catch_unwind(|| pinned.starting_from(&[2, 1]));
+        assert!(disagreeing.is_err());
+    }
+
+    #[test]
+    fn test_permutations_size_hint() {
+        for n in 0..5 {
+            let mut iterators = vec![Permutations::all(0..n)];
+            for length in 0..=n + 1 {
+                iterators.push(Permutations::of_length(0..n, length));
+            }
+            for mut perms in iterators {
+                let total = perms.total_count().unwrap() as usize;
+                assert_eq!(perms.size_hint(), (total, Some(total)));
+                let mut remaining = total;
+                while perms.next().is_some() {
+                    remaining -= 1;
+                    assert_eq!(perms.size_hint(), (remaining, Some(remaining)));
+                }
+                assert_eq!(remaining, 0);
+            }
+        }
+        let mut pinned = Permutations::of_length(0..4, 4).with_fixed(&[(0, 2)]);
+        assert_eq!(pinned.size_hint(), (0, Some(24)));
+        pinned.next();
+        assert_eq!(pinned.size_hint(), (0, Some(11)));
+    }
+
+    #[test]
+    fn test_permutations_by_key_matches_mapped_order() {
+        for length in 0..5 {
+            let expected: Vec<Vec<i32>> = Permutations::of_length(0..4, length)
+                .map(|perm| perm.into_iter().map(|x| 3 - x).collect())
+                .collect();
+            let perms = Permutations::of_length_by_key(vec![1, 3, 0, 2, 3], length, |x| -x);
+            assert_eq!(perms.collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_permutations_current_matches_next() {
+        let mut perms = Permutations::all(0..4).with_fixed(&[(1, 2)]);
+        loop {
+            let current = perms.current();
+            assert_eq!(current, perms.next());
+            if current.is_none() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_permutations_of_slice_matches_indices() {
+        let elements = ['c', 'a', 'c', 'b'];
+        let expected: Vec<Vec<&char>> = Permutations::of_length(0..4, 4)
+            .map(|indices| indices.iter().map(|i| &elements[*i]).collect())
+            .collect();
+        let perms: Vec<Vec<&char>> = Permutations::of_slice(&elements).collect();
+        assert_eq!(perms, expected);
+        assert_eq!(Permutations::of_slice(&[] as &[char]).count(), 1);
+    }
+
+    #[test]
+    fn test_permutations_next_into_matches_next() {
+        let mut perms = Permutations::all(0..5).with_fixed(&[(1, 3)]);
+        let mut buffer = vec![9, 9, 9, 9, 9, 9, 9];
+        let mut collected = Vec::new();
+        while perms.next_into(&mut buffer) {
+            collected.push(buffer.clone());
+        }
+        let expected: Vec<Vec<usize>> = Permutations::all(0..5).with_fixed(&[(1, 3)]).collect();
+        assert_eq!(collected, expected);
+        assert_eq!(perms.next(), None);
+    }
+
+    #[test]
+    fn test_permutations_shuffled_visits_each_once() {
+        for n in 0..6 {
+            for length in 0..=n + 1 {
+                let lex: Vec<Vec<usize>> = Permutations::of_length(0..n, length).collect();
+                let mut perms: Vec<Vec<usize>> = Permutations::of_length(0..n, length)
+                    .shuffled(length as u64)
+                    .collect();
+                perms.sort();
+                assert_eq!(perms, lex);
+            }
+            let mut lex: Vec<Vec<usize>> = Permutations::all(0..n).collect();
+            let mut perms: Vec<Vec<usize>> = Permutations::all(0..n).shuffled(1).collect();
+            lex.sort();
+            perms.sort();
+            assert_eq!(perms, lex);
+        }
+        let constrained = || {
+            Permutations::of_length(0..5, 4)
+                .with_fixed(&[(1, 3)])
+                .avoiding(&[(0, 0), (2, 4)])
+        };
+        let lex: Vec<Vec<usize>> = constrained().collect();
+        let mut perms: Vec<Vec<usize>> = constrained().shuffled(2).collect();
+        assert_ne!(perms, lex);
+        perms.sort();
+        assert_eq!(perms, lex);
+    }
+
+    #[test]
+    fn test_permutations_avoiding_matches_filter() {
+        let forbidden = [(0, 0), (0, 3), (1, 1), (2, 0), (2, 4), (4, 4), (5, 2)];
+        for length in 0..=6 {
+            let filtered: Vec<Vec<usize>> = Permutations::of_length(0..6, length)
+                .filter(|perm| {
+                    perm.iter()
+                        .enumerate()
+                        .all(|(i, j)| !forbidden.contains(&(i, *j)))
+                })
+                .collect();
+            let avoiding: Vec<Vec<usize>> = Permutations::of_length(0..6, length)
+                .avoiding(&forbidden)
+                .collect();
+            assert_eq!(avoiding, filtered);
+        }
+        let derangement_counts: Vec<usize> = (0..8)
+            .map(|n| {
+                let diagonal: Vec<(usize, usize)> = (0..n).map(|i| (i, i)).collect();
+                Permutations::of_length(0..n, n).avoiding(&diagonal).count()
+            })
+            .collect();
+        assert_eq!(derangement_counts, vec![1, 0, 1, 2, 9, 44, 265, 1854]);
+        let mut conflicting = Permutations::of_length(0..3, 3)
+            .with_fixed(&[(1, 2)])
+            .avoiding(&[(1, 2)]);
+        assert_eq!(conflicting.next(), None);
+    }
+
+    #[test]
+    fn test_permutations_starting_from_and_between_match_filter() {
+        let mut starts: Vec<Vec<usize>> = vec![Vec::new()];
+        for length in 1..=3 {
+            let longer: Vec<Vec<usize>> = starts
+                .iter()
+                .filter(|start| start.len() == length - 1)
+                .flat_map(|start| (0..6).map(move |x| [start.clone(), vec![x]].concat()))
+                .collect();
+            starts.extend(longer);
+        }
+        for mut perm in Permutations::of_length(0..5, 5).step_by(7) {
+            starts.push(perm.clone());
+            perm[3] += 1;
+            starts.push(perm);
+        }
+        let variants = [
+            Permutations::of_length(1..5, 3),
+            Permutations::all(0..4),
+            Permutations::of_length_range(0..5, 2..=3),
+            Permutations::of_length(0..5, 5).avoiding(&[(0, 1), (2, 2), (3, 0)]),
+            Permutations::of_length(0..5, 5).with_fixed(&[(1, 3)]),
+            Permutations::of_length(0..5, 5).with_cycle_type(&[2, 3]),
+        ];
+        for perms in variants {
+            let all: Vec<Vec<usize>> = perms.clone().collect();
+            let shortest = all.iter().map(|perm| perm.len()).min().unwrap();
+            let key = |seq: &Vec<usize>| (seq.len().max(shortest), seq.clone());
+            let agrees = |seq: &Vec<usize>| {
+                seq.iter().enumerate().all(|(position, x)| {
+                    let pinned = perms.pinned.get(position).copied().flatten();
+                    pinned.is_none_or(|index| perms.elements[index] == *x)
+                })
+            };
+            let starts: Vec<&Vec<usize>> = starts.iter().filter(|start| agrees(start)).collect();
+            for start in starts.iter().copied() {
+                let expected: Vec<Vec<usize>> = all
+                    .iter()
+                    .filter(|perm| key(perm) >= key(start))
+                    .cloned()
+                    .collect();
+                let generated: Vec<Vec<usize>> = perms.clone().starting_from(start).collect();
+                assert_eq!(generated, expected);
+            }
+            for (start, end) in starts
+                .iter()
+                .copied()
+                .zip(starts.iter().rev().copied())
+                .step_by(5)
+            {
+                let expected: Vec<Vec<usize>> = all
+                    .iter()
+                    .filter(|perm| key(perm) >= key(start) && key(perm) < key(end))
+                    .cloned()
+                    .collect();
+                let generated: Vec<Vec<usize>> = perms.clone().between(start, end).collect();
+                assert_eq!(generated, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_permutations_with_prefix_matches_filter() {
+        let prefixes: [&[usize]; 6] = [&[], &[2], &[0, 3], &[3, 0, 1], &[1, 1], &[7]];
+        for perms in [Permutations::of_length(0..5, 3), Permutations::all(0..4)] {
+            for prefix in prefixes {
+                let expected: Vec<Vec<usize>> = perms
+                    .clone()
+                    .filter(|perm| perm.starts_with(prefix))
+                    .collect();
+                let generated: Vec<Vec<usize>> = perms.clone().with_prefix(prefix).collect();
+                assert_eq!(generated, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_permutations_with_cycle_type_matches_filter() {
+        use crate::Permutation;
+        use std::collections::BTreeSet;
+
+        for n in 0..7 {
+            let cycle_type =
+                |perm: &Vec<usize>| Permutation::from_images(perm.clone()).unwrap().cycle_type();
+            let all: Vec<Vec<usize>> = Permutations::of_length(0..n, n).collect();
+            let types: BTreeSet<Vec<usize>> = all.iter().map(cycle_type).collect();
+            for wanted in types {
+                let expected: Vec<Vec<usize>> = all
+                    .iter()
+                    .filter(|perm| cycle_type(perm) == wanted)
+                    .cloned()
+                    .collect();
+                let mut shuffled = wanted.clone();
+                shuffled.reverse();
+                let generated: Vec<Vec<usize>> = Permutations::of_length(0..n, n)
+                    .with_cycle_type(&shuffled)
+                    .collect();
+                assert_eq!(generated, expected);
+            }
+            let shorter = Permutations::all(0..n).with_cycle_type(&vec![1; n]);
+            assert_eq!(
+                shorter.collect::<Vec<_>>(),
+                vec![(0..n).collect::<Vec<_>>()]
+            );
+        }
+    }
+}
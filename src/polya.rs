@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use crate::PermutationGroup;
+
+/// The cycle index of a permutation group, which records how many elements of the group have each
+/// cycle type.
+///
+/// The cycle index is the polynomial `(1 / |G|) * sum(x_1^c_1 * x_2^c_2 * ...)` over the elements
+/// of `G`, where `c_i` is the number of cycles of length `i`.  It is stored as a map from each
+/// cycle type (in non-increasing order, including fixed points) to the number of group elements
+/// with that cycle type.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{CycleIndex, Permutation, PermutationGroup};
+///
+/// let rotation = Permutation::from_images(vec![1, 2, 3, 0]).unwrap();
+/// let rotations = PermutationGroup::generated_by(vec![rotation]);
+/// let index = CycleIndex::of(&rotations);
+/// assert_eq!(index.order(), 4);
+/// assert_eq!(index.count_of(&[1, 1, 1, 1]), 1);
+/// assert_eq!(index.count_of(&[2, 2]), 1);
+/// assert_eq!(index.count_of(&[4]), 2);
+/// assert_eq!(index.evaluate(2), Some(6));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleIndex {
+    order: u128,
+    terms: BTreeMap<Vec<usize>, u128>,
+}
+
+impl CycleIndex {
+    /// Computes the cycle index of the given group by enumerating its elements.
+    pub fn of(group: &PermutationGroup) -> Self {
+        let mut terms = BTreeMap::new();
+        let mut order = 0;
+        for element in group.elements() {
+            *terms.entry(element.cycle_type()).or_insert(0) += 1;
+            order += 1;
+        }
+        CycleIndex { order, terms }
+    }
+
+    /// Returns the order of the group, which is the sum of the counts of all cycle types.
+    pub fn order(&self) -> u128 {
+        self.order
+    }
+
+    /// Returns the number of group elements with the given cycle type, which should be listed in
+    /// non-increasing order and include fixed points.
+    pub fn count_of(&self, cycle_type: &[usize]) -> u128 {
+        self.terms.get(cycle_type).copied().unwrap_or(0)
+    }
+
+    /// Returns an iterator over each cycle type occurring in the group, in increasing
+    /// lexicographic order, along with the number of elements with that cycle type.
+    pub fn terms(&self) -> impl Iterator<Item = (&[usize], u128)> {
+        self.terms
+            .iter()
+            .map(|(cycle_type, count)| (cycle_type.as_slice(), *count))
+    }
+
+    /// Evaluates the cycle index with every variable set to `num_colors`, which by the
+    /// Pólya enumeration theorem is the number of colorings of the points with `num_colors`
+    /// colors which are distinct up to the action of the group.  Returns `None` if the sum over
+    /// the group elements, which is the order of the group times the result, does not fit in a
+    /// `u128`.
+    pub fn evaluate(&self, num_colors: u128) -> Option<u128> {
+        let total = self
+            .terms
+            .iter()
+            .try_fold(0u128, |total, (cycle_type, count)| {
+                let fixed = num_colors.checked_pow(u32::try_from(cycle_type.len()).ok()?)?;
+                total.checked_add(count.checked_mul(fixed)?)
+            })?;
+        Some(total / self.order)
+    }
+}
+
+/// Returns the number of ways to assign one of `num_colors` colors to each point acted on by the
+/// given group, counting two colorings as the same if an element of the group maps one to the
+/// other.  This is computed with Burnside's lemma, by evaluating the cycle index of the group, and
+/// is `None` if that overflows a `u128` as described in [`CycleIndex::evaluate`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{count_distinct_colorings, Orbits, Permutation, PermutationGroup};
+///
+/// // Bracelets of six beads in three colors, up to rotation and reflection.
+/// let rotation = Permutation::from_images(vec![1, 2, 3, 4, 5, 0]).unwrap();
+/// let reflection = Permutation::from_images(vec![5, 4, 3, 2, 1, 0]).unwrap();
+/// let group = PermutationGroup::generated_by(vec![rotation, reflection]);
+/// assert_eq!(count_distinct_colorings(&group, 3), Some(92));
+/// assert_eq!(
+///     count_distinct_colorings(&group, 3),
+///     Some(Orbits::of_sequences(&group, 3).count() as u128)
+/// );
+/// assert_eq!(count_distinct_colorings(&group, u128::MAX), None);
+/// ```
+pub fn count_distinct_colorings(group: &PermutationGroup, num_colors: u128) -> Option<u128> {
+    CycleIndex::of(group).evaluate(num_colors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Orbits, Permutation};
+
+    #[test]
+    fn test_polya_cycle_index_of_symmetric_group() {
+        let group = PermutationGroup::generated_by(vec![
+            Permutation::from_images(vec![1, 2, 0]).unwrap(),
+            Permutation::from_images(vec![1, 0, 2]).unwrap(),
+        ]);
+        let index = CycleIndex::of(&group);
+        assert_eq!(
+            index.terms().collect::<Vec<(&[usize], u128)>>(),
+            vec![(&[1, 1, 1][..], 1), (&[2, 1][..], 3), (&[3][..], 2)]
+        );
+        // Colorings up to any rearrangement are multisets of three colors.
+        let counts: Vec<u128> = (0..6)
+            .map(|colors| index.evaluate(colors).unwrap())
+            .collect();
+        assert_eq!(counts, vec![0, 1, 4, 10, 20, 35]);
+        // The cube of 6981463658331 is the term for the identity, which fits in a `u128` though
+        // the sum does not.
+        assert_eq!(index.evaluate(6981463658331), None);
+        assert_eq!(index.evaluate(1 << 43), None);
+        let colors = 1 << 20;
+        let expected = (colors * colors * colors + 3 * colors * colors + 2 * colors) / 6;
+        assert_eq!(index.evaluate(colors), Some(expected));
+    }
+
+    #[test]
+    fn test_polya_matches_orbit_enumeration() {
+        let group = PermutationGroup::generated_by(vec![
+            Permutation::from_images(vec![1, 2, 3, 4, 0]).unwrap(),
+            Permutation::from_images(vec![0, 2, 1, 3, 4]).unwrap(),
+        ]);
+        for colors in 1..4 {
+            assert_eq!(
+                count_distinct_colorings(&group, colors as u128),
+                Some(Orbits::of_sequences(&group, colors).count() as u128)
+            );
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::sorted_unique;
+
+/// An iterator which generates the rounds of a round-robin tournament, in which every pair of
+/// teams meets exactly once, using the circle method.
+///
+/// Each round is yielded as a list of matches together with the team which has a bye in that
+/// round, if any.  With an even number `n` of teams there are `n - 1` rounds and no byes, and
+/// every round is a perfect matching of the teams, in the same format as the matchings yielded by
+/// [`Pairings`](crate::Pairings): the first team of each match is the smaller, and the matches
+/// are sorted.  With an odd number of teams there are `n` rounds, and each team has a bye in
+/// exactly one of them.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::RoundRobin;
+///
+/// let mut rounds = RoundRobin::new(vec!['a', 'b', 'c', 'd']);
+/// assert_eq!(rounds.next(), Some((vec![('a', 'b'), ('c', 'd')], None)));
+/// assert_eq!(rounds.next(), Some((vec![('a', 'c'), ('b', 'd')], None)));
+/// assert_eq!(rounds.next(), Some((vec![('a', 'd'), ('b', 'c')], None)));
+/// assert_eq!(rounds.next(), None);
+///
+/// let mut rounds = RoundRobin::new(1..=3);
+/// assert_eq!(rounds.next(), Some((vec![(1, 2)], Some(3))));
+/// assert_eq!(rounds.next(), Some((vec![(1, 3)], Some(2))));
+/// assert_eq!(rounds.next(), Some((vec![(2, 3)], Some(1))));
+/// assert_eq!(rounds.next(), None);
+/// ```
+pub struct RoundRobin<T> {
+    teams: Vec<T>,
+    round: usize,
+    rounds: usize,
+}
+
+impl<T: Ord + Clone> RoundRobin<T> {
+    /// Creates a new `RoundRobin` iterator which will yield the rounds of a tournament between the
+    /// unique elements of the given iterable.  Fewer than two teams require no rounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::RoundRobin;
+    ///
+    /// assert_eq!(RoundRobin::new(0..10).count(), 9);
+    /// assert_eq!(RoundRobin::new(0..9).count(), 9);
+    /// assert_eq!(RoundRobin::new(vec!["solo"]).next(), None);
+    /// ```
+    pub fn new(teams: impl IntoIterator<Item = T>) -> Self {
+        let teams = sorted_unique(teams);
+        let rounds = match teams.len() {
+            0 | 1 => 0,
+            count => count + count % 2 - 1,
+        };
+        RoundRobin {
+            teams,
+            round: 0,
+            rounds,
+        }
+    }
+
+    /// Returns the sorted, deduplicated teams which take part in the tournament.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::RoundRobin;
+    ///
+    /// assert_eq!(RoundRobin::new(vec!["cats", "ants", "bees"]).teams(), &["ants", "bees", "cats"]);
+    /// ```
+    pub fn teams(&self) -> &[T] {
+        &self.teams
+    }
+
+    /// Returns the round which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if no rounds remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::RoundRobin;
+    ///
+    /// let mut rounds = RoundRobin::new(1..=3);
+    /// assert_eq!(rounds.current(), Some((vec![(1, 2)], Some(3))));
+    /// assert_eq!(rounds.next(), Some((vec![(1, 2)], Some(3))));
+    /// assert_eq!(rounds.current(), Some((vec![(1, 3)], Some(2))));
+    /// ```
+    pub fn current(&self) -> Option<<Self as Iterator>::Item> {
+        if self.round >= self.rounds {
+            return None;
+        }
+        let mut matches = Vec::with_capacity(self.teams.len() / 2);
+        let mut bye = None;
+        for (a, b) in self.round_indices(self.round) {
+            let (a, b) = (a.min(b), a.max(b));
+            if b == self.teams.len() {
+                bye = Some(self.teams[a].clone());
+            } else {
+                matches.push((a, b));
+            }
+        }
+        matches.sort_unstable();
+        Some((
+            matches
+                .into_iter()
+                .map(|(a, b)| (self.teams[a].clone(), self.teams[b].clone()))
+                .collect(),
+            bye,
+        ))
+    }
+
+    /// Consumes the iterator and returns its teams, as given by [`RoundRobin::teams`], along with
+    /// the index of the next round which would have been yielded, or `None` if no rounds remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::RoundRobin;
+    ///
+    /// let mut rounds = RoundRobin::new(1..=4);
+    /// rounds.next();
+    /// assert_eq!(rounds.into_parts(), (vec![1, 2, 3, 4], Some(1)));
+    /// ```
+    pub fn into_parts(self) -> (Vec<T>, Option<usize>) {
+        (self.teams, (self.round < self.rounds).then_some(self.round))
+    }
+
+    /// Returns the indices of the teams which meet in the given round, where the index equal to
+    /// the number of teams stands for the bye when the number of teams is odd.
+    fn round_indices(&self, round: usize) -> Vec<(usize, usize)> {
+        let ring = self.rounds;
+        let mut matches = vec![(0, 1 + round)];
+        for offset in 1..=ring / 2 {
+            matches.push((
+                1 + (round + offset) % ring,
+                1 + (round + ring - offset) % ring,
+            ));
+        }
+        matches
+    }
+}
+
+impl<T: Ord + Clone> Iterator for RoundRobin<T> {
+    type Item = (Vec<(T, T)>, Option<T>);
+
+    /// Returns the next round and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let round = self.current()?;
+        self.round += 1;
+        Some(round)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_round_robin_every_pair_meets_once() {
+        for n in 0..12 {
+            let mut met = Vec::new();
+            let mut byes = BTreeSet::new();
+            for (matches, bye) in RoundRobin::new(0..n) {
+                let mut playing: Vec<usize> = matches.iter().flat_map(|(a, b)| [*a, *b]).collect();
+                playing.extend(bye);
+                playing.sort_unstable();
+                assert_eq!(playing, (0..n).collect::<Vec<usize>>());
+                assert!(matches.iter().all(|(a, b)| a < b));
+                assert!(matches.windows(2).all(|pair| pair[0] < pair[1]));
+                assert_eq!(bye.is_some(), n % 2 == 1);
+                byes.extend(bye);
+                met.extend(matches.into_iter().map(|(a, b)| vec![a, b]));
+            }
+            met.sort();
+            assert_eq!(met, Combinations::of_size(0..n, 2).collect::<Vec<_>>());
+            if n % 2 == 1 && n > 1 {
+                assert_eq!(byes.len(), n);
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{Combinations, Order};
+
+/// A source of combinations which can be shared between threads, each of which takes disjoint
+/// batches of consecutive combinations with [`SharedCombinations::next_batch`].
+///
+/// The combinations are numbered by their ranks in lexicographic order, and the next unclaimed
+/// rank is held in a single atomic counter, so claiming a batch never blocks.  Each batch is then
+/// generated independently by jumping straight to the first combination of the batch, so threads
+/// only contend on the counter, not on the generator.  Every combination is yielded in exactly
+/// one batch, which makes this a simple way to spread an enumeration over a custom thread pool.
+/// Ranks are counted with a `u64`, so at most `u64::MAX` combinations are handed out.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{Combinations, SharedCombinations};
+/// use std::thread;
+///
+/// let is_match = |combo: &Vec<i32>| combo.iter().sum::<i32>() == 30;
+/// let shared = SharedCombinations::new(Combinations::of_size(0..20, 4));
+/// let counts: Vec<usize> = thread::scope(|scope| {
+///     let workers: Vec<_> = (0..4)
+///         .map(|_| {
+///             scope.spawn(|| {
+///                 let mut count = 0;
+///                 loop {
+///                     let batch = shared.next_batch(100);
+///                     if batch.is_empty() {
+///                         return count;
+///                     }
+///                     count += batch.iter().filter(|combo| is_match(combo)).count();
+///                 }
+///             })
+///         })
+///         .collect();
+///     workers.into_iter().map(|worker| worker.join().unwrap()).collect()
+/// });
+/// let expected = Combinations::of_size(0..20, 4).filter(is_match).count();
+/// assert_eq!(counts.iter().sum::<usize>(), expected);
+/// ```
+pub struct SharedCombinations<T> {
+    combinations: Combinations<T>,
+    next: AtomicU64,
+    total: u64,
+}
+
+impl<T: Clone> SharedCombinations<T> {
+    /// Creates a new `SharedCombinations` which will hand out every combination which the given
+    /// generator yields from the beginning, in lexicographic order, regardless of the order
+    /// selected for the generator or how far it has advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, SharedCombinations};
+    ///
+    /// let shared = SharedCombinations::new(Combinations::all(0..10));
+    /// assert_eq!(shared.total(), 1024);
+    /// ```
+    pub fn new(combinations: Combinations<T>) -> Self {
+        let total = combinations.total_count().unwrap_or(u128::MAX);
+        SharedCombinations {
+            combinations: combinations.order(Order::Lex),
+            next: AtomicU64::new(0),
+            total: u64::try_from(total).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Claims the next batch of at most `size` combinations and returns them, in lexicographic
+    /// order.  The batch is smaller than `size` only if it is the last, and empty once every
+    /// combination has been claimed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, SharedCombinations};
+    ///
+    /// let shared = SharedCombinations::new(Combinations::of_size(1..5, 2));
+    /// assert_eq!(shared.next_batch(4), vec![vec![1, 2], vec![1, 3], vec![1, 4], vec![2, 3]]);
+    /// assert_eq!(shared.next_batch(4), vec![vec![2, 4], vec![3, 4]]);
+    /// assert!(shared.next_batch(4).is_empty());
+    /// ```
+    pub fn next_batch(&self, size: usize) -> Vec<Vec<T>> {
+        let Some(ranks) = self.claim(size) else {
+            return Vec::new();
+        };
+        let mut combinations = self.combinations.clone();
+        combinations.seek_rank(ranks.start as u128);
+        combinations
+            .take((ranks.end - ranks.start) as usize)
+            .collect()
+    }
+
+    /// Claims the ranks of the next batch of at most `size` combinations, or returns `None` if
+    /// none remain.
+    fn claim(&self, size: usize) -> Option<Range<u64>> {
+        let size = u64::try_from(size).unwrap_or(u64::MAX);
+        let start = self
+            .next
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |next| {
+                (next < self.total && size > 0).then(|| next.saturating_add(size).min(self.total))
+            })
+            .ok()?;
+        Some(start..start.saturating_add(size).min(self.total))
+    }
+}
+
+impl<T> SharedCombinations<T> {
+    /// Returns the total number of combinations which are handed out over all batches.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns the number of combinations which have not yet been claimed.  Other threads may
+    /// claim some of them at any time, so this is only a snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Combinations, SharedCombinations};
+    ///
+    /// let shared = SharedCombinations::new(Combinations::of_size(0..10, 3));
+    /// shared.next_batch(100);
+    /// assert_eq!(shared.remaining(), 20);
+    /// ```
+    pub fn remaining(&self) -> u64 {
+        self.total - self.next.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_batches_cover_each_combination_once() {
+        for n in 0..8 {
+            for batch_size in [1, 3, 100] {
+                let shared = SharedCombinations::new(Combinations::all(0..n));
+                let mut combos = Vec::new();
+                loop {
+                    let batch = shared.next_batch(batch_size);
+                    if batch.is_empty() {
+                        break;
+                    }
+                    assert!(batch.len() == batch_size || shared.remaining() == 0);
+                    combos.extend(batch);
+                }
+                assert_eq!(combos, Combinations::all(0..n).collect::<Vec<_>>());
+            }
+        }
+        let colex = Combinations::of_size(0..6, 3).order(Order::Colex);
+        let shared = SharedCombinations::new(colex);
+        assert_eq!(shared.next_batch(0), Vec::<Vec<i32>>::new());
+        assert_eq!(shared.next_batch(3)[2], vec![0, 1, 4]);
+    }
+}
@@ -0,0 +1,138 @@
+/// The number of rounds of the Feistel network which shuffles the ranks.
+const ROUNDS: usize = 4;
+
+/// An iterator which yields every item of a generator exactly once, in a pseudo-random order
+/// determined by a seed, created by methods such as [`Combinations::shuffled`].
+///
+/// The order is found without storing the items: the ranks `0..n` of the items in the
+/// generator's usual order are put through a bijection built from a keyed Feistel network, and
+/// the item with each resulting rank is constructed directly.  The same seed always gives the
+/// same order, so a shuffled search can be reproduced, while different seeds give unrelated
+/// orders.  The order is suitable for spreading work or sampling without repetition, but not for
+/// anything which must be unpredictable.
+///
+/// [`Combinations::shuffled`]: crate::Combinations::shuffled
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Combinations;
+///
+/// let mut shuffled: Vec<Vec<i32>> = Combinations::of_size(1..6, 2).shuffled(7).collect();
+/// let again: Vec<Vec<i32>> = Combinations::of_size(1..6, 2).shuffled(7).collect();
+/// assert_eq!(shuffled, again);
+///
+/// shuffled.sort();
+/// assert_eq!(shuffled, Combinations::of_size(1..6, 2).collect::<Vec<_>>());
+/// ```
+#[derive(Clone)]
+pub struct Shuffled<G> {
+    pub(crate) inner: G,
+    total: u128,
+    index: u128,
+    half_bits: u32,
+    keys: [u64; ROUNDS],
+}
+
+impl<G> Shuffled<G> {
+    /// Wraps the given generator so that the ranks of its `total` items are visited in an order
+    /// determined by the seed.  If the total is `None`, because it does not fit in a `u128`, no
+    /// ranks are visited.
+    pub(crate) fn new(inner: G, total: Option<u128>, seed: u64) -> Self {
+        let total = total.unwrap_or(0);
+        let bits = 128 - total.saturating_sub(1).leading_zeros();
+        let mut state = seed;
+        Shuffled {
+            inner,
+            total,
+            index: 0,
+            half_bits: bits.div_ceil(2).max(1),
+            keys: std::array::from_fn(|_| split_mix(&mut state)),
+        }
+    }
+
+    /// Returns the rank of the next item to visit, or `None` if every rank has been visited.
+    pub(crate) fn next_rank(&mut self) -> Option<u128> {
+        if self.index >= self.total {
+            return None;
+        }
+        let mut rank = self.index;
+        self.index += 1;
+        // The network permutes a range of at most four times the total, so walking the cycle
+        // until the rank is back in range takes few steps on average.
+        loop {
+            rank = self.encrypt(rank);
+            if rank < self.total {
+                return Some(rank);
+            }
+        }
+    }
+
+    /// Returns the number of ranks which have not yet been visited.
+    pub(crate) fn remaining(&self) -> u128 {
+        self.total - self.index
+    }
+
+    /// Applies the keyed Feistel network to a value of twice `half_bits` bits, which is a
+    /// bijection on the values of that many bits.
+    fn encrypt(&self, value: u128) -> u128 {
+        let mask = (1u128 << self.half_bits) - 1;
+        let (mut left, mut right) = (value >> self.half_bits, value & mask);
+        for key in self.keys {
+            let mut state = key ^ right as u64;
+            let mixed = split_mix(&mut state) as u128 & mask;
+            (left, right) = (right, left ^ mixed);
+        }
+        (left << self.half_bits) | right
+    }
+
+    /// Returns the underlying generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Permutations;
+    ///
+    /// let shuffled = Permutations::of_length(0..3, 2).shuffled(1);
+    /// assert_eq!(shuffled.into_inner().elements(), &[0, 1, 2]);
+    /// ```
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+}
+
+/// Advances the SplitMix64 generator with the given state and returns its next output.
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffled_ranks_are_a_permutation() {
+        for total in [0, 1, 2, 3, 5, 16, 17, 100, 1000] {
+            for seed in 0..4 {
+                let mut shuffled = Shuffled::new((), Some(total), seed);
+                let mut ranks = Vec::new();
+                while let Some(rank) = shuffled.next_rank() {
+                    ranks.push(rank);
+                    assert_eq!(shuffled.remaining(), total - ranks.len() as u128);
+                }
+                if total > 16 {
+                    assert!(ranks.windows(2).any(|pair| pair[0] > pair[1]));
+                }
+                ranks.sort();
+                assert_eq!(ranks, (0..total).collect::<Vec<u128>>());
+            }
+        }
+        let mut huge = Shuffled::new((), Some(u128::MAX), 0);
+        assert!(huge.next_rank().is_some());
+        assert_eq!(Shuffled::new((), None, 0).next_rank(), None);
+    }
+}
@@ -0,0 +1,294 @@
+use crate::sorted_unique;
+
+/// An iterator which generates the ways to partition a set of elements into groups of specified
+/// sizes.
+///
+/// Groups are yielded in the order of the given sizes, and each group is sorted.  Groups of equal
+/// size are interchangeable, so each partition is yielded exactly once, with groups of equal size
+/// ordered by their smallest elements.  Partitions are yielded in lexicographic order of the
+/// group to which each element is assigned, taking the elements in sorted order.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::SizedPartitions;
+///
+/// let mut teams = SizedPartitions::new(vec!['a', 'b', 'c', 'd'], &[2, 2]);
+/// assert_eq!(teams.next(), Some(vec![vec!['a', 'b'], vec!['c', 'd']]));
+/// assert_eq!(teams.next(), Some(vec![vec!['a', 'c'], vec!['b', 'd']]));
+/// assert_eq!(teams.next(), Some(vec![vec!['a', 'd'], vec!['b', 'c']]));
+/// assert_eq!(teams.next(), None);
+///
+/// // Twelve players split into three teams of four.
+/// assert_eq!(SizedPartitions::new(1..=12, &[4, 4, 4]).count(), 5775);
+/// ```
+#[derive(Clone)]
+pub struct SizedPartitions<T> {
+    elements: Vec<T>,
+    sizes: Vec<usize>,
+    previous_same_size: Vec<Option<usize>>,
+    assignment: Vec<usize>,
+    filled: Vec<usize>,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord + Clone> SizedPartitions<T> {
+    /// Creates a new `SizedPartitions` iterator which will yield every partition of the elements
+    /// in the given iterable into groups with the given sizes.  If the sizes do not sum to the
+    /// number of unique elements, then no partitions are yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SizedPartitions;
+    ///
+    /// let mut splits = SizedPartitions::new(1..4, &[1, 2]);
+    /// assert_eq!(splits.next(), Some(vec![vec![1], vec![2, 3]]));
+    /// assert_eq!(splits.next(), Some(vec![vec![2], vec![1, 3]]));
+    /// assert_eq!(splits.next(), Some(vec![vec![3], vec![1, 2]]));
+    /// assert_eq!(splits.next(), None);
+    ///
+    /// assert_eq!(SizedPartitions::new(1..4, &[2, 2]).next(), None);
+    /// ```
+    pub fn new(elements: impl IntoIterator<Item = T>, sizes: &[usize]) -> Self {
+        let elements = sorted_unique(elements);
+        let previous_same_size = (0..sizes.len())
+            .map(|group| (0..group).rev().find(|other| sizes[*other] == sizes[group]))
+            .collect();
+        SizedPartitions {
+            done: sizes.iter().sum::<usize>() != elements.len(),
+            assignment: Vec::with_capacity(elements.len()),
+            elements,
+            sizes: sizes.to_vec(),
+            previous_same_size,
+            filled: vec![0; sizes.len()],
+            started: false,
+        }
+    }
+
+    /// Returns the sorted, deduplicated elements which this iterator partitions into groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SizedPartitions;
+    ///
+    /// let partitions = SizedPartitions::new(vec!['c', 'a', 'c', 'b'], &[1, 2]);
+    /// assert_eq!(partitions.elements(), &['a', 'b', 'c']);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the partition which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if none remain.  Since the next partition is only found
+    /// when the iterator advances, this searches for it using a copy of the iterator, so it costs
+    /// about as much as a call to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SizedPartitions;
+    ///
+    /// let mut partitions = SizedPartitions::new(1..4, &[1, 2]);
+    /// assert_eq!(partitions.current(), Some(vec![vec![1], vec![2, 3]]));
+    /// assert_eq!(partitions.next(), Some(vec![vec![1], vec![2, 3]]));
+    /// ```
+    pub fn current(&self) -> Option<Vec<Vec<T>>> {
+        self.clone().next()
+    }
+
+    /// Consumes the iterator and returns its elements, as given by
+    /// [`SizedPartitions::elements`], along with the group to which each element is assigned in
+    /// the next partition which would have been yielded, or `None` if no partitions remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SizedPartitions;
+    ///
+    /// let mut partitions = SizedPartitions::new(vec!['c', 'b', 'a'], &[1, 2]);
+    /// assert_eq!(partitions.next(), Some(vec![vec!['a'], vec!['b', 'c']]));
+    /// assert_eq!(partitions.into_parts(), (vec!['a', 'b', 'c'], Some(vec![1, 0, 1])));
+    /// ```
+    pub fn into_parts(mut self) -> (Vec<T>, Option<Vec<usize>>) {
+        let next = self.advance().then_some(self.assignment);
+        (self.elements, next)
+    }
+
+    /// Returns the total number of partitions which this iterator yields from the beginning,
+    /// regardless of how far it has advanced, as a big integer, so the count is exact however
+    /// large it is.  This is only available with the `bigint` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SizedPartitions;
+    /// use num_bigint::BigUint;
+    ///
+    /// let teams = SizedPartitions::new(1..=12, &[4, 4, 4]);
+    /// assert_eq!(teams.total_count_big(), BigUint::from(5775u16));
+    /// assert_eq!(SizedPartitions::new(1..4, &[2, 2]).total_count_big(), BigUint::from(0u8));
+    /// ```
+    #[cfg(feature = "bigint")]
+    pub fn total_count_big(&self) -> num_bigint::BigUint {
+        use crate::factorial_big;
+
+        if self.sizes.iter().sum::<usize>() != self.elements.len() {
+            return num_bigint::BigUint::default();
+        }
+        let mut count = factorial_big(self.elements.len());
+        for (group, size) in self.sizes.iter().enumerate() {
+            count /= factorial_big(*size);
+            // Groups of equal size are interchangeable, so divide by the number of ways to order
+            // them, one factor for each, except that empty groups are all the same.
+            if *size > 0 {
+                count /= self.sizes[..=group]
+                    .iter()
+                    .filter(|other| *other == size)
+                    .count();
+            }
+        }
+        count
+    }
+
+    /// Returns `true` if the next element may be assigned to the given group, which requires that
+    /// the group is not full, and that if the group is empty, then every earlier group of the same
+    /// size is not.
+    fn can_place(&self, group: usize) -> bool {
+        if self.filled[group] >= self.sizes[group] {
+            return false;
+        }
+        self.filled[group] > 0
+            || self.previous_same_size[group].is_none_or(|other| self.filled[other] > 0)
+    }
+
+    /// Assigns the next element to the given group.
+    fn place(&mut self, group: usize) {
+        self.filled[group] += 1;
+        self.assignment.push(group);
+    }
+
+    /// Removes and returns the group of the last assigned element, if any.
+    fn unplace(&mut self) -> Option<usize> {
+        let group = self.assignment.pop()?;
+        self.filled[group] -= 1;
+        Some(group)
+    }
+
+    /// Completes the assignment with the lexicographically smallest valid groups, backtracking as
+    /// necessary.  If `previous` is given, the search resumes by trying groups after `previous`
+    /// for the next element.  Returns `true` if the assignment was completed, or `false` if no
+    /// further partitions exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            if previous.is_none() && self.assignment.len() == self.elements.len() {
+                return true;
+            }
+            let start = previous.map_or(0, |group| group + 1);
+            match (start..self.sizes.len()).find(|group| self.can_place(*group)) {
+                Some(group) => {
+                    self.place(group);
+                    previous = None;
+                }
+                None => match self.unplace() {
+                    Some(group) => previous = Some(group),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Moves to the next partition.  Returns `false` and marks the iterator as done if no
+    /// partitions remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let found = if self.started {
+            match self.unplace() {
+                Some(group) => self.fill(Some(group)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.fill(None)
+        };
+        if !found {
+            self.done = true;
+        }
+        found
+    }
+}
+
+impl<T: Ord + Clone> Iterator for SizedPartitions<T> {
+    type Item = Vec<Vec<T>>;
+
+    /// Returns the next partition and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        let mut groups: Vec<Vec<T>> = self
+            .sizes
+            .iter()
+            .map(|size| Vec::with_capacity(*size))
+            .collect();
+        for (element, group) in self.elements.iter().zip(self.assignment.iter()) {
+            groups[*group].push(element.clone());
+        }
+        Some(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factorial;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_sized_partitions_counts_and_uniqueness() {
+        for sizes in [
+            vec![],
+            vec![3],
+            vec![2, 2, 1],
+            vec![1, 2, 2],
+            vec![2, 1, 2],
+            vec![1, 1, 1, 1],
+            vec![3, 0, 2, 0],
+            vec![2, 3, 2, 1],
+        ] {
+            let n: usize = sizes.iter().sum();
+            let partitions: Vec<Vec<Vec<usize>>> = SizedPartitions::new(0..n, &sizes).collect();
+            let mut multiplicities = BTreeSet::new();
+            let mut expected = factorial(n);
+            for size in sizes.iter() {
+                expected /= factorial(*size);
+                let count = sizes.iter().filter(|other| *other == size).count();
+                multiplicities.insert((*size, count));
+            }
+            for (size, count) in multiplicities {
+                if size > 0 {
+                    expected /= factorial(count);
+                }
+            }
+            assert_eq!(partitions.len(), expected);
+            #[cfg(feature = "bigint")]
+            assert_eq!(
+                SizedPartitions::new(0..n, &sizes).total_count_big(),
+                num_bigint::BigUint::from(expected)
+            );
+            let canonical: BTreeSet<BTreeSet<Vec<usize>>> = partitions
+                .iter()
+                .map(|groups| groups.iter().cloned().collect())
+                .collect();
+            assert_eq!(canonical.len(), partitions.len());
+            for groups in partitions {
+                let sizes_found: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+                assert_eq!(sizes_found, sizes);
+            }
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use crate::combination_count;
+use crate::combinations::size_hint_from;
+use crate::sorted_unique;
+
+/// The indices of combinations of a fixed size, advanced in lexicographic order without any heap
+/// allocation beyond the storage `P` for the indices themselves.
+#[derive(Clone)]
+struct LexPositions<P> {
+    positions: P,
+    length: usize,
+    remaining: Option<u128>,
+    started: bool,
+}
+
+impl<P: AsRef<[usize]> + AsMut<[usize]>> LexPositions<P> {
+    /// Creates the positions for combinations of `length` elements with the size of the given
+    /// storage, which must hold the indices `0..k` in order.
+    fn new(positions: P, length: usize) -> Self {
+        let size = positions.as_ref().len();
+        LexPositions {
+            positions,
+            length,
+            remaining: combination_count(length, size),
+            started: false,
+        }
+    }
+
+    /// Advances to the positions of the next combination, returning them if there are any.
+    fn next(&mut self) -> Option<&P> {
+        if self.remaining == Some(0) {
+            return None;
+        }
+        if self.started {
+            let positions = self.positions.as_mut();
+            let size = positions.len();
+            let slack = self.length - size;
+            let index = (0..size).rev().find(|i| positions[*i] < slack + i)?;
+            let start = positions[index] + 1;
+            for (offset, position) in positions[index..].iter_mut().enumerate() {
+                *position = start + offset;
+            }
+        } else {
+            self.started = true;
+        }
+        self.remaining = self.remaining.map(|remaining| remaining - 1);
+        Some(&self.positions)
+    }
+}
+
+/// An iterator which generates the pairs of distinct elements from a set of elements as tuples,
+/// created by [`pairs`].
+///
+/// This yields the same combinations in the same order as
+/// [`Combinations::of_size`](crate::Combinations::of_size) with a size of 2, but since each pair
+/// is a tuple rather than a vector, no allocation is needed for each item, and the elements can be
+/// destructured directly.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::pairs;
+///
+/// let mut distances = Vec::new();
+/// for (a, b) in pairs(vec![1, 4, 9]) {
+///     distances.push(b - a);
+/// }
+/// assert_eq!(distances, vec![3, 8, 5]);
+/// ```
+#[derive(Clone)]
+pub struct Pairs<T> {
+    elements: Vec<T>,
+    positions: LexPositions<[usize; 2]>,
+}
+
+/// Returns an iterator over the pairs of distinct unique elements from the given iterable, in
+/// lexicographic order, with the smaller element first in each pair.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::pairs;
+///
+/// let mut pairs = pairs(vec!['c', 'a', 'b', 'a']);
+/// assert_eq!(pairs.next(), Some(('a', 'b')));
+/// assert_eq!(pairs.next(), Some(('a', 'c')));
+/// assert_eq!(pairs.next(), Some(('b', 'c')));
+/// assert_eq!(pairs.next(), None);
+/// ```
+pub fn pairs<T: Ord + Clone>(elements: impl IntoIterator<Item = T>) -> Pairs<T> {
+    let elements = sorted_unique(elements);
+    Pairs {
+        positions: LexPositions::new(std::array::from_fn(|i| i), elements.len()),
+        elements,
+    }
+}
+
+impl<T: Clone> Iterator for Pairs<T> {
+    type Item = (T, T);
+
+    /// Returns the next pair and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let [a, b] = *self.positions.next()?;
+        Some((self.elements[a].clone(), self.elements[b].clone()))
+    }
+
+    /// Returns the exact number of remaining pairs, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_from(self.positions.remaining)
+    }
+}
+
+/// An iterator which generates the triples of distinct elements from a set of elements as tuples,
+/// created by [`triples`].
+///
+/// This yields the same combinations in the same order as
+/// [`Combinations::of_size`](crate::Combinations::of_size) with a size of 3, but since each
+/// triple is a tuple rather than a vector, no allocation is needed for each item, and the
+/// elements can be destructured directly.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::triples;
+///
+/// // Side lengths which form a non-degenerate triangle.
+/// let triangles = triples(1..=6).filter(|(a, b, c)| a + b > *c).count();
+/// assert_eq!(triangles, 7);
+/// ```
+#[derive(Clone)]
+pub struct Triples<T> {
+    elements: Vec<T>,
+    positions: LexPositions<[usize; 3]>,
+}
+
+/// Returns an iterator over the triples of distinct unique elements from the given iterable, in
+/// lexicographic order, with the elements of each triple in increasing order.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::triples;
+///
+/// let mut triples = triples(1..5);
+/// assert_eq!(triples.next(), Some((1, 2, 3)));
+/// assert_eq!(triples.next(), Some((1, 2, 4)));
+/// assert_eq!(triples.next(), Some((1, 3, 4)));
+/// assert_eq!(triples.next(), Some((2, 3, 4)));
+/// assert_eq!(triples.next(), None);
+/// ```
+pub fn triples<T: Ord + Clone>(elements: impl IntoIterator<Item = T>) -> Triples<T> {
+    let elements = sorted_unique(elements);
+    Triples {
+        positions: LexPositions::new(std::array::from_fn(|i| i), elements.len()),
+        elements,
+    }
+}
+
+impl<T: Clone> Iterator for Triples<T> {
+    type Item = (T, T, T);
+
+    /// Returns the next triple and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let [a, b, c] = *self.positions.next()?;
+        Some((
+            self.elements[a].clone(),
+            self.elements[b].clone(),
+            self.elements[c].clone(),
+        ))
+    }
+
+    /// Returns the exact number of remaining triples, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_from(self.positions.remaining)
+    }
+}
+
+/// A tuple whose fields all have the same type, which can be yielded by
+/// [`Combinations::tuples`](crate::Combinations::tuples).  This is implemented for tuples with 1
+/// to 12 fields.
+pub trait Tuple: Sized {
+    /// The type of each field of the tuple.
+    type Item;
+
+    /// The number of fields in the tuple.
+    const ARITY: usize;
+
+    /// Builds a tuple by cloning the elements at each of the given positions, of which there must
+    /// be exactly [`Self::ARITY`].
+    fn from_positions(elements: &[Self::Item], positions: &[usize]) -> Self
+    where
+        Self::Item: Clone;
+}
+
+/// Expands to the given type, ignoring the token before it, so that a type can be repeated once
+/// for each index of a tuple.
+macro_rules! repeat_type {
+    ($ignored:tt, $t:ty) => {
+        $t
+    };
+}
+
+/// Implements [`Tuple`] for the tuple with one field for each of the given indices.
+macro_rules! impl_tuple {
+    ($arity:literal; $($index:tt),+) => {
+        impl<T> Tuple for ($(repeat_type!($index, T),)+) {
+            type Item = T;
+
+            const ARITY: usize = $arity;
+
+            fn from_positions(elements: &[T], positions: &[usize]) -> Self
+            where
+                T: Clone,
+            {
+                ($(elements[positions[$index]].clone(),)+)
+            }
+        }
+    };
+}
+
+impl_tuple!(1; 0);
+impl_tuple!(2; 0, 1);
+impl_tuple!(3; 0, 1, 2);
+impl_tuple!(4; 0, 1, 2, 3);
+impl_tuple!(5; 0, 1, 2, 3, 4);
+impl_tuple!(6; 0, 1, 2, 3, 4, 5);
+impl_tuple!(7; 0, 1, 2, 3, 4, 5, 6);
+impl_tuple!(8; 0, 1, 2, 3, 4, 5, 6, 7);
+impl_tuple!(9; 0, 1, 2, 3, 4, 5, 6, 7, 8);
+impl_tuple!(10; 0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+impl_tuple!(11; 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10);
+impl_tuple!(12; 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11);
+
+/// An iterator which generates combinations as tuples, with a size given by the number of fields
+/// in the tuple type `U`, created by [`Combinations::tuples`](crate::Combinations::tuples).
+///
+/// This yields the same combinations in the same order as
+/// [`Combinations::of_size`](crate::Combinations::of_size), but since each combination is a tuple
+/// rather than a vector, no allocation is needed for each item, and the elements can be
+/// destructured directly.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Combinations;
+///
+/// let mut sums = Vec::new();
+/// for (a, b, c, d) in Combinations::tuples(1..=5) {
+///     sums.push(a + b + c + d);
+/// }
+/// assert_eq!(sums, vec![10, 11, 12, 13, 14]);
+/// ```
+#[derive(Clone)]
+pub struct TupleCombinations<U: Tuple> {
+    elements: Vec<U::Item>,
+    positions: LexPositions<Vec<usize>>,
+}
+
+impl<U: Tuple> TupleCombinations<U> {
+    /// Creates a new `TupleCombinations` iterator over the given elements, which must already be
+    /// sorted and deduplicated.
+    pub(crate) fn new(elements: Vec<U::Item>) -> Self {
+        TupleCombinations {
+            positions: LexPositions::new((0..U::ARITY).collect(), elements.len()),
+            elements,
+        }
+    }
+}
+
+impl<U: Tuple> Iterator for TupleCombinations<U>
+where
+    U::Item: Clone,
+{
+    type Item = U;
+
+    /// Returns the next combination and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let positions = self.positions.next()?;
+        Some(U::from_positions(&self.elements, positions))
+    }
+
+    /// Returns the exact number of remaining combinations, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_from(self.positions.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_small_combinations_match_combinations() {
+        for n in 0..8 {
+            let expected: Vec<Vec<usize>> = Combinations::of_size(0..n, 2).collect();
+            let pairs = pairs(0..n);
+            assert_eq!(pairs.size_hint(), (expected.len(), Some(expected.len())));
+            let pairs: Vec<Vec<usize>> = pairs.map(|(a, b)| vec![a, b]).collect();
+            assert_eq!(pairs, expected);
+
+            let expected: Vec<Vec<usize>> = Combinations::of_size(0..n, 3).collect();
+            let mut triples = triples(0..n);
+            assert_eq!(triples.size_hint(), (expected.len(), Some(expected.len())));
+            triples.next();
+            assert_eq!(
+                triples.size_hint().1,
+                Some(expected.len().saturating_sub(1))
+            );
+            let triples: Vec<Vec<usize>> = triples.map(|(a, b, c)| vec![a, b, c]).collect();
+            assert_eq!(triples, expected.get(1..).unwrap_or_default());
+        }
+    }
+
+    #[test]
+    fn test_small_combinations_tuples() {
+        for n in 0..14 {
+            let expected: Vec<Vec<usize>> = Combinations::of_size(0..n, 1).collect();
+            let singles: Vec<Vec<usize>> = Combinations::tuples(0..n).map(|(a,)| vec![a]).collect();
+            assert_eq!(singles, expected);
+
+            let expected: Vec<Vec<usize>> = Combinations::of_size(0..n, 12).collect();
+            let tuples = Combinations::tuples::<(_, _, _, _, _, _, _, _, _, _, _, _)>(0..n);
+            assert_eq!(tuples.size_hint(), (expected.len(), Some(expected.len())));
+            let tuples: Vec<Vec<usize>> = tuples
+                .map(|(a, b, c, d, e, f, g, h, i, j, k, l)| {
+                    vec![a, b, c, d, e, f, g, h, i, j, k, l]
+                })
+                .collect();
+            assert_eq!(tuples, expected);
+        }
+        let mut pairs = Combinations::tuples::<(_, _)>(0..4);
+        pairs.next();
+        let rest: Vec<(i32, i32)> = pairs.clone().collect();
+        assert_eq!(rest, pairs.collect::<Vec<_>>());
+        assert_eq!(rest.first(), Some(&(0, 2)));
+    }
+}
@@ -0,0 +1,299 @@
+use crate::Permutations;
+
+/// A statistic of a permutation, computed from the indices of its elements, as yielded by
+/// [`Permutations::with_statistic`].
+///
+/// Positions are counted from one in the usual definitions, so a descent between the first and
+/// second positions adds one to the major index, and an excedance is a position holding a larger
+/// index, counting both from zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermutationStatistic {
+    /// The number of positions whose index is greater than the next one.
+    Descents,
+    /// The sum of the positions, counting from one, whose index is greater than the next one.
+    MajorIndex,
+    /// The number of pairs of positions whose indices are out of order.
+    Inversions,
+    /// The number of positions holding a greater index than the position itself.
+    Excedances,
+}
+
+impl PermutationStatistic {
+    /// Returns the value of the statistic for the permutation with the given indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::PermutationStatistic;
+    ///
+    /// let indices = [2, 0, 3, 1];
+    /// assert_eq!(PermutationStatistic::Descents.of(&indices), 2);
+    /// assert_eq!(PermutationStatistic::MajorIndex.of(&indices), 1 + 3);
+    /// assert_eq!(PermutationStatistic::Inversions.of(&indices), 3);
+    /// assert_eq!(PermutationStatistic::Excedances.of(&indices), 2);
+    /// ```
+    pub fn of(self, indices: &[usize]) -> usize {
+        (0..indices.len())
+            .map(|position| self.contribution(indices, position))
+            .sum()
+    }
+
+    /// Returns the part of the statistic contributed by the given position, which depends only
+    /// on the indices up to and including that position.
+    fn contribution(self, indices: &[usize], position: usize) -> usize {
+        let index = indices[position];
+        let descent = position > 0 && indices[position - 1] > index;
+        match self {
+            PermutationStatistic::Descents => usize::from(descent),
+            PermutationStatistic::MajorIndex if descent => position,
+            PermutationStatistic::MajorIndex => 0,
+            PermutationStatistic::Inversions => {
+                indices[..position].iter().filter(|i| **i > index).count()
+            }
+            PermutationStatistic::Excedances => usize::from(index > position),
+        }
+    }
+}
+
+/// An iterator which generates permutations along with the value of a statistic for each,
+/// created by [`Permutations::with_statistic`].
+///
+/// Every statistic is a sum of contributions from each position which depend only on the indices
+/// up to that position, so the running totals for the previous permutation are kept, and only the
+/// positions after the longest prefix it shares with the next permutation are recomputed.  In
+/// lexicographic order, consecutive permutations usually differ only in their last few
+/// positions.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{PermutationStatistic, Permutations};
+///
+/// let inversions = PermutationStatistic::Inversions;
+/// let mut perms = Permutations::of_length(1..4, 3).with_statistic(inversions);
+/// assert_eq!(perms.next(), Some((vec![1, 2, 3], 0)));
+/// assert_eq!(perms.next(), Some((vec![1, 3, 2], 1)));
+/// assert_eq!(perms.next(), Some((vec![2, 1, 3], 1)));
+/// assert_eq!(perms.map(|(_, inversions)| inversions).collect::<Vec<_>>(), vec![2, 2, 3]);
+/// ```
+pub struct WithStatistic<T> {
+    pub(crate) permutations: Permutations<T>,
+    statistic: PermutationStatistic,
+    /// The indices of the previous permutation.
+    previous: Vec<usize>,
+    /// The totals of the contributions of the first `i` positions of the previous permutation,
+    /// for each `i` up to its length.
+    totals: Vec<usize>,
+}
+
+impl<T> WithStatistic<T> {
+    /// Wraps the given permutations so that each is yielded with the given statistic.
+    pub(crate) fn new(permutations: Permutations<T>, statistic: PermutationStatistic) -> Self {
+        WithStatistic {
+            permutations,
+            statistic,
+            previous: Vec::new(),
+            totals: vec![0],
+        }
+    }
+
+    /// Returns the statistic computed for each permutation.
+    pub fn statistic(&self) -> PermutationStatistic {
+        self.statistic
+    }
+
+    /// Returns the value of the statistic for the permutation with the given indices, reusing
+    /// the totals for the prefix it shares with the previous permutation.
+    pub(crate) fn statistic_of(&mut self, indices: &[usize]) -> usize {
+        let shared = self
+            .previous
+            .iter()
+            .zip(indices)
+            .take_while(|(a, b)| a == b)
+            .count();
+        self.totals.truncate(shared + 1);
+        for position in shared..indices.len() {
+            let total = self.totals[position] + self.statistic.contribution(indices, position);
+            self.totals.push(total);
+        }
+        self.previous.clear();
+        self.previous.extend_from_slice(indices);
+        self.totals[indices.len()]
+    }
+}
+
+/// Returns the Eulerian number for `n` and `k`, which is the number of permutations of `n`
+/// elements with exactly `k` descents, or `None` if it does not fit in a `u128`.  Returns zero if
+/// there can be no such permutations.
+///
+/// The numbers are computed row by row with the recurrence
+/// `A(n, k) = (k + 1) A(n - 1, k) + (n - k) A(n - 1, k - 1)`, keeping only the entries up to `k`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{eulerian, PermutationStatistic, Permutations};
+///
+/// let row: Vec<u128> = (0..5).map(|k| eulerian(5, k).unwrap()).collect();
+/// assert_eq!(row, vec![1, 26, 66, 26, 1]);
+/// assert_eq!(eulerian(0, 0), Some(1));
+/// assert_eq!(eulerian(5, 5), Some(0));
+///
+/// let one_descent = Permutations::of_length(0..6, 6)
+///     .with_statistic(PermutationStatistic::Descents)
+///     .filter(|(_, descents)| *descents == 1)
+///     .count();
+/// assert_eq!(one_descent as u128, eulerian(6, 1).unwrap());
+/// ```
+pub fn eulerian(n: usize, k: usize) -> Option<u128> {
+    let mut row: Vec<Option<u128>> = vec![Some(1)];
+    row.resize(k + 1, Some(0));
+    for m in 1..=n {
+        // Each entry depends on the entries at and to the left of it in the row above, so the
+        // row is updated from right to left.
+        for j in (0..=k).rev() {
+            let stay = row[j].and_then(|above| above.checked_mul(j as u128 + 1));
+            let moved = match j {
+                0 => Some(0),
+                _ => row[j - 1].and_then(|left| left.checked_mul(m.saturating_sub(j) as u128)),
+            };
+            row[j] = stay.zip(moved).and_then(|(a, b)| a.checked_add(b));
+        }
+    }
+    row[k]
+}
+
+/// Returns the Mahonian number for `n` and `k`, which is the number of permutations of `n`
+/// elements with exactly `k` inversions, or equally with major index `k`, or `None` if it does
+/// not fit in a `u128`.  Returns zero if `k` is greater than `n (n - 1) / 2`.
+///
+/// This is the coefficient of `q^k` in the q-factorial given by [`q_factorial`].  The coefficients
+/// are symmetric, so only those up to the smaller of `k` and `n (n - 1) / 2 - k` are computed.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{mahonian, PermutationStatistic, Permutations};
+///
+/// let row: Vec<u128> = (0..7).map(|k| mahonian(4, k).unwrap()).collect();
+/// assert_eq!(row, vec![1, 3, 5, 6, 5, 3, 1]);
+/// assert_eq!(mahonian(4, 7), Some(0));
+///
+/// let major_index_five = Permutations::of_length(0..6, 6)
+///     .with_statistic(PermutationStatistic::MajorIndex)
+///     .filter(|(_, major)| *major == 5)
+///     .count();
+/// assert_eq!(major_index_five as u128, mahonian(6, 5).unwrap());
+/// ```
+pub fn mahonian(n: usize, k: usize) -> Option<u128> {
+    let degree = n * n.saturating_sub(1) / 2;
+    if k > degree {
+        return Some(0);
+    }
+    let k = k.min(degree - k);
+    q_factorial_up_to(n, k)[k]
+}
+
+/// Returns the coefficients of the q-factorial `[n]_q! = [1]_q [2]_q ... [n]_q`, where
+/// `[i]_q = 1 + q + ... + q^(i - 1)`, from the constant term up to the term of degree
+/// `n (n - 1) / 2`, or `None` if any of them does not fit in a `u128`.  The coefficient of `q^k`
+/// is the Mahonian number for `n` and `k`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{factorial, q_factorial};
+///
+/// assert_eq!(q_factorial(3), Some(vec![1, 2, 2, 1]));
+/// assert_eq!(q_factorial(0), Some(vec![1]));
+///
+/// let coefficients = q_factorial(10).unwrap();
+/// assert_eq!(coefficients.len(), 46);
+/// assert_eq!(coefficients.iter().sum::<u128>(), factorial(10));
+/// ```
+pub fn q_factorial(n: usize) -> Option<Vec<u128>> {
+    let degree = n * n.saturating_sub(1) / 2;
+    q_factorial_up_to(n, degree).into_iter().collect()
+}
+
+/// Returns the coefficients of the q-factorial `[n]_q!` up to the term of degree `k`, each of
+/// which is `None` if it does not fit in a `u128`.
+fn q_factorial_up_to(n: usize, k: usize) -> Vec<Option<u128>> {
+    let mut coefficients: Vec<Option<u128>> = vec![Some(1)];
+    coefficients.resize(k + 1, Some(0));
+    for i in 2..=n {
+        // Multiplying by `[i]_q` replaces each coefficient by the sum of the `i` coefficients
+        // ending at it, which is a running sum less the part which has fallen out of the window.
+        let mut sums: Vec<Option<u128>> = Vec::with_capacity(k + 2);
+        sums.push(Some(0));
+        for coefficient in coefficients.iter() {
+            let sum = sums.last().unwrap().zip(*coefficient);
+            sums.push(sum.and_then(|(a, b)| a.checked_add(b)));
+        }
+        for (j, coefficient) in coefficients.iter_mut().enumerate() {
+            let window = sums[j + 1].zip(sums[(j + 1).saturating_sub(i)]);
+            *coefficient = window.map(|(total, before)| total - before);
+        }
+    }
+    coefficients
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statistics_match_definitions() {
+        let statistics = [
+            PermutationStatistic::Descents,
+            PermutationStatistic::MajorIndex,
+            PermutationStatistic::Inversions,
+            PermutationStatistic::Excedances,
+        ];
+        for statistic in statistics {
+            for perms in [Permutations::of_length(0..6, 6), Permutations::all(0..4)] {
+                for (perm, value) in perms.with_statistic(statistic) {
+                    let descents = (1..perm.len()).filter(|i| perm[i - 1] > perm[*i]);
+                    let expected = match statistic {
+                        PermutationStatistic::Descents => descents.count(),
+                        PermutationStatistic::MajorIndex => descents.sum(),
+                        PermutationStatistic::Inversions => (0..perm.len())
+                            .map(|j| (0..j).filter(|i| perm[*i] > perm[j]).count())
+                            .sum(),
+                        PermutationStatistic::Excedances => {
+                            perm.iter().enumerate().filter(|(i, p)| **p > *i).count()
+                        }
+                    };
+                    assert_eq!(value, expected);
+                    assert_eq!(statistic.of(&perm), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_statistics_counts_match_distributions() {
+        for n in 0..8 {
+            let mut descents = vec![0u128; n.max(1)];
+            let mut inversions = vec![0u128; n * n.saturating_sub(1) / 2 + 1];
+            let perms = Permutations::of_length(0..n, n);
+            for (perm, value) in perms.with_statistic(PermutationStatistic::Descents) {
+                descents[value] += 1;
+                inversions[PermutationStatistic::Inversions.of(&perm)] += 1;
+            }
+            for (k, count) in descents.iter().enumerate() {
+                assert_eq!(eulerian(n, k), Some(*count));
+            }
+            assert_eq!(eulerian(n, n.max(1)), Some(0));
+            for (k, count) in inversions.iter().enumerate() {
+                assert_eq!(mahonian(n, k), Some(*count));
+            }
+            assert_eq!(mahonian(n, inversions.len()), Some(0));
+            assert_eq!(q_factorial(n), Some(inversions));
+        }
+        assert_eq!(eulerian(40, 20), None);
+        assert!(eulerian(40, 0) == Some(1) && eulerian(40, 39) == Some(1));
+        assert_eq!(mahonian(40, 390), None);
+        assert_eq!(mahonian(40, 779), Some(39));
+    }
+}
@@ -0,0 +1,206 @@
+//! Strategies for drawing random items from the spaces enumerated by the generators, for use in
+//! property tests with `proptest`.
+//!
+//! This module is only available with the `proptest` feature.  Each strategy draws its values
+//! uniformly at random from the items which would be yielded by the corresponding generator, and
+//! in the same form, so a property can be checked against a random sample of a space which is too
+//! large to enumerate.  Permutations of points can also be drawn with `quickcheck`, since
+//! [`Permutation`](crate::Permutation) implements `quickcheck::Arbitrary` when the `quickcheck`
+//! feature is enabled.
+//!
+//! There are no `quickcheck::Arbitrary` implementations for combinations or partitions: they are
+//! plain `Vec`s, for which `quickcheck` already provides an implementation, and an `Arbitrary`
+//! value cannot be told which elements to draw from or what size to be, so these spaces are only
+//! available as the parameterized strategies below.
+//!
+//! # Examples
+//!
+//! ```
+//! use combinatorial::strategy;
+//! use proptest::strategy::{Strategy, ValueTree};
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::deterministic();
+//! let combos = strategy::combinations(0..20, 5);
+//! for _ in 0..100 {
+//!     let combo = combos.new_tree(&mut runner).unwrap().current();
+//!     assert_eq!(combo.len(), 5);
+//!     assert!(combo.windows(2).all(|pair| pair[0] < pair[1]));
+//! }
+//! ```
+
+use std::fmt::Debug;
+
+use proptest::sample::subsequence;
+use proptest::strategy::{Just, Strategy};
+
+use crate::sorted_unique;
+
+/// Returns a strategy which draws a combination of the given size uniformly at random from those
+/// yielded by [`Combinations::of_size`](crate::Combinations::of_size) for the given elements,
+/// with its elements in increasing order.
+///
+/// # Panics
+///
+/// Panics if the size is greater than the number of unique elements.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::strategy;
+/// use proptest::strategy::{Strategy, ValueTree};
+/// use proptest::test_runner::TestRunner;
+///
+/// let mut runner = TestRunner::deterministic();
+/// let combo = strategy::combinations(vec!['a', 'b', 'c'], 3)
+///     .new_tree(&mut runner)
+///     .unwrap()
+///     .current();
+/// assert_eq!(combo, vec!['a', 'b', 'c']);
+/// ```
+pub fn combinations<T>(
+    elements: impl IntoIterator<Item = T>,
+    size: usize,
+) -> impl Strategy<Value = Vec<T>>
+where
+    T: Ord + Clone + Debug + 'static,
+{
+    subsequence(sorted_unique(elements), size)
+}
+
+/// Returns a strategy which draws a permutation of the given length uniformly at random from
+/// those yielded by [`Permutations::of_length`](crate::Permutations::of_length) for the given
+/// elements.
+///
+/// # Panics
+///
+/// Panics if the length is greater than the number of unique elements.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::strategy;
+/// use proptest::strategy::{Strategy, ValueTree};
+/// use proptest::test_runner::TestRunner;
+///
+/// let mut runner = TestRunner::deterministic();
+/// let mut perm = strategy::permutations(1..=4, 4)
+///     .new_tree(&mut runner)
+///     .unwrap()
+///     .current();
+/// perm.sort();
+/// assert_eq!(perm, vec![1, 2, 3, 4]);
+/// ```
+pub fn permutations<T>(
+    elements: impl IntoIterator<Item = T>,
+    length: usize,
+) -> impl Strategy<Value = Vec<T>>
+where
+    T: Ord + Clone + Debug + 'static,
+{
+    subsequence(sorted_unique(elements), length).prop_shuffle()
+}
+
+/// Returns a strategy which draws a partition into groups of the given sizes uniformly at random
+/// from those yielded by [`SizedPartitions::new`](crate::SizedPartitions::new) for the given
+/// elements, in the same canonical form: each group is sorted, and groups of equal size are
+/// ordered by their smallest elements.
+///
+/// # Panics
+///
+/// Panics if the sizes do not sum to the number of unique elements.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::strategy;
+/// use proptest::strategy::{Strategy, ValueTree};
+/// use proptest::test_runner::TestRunner;
+///
+/// let mut runner = TestRunner::deterministic();
+/// let teams = strategy::sized_partitions(1..=6, &[2, 2, 2])
+///     .new_tree(&mut runner)
+///     .unwrap()
+///     .current();
+/// assert_eq!(teams.len(), 3);
+/// assert_eq!(teams[0][0], 1);
+/// assert!(teams[0][0] < teams[1][0] && teams[1][0] < teams[2][0]);
+/// ```
+pub fn sized_partitions<T>(
+    elements: impl IntoIterator<Item = T>,
+    sizes: &[usize],
+) -> impl Strategy<Value = Vec<Vec<T>>>
+where
+    T: Ord + Clone + Debug + 'static,
+{
+    let elements = sorted_unique(elements);
+    assert_eq!(
+        sizes.iter().sum::<usize>(),
+        elements.len(),
+        "the sizes must sum to the number of unique elements"
+    );
+    let sizes = sizes.to_vec();
+    Just(elements)
+        .prop_shuffle()
+        .prop_map(move |shuffled| canonical_partition(shuffled, &sizes))
+}
+
+/// Splits the given elements into consecutive groups of the given sizes, then puts the partition
+/// into the canonical form yielded by [`SizedPartitions`](crate::SizedPartitions).
+fn canonical_partition<T: Ord>(elements: Vec<T>, sizes: &[usize]) -> Vec<Vec<T>> {
+    let mut remaining = elements.into_iter();
+    let mut groups: Vec<Vec<T>> = sizes
+        .iter()
+        .map(|size| {
+            let mut group: Vec<T> = remaining.by_ref().take(*size).collect();
+            group.sort();
+            group
+        })
+        .collect();
+    for size in sizes {
+        let slots: Vec<usize> = (0..sizes.len()).filter(|i| sizes[*i] == *size).collect();
+        let mut same_size: Vec<Vec<T>> = slots
+            .iter()
+            .map(|slot| std::mem::take(&mut groups[*slot]))
+            .collect();
+        same_size.sort();
+        for (slot, group) in slots.into_iter().zip(same_size) {
+            groups[slot] = group;
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Combinations, SizedPartitions};
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_strategy_draws_uniformly() {
+        let mut runner = TestRunner::deterministic();
+        let partitions = sized_partitions(0..5, &[2, 1, 2]);
+        let mut counts: BTreeMap<Vec<Vec<i32>>, usize> = SizedPartitions::new(0..5, &[2, 1, 2])
+            .map(|partition| (partition, 0))
+            .collect();
+        for _ in 0..3000 {
+            let partition = partitions.new_tree(&mut runner).unwrap().current();
+            *counts.get_mut(&partition).unwrap() += 1;
+        }
+        assert_eq!(counts.len(), 15);
+        assert!(counts.values().all(|count| (100..300).contains(count)));
+
+        let combos = combinations(0..6, 3);
+        let mut counts: BTreeMap<Vec<i32>, usize> = Combinations::of_size(0..6, 3)
+            .map(|combo| (combo, 0))
+            .collect();
+        for _ in 0..2000 {
+            let combo = combos.new_tree(&mut runner).unwrap().current();
+            *counts.get_mut(&combo).unwrap() += 1;
+        }
+        assert!(counts.values().all(|count| (50..150).contains(count)));
+    }
+}
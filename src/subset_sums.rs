@@ -0,0 +1,231 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+use num_traits::{CheckedAdd, Num};
+
+use crate::sorted_unique;
+
+/// An iterator which generates the subsets of a set of elements whose sum is a given target,
+/// created by [`subset_sums`].
+///
+/// Rather than filtering all `2^n` subsets, the sorted elements are split into a low half and a
+/// high half, and the sums of all subsets of the high half are computed and sorted up front.  Each
+/// subset of the low half is then completed by looking up the high subsets with exactly the
+/// remaining sum, so only `O(2^(n/2))` sums are ever computed and stored.  This makes it practical
+/// to search sets of up to around 40 elements.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::subset_sums;
+///
+/// let mut subsets = subset_sums(vec![1, 2, 3, 4, 5], 8);
+/// assert_eq!(subsets.next(), Some(vec![3, 5]));
+/// assert_eq!(subsets.next(), Some(vec![1, 3, 4]));
+/// assert_eq!(subsets.next(), Some(vec![1, 2, 5]));
+/// assert_eq!(subsets.next(), None);
+/// ```
+pub struct SubsetSums<T> {
+    elements: Vec<T>,
+    split: usize,
+    target: T,
+    high: Vec<(T, usize)>,
+    low: usize,
+    matches: Range<usize>,
+    started: bool,
+    done: bool,
+}
+
+/// Returns an iterator over the subsets of the unique elements in the given iterable whose sum is
+/// equal to the given target.  Each subset is yielded in sorted order, and the subsets are the
+/// same as those found by filtering [`powerset`](crate::powerset) by their sums, though they are
+/// yielded in a different order.  Negative elements and targets are supported.
+///
+/// Sums are computed in `T` and never overflow: a subset is skipped if the sum of its elements from
+/// either half overflows.  This only loses subsets with the target sum when the elements are of a
+/// signed type and have mixed signs, in which case a wider element type should be used.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::subset_sums;
+///
+/// let subsets: Vec<Vec<i32>> = subset_sums(vec![-3, -1, 2, 4], 1).collect();
+/// assert_eq!(subsets, vec![vec![-3, 4], vec![-1, 2]]);
+///
+/// assert_eq!(subset_sums(vec![1, 2, 3], 0).collect::<Vec<_>>(), vec![vec![]]);
+/// assert_eq!(subset_sums(vec![2, 4, 6], 5).next(), None);
+///
+/// let powers: Vec<u64> = (0..40).map(|i| 1 << i).collect();
+/// let mut subsets = subset_sums(powers, 0b1010_0000_0000_0000_0000_0000_0000_0000_0000_1001);
+/// assert_eq!(subsets.next(), Some(vec![1, 8, 1 << 37, 1 << 39]));
+/// assert_eq!(subsets.next(), None);
+///
+/// let subsets: Vec<Vec<u8>> = subset_sums(vec![100, 200, 250], 250).collect();
+/// assert_eq!(subsets, vec![vec![250]]);
+/// ```
+pub fn subset_sums<T: Num + CheckedAdd + Ord + Clone>(
+    elements: impl IntoIterator<Item = T>,
+    target: T,
+) -> SubsetSums<T> {
+    let elements = sorted_unique(elements);
+    let split = elements.len() / 2;
+    let mut high: Vec<(T, usize)> = (0..1 << (elements.len() - split))
+        .filter_map(|mask| Some((mask_sum(&elements[split..], mask)?, mask)))
+        .collect();
+    high.sort_by(|a, b| a.0.cmp(&b.0));
+    SubsetSums {
+        elements,
+        split,
+        target,
+        high,
+        low: 0,
+        matches: 0..0,
+        started: false,
+        done: false,
+    }
+}
+
+/// Returns the sum of the sorted elements whose indices are set in the given mask, or `None` if
+/// it overflows `T`.
+///
+/// The smallest remaining element is added while the running sum is non-negative and the largest
+/// while it is negative, so the running sum only overflows if the final sum does.
+fn mask_sum<T: Num + CheckedAdd + Ord>(elements: &[T], mask: usize) -> Option<T> {
+    let mut selected = elements
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| mask >> i & 1 == 1)
+        .map(|(_, element)| element);
+    let mut sum = T::zero();
+    loop {
+        let next = match sum < T::zero() {
+            true => selected.next_back(),
+            false => selected.next(),
+        };
+        match next {
+            Some(element) => sum = sum.checked_add(element)?,
+            None => return Some(sum),
+        }
+    }
+}
+
+/// Compares `low + high` against the target without computing the total in `T`, where it could
+/// overflow.  A total which overflows is greater than every target if `high` is positive and less
+/// than every target otherwise, which keeps the comparison monotone in `high`.
+fn compare_total<T: Num + CheckedAdd + Ord>(low: &T, high: &T, target: &T) -> Ordering {
+    match low.checked_add(high) {
+        Some(total) => total.cmp(target),
+        None if *high > T::zero() => Ordering::Greater,
+        None => Ordering::Less,
+    }
+}
+
+impl<T: Num + CheckedAdd + Ord + Clone> SubsetSums<T> {
+    /// Returns the subset made up of the low elements in the given low mask and the high elements
+    /// in the given high mask.
+    fn get_subset(&self, low: usize, high: usize) -> Vec<T> {
+        let (lower, upper) = self.elements.split_at(self.split);
+        let lower = lower.iter().enumerate().filter(|(i, _)| low >> i & 1 == 1);
+        let upper = upper.iter().enumerate().filter(|(i, _)| high >> i & 1 == 1);
+        lower.chain(upper).map(|(_, e)| e.clone()).collect()
+    }
+
+    /// Moves on to the next subset of the low elements, finding the range of high subsets whose
+    /// sums complete it to the target.  Returns `false` if every low subset has been used.
+    fn move_to_next_low(&mut self) -> bool {
+        if self.started {
+            self.low += 1;
+        } else {
+            self.started = true;
+        }
+        if self.low >> self.split != 0 {
+            return false;
+        }
+        // Compare totals rather than subtracting from the target, which could underflow for
+        // unsigned types, and without adding in `T`, which could overflow.
+        let Some(low_sum) = mask_sum(&self.elements[..self.split], self.low) else {
+            self.matches = 0..0;
+            return true;
+        };
+        let compare = |sum: &T| compare_total(&low_sum, sum, &self.target);
+        let start = self
+            .high
+            .partition_point(|(sum, _)| compare(sum) == Ordering::Less);
+        let end = self
+            .high
+            .partition_point(|(sum, _)| compare(sum) != Ordering::Greater);
+        self.matches = start..end;
+        true
+    }
+}
+
+impl<T: Num + CheckedAdd + Ord + Clone> Iterator for SubsetSums<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next subset with the target sum and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            if let Some(index) = self.matches.next() {
+                return Some(self.get_subset(self.low, self.high[index].1));
+            }
+            if !self.move_to_next_low() {
+                self.done = true;
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::powerset;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_subset_sums_match_filtered_powerset() {
+        let elements = vec![7, -4, 3, 0, 12, -9, 5, 5, 1, 8, -2];
+        for target in -20..=30 {
+            let subsets: Vec<Vec<i32>> = subset_sums(elements.clone(), target).collect();
+            let unique: BTreeSet<Vec<i32>> = subsets.iter().cloned().collect();
+            assert_eq!(unique.len(), subsets.len());
+            let expected: BTreeSet<Vec<i32>> = powerset(elements.clone())
+                .filter(|subset| subset.iter().sum::<i32>() == target)
+                .collect();
+            assert_eq!(unique, expected);
+        }
+        let empty: Vec<Vec<u8>> = subset_sums(Vec::new(), 0).collect();
+        assert_eq!(empty, vec![vec![]]);
+        assert_eq!(subset_sums(Vec::<u8>::new(), 1).next(), None);
+        let subsets: Vec<Vec<u8>> = subset_sums(vec![2, 3, 1, 4], 3).collect();
+        assert_eq!(subsets, vec![vec![3], vec![1, 2]]);
+    }
+
+    #[test]
+    fn test_subset_sums_do_not_overflow() {
+        let subsets: Vec<Vec<u8>> = subset_sums(vec![100, 200], 100).collect();
+        assert_eq!(subsets, vec![vec![100]]);
+        let elements: Vec<u8> = vec![100, 200, 150, 250, 5, 55];
+        for target in 0..=u8::MAX {
+            let subsets: BTreeSet<Vec<u8>> = subset_sums(elements.clone(), target).collect();
+            let expected: BTreeSet<Vec<u8>> = powerset(elements.clone())
+                .filter(|subset| subset.iter().map(|&e| e as u32).sum::<u32>() == target as u32)
+                .collect();
+            assert_eq!(subsets, expected);
+        }
+        let elements: Vec<i8> = vec![-128, -100, -50, 50, 100, 127];
+        for target in i8::MIN..=i8::MAX {
+            let subsets: BTreeSet<Vec<i8>> = subset_sums(elements.clone(), target).collect();
+            let expected: BTreeSet<Vec<i8>> = powerset(elements.clone())
+                .filter(|subset| subset.iter().map(|&e| e as i32).sum::<i32>() == target as i32)
+                .collect();
+            assert!(subsets.is_subset(&expected));
+        }
+        let subsets: BTreeSet<Vec<i8>> = subset_sums(vec![-60, -20, 30, 90], 10).collect();
+        assert_eq!(subsets, BTreeSet::from([vec![-20, 30], vec![-60, -20, 90]]));
+    }
+}
@@ -0,0 +1,288 @@
+use crate::combinations::size_hint_from;
+use crate::sorted_unique;
+
+/// The order in which a [`SwapWalk`] visits the permutations of its elements.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{SwapOrder, SwapWalk};
+///
+/// let lex: Vec<Vec<i32>> = SwapWalk::of(1..=3).map(|(perm, _)| perm).collect();
+/// assert_eq!(lex[..3], [vec![1, 2, 3], vec![1, 3, 2], vec![2, 1, 3]]);
+///
+/// let sjt = SwapWalk::of(1..=3).order(SwapOrder::SteinhausJohnsonTrotter);
+/// let sjt: Vec<Vec<i32>> = sjt.map(|(perm, _)| perm).collect();
+/// assert_eq!(sjt[..3], [vec![1, 2, 3], vec![1, 3, 2], vec![3, 1, 2]]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SwapOrder {
+    /// Lexicographic order.  Consecutive permutations may differ by several adjacent swaps.
+    #[default]
+    Lex,
+    /// The Steinhaus-Johnson-Trotter order, or plain changes, in which consecutive permutations
+    /// always differ by a single adjacent swap, so the walk follows the edges of the
+    /// permutohedron.
+    SteinhausJohnsonTrotter,
+}
+
+/// An iterator which generates every permutation of a set of elements, each together with the
+/// shortest sequence of adjacent swaps which turns the previous permutation into it.
+///
+/// Each swap is given by the position `i` of the left element of the pair, meaning that the
+/// elements in positions `i` and `i + 1` were exchanged, and the swaps are listed in the order in
+/// which they are applied.  The first permutation has no swaps.  Consumers which maintain a data
+/// structure derived from the current permutation, such as a running cost or a partial sum, can
+/// update it one swap at a time rather than rebuilding it.  In lexicographic order, consecutive
+/// permutations differ by as many swaps as they have inversions relative to each other, which is
+/// fewer than two on average, while in [`SwapOrder::SteinhausJohnsonTrotter`] order they always
+/// differ by exactly one.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{SwapOrder, SwapWalk};
+///
+/// let mut walk = SwapWalk::of(vec!['a', 'b', 'c']);
+/// assert_eq!(walk.next(), Some((vec!['a', 'b', 'c'], vec![])));
+/// assert_eq!(walk.next(), Some((vec!['a', 'c', 'b'], vec![1])));
+/// assert_eq!(walk.next(), Some((vec!['b', 'a', 'c'], vec![1, 0])));
+///
+/// // Keep a running total of each element weighted by its position, one swap at a time.
+/// let weighted = |perm: &[i64]| perm.iter().zip(0..).map(|(x, i)| x * i).sum::<i64>();
+/// let mut current = vec![1, 2, 3, 4, 5];
+/// let mut total = weighted(&current);
+/// for (perm, swaps) in SwapWalk::of(1..=5).order(SwapOrder::SteinhausJohnsonTrotter) {
+///     for i in swaps {
+///         total += current[i] - current[i + 1];
+///         current.swap(i, i + 1);
+///     }
+///     assert_eq!(current, perm);
+///     assert_eq!(total, weighted(&perm));
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SwapWalk<T> {
+    elements: Vec<T>,
+    order: SwapOrder,
+    positions: Vec<usize>,
+    moving_left: Vec<bool>,
+    swaps: Vec<usize>,
+    total: Option<u128>,
+    yielded: u128,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord + Clone> SwapWalk<T> {
+    /// Creates a new `SwapWalk` iterator which will yield every permutation of the elements in the
+    /// given iterable, in lexicographic order unless another order is selected with
+    /// [`SwapWalk::order`].  With no elements, a single empty permutation is yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SwapWalk;
+    ///
+    /// let walk = SwapWalk::of(0..4);
+    /// assert_eq!(walk.size_hint(), (24, Some(24)));
+    /// assert_eq!(walk.last(), Some((vec![3, 2, 1, 0], vec![2])));
+    ///
+    /// assert_eq!(SwapWalk::of(Vec::<u8>::new()).collect::<Vec<_>>(), vec![(vec![], vec![])]);
+    /// ```
+    pub fn of(elements: impl IntoIterator<Item = T>) -> Self {
+        let elements = sorted_unique(elements);
+        let count = elements.len();
+        SwapWalk {
+            total: (1..=count as u128).try_fold(1u128, |total, k| total.checked_mul(k)),
+            elements,
+            order: SwapOrder::Lex,
+            positions: (0..count).collect(),
+            moving_left: vec![true; count],
+            swaps: Vec::new(),
+            yielded: 0,
+            started: false,
+            done: false,
+        }
+    }
+}
+
+impl<T> SwapWalk<T> {
+    /// Sets the order in which permutations are visited.  This should be called before iterating,
+    /// since the first permutation is the same in every order but later ones are not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{SwapOrder, SwapWalk};
+    ///
+    /// let walk = SwapWalk::of(0..5).order(SwapOrder::SteinhausJohnsonTrotter);
+    /// assert!(walk.skip(1).all(|(_, swaps)| swaps.len() == 1));
+    /// ```
+    pub fn order(mut self, order: SwapOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Returns the sorted, deduplicated elements which this iterator permutes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SwapWalk;
+    ///
+    /// assert_eq!(SwapWalk::of(vec![3, 1, 3, 2]).elements(), &[1, 2, 3]);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Exchanges the elements in positions `i` and `i + 1` and records the swap.
+    fn swap(&mut self, i: usize) {
+        self.positions.swap(i, i + 1);
+        self.swaps.push(i);
+    }
+
+    /// Moves to the next permutation in lexicographic order, recording the adjacent swaps which
+    /// reach it.  Returns `false` if the current permutation is the last.
+    fn step_lex(&mut self) -> bool {
+        let Some(pivot) = self
+            .positions
+            .windows(2)
+            .rposition(|pair| pair[0] < pair[1])
+        else {
+            return false;
+        };
+        let mut target = self.positions[pivot..].to_vec();
+        let successor = target.iter().rposition(|p| *p > target[0]).unwrap();
+        target.swap(0, successor);
+        target[1..].reverse();
+        for (offset, wanted) in target.into_iter().enumerate() {
+            let start = pivot + offset;
+            let found = start
+                + self.positions[start..]
+                    .iter()
+                    .position(|p| *p == wanted)
+                    .unwrap();
+            for i in (start..found).rev() {
+                self.swap(i);
+            }
+        }
+        true
+    }
+
+    /// Moves to the next permutation in Steinhaus-Johnson-Trotter order, by moving the largest
+    /// element which can move one step in its direction past a smaller element, then reversing
+    /// the direction of every larger element.  Returns `false` if no element can move.
+    fn step_sjt(&mut self) -> bool {
+        let count = self.positions.len();
+        let mobile = (0..count)
+            .filter(|i| {
+                let index = self.positions[*i];
+                if self.moving_left[index] {
+                    *i > 0 && self.positions[*i - 1] < index
+                } else {
+                    *i + 1 < count && self.positions[*i + 1] < index
+                }
+            })
+            .max_by_key(|i| self.positions[*i]);
+        let Some(i) = mobile else {
+            return false;
+        };
+        let index = self.positions[i];
+        self.swap(if self.moving_left[index] { i - 1 } else { i });
+        for direction in self.moving_left[index + 1..].iter_mut() {
+            *direction = !*direction;
+        }
+        true
+    }
+
+    /// Moves to the next permutation.  Returns `false` and marks the iterator as done if no
+    /// permutations remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        self.swaps.clear();
+        let found = if self.started {
+            match self.order {
+                SwapOrder::Lex => self.step_lex(),
+                SwapOrder::SteinhausJohnsonTrotter => self.step_sjt(),
+            }
+        } else {
+            self.started = true;
+            true
+        };
+        if found {
+            self.yielded += 1;
+        } else {
+            self.done = true;
+        }
+        found
+    }
+}
+
+impl<T: Clone> Iterator for SwapWalk<T> {
+    type Item = (Vec<T>, Vec<usize>);
+
+    /// Returns the next permutation, along with the swaps which turn the previous permutation
+    /// into it, and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        let perm = self
+            .positions
+            .iter()
+            .map(|p| self.elements[*p].clone())
+            .collect();
+        Some((perm, self.swaps.clone()))
+    }
+
+    /// Returns the exact number of remaining permutations, if it fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_from(self.total.map(|total| total - self.yielded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Permutations;
+
+    #[test]
+    fn test_swap_walk_swaps_reach_each_permutation() {
+        for n in 0..7usize {
+            for order in [SwapOrder::Lex, SwapOrder::SteinhausJohnsonTrotter] {
+                let mut walk = SwapWalk::of(0..n).order(order);
+                let mut previous: Option<Vec<usize>> = None;
+                let mut seen = Vec::new();
+                while let Some((perm, swaps)) = walk.next() {
+                    match previous {
+                        None => assert!(swaps.is_empty()),
+                        Some(mut previous) => {
+                            let position = |x| perm.iter().position(|y| *y == x);
+                            let inversions = (0..n)
+                                .flat_map(|a| (a + 1..n).map(move |b| (a, b)))
+                                .filter(|(a, b)| position(previous[*a]) > position(previous[*b]))
+                                .count();
+                            assert_eq!(swaps.len(), inversions);
+                            for i in swaps {
+                                previous.swap(i, i + 1);
+                            }
+                            assert_eq!(previous, perm);
+                        }
+                    }
+                    seen.push(perm.clone());
+                    previous = Some(perm);
+                    let remaining = (1..=n).product::<usize>() - seen.len();
+                    assert_eq!(walk.size_hint(), (remaining, Some(remaining)));
+                }
+                if order == SwapOrder::SteinhausJohnsonTrotter {
+                    seen.sort();
+                }
+                assert_eq!(seen, Permutations::of_length(0..n, n).collect::<Vec<_>>());
+            }
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use crate::combinations::size_hint_from;
+use crate::{permutation_count, Permutation};
+
+/// An iterator over the symmetric group `S_n`: every [`Permutation`] of the points `0..n`, in
+/// lexicographic order of their images.
+///
+/// Unlike [`Permutations`](crate::Permutations), which arranges a list of elements and yields
+/// each arrangement as a `Vec`, this yields the permutations themselves, ready to be composed,
+/// inverted, or applied, without a list of elements standing in for the points.  The position
+/// of each permutation in the enumeration is given by [`SymmetricGroup::rank`], and the
+/// permutation at any position by [`SymmetricGroup::unrank`].  With
+/// [`SymmetricGroup::even_only`], only the even permutations are generated, as by
+/// [`AlternatingGroup`].
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{Permutation, SymmetricGroup};
+///
+/// let group = SymmetricGroup::new(3);
+/// let cycle = Permutation::from_images(vec![1, 2, 0]).unwrap();
+/// assert_eq!(group.order(), Some(6));
+/// assert_eq!(group.rank(&cycle), Some(3));
+/// assert_eq!(group.unrank(3), Some(cycle));
+///
+/// let transpositions = group.filter(|p| p.cycle_type() == vec![2, 1]).count();
+/// assert_eq!(transpositions, 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SymmetricGroup {
+    degree: usize,
+    current: Option<Vec<usize>>,
+    /// Whether the permutation in `current` is odd.
+    odd: bool,
+    even_only: bool,
+    yielded: u128,
+}
+
+impl SymmetricGroup {
+    /// Creates a new `SymmetricGroup` iterator over every permutation of the points `0..n`,
+    /// starting from the identity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SymmetricGroup;
+    ///
+    /// let images: Vec<Vec<usize>> = SymmetricGroup::new(3).map(|p| p.into_images()).collect();
+    /// assert_eq!(images[0], vec![0, 1, 2]);
+    /// assert_eq!(images[1], vec![0, 2, 1]);
+    /// assert_eq!(images[5], vec![2, 1, 0]);
+    /// assert_eq!(SymmetricGroup::new(0).count(), 1);
+    /// ```
+    pub fn new(n: usize) -> Self {
+        SymmetricGroup {
+            degree: n,
+            current: Some((0..n).collect()),
+            odd: false,
+            even_only: false,
+            yielded: 0,
+        }
+    }
+
+    /// Restricts the iterator to the even permutations, which form the alternating group `A_n`,
+    /// and restarts it from the identity.  The positions given by [`SymmetricGroup::rank`] and
+    /// taken by [`SymmetricGroup::unrank`] then count only the even permutations.
+    ///
+    /// The even permutations are generated directly rather than by filtering: in lexicographic
+    /// order, the permutations come in consecutive pairs which differ only by swapping the images
+    /// of the last two points, so exactly one of each pair is even.  The iterator steps from pair
+    /// to pair, keeping track of the parity of each step, and yields the even one of each pair
+    /// without testing the parity of any permutation, which halves the work of generating every
+    /// permutation and keeping the even ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SymmetricGroup;
+    ///
+    /// let even = SymmetricGroup::new(4).even_only();
+    /// assert_eq!(even.order(), Some(12));
+    /// assert!(even.clone().all(|p| p.is_even()));
+    /// assert_eq!(even.count(), 12);
+    /// ```
+    pub fn even_only(self) -> Self {
+        SymmetricGroup {
+            even_only: true,
+            ..SymmetricGroup::new(self.degree)
+        }
+    }
+
+    /// Returns the number of digits of the Lehmer code which determine a position, since with
+    /// [`SymmetricGroup::even_only`] the digit for the second last point is fixed by the parity.
+    fn ranked_digits(&self) -> usize {
+        if self.even_only {
+            self.degree.saturating_sub(2)
+        } else {
+            self.degree
+        }
+    }
+
+    /// Returns the degree of the group, which is the number of points it acts on.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Returns the number of elements in the group, which is `n` factorial, or half of that for
+    /// only the even permutations, or `None` if it does not fit in a `u128`.
+    pub fn order(&self) -> Option<u128> {
+        permutation_count(self.degree, self.ranked_digits())
+    }
+
+    /// Returns the number of elements in the group, as by [`SymmetricGroup::order`], but as a big
+    /// integer, so the count is exact however large it is.  This is only available with the
+    /// `bigint` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{factorial_big, SymmetricGroup};
+    ///
+    /// let group = SymmetricGroup::new(40);
+    /// assert_eq!(group.order(), None);
+    /// assert_eq!(group.total_count_big(), factorial_big(40));
+    /// assert_eq!(group.even_only().total_count_big(), factorial_big(40) / 2u8);
+    /// ```
+    #[cfg(feature = "bigint")]
+    pub fn total_count_big(&self) -> num_bigint::BigUint {
+        crate::big::permutation_count_big(self.degree, self.ranked_digits())
+    }
+
+    /// Returns the position of the given permutation in the enumeration, counting from zero, or
+    /// `None` if it moves a point not less than the degree of the group, if it is odd when only
+    /// the even permutations are generated, or if its position does not fit in a `u128`.  A
+    /// permutation of smaller degree is treated as fixing the remaining points.  This does not
+    /// depend on how far the iterator has advanced.
+    ///
+    /// The position is found from the Lehmer code of the permutation, which counts for each point
+    /// the images of later points which are smaller than its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{Permutation, SymmetricGroup};
+    ///
+    /// let group = SymmetricGroup::new(4);
+    /// let swap = Permutation::from_images(vec![1, 0]).unwrap();
+    /// assert_eq!(group.rank(&swap), Some(6));
+    /// assert_eq!(group.rank(&Permutation::identity(4)), Some(0));
+    /// assert_eq!(SymmetricGroup::new(1).rank(&swap), None);
+    /// assert_eq!(group.even_only().rank(&swap), None);
+    /// ```
+    pub fn rank(&self, permutation: &Permutation) -> Option<u128> {
+        let permutation = permutation.with_degree(self.degree)?;
+        if self.even_only && !permutation.is_even() {
+            return None;
+        }
+        let images = permutation.images();
+        let mut rank = 0u128;
+        for (point, image) in images.iter().enumerate().take(self.ranked_digits()) {
+            let smaller_later = images[point + 1..].iter().filter(|i| *i < image).count();
+            rank = rank
+                .checked_mul((self.degree - point) as u128)?
+                .checked_add(smaller_later as u128)?;
+        }
+        Some(rank)
+    }
+
+    /// Returns the permutation at the given position in the enumeration, counting from zero, or
+    /// `None` if the position is not less than the order of the group.  This does not depend on
+    /// how far the iterator has advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::SymmetricGroup;
+    ///
+    /// let group = SymmetricGroup::new(4);
+    /// assert_eq!(group.unrank(0).map(|p| p.into_images()), Some(vec![0, 1, 2, 3]));
+    /// assert_eq!(group.unrank(23).map(|p| p.into_images()), Some(vec![3, 2, 1, 0]));
+    /// assert_eq!(group.unrank(24), None);
+    ///
+    /// let large = SymmetricGroup::new(40);
+    /// assert_eq!(large.order(), None);
+    /// let last = large.unrank(u128::MAX).unwrap();
+    /// assert_eq!(large.rank(&last), Some(u128::MAX));
+    ///
+    /// let even = SymmetricGroup::new(4).even_only();
+    /// assert_eq!(even.unrank(11).map(|p| p.into_images()), Some(vec![3, 2, 1, 0]));
+    /// assert_eq!(even.unrank(12), None);
+    /// ```
+    pub fn unrank(&self, mut rank: u128) -> Option<Permutation> {
+        let n = self.degree;
+        // The Lehmer code, read off as the digits of the rank in the factorial number system.
+        let mut code = vec![0; n];
+        for point in (0..self.ranked_digits()).rev() {
+            let base = (n - point) as u128;
+            code[point] = (rank % base) as usize;
+            rank /= base;
+        }
+        if rank > 0 {
+            return None;
+        }
+        if self.even_only && n >= 2 {
+            // The digits of the Lehmer code sum to the number of inversions, so choosing the
+            // digit for the second last point to make the sum even makes the permutation even.
+            code[n - 2] = code.iter().sum::<usize>() % 2;
+        }
+        let mut available: Vec<usize> = (0..n).collect();
+        let images = code.into_iter().map(|d| available.remove(d)).collect();
+        Permutation::from_images(images)
+    }
+}
+
+/// Steps the given images to their lexicographic successor.  Returns whether the step changed the
+/// parity of the permutation, or `None` if the images were the last permutation.
+fn advance(images: &mut [usize]) -> Option<bool> {
+    let pivot = images.windows(2).rposition(|pair| pair[0] < pair[1])?;
+    let swap = images.iter().rposition(|i| *i > images[pivot]).unwrap();
+    images.swap(pivot, swap);
+    images[pivot + 1..].reverse();
+    // One transposition for the swap, and one for each pair exchanged by the reversal.
+    let reversed = images.len() - pivot - 1;
+    Some((reversed / 2).is_multiple_of(2))
+}
+
+impl Iterator for SymmetricGroup {
+    type Item = Permutation;
+
+    /// Returns the next permutation and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut images = self.current.take()?;
+        let n = self.degree;
+        let mut odd = self.odd;
+        if self.even_only && n >= 2 {
+            // Move to the second permutation of the pair, from which the next pair is reached,
+            // and move back afterwards if the first was the even one.
+            images.swap(n - 2, n - 1);
+            odd = !odd;
+        }
+        let mut successor = images.clone();
+        if let Some(changed) = advance(&mut successor) {
+            self.current = Some(successor);
+            self.odd = odd != changed;
+        }
+        if self.even_only && odd {
+            images.swap(n - 2, n - 1);
+        }
+        self.yielded += 1;
+        Permutation::from_images(images)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.current.is_none() {
+            return (0, Some(0));
+        }
+        size_hint_from(self.order().map(|order| order - self.yielded))
+    }
+}
+
+/// An iterator over the alternating group `A_n`: every even [`Permutation`] of the points
+/// `0..n`, in lexicographic order of their images.
+///
+/// This is a [`SymmetricGroup`] restricted with [`SymmetricGroup::even_only`], so the even
+/// permutations are generated directly, and ranked and unranked among themselves.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{AlternatingGroup, Permutation};
+///
+/// let group = AlternatingGroup::new(5);
+/// assert_eq!(group.order(), Some(60));
+/// let three_cycle = Permutation::from_cycles(5, &[&[0, 1, 2]]).unwrap();
+/// assert_eq!(group.unrank(group.rank(&three_cycle).unwrap()), Some(three_cycle));
+/// assert_eq!(group.filter(|p| p.cycle_type() == vec![3, 1, 1]).count(), 20);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AlternatingGroup {
+    group: SymmetricGroup,
+}
+
+impl AlternatingGroup {
+    /// Creates a new `AlternatingGroup` iterator over every even permutation of the points
+    /// `0..n`, starting from the identity.
+    pub fn new(n: usize) -> Self {
+        AlternatingGroup {
+            group: SymmetricGroup::new(n).even_only(),
+        }
+    }
+
+    /// Returns the degree of the group, which is the number of points it acts on.
+    pub fn degree(&self) -> usize {
+        self.group.degree()
+    }
+
+    /// Returns the number of elements in the group, which is half of `n` factorial for `n` at
+    /// least two, or `None` if it does not fit in a `u128`.
+    pub fn order(&self) -> Option<u128> {
+        self.group.order()
+    }
+
+    /// Returns the number of elements in the group, as by [`SymmetricGroup::total_count_big`]
+    /// for the even permutations.  This is only available with the `bigint` feature.
+    #[cfg(feature = "bigint")]
+    pub fn total_count_big(&self) -> num_bigint::BigUint {
+        self.group.total_count_big()
+    }
+
+    /// Returns the position of the given permutation in the enumeration, as by
+    /// [`SymmetricGroup::rank`], or `None` if it is odd.
+    pub fn rank(&self, permutation: &Permutation) -> Option<u128> {
+        self.group.rank(permutation)
+    }
+
+    /// Returns the permutation at the given position in the enumeration, as by
+    /// [`SymmetricGroup::unrank`].
+    pub fn unrank(&self, rank: u128) -> Option<Permutation> {
+        self.group.unrank(rank)
+    }
+}
+
+impl Iterator for AlternatingGroup {
+    type Item = Permutation;
+
+    /// Returns the next even permutation and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.group.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.group.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Permutations;
+
+    #[test]
+    fn test_symmetric_group_matches_permutations() {
+        for n in 0..7 {
+            let group = SymmetricGroup::new(n);
+            assert_eq!(group.size_hint().0 as u128, group.order().unwrap());
+            let expected = Permutations::of_length(0..n, n);
+            for (rank, (element, images)) in group.clone().zip(expected).enumerate() {
+                assert_eq!(element.images(), images.as_slice());
+                assert_eq!(group.rank(&element), Some(rank as u128));
+                assert_eq!(group.unrank(rank as u128), Some(element));
+            }
+            assert_eq!(group.clone().count() as u128, group.order().unwrap());
+            assert_eq!(group.unrank(group.order().unwrap()), None);
+        }
+    }
+
+    #[test]
+    fn test_alternating_group_matches_filtered_symmetric_group() {
+        for n in 0..8 {
+            let group = AlternatingGroup::new(n);
+            let expected: Vec<Permutation> =
+                SymmetricGroup::new(n).filter(|p| p.is_even()).collect();
+            assert_eq!(group.size_hint().0 as u128, group.order().unwrap());
+            assert_eq!(group.order(), Some(expected.len() as u128));
+            for (rank, element) in group.clone().enumerate() {
+                assert_eq!(element, expected[rank]);
+                assert_eq!(group.rank(&element), Some(rank as u128));
+                assert_eq!(group.unrank(rank as u128), Some(element));
+            }
+            assert_eq!(group.unrank(expected.len() as u128), None);
+            if n >= 2 {
+                let odd = Permutation::from_cycles(n, &[&[0, 1]]).unwrap();
+                assert_eq!(group.rank(&odd), None);
+            }
+        }
+    }
+}
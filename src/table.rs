@@ -0,0 +1,223 @@
+use num_traits::{CheckedAdd, CheckedMul, Num};
+
+/// A table of factorials, binomial coefficients, and Stirling numbers of both kinds, precomputed
+/// up to a given size in a chosen numeric type, for constant-time lookups.
+///
+/// Unranking combinations looks up many binomial coefficients in a tight loop, and computing
+/// each one from scratch costs a loop of multiplications and divisions.  Building a table once,
+/// with only additions and small multiplications, and sharing it between calls to
+/// [`Combinations::unrank_colex_with`] avoids repeating that work.  Permutations need no table:
+/// [`SymmetricGroup::rank`] and [`SymmetricGroup::unrank`] read the rank as a number in the
+/// factorial number system, one small multiplication or division per point, without computing
+/// any factorials.  The table uses `O(n^2)` memory for size `n`.
+///
+/// Every entry is computed with checked arithmetic, and lookups return `None` for an entry which
+/// does not fit in the numeric type, so one table may hold, say, every binomial coefficient which
+/// fits in a `u128` even though the factorials overflow much sooner.  With a big integer type
+/// such as `num_bigint::BigUint`, every entry is present.
+///
+/// [`Combinations::unrank_colex_with`]: crate::Combinations::unrank_colex_with
+/// [`SymmetricGroup::rank`]: crate::SymmetricGroup::rank
+/// [`SymmetricGroup::unrank`]: crate::SymmetricGroup::unrank
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{binomial, CombinatoricsTable};
+///
+/// let table = CombinatoricsTable::<u64>::new(70);
+/// assert_eq!(table.factorial(20), Some(2432902008176640000));
+/// assert_eq!(table.factorial(21), None);
+/// assert_eq!(table.binomial(60, 30), Some(binomial(60, 30)));
+/// assert_eq!(table.stirling_first(5, 2), Some(50));
+/// assert_eq!(table.stirling_second(5, 2), Some(15));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CombinatoricsTable<T> {
+    factorials: Vec<Option<T>>,
+    binomials: Vec<Vec<Option<T>>>,
+    stirling_first: Vec<Vec<Option<T>>>,
+    stirling_second: Vec<Vec<Option<T>>>,
+}
+
+impl<T: Num + Clone + CheckedAdd + CheckedMul> CombinatoricsTable<T> {
+    /// Creates a new table of every count with `n` at most the given size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::CombinatoricsTable;
+    ///
+    /// let table = CombinatoricsTable::<u8>::new(6);
+    /// assert_eq!(table.size(), 6);
+    /// assert_eq!(table.factorial(5), Some(120));
+    /// assert_eq!(table.factorial(6), None);
+    /// ```
+    pub fn new(size: usize) -> Self {
+        let mut naturals = vec![Some(T::zero())];
+        let mut factorials = vec![Some(T::one())];
+        for n in 1..=size {
+            let natural = naturals[n - 1]
+                .as_ref()
+                .and_then(|m| m.checked_add(&T::one()));
+            factorials.push(checked_mul(&factorials[n - 1], &natural));
+            naturals.push(natural);
+        }
+        CombinatoricsTable {
+            factorials,
+            binomials: triangle(size, |_, _| Some(T::one())),
+            stirling_first: triangle(size, |n, _| naturals[n - 1].clone()),
+            stirling_second: triangle(size, |_, k| naturals[k].clone()),
+        }
+    }
+}
+
+impl<T: Num + Clone> CombinatoricsTable<T> {
+    /// Returns the largest `n` for which the table holds counts.
+    pub fn size(&self) -> usize {
+        self.factorials.len() - 1
+    }
+
+    /// Returns `n` factorial, or `None` if it does not fit in the numeric type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the size of the table.
+    pub fn factorial(&self, n: usize) -> Option<T> {
+        self.factorials[n].clone()
+    }
+
+    /// Returns the binomial coefficient `n` choose `k`, or `None` if it does not fit in the
+    /// numeric type.  Returns zero if `k` is greater than `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the size of the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{combination_count, CombinatoricsTable};
+    ///
+    /// let table = CombinatoricsTable::<u128>::new(200);
+    /// assert_eq!(table.binomial(100, 50), combination_count(100, 50));
+    /// assert_eq!(table.binomial(200, 100), None);
+    /// assert_eq!(table.binomial(3, 4), Some(0));
+    /// ```
+    pub fn binomial(&self, n: usize, k: usize) -> Option<T> {
+        lookup(&self.binomials, n, k)
+    }
+
+    /// Returns the unsigned Stirling number of the first kind for `n` and `k`, which is the
+    /// number of permutations of `n` elements with exactly `k` cycles, or `None` if it does not
+    /// fit in the numeric type.  Returns zero if `k` is greater than `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the size of the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{factorial, CombinatoricsTable};
+    ///
+    /// let table = CombinatoricsTable::<u64>::new(10);
+    /// let row: Vec<u64> = (0..=4).map(|k| table.stirling_first(4, k).unwrap()).collect();
+    /// assert_eq!(row, vec![0, 6, 11, 6, 1]);
+    /// let total: u64 = (0..=10).map(|k| table.stirling_first(10, k).unwrap()).sum();
+    /// assert_eq!(total, factorial(10));
+    /// ```
+    pub fn stirling_first(&self, n: usize, k: usize) -> Option<T> {
+        lookup(&self.stirling_first, n, k)
+    }
+
+    /// Returns the Stirling number of the second kind for `n` and `k`, which is the number of
+    /// ways to partition a set of `n` elements into exactly `k` nonempty blocks, or `None` if it
+    /// does not fit in the numeric type.  Returns zero if `k` is greater than `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than the size of the table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::{bell_number, CombinatoricsTable};
+    ///
+    /// let table = CombinatoricsTable::<u64>::new(10);
+    /// let row: Vec<u64> = (0..=4).map(|k| table.stirling_second(4, k).unwrap()).collect();
+    /// assert_eq!(row, vec![0, 1, 7, 6, 1]);
+    /// let total: u64 = (0..=10).map(|k| table.stirling_second(10, k).unwrap()).sum();
+    /// assert_eq!(total, bell_number(10));
+    /// ```
+    pub fn stirling_second(&self, n: usize, k: usize) -> Option<T> {
+        lookup(&self.stirling_second, n, k)
+    }
+}
+
+/// Returns the product of two entries, or `None` if either is missing or the product overflows.
+fn checked_mul<T: CheckedMul>(a: &Option<T>, b: &Option<T>) -> Option<T> {
+    a.as_ref()?.checked_mul(b.as_ref()?)
+}
+
+/// Builds the rows `0..=size` of a triangle of counts in which the entry for `n` and `k` is
+/// `multiplier(n, k)` times the entry above it plus the entry above and to its left, with the
+/// single entry of row zero being one and every entry outside the triangle being zero.
+fn triangle<T: Num + Clone + CheckedAdd + CheckedMul>(
+    size: usize,
+    multiplier: impl Fn(usize, usize) -> Option<T>,
+) -> Vec<Vec<Option<T>>> {
+    let mut rows = vec![vec![Some(T::one())]];
+    for n in 1..=size {
+        let above = &rows[n - 1];
+        let entry = |k: usize| above.get(k).cloned().unwrap_or(Some(T::zero()));
+        let row = (0..=n)
+            .map(|k| {
+                let left = if k == 0 {
+                    Some(T::zero())
+                } else {
+                    entry(k - 1)
+                };
+                checked_mul(&multiplier(n, k), &entry(k))?.checked_add(left.as_ref()?)
+            })
+            .collect();
+        rows.push(row);
+    }
+    rows
+}
+
+/// Returns the entry of the given triangle for `n` and `k`, which is zero if `k` is greater than
+/// `n`.
+fn lookup<T: Num + Clone>(triangle: &[Vec<Option<T>>], n: usize, k: usize) -> Option<T> {
+    triangle[n].get(k).cloned().unwrap_or(Some(T::zero()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bell_number, combination_count, permutation_count};
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_table_matches_counting_functions() {
+        let table = CombinatoricsTable::<u128>::new(150);
+        let big = CombinatoricsTable::<BigUint>::new(150);
+        for n in 0..=150 {
+            assert_eq!(table.factorial(n), permutation_count(n, n));
+            for k in 0..=n + 1 {
+                assert_eq!(table.binomial(n, k), combination_count(n, k));
+                let exact = big.binomial(n, k).unwrap();
+                assert_eq!(table.binomial(n, k), u128::try_from(exact).ok());
+            }
+        }
+        let table = CombinatoricsTable::<u64>::new(20);
+        for n in 1..=20 {
+            let cycles = (0..=n).map(|k| table.stirling_first(n, k).unwrap());
+            assert_eq!(cycles.sum::<u64>(), table.factorial(n).unwrap());
+            let blocks = (0..=n).map(|k| table.stirling_second(n, k).unwrap());
+            assert_eq!(blocks.sum::<u64>(), bell_number(n));
+            assert_eq!(table.stirling_first(n, n - 1), table.binomial(n, 2));
+            assert_eq!(table.stirling_second(n, n - 1), table.binomial(n, 2));
+        }
+    }
+}
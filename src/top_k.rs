@@ -0,0 +1,122 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::iter::Sum;
+
+/// A combination waiting in the search frontier, given by the ranks of its elements in order of
+/// decreasing score, along with its total score.
+struct Candidate<S> {
+    score: S,
+    ranks: Vec<usize>,
+}
+
+impl<S: PartialOrd> Ord for Candidate<S> {
+    /// Orders candidates by score, breaking ties in favor of the lexicographically smaller ranks,
+    /// so that the greatest candidate is the one to visit next.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .partial_cmp(&other.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.ranks.cmp(&self.ranks))
+    }
+}
+
+impl<S: PartialOrd> PartialOrd for Candidate<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: PartialOrd> PartialEq for Candidate<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: PartialOrd> Eq for Candidate<S> {}
+
+/// Returns up to `k` of the combinations of the given size of positions into `scores` with the
+/// greatest total scores, in order of decreasing total, each with its positions in increasing
+/// order and paired with its total.
+///
+/// The positions are ranked by decreasing score, and every combination of ranks other than the
+/// first `size` has a single parent, found by decrementing the rank at its first gap, whose total
+/// is at least as great.  Searching this tree best-first from the first `size` ranks visits the
+/// combinations in order of decreasing total, and each combination has at most two children, so
+/// only about `2 k` combinations are ever scored.
+pub(crate) fn best_by_sum<S: PartialOrd + Copy + Sum<S>>(
+    scores: &[S],
+    size: usize,
+    k: usize,
+) -> Vec<(S, Vec<usize>)> {
+    let n = scores.len();
+    let mut best = Vec::with_capacity(k.min(64));
+    if size > n || k == 0 {
+        return best;
+    }
+    let mut by_score: Vec<usize> = (0..n).collect();
+    by_score.sort_by(|a, b| {
+        scores[*b]
+            .partial_cmp(&scores[*a])
+            .unwrap_or(Ordering::Equal)
+    });
+    let total = |ranks: &[usize]| ranks.iter().map(|r| scores[by_score[*r]]).sum::<S>();
+    let ranks: Vec<usize> = (0..size).collect();
+    let mut frontier = BinaryHeap::new();
+    frontier.push(Candidate {
+        score: total(&ranks),
+        ranks,
+    });
+    while let Some(Candidate { score, ranks }) = frontier.pop() {
+        // The children move up the rank at the first gap, or the one before it, when the next
+        // rank is free.
+        let gap = (0..size).find(|i| ranks[*i] > *i).unwrap_or(size);
+        for index in gap.saturating_sub(1)..size.min(gap + 1) {
+            let limit = ranks.get(index + 1).copied().unwrap_or(n);
+            if ranks[index] + 1 < limit {
+                let mut child = ranks.clone();
+                child[index] += 1;
+                frontier.push(Candidate {
+                    score: total(&child),
+                    ranks: child,
+                });
+            }
+        }
+        let mut positions: Vec<usize> = ranks.iter().map(|r| by_score[*r]).collect();
+        positions.sort_unstable();
+        best.push((score, positions));
+        if best.len() == k {
+            break;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_top_k_best_by_sum_matches_sorted_scores() {
+        let scores = [4, -1, 7, 7, 0, 3, -5, 2];
+        for size in 0..=scores.len() + 1 {
+            let mut expected: Vec<i32> = Combinations::of_size(0..scores.len(), size)
+                .map(|combo| combo.iter().map(|p| scores[*p]).sum())
+                .collect();
+            expected.sort_unstable_by(|a, b| b.cmp(a));
+            for k in [0, 1, 5, 20, 300] {
+                let best = best_by_sum(&scores, size, k);
+                let totals: Vec<i32> = best.iter().map(|(total, _)| *total).collect();
+                assert_eq!(totals, expected[..k.min(expected.len())]);
+                let distinct: BTreeSet<&Vec<usize>> = best.iter().map(|(_, p)| p).collect();
+                assert_eq!(distinct.len(), best.len());
+                for (total, positions) in best.iter() {
+                    assert_eq!(positions.len(), size);
+                    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+                    assert_eq!(positions.iter().map(|p| scores[*p]).sum::<i32>(), *total);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::sorted_unique;
+
+/// An iterator which generates the topological orderings of a directed acyclic graph, that is,
+/// the linear extensions of a partial order: the permutations of the nodes in which every edge
+/// `(a, b)` has `a` before `b`.
+///
+/// The nodes are sorted and deduplicated, and the orderings are yielded in lexicographic order.
+/// Each ordering is built by backtracking, tracking the number of predecessors of each node which
+/// have not yet been placed, so only nodes whose predecessors have all been placed are ever tried
+/// and no ordering which breaks an edge is searched.  If the graph has a cycle, nothing is
+/// yielded.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::TopologicalOrders;
+///
+/// // Getting dressed: socks and trousers before shoes, shirt before jacket.
+/// let nodes = ["jacket", "shirt", "shoes", "socks", "trousers"];
+/// let edges = [("socks", "shoes"), ("trousers", "shoes"), ("shirt", "jacket")];
+/// let mut orders = TopologicalOrders::new(nodes, edges);
+/// assert_eq!(orders.next(), Some(vec!["shirt", "jacket", "socks", "trousers", "shoes"]));
+/// assert_eq!(orders.next(), Some(vec!["shirt", "jacket", "trousers", "socks", "shoes"]));
+/// assert_eq!(orders.count(), 18);
+/// ```
+#[derive(Clone)]
+pub struct TopologicalOrders<T> {
+    elements: Vec<T>,
+    successors: Vec<Vec<usize>>,
+    unplaced_predecessors: Vec<usize>,
+    placed: Vec<bool>,
+    order: Vec<usize>,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord + Clone> TopologicalOrders<T> {
+    /// Creates a new `TopologicalOrders` iterator which will yield every ordering of the given
+    /// nodes in which, for each of the given edges `(a, b)`, `a` comes before `b`.  Repeated edges
+    /// are ignored, as are edges with an endpoint which is not among the nodes.  A self-loop is a
+    /// cycle, so no orderings are yielded.  With no edges, every permutation of the nodes is
+    /// yielded, and with no nodes, a single empty ordering is yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::TopologicalOrders;
+    ///
+    /// let orders: Vec<Vec<i32>> = TopologicalOrders::new(1..=3, [(3, 1)]).collect();
+    /// assert_eq!(orders, vec![vec![2, 3, 1], vec![3, 1, 2], vec![3, 2, 1]]);
+    ///
+    /// assert_eq!(TopologicalOrders::new(1..=3, [(1, 2), (2, 3), (3, 1)]).next(), None);
+    /// assert_eq!(TopologicalOrders::new(1..=4, []).count(), 24);
+    /// ```
+    pub fn new(
+        nodes: impl IntoIterator<Item = T>,
+        edges: impl IntoIterator<Item = (T, T)>,
+    ) -> Self {
+        let elements = sorted_unique(nodes);
+        let count = elements.len();
+        let mut successors = vec![Vec::new(); count];
+        for (a, b) in edges {
+            if let (Ok(a), Ok(b)) = (elements.binary_search(&a), elements.binary_search(&b)) {
+                successors[a].push(b);
+            }
+        }
+        let mut unplaced_predecessors = vec![0; count];
+        for list in successors.iter_mut() {
+            list.sort_unstable();
+            list.dedup();
+            for b in list.iter() {
+                unplaced_predecessors[*b] += 1;
+            }
+        }
+        TopologicalOrders {
+            elements,
+            successors,
+            unplaced_predecessors,
+            placed: vec![false; count],
+            order: Vec::with_capacity(count),
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Returns the sorted, deduplicated nodes which this iterator orders.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::TopologicalOrders;
+    ///
+    /// let orders = TopologicalOrders::new(vec!['c', 'a', 'b', 'a'], [('a', 'c')]);
+    /// assert_eq!(orders.elements(), &['a', 'b', 'c']);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Returns the index of the first node after `previous`, if given, which has not been placed
+    /// and whose predecessors have all been placed.
+    fn next_candidate(&self, previous: Option<usize>) -> Option<usize> {
+        let start = previous.map_or(0, |previous| previous + 1);
+        (start..self.elements.len())
+            .find(|index| !self.placed[*index] && self.unplaced_predecessors[*index] == 0)
+    }
+
+    /// Places the node with the given index next in the ordering.
+    fn place(&mut self, index: usize) {
+        self.placed[index] = true;
+        for successor in self.successors[index].iter() {
+            self.unplaced_predecessors[*successor] -= 1;
+        }
+        self.order.push(index);
+    }
+
+    /// Removes the last node from the ordering and returns its index, if any.
+    fn unplace(&mut self) -> Option<usize> {
+        let index = self.order.pop()?;
+        self.placed[index] = false;
+        for successor in self.successors[index].iter() {
+            self.unplaced_predecessors[*successor] += 1;
+        }
+        Some(index)
+    }
+
+    /// Completes the ordering with the lexicographically smallest valid nodes, backtracking as
+    /// necessary.  If `previous` is given, the search resumes by trying nodes after `previous` in
+    /// the next position.  Returns `true` if the ordering was completed, or `false` if no further
+    /// orderings exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            if previous.is_none() && self.order.len() == self.elements.len() {
+                return true;
+            }
+            match self.next_candidate(previous) {
+                Some(index) => {
+                    self.place(index);
+                    previous = None;
+                }
+                None => match self.unplace() {
+                    Some(index) => previous = Some(index),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Moves to the next ordering.  Returns `false` and marks the iterator as done if no
+    /// orderings remain.
+    fn advance(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        let found = if self.started {
+            match self.unplace() {
+                Some(index) => self.fill(Some(index)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.fill(None)
+        };
+        if !found {
+            self.done = true;
+        }
+        found
+    }
+}
+
+impl<T: Ord + Clone> Iterator for TopologicalOrders<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next ordering and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.advance() {
+            return None;
+        }
+        Some(
+            self.order
+                .iter()
+                .map(|index| self.elements[*index].clone())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Permutations;
+
+    #[test]
+    fn test_topological_orders_match_filtered_permutations() {
+        for n in 0..7usize {
+            for seed in 0..8usize {
+                let edges: Vec<(usize, usize)> = (0..n)
+                    .flat_map(|a| (0..n).map(move |b| (a, b)))
+                    .filter(|(a, b)| a != b && (a * 7 + b * 3 + seed) % 5 == 0)
+                    .filter(|(a, b)| seed % 2 == 0 || a < b)
+                    .collect();
+                let respects = |order: &Vec<usize>| {
+                    let position = |node| order.iter().position(|x| *x == node);
+                    edges.iter().all(|(a, b)| position(*a) < position(*b))
+                };
+                let expected: Vec<Vec<usize>> =
+                    Permutations::of_length(0..n, n).filter(respects).collect();
+                let orders = TopologicalOrders::new(0..n, edges.iter().copied());
+                assert_eq!(orders.collect::<Vec<_>>(), expected);
+            }
+        }
+        assert_eq!(TopologicalOrders::new(0..3, [(1, 1)]).next(), None);
+        assert_eq!(TopologicalOrders::new(0..3, [(0, 5)]).count(), 6);
+    }
+}
@@ -0,0 +1,336 @@
+//! Tools for checking the output of a generator against a brute-force reference.
+//!
+//! The functions in this module compare the items yielded by a generator with those produced by
+//! some simpler, obviously-correct reference enumeration, such as a filtered [`cartesian_power`].
+//! They report the first discrepancy found in the count, ordering, or uniqueness of the items,
+//! which makes them suitable both for the crate's own tests and for downstream users who want to
+//! check their own generator configurations.
+//!
+//! # Examples
+//!
+//! ```
+//! use combinatorial::verify::{cartesian_power, verify_up_to};
+//! use combinatorial::Combinations;
+//!
+//! let elements = vec!['a', 'b', 'c', 'd'];
+//! let result = verify_up_to(
+//!     4,
+//!     |k| Combinations::of_size(elements.clone(), k),
+//!     |k| {
+//!         cartesian_power(&elements, k)
+//!             .into_iter()
+//!             .filter(|seq| seq.windows(2).all(|pair| pair[0] < pair[1]))
+//!     },
+//! );
+//! assert_eq!(result, Ok(()));
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// A discrepancy between the items yielded by a generator and the items of a reference
+/// enumeration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch<T> {
+    /// The generator yielded the given item more than once; `index` is the position of its
+    /// second occurrence.
+    Duplicate { index: usize, item: T },
+    /// The generator yielded a different number of items than the reference.
+    Count { expected: usize, actual: usize },
+    /// The generator and the reference differ at the given position.
+    Order {
+        index: usize,
+        expected: T,
+        actual: T,
+    },
+    /// The generator yielded an item which does not occur in the reference.
+    Unexpected { item: T },
+    /// The generator did not yield an item which occurs in the reference.
+    Missing { item: T },
+}
+
+impl<T: fmt::Debug> fmt::Display for Mismatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Duplicate { index, item } => {
+                write!(f, "duplicate item {:?} at index {}", item, index)
+            }
+            Mismatch::Count { expected, actual } => {
+                write!(f, "expected {} items but found {}", expected, actual)
+            }
+            Mismatch::Order {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected {:?} at index {} but found {:?}",
+                expected, index, actual
+            ),
+            Mismatch::Unexpected { item } => write!(f, "unexpected item {:?}", item),
+            Mismatch::Missing { item } => write!(f, "missing item {:?}", item),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for Mismatch<T> {}
+
+/// Returns every sequence of the given length whose entries are drawn from the given elements,
+/// in lexicographic order of the element indices.  Combined with a filter, this serves as a
+/// brute-force reference for most generators in this crate.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::verify::cartesian_power;
+///
+/// assert_eq!(
+///     cartesian_power(&[0, 1], 2),
+///     vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]]
+/// );
+/// assert_eq!(cartesian_power(&['x'], 0), vec![Vec::<char>::new()]);
+/// assert_eq!(cartesian_power::<char>(&[], 1), Vec::<Vec<char>>::new());
+/// ```
+pub fn cartesian_power<T: Clone>(elements: &[T], length: usize) -> Vec<Vec<T>> {
+    let mut sequences: Vec<Vec<T>> = vec![Vec::with_capacity(length)];
+    for _ in 0..length {
+        sequences = sequences
+            .into_iter()
+            .flat_map(|prefix| {
+                elements.iter().map(move |element| {
+                    let mut sequence = prefix.clone();
+                    sequence.push(element.clone());
+                    sequence
+                })
+            })
+            .collect();
+    }
+    sequences
+}
+
+/// Checks that the given generator yields no item more than once.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::verify::{verify_unique, Mismatch};
+///
+/// assert_eq!(verify_unique(vec![1, 2, 3]), Ok(()));
+/// assert_eq!(
+///     verify_unique(vec![1, 2, 1]),
+///     Err(Mismatch::Duplicate { index: 2, item: 1 })
+/// );
+/// ```
+pub fn verify_unique<T: Ord + Clone>(
+    actual: impl IntoIterator<Item = T>,
+) -> Result<(), Mismatch<T>> {
+    let mut seen = BTreeSet::new();
+    for (index, item) in actual.into_iter().enumerate() {
+        if !seen.insert(item.clone()) {
+            return Err(Mismatch::Duplicate { index, item });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the given generator yields exactly the items of the reference, in the same order,
+/// with no duplicates.
+///
+/// Uniqueness is checked first, then the number of items, and finally the order in which they
+/// are yielded.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::verify::{verify_ordered, Mismatch};
+/// use combinatorial::Combinations;
+///
+/// let reference = vec![vec![1, 2], vec![1, 3], vec![2, 3]];
+/// assert_eq!(verify_ordered(Combinations::of_size(1..4, 2), reference), Ok(()));
+///
+/// let reversed = vec![vec![2, 3], vec![1, 3], vec![1, 2]];
+/// assert_eq!(
+///     verify_ordered(Combinations::of_size(1..4, 2), reversed),
+///     Err(Mismatch::Order {
+///         index: 0,
+///         expected: vec![2, 3],
+///         actual: vec![1, 2],
+///     })
+/// );
+/// ```
+pub fn verify_ordered<T: Ord + Clone>(
+    actual: impl IntoIterator<Item = T>,
+    expected: impl IntoIterator<Item = T>,
+) -> Result<(), Mismatch<T>> {
+    let actual: Vec<T> = actual.into_iter().collect();
+    let expected: Vec<T> = expected.into_iter().collect();
+    verify_unique(actual.iter().cloned())?;
+    if actual.len() != expected.len() {
+        return Err(Mismatch::Count {
+            expected: expected.len(),
+            actual: actual.len(),
+        });
+    }
+    match actual.iter().zip(expected.iter()).position(|(a, e)| a != e) {
+        Some(index) => Err(Mismatch::Order {
+            index,
+            expected: expected[index].clone(),
+            actual: actual[index].clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Checks that the given generator yields exactly the items of the reference, with no
+/// duplicates, but in any order.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::verify::{verify_unordered, Mismatch};
+/// use combinatorial::Combinations;
+///
+/// let reference = vec![vec![2, 3], vec![1, 3], vec![1, 2]];
+/// assert_eq!(verify_unordered(Combinations::of_size(1..4, 2), reference), Ok(()));
+///
+/// let reference = vec![vec![2, 3], vec![1, 3], vec![1, 4]];
+/// assert_eq!(
+///     verify_unordered(Combinations::of_size(1..4, 2), reference),
+///     Err(Mismatch::Unexpected { item: vec![1, 2] })
+/// );
+/// ```
+pub fn verify_unordered<T: Ord + Clone>(
+    actual: impl IntoIterator<Item = T>,
+    expected: impl IntoIterator<Item = T>,
+) -> Result<(), Mismatch<T>> {
+    let actual: Vec<T> = actual.into_iter().collect();
+    verify_unique(actual.iter().cloned())?;
+    let mut remaining: BTreeMap<T, usize> = BTreeMap::new();
+    for item in expected {
+        *remaining.entry(item).or_insert(0) += 1;
+    }
+    for item in actual.iter() {
+        match remaining.get_mut(item) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => return Err(Mismatch::Unexpected { item: item.clone() }),
+        }
+    }
+    match remaining.into_iter().find(|(_, count)| *count > 0) {
+        Some((item, _)) => Err(Mismatch::Missing { item }),
+        None => Ok(()),
+    }
+}
+
+/// Checks that, for every size from zero up to and including `max_size`, the generator returned
+/// by `generator` yields exactly the items of the reference returned by `reference`, in the same
+/// order, with no duplicates.  On failure, returns the first size at which a mismatch was found
+/// along with the mismatch itself.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::verify::{cartesian_power, verify_up_to};
+/// use combinatorial::CombinationsWithReplacement;
+///
+/// let elements = vec![1, 2, 3];
+/// let result = verify_up_to(
+///     3,
+///     |k| CombinationsWithReplacement::of_size(elements.clone(), k),
+///     |k| {
+///         cartesian_power(&elements, k)
+///             .into_iter()
+///             .filter(|seq| seq.windows(2).all(|pair| pair[0] <= pair[1]))
+///     },
+/// );
+/// assert_eq!(result, Ok(()));
+/// ```
+pub fn verify_up_to<T, G, R>(
+    max_size: usize,
+    mut generator: impl FnMut(usize) -> G,
+    mut reference: impl FnMut(usize) -> R,
+) -> Result<(), (usize, Mismatch<T>)>
+where
+    T: Ord + Clone,
+    G: IntoIterator<Item = T>,
+    R: IntoIterator<Item = T>,
+{
+    for size in 0..=max_size {
+        verify_ordered(generator(size), reference(size)).map_err(|mismatch| (size, mismatch))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Combinations, CombinationsWithReplacement};
+
+    #[test]
+    fn test_verify_cartesian_power() {
+        assert_eq!(cartesian_power(&[1, 2, 3], 3).len(), 27);
+        let sequences = cartesian_power(&[1, 2, 3], 3);
+        assert!(sequences.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_verify_ordered_mismatches() {
+        assert_eq!(
+            verify_ordered(vec![1, 2], vec![1, 2, 3]),
+            Err(Mismatch::Count {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            verify_ordered(vec![1, 2, 2], vec![1, 2, 3]),
+            Err(Mismatch::Duplicate { index: 2, item: 2 })
+        );
+        assert_eq!(
+            verify_ordered(vec![1, 3, 2], vec![1, 2, 3]),
+            Err(Mismatch::Order {
+                index: 1,
+                expected: 2,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_verify_unordered_mismatches() {
+        assert_eq!(verify_unordered(vec![3, 1, 2], vec![1, 2, 3]), Ok(()));
+        assert_eq!(
+            verify_unordered(vec![1, 2], vec![1, 2, 3]),
+            Err(Mismatch::Missing { item: 3 })
+        );
+        assert_eq!(
+            verify_unordered(vec![1, 2, 3], vec![1, 2, 2, 3]),
+            Err(Mismatch::Missing { item: 2 })
+        );
+    }
+
+    #[test]
+    fn test_verify_combinations_against_reference() {
+        let elements: Vec<u8> = (0..6).collect();
+        assert_eq!(
+            verify_up_to(
+                6,
+                |k| Combinations::of_size(elements.clone(), k),
+                |k| cartesian_power(&elements, k)
+                    .into_iter()
+                    .filter(|seq| seq.windows(2).all(|pair| pair[0] < pair[1]))
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            verify_up_to(
+                4,
+                |k| CombinationsWithReplacement::of_size(elements.clone(), k),
+                |k| cartesian_power(&elements, k)
+                    .into_iter()
+                    .filter(|seq| seq.windows(2).all(|pair| pair[0] <= pair[1]))
+            ),
+            Ok(())
+        );
+    }
+}
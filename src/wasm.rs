@@ -0,0 +1,205 @@
+//! JavaScript-friendly wrappers around the core generators, for use from WebAssembly through
+//! `wasm-bindgen`.
+//!
+//! This module is only available with the `wasm` feature.  Since JavaScript values cannot be
+//! sorted or hashed by the generators, the wrappers work with indices: a generator over `n`
+//! elements yields arrays of indices into `0..n`, which JavaScript code can map onto its own
+//! items.  Each item is returned to JavaScript as a `Uint32Array` (or an `Int32Array` for
+//! [`JsSubsetSums`]), and `undefined` is returned once the generator is exhausted.
+//!
+//! The rest of the crate is also usable from WebAssembly, with two exceptions: [`drive`] measures
+//! its time slices using [`std::time::Instant`], which is unavailable on `wasm32-unknown-unknown`,
+//! and the checkpoint files written by methods such as
+//! [`Combinations::save_to`](crate::Combinations::save_to) require a filesystem.  From JavaScript,
+//! a long enumeration can instead be spread across animation frames by calling `next` a fixed
+//! number of times per frame.
+//!
+//! ```js
+//! import { Combinations } from "combinatorial";
+//!
+//! const items = ["apple", "banana", "cherry"];
+//! const pairs = new Combinations(items.length, 2);
+//! for (let combo = pairs.next(); combo !== undefined; combo = pairs.next()) {
+//!     console.log(Array.from(combo, (i) => items[i]));
+//! }
+//! ```
+//!
+//! [`drive`]: crate::drive
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{subset_sums, Combinations, Permutations, SubsetSums};
+
+/// A wrapper around [`Combinations`] of the indices `0..n`, exported to JavaScript as
+/// `Combinations`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::wasm::JsCombinations;
+///
+/// let mut combos = JsCombinations::new(3, Some(2));
+/// assert_eq!(combos.next(), Some(vec![0, 1]));
+/// assert_eq!(combos.next(), Some(vec![0, 2]));
+/// assert_eq!(combos.next(), Some(vec![1, 2]));
+/// assert_eq!(combos.next(), None);
+/// ```
+#[wasm_bindgen(js_name = Combinations)]
+pub struct JsCombinations {
+    inner: Combinations<u32>,
+}
+
+#[wasm_bindgen(js_class = Combinations)]
+impl JsCombinations {
+    /// Creates a generator of the combinations of the indices `0..n` with the given size, or of
+    /// every size if the size is omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::wasm::JsCombinations;
+    ///
+    /// let mut combos = JsCombinations::new(2, None);
+    /// assert_eq!(combos.next(), Some(vec![]));
+    /// assert_eq!(combos.next(), Some(vec![0]));
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(n: u32, size: Option<u32>) -> JsCombinations {
+        let indices: Vec<u32> = (0..n).collect();
+        let inner = match size {
+            Some(size) => Combinations::of_size_unchecked(indices, size as usize),
+            None => Combinations::all_unchecked(indices),
+        };
+        JsCombinations { inner }
+    }
+
+    /// Returns the next combination, or `undefined` once every combination has been returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::wasm::JsCombinations;
+    ///
+    /// let mut combos = JsCombinations::new(4, Some(4));
+    /// assert_eq!(combos.next(), Some(vec![0, 1, 2, 3]));
+    /// assert_eq!(combos.next(), None);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Vec<u32>> {
+        self.inner.next()
+    }
+}
+
+/// A wrapper around [`Permutations`] of the indices `0..n`, exported to JavaScript as
+/// `Permutations`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::wasm::JsPermutations;
+///
+/// let mut perms = JsPermutations::new(3, None);
+/// assert_eq!(perms.next(), Some(vec![0, 1, 2]));
+/// assert_eq!(perms.next(), Some(vec![0, 2, 1]));
+/// ```
+#[wasm_bindgen(js_name = Permutations)]
+pub struct JsPermutations {
+    inner: Permutations<u32>,
+}
+
+#[wasm_bindgen(js_class = Permutations)]
+impl JsPermutations {
+    /// Creates a generator of the permutations of the indices `0..n` with the given length, or of
+    /// all `n` indices if the length is omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::wasm::JsPermutations;
+    ///
+    /// let mut perms = JsPermutations::new(3, Some(1));
+    /// assert_eq!(perms.next(), Some(vec![0]));
+    /// assert_eq!(perms.next(), Some(vec![1]));
+    /// assert_eq!(perms.next(), Some(vec![2]));
+    /// assert_eq!(perms.next(), None);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(n: u32, length: Option<u32>) -> JsPermutations {
+        let length = length.unwrap_or(n) as usize;
+        JsPermutations {
+            inner: Permutations::of_length_unchecked((0..n).collect(), length),
+        }
+    }
+
+    /// Returns the next permutation, or `undefined` once every permutation has been returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::wasm::JsPermutations;
+    ///
+    /// let mut perms = JsPermutations::new(0, None);
+    /// assert_eq!(perms.next(), Some(vec![]));
+    /// assert_eq!(perms.next(), None);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Vec<u32>> {
+        self.inner.next()
+    }
+}
+
+/// A wrapper around [`subset_sums`], exported to JavaScript as `SubsetSums`.  Unlike the other
+/// wrappers, this yields the elements of each subset rather than their indices, since the
+/// elements must be numbers in order to be summed.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::wasm::JsSubsetSums;
+///
+/// let mut subsets = JsSubsetSums::new(vec![3, 1, 4, 2], 5);
+/// assert_eq!(subsets.next(), Some(vec![1, 4]));
+/// assert_eq!(subsets.next(), Some(vec![2, 3]));
+/// assert_eq!(subsets.next(), None);
+/// ```
+#[wasm_bindgen(js_name = SubsetSums)]
+pub struct JsSubsetSums {
+    inner: SubsetSums<i32>,
+}
+
+#[wasm_bindgen(js_class = SubsetSums)]
+impl JsSubsetSums {
+    /// Creates a generator of the subsets of the unique given numbers whose sum is the target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::wasm::JsSubsetSums;
+    ///
+    /// let mut subsets = JsSubsetSums::new(vec![-2, 2, 5], 0);
+    /// assert_eq!(subsets.next(), Some(vec![]));
+    /// assert_eq!(subsets.next(), Some(vec![-2, 2]));
+    /// assert_eq!(subsets.next(), None);
+    /// ```
+    #[wasm_bindgen(constructor)]
+    pub fn new(elements: Vec<i32>, target: i32) -> JsSubsetSums {
+        JsSubsetSums {
+            inner: subset_sums(elements, target),
+        }
+    }
+
+    /// Returns the next subset, or `undefined` once every subset has been returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::wasm::JsSubsetSums;
+    ///
+    /// let mut subsets = JsSubsetSums::new(vec![1, 2], 4);
+    /// assert_eq!(subsets.next(), None);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Vec<i32>> {
+        self.inner.next()
+    }
+}
@@ -0,0 +1,219 @@
+use crate::sorted_unique;
+
+/// An iterator which generates the windows of a set of elements, that is, the combinations of a
+/// given size whose elements are consecutive in sorted order, created by
+/// [`contiguous_combinations`] or [`circular_windows`].
+///
+/// Each window is found directly from its starting index, so this is much cheaper than filtering
+/// the output of [`Combinations::of_size`](crate::Combinations::of_size) when only the contiguous
+/// combinations are needed.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::contiguous_combinations;
+///
+/// let mut windows = contiguous_combinations(vec!['a', 'b', 'c', 'd'], 2);
+/// assert_eq!(windows.next(), Some(vec!['a', 'b']));
+/// assert_eq!(windows.next(), Some(vec!['b', 'c']));
+/// assert_eq!(windows.next(), Some(vec!['c', 'd']));
+/// assert_eq!(windows.next(), None);
+/// ```
+#[derive(Clone)]
+pub struct Windows<T> {
+    elements: Vec<T>,
+    size: usize,
+    start: usize,
+    count: usize,
+}
+
+/// Returns an iterator over the combinations of the specified size from the elements in the given
+/// iterable whose elements are contiguous in sorted order.  There are `n - k + 1` such windows of
+/// `n` elements, except that a single empty combination is yielded when `k` is 0, and none are
+/// yielded when `k` is greater than `n`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::contiguous_combinations;
+///
+/// let windows: Vec<Vec<i32>> = contiguous_combinations(vec![4, 2, 3, 1, 2], 3).collect();
+/// assert_eq!(windows, vec![vec![1, 2, 3], vec![2, 3, 4]]);
+///
+/// assert_eq!(contiguous_combinations(0..5, 0).collect::<Vec<Vec<i32>>>(), vec![vec![]]);
+/// assert_eq!(contiguous_combinations(0..5, 6).next(), None);
+/// ```
+pub fn contiguous_combinations<T: Ord + Clone>(
+    elements: impl IntoIterator<Item = T>,
+    k: usize,
+) -> Windows<T> {
+    let elements = sorted_unique(elements);
+    let count = match k {
+        0 => 1,
+        k if k > elements.len() => 0,
+        k => elements.len() - k + 1,
+    };
+    Windows {
+        elements,
+        size: k,
+        start: 0,
+        count,
+    }
+}
+
+/// Returns an iterator over the windows of the specified size from the elements in the given
+/// iterable, where the elements are arranged in a circle in sorted order, so windows may wrap
+/// around from the largest element to the smallest.  Each window lists its elements in circular
+/// order from its start.  There are `n` such windows of `n` elements when `k` is between 1 and
+/// `n - 1`, while a single window is yielded when `k` is 0 or `n`, and none are yielded when `k`
+/// is greater than `n`.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::circular_windows;
+///
+/// let mut windows = circular_windows(vec!['a', 'b', 'c', 'd'], 3);
+/// assert_eq!(windows.next(), Some(vec!['a', 'b', 'c']));
+/// assert_eq!(windows.next(), Some(vec!['b', 'c', 'd']));
+/// assert_eq!(windows.next(), Some(vec!['c', 'd', 'a']));
+/// assert_eq!(windows.next(), Some(vec!['d', 'a', 'b']));
+/// assert_eq!(windows.next(), None);
+///
+/// assert_eq!(circular_windows(1..=4, 4).collect::<Vec<Vec<i32>>>(), vec![vec![1, 2, 3, 4]]);
+/// ```
+pub fn circular_windows<T: Ord + Clone>(
+    elements: impl IntoIterator<Item = T>,
+    k: usize,
+) -> Windows<T> {
+    let elements = sorted_unique(elements);
+    let count = match k {
+        0 => 1,
+        k if k > elements.len() => 0,
+        k if k == elements.len() => 1,
+        _ => elements.len(),
+    };
+    Windows {
+        elements,
+        size: k,
+        start: 0,
+        count,
+    }
+}
+
+impl<T> Windows<T> {
+    /// Returns the sorted, deduplicated elements from which this iterator takes windows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::contiguous_combinations;
+    ///
+    /// assert_eq!(contiguous_combinations(vec![3, 1, 2, 1], 2).elements(), &[1, 2, 3]);
+    /// ```
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Consumes the iterator and returns its elements, as given by [`Windows::elements`], along
+    /// with the index at which the next window would have started, or `None` if no windows
+    /// remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::circular_windows;
+    ///
+    /// let mut windows = circular_windows(vec!['c', 'a', 'b'], 2);
+    /// assert_eq!(windows.next(), Some(vec!['a', 'b']));
+    /// assert_eq!(windows.into_parts(), (vec!['a', 'b', 'c'], Some(1)));
+    /// ```
+    pub fn into_parts(self) -> (Vec<T>, Option<usize>) {
+        (
+            self.elements,
+            (self.start < self.count).then_some(self.start),
+        )
+    }
+}
+
+impl<T: Clone> Windows<T> {
+    /// Returns the window which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if no windows remain.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::contiguous_combinations;
+    ///
+    /// let mut windows = contiguous_combinations(1..5, 3);
+    /// assert_eq!(windows.current(), Some(vec![1, 2, 3]));
+    /// windows.next();
+    /// assert_eq!(windows.current(), Some(vec![2, 3, 4]));
+    /// ```
+    pub fn current(&self) -> Option<Vec<T>> {
+        if self.start >= self.count {
+            return None;
+        }
+        Some(
+            (self.start..self.start + self.size)
+                .map(|index| self.elements[index % self.elements.len()].clone())
+                .collect(),
+        )
+    }
+}
+
+impl<T: Clone> Iterator for Windows<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next window and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let window = self.current()?;
+        self.start += 1;
+        Some(window)
+    }
+
+    /// Returns the exact number of remaining windows.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.start.min(self.count);
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Combinations;
+
+    #[test]
+    fn test_windows_match_filtered_combinations() {
+        for n in 0..7 {
+            for k in 0..=n + 1 {
+                let expected: Vec<Vec<usize>> = Combinations::of_size(0..n, k)
+                    .filter(|combo| combo.windows(2).all(|pair| pair[1] == pair[0] + 1))
+                    .collect();
+                let windows = contiguous_combinations(0..n, k);
+                assert_eq!(windows.size_hint(), (expected.len(), Some(expected.len())));
+                assert_eq!(windows.collect::<Vec<_>>(), expected);
+
+                let mut expected: Vec<Vec<usize>> = Combinations::of_size(0..n, k)
+                    .filter(|combo| {
+                        let gaps = combo
+                            .windows(2)
+                            .filter(|pair| pair[1] != pair[0] + 1)
+                            .count();
+                        gaps == 0 || (gaps == 1 && combo[0] == 0 && combo[k - 1] == n - 1)
+                    })
+                    .collect();
+                let mut windows: Vec<Vec<usize>> = circular_windows(0..n, k)
+                    .map(|mut window| {
+                        window.sort_unstable();
+                        window
+                    })
+                    .collect();
+                windows.sort();
+                expected.sort();
+                assert_eq!(windows, expected);
+            }
+        }
+    }
+}
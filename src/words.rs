@@ -0,0 +1,229 @@
+use crate::collecting::Collecting;
+use crate::combinations::size_hint_from;
+use crate::sorted_unique;
+
+/// An iterator which generates every word over an alphabet, that is, every sequence of letters
+/// from the alphabet in which letters may repeat, in shortlex order.
+///
+/// The letters are sorted and deduplicated, and words are yielded shortest first, beginning with
+/// the empty word, with the words of each length in lexicographic order.  This is the ordered
+/// analogue of [`CombinationsWithReplacement::all_unbounded`]: by default there is no limit on the
+/// length, so the iterator never ends unless the alphabet is empty, and should be stopped with an
+/// adaptor such as [`Iterator::find`], or limited with [`Words::max_length`].
+///
+/// [`CombinationsWithReplacement::all_unbounded`]:
+///     crate::CombinationsWithReplacement::all_unbounded
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::Words;
+///
+/// let words: Vec<String> = Words::over("ba".chars()).collecting().take(7).collect();
+/// assert_eq!(words, vec!["", "a", "b", "aa", "ab", "ba", "bb"]);
+///
+/// let keyword = Words::over('a'..='z').find(|word| word == &['k', 'e', 'y']);
+/// assert_eq!(keyword, Some(vec!['k', 'e', 'y']));
+/// ```
+#[derive(Clone)]
+pub struct Words<T> {
+    alphabet: Vec<T>,
+    positions: Vec<usize>,
+    max_length: Option<usize>,
+    done: bool,
+}
+
+impl<T: Ord + Clone> Words<T> {
+    /// Creates a new `Words` iterator which will yield every word over the letters in the given
+    /// iterable, of every length without end.  If there are no letters, only the empty word is
+    /// yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Words;
+    ///
+    /// let mut words = Words::over(vec![1, 0, 1]);
+    /// assert_eq!(words.next(), Some(vec![]));
+    /// assert_eq!(words.next(), Some(vec![0]));
+    /// assert_eq!(words.next(), Some(vec![1]));
+    /// assert_eq!(words.next(), Some(vec![0, 0]));
+    /// assert_eq!(words.size_hint(), (usize::MAX, None));
+    ///
+    /// assert_eq!(Words::over(Vec::<char>::new()).count(), 1);
+    /// ```
+    pub fn over(alphabet: impl IntoIterator<Item = T>) -> Self {
+        Words {
+            alphabet: sorted_unique(alphabet),
+            positions: Vec::new(),
+            max_length: None,
+            done: false,
+        }
+    }
+}
+
+impl<T> Words<T> {
+    /// Limits the iterator to words of at most the given length, after which it ends.  This
+    /// should be called before iterating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Words;
+    ///
+    /// let words = Words::over(0..3).max_length(2);
+    /// assert_eq!(words.size_hint(), (1 + 3 + 9, Some(1 + 3 + 9)));
+    /// assert_eq!(words.last(), Some(vec![2, 2]));
+    /// ```
+    pub fn max_length(mut self, length: usize) -> Self {
+        self.max_length = Some(length);
+        if self.positions.len() > length {
+            self.done = true;
+        }
+        self
+    }
+
+    /// Converts the iterator into one which collects each word directly into a container of type
+    /// `C`, such as a `String`, instead of a `Vec`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Words;
+    ///
+    /// let words: Vec<String> = Words::over("xy".chars()).max_length(2).collecting().collect();
+    /// assert_eq!(words, vec!["", "x", "y", "xx", "xy", "yx", "yy"]);
+    /// ```
+    pub fn collecting<C: FromIterator<T>>(self) -> Collecting<Self, C> {
+        Collecting::new(self)
+    }
+
+    /// Returns the sorted, deduplicated letters of the alphabet over which words are generated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::Words;
+    ///
+    /// assert_eq!(Words::over("hello".chars()).alphabet(), &['e', 'h', 'l', 'o']);
+    /// ```
+    pub fn alphabet(&self) -> &[T] {
+        &self.alphabet
+    }
+
+    /// Returns the current word collected into a container, if one remains.
+    fn get_current_word<C: FromIterator<T>>(&self) -> Option<C>
+    where
+        T: Clone,
+    {
+        if self.done {
+            return None;
+        }
+        Some(
+            self.positions
+                .iter()
+                .map(|p| self.alphabet[*p].clone())
+                .collect(),
+        )
+    }
+
+    /// Advances the internal positions to the next word, moving on to the next length once every
+    /// word of the current length has been yielded, and marks the iterator as done if none remain.
+    fn advance(&mut self) {
+        for position in self.positions.iter_mut().rev() {
+            *position += 1;
+            if *position < self.alphabet.len() {
+                return;
+            }
+            *position = 0;
+        }
+        let length = self.positions.len();
+        if self.alphabet.is_empty() || self.max_length.is_some_and(|max| length >= max) {
+            self.done = true;
+        } else {
+            self.positions.push(0);
+        }
+    }
+
+    /// Returns the number of words which have not yet been yielded, or `None` if the count does
+    /// not fit in a `u128` or is infinite.
+    fn remaining_count(&self) -> Option<u128> {
+        if self.done {
+            return Some(0);
+        }
+        let n = self.alphabet.len() as u128;
+        if n == 0 {
+            return Some(1);
+        }
+        let length = self.positions.len();
+        let words_of_length = |length: usize| n.checked_pow(u32::try_from(length).ok()?);
+        let before = self.positions.iter().try_fold(0u128, |rank, p| {
+            rank.checked_mul(n)?.checked_add(*p as u128)
+        })?;
+        (length + 1..=self.max_length?).try_fold(words_of_length(length)? - before, |total, k| {
+            total.checked_add(words_of_length(k)?)
+        })
+    }
+}
+
+impl<T: Clone> Iterator for Words<T> {
+    type Item = Vec<T>;
+
+    /// Returns the next word and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let word = self.get_current_word()?;
+        self.advance();
+        Some(word)
+    }
+
+    /// Returns the exact number of remaining words, if it is finite and fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint_from(self.remaining_count())
+    }
+}
+
+impl<T: Clone, C: FromIterator<T>> Iterator for Collecting<Words<T>, C> {
+    type Item = C;
+
+    /// Returns the next word and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        let word = self.inner.get_current_word()?;
+        self.inner.advance();
+        Some(word)
+    }
+
+    /// Returns the exact number of remaining words, if it is finite and fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::cartesian_power;
+
+    #[test]
+    fn test_words_match_cartesian_powers() {
+        for n in 0..4 {
+            for max in 0..5 {
+                let letters: Vec<usize> = (0..n).collect();
+                let expected: Vec<Vec<usize>> = (0..=max)
+                    .flat_map(|length| cartesian_power(&letters, length))
+                    .collect();
+                let mut words = Words::over(0..n).max_length(max);
+                let mut remaining = expected.len();
+                assert_eq!(words.size_hint(), (remaining, Some(remaining)));
+                for word in expected {
+                    assert_eq!(words.next(), Some(word));
+                    remaining -= 1;
+                    assert_eq!(words.size_hint(), (remaining, Some(remaining)));
+                }
+                assert_eq!(words.next(), None);
+            }
+        }
+        let mut words = Words::over(0..2);
+        assert_eq!(words.nth(1 + 2 + 4 + 8), Some(vec![0, 0, 0, 0]));
+        assert_eq!(words.size_hint(), (usize::MAX, None));
+    }
+}
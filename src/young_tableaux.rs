@@ -0,0 +1,274 @@
+use crate::gcd;
+
+/// An iterator which generates all standard Young tableaux of a given shape.
+///
+/// The shape is a partition of `n`, given as a non-increasing list of row lengths.  A standard
+/// Young tableau of that shape is a filling of its rows with the numbers `1..=n`, each used once,
+/// such that the numbers increase along every row and down every column.  Tableaux are yielded as
+/// vectors of rows, ordered lexicographically by the sequence of rows in which `1`, `2`, ..., `n`
+/// are placed.  If the shape is not a partition, no tableaux are yielded.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::YoungTableaux;
+///
+/// let mut tableaux = YoungTableaux::of_shape(&[2, 1]);
+/// assert_eq!(tableaux.next(), Some(vec![vec![1, 2], vec![3]]));
+/// assert_eq!(tableaux.next(), Some(vec![vec![1, 3], vec![2]]));
+/// assert_eq!(tableaux.next(), None);
+///
+/// assert_eq!(YoungTableaux::of_shape(&[3, 2, 1]).count(), 16);
+/// ```
+#[derive(Clone)]
+pub struct YoungTableaux {
+    shape: Vec<usize>,
+    size: usize,
+    rows: Vec<usize>,
+    row_lengths: Vec<usize>,
+    started: bool,
+    done: bool,
+}
+
+/// Returns a copy of the given shape with any trailing rows of length zero removed.
+fn trim_shape(shape: &[usize]) -> Vec<usize> {
+    let end = shape
+        .iter()
+        .rposition(|len| *len > 0)
+        .map_or(0, |last| last + 1);
+    shape[..end].to_vec()
+}
+
+/// Returns `true` if the given row lengths are non-increasing.
+fn is_partition(shape: &[usize]) -> bool {
+    shape.windows(2).all(|pair| pair[0] >= pair[1])
+}
+
+impl YoungTableaux {
+    /// Creates a new `YoungTableaux` iterator which will yield all standard Young tableaux of the
+    /// given shape.  Trailing rows of length zero are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::YoungTableaux;
+    ///
+    /// let mut tableaux = YoungTableaux::of_shape(&[2, 2]);
+    /// assert_eq!(tableaux.next(), Some(vec![vec![1, 2], vec![3, 4]]));
+    /// assert_eq!(tableaux.next(), Some(vec![vec![1, 3], vec![2, 4]]));
+    /// assert_eq!(tableaux.next(), None);
+    ///
+    /// let mut tableaux = YoungTableaux::of_shape(&[]);
+    /// assert_eq!(tableaux.next(), Some(Vec::new()));
+    /// assert_eq!(tableaux.next(), None);
+    ///
+    /// let mut tableaux = YoungTableaux::of_shape(&[1, 2]);
+    /// assert_eq!(tableaux.next(), None);
+    /// ```
+    pub fn of_shape(shape: &[usize]) -> Self {
+        let shape = trim_shape(shape);
+        let size = shape.iter().sum();
+        YoungTableaux {
+            row_lengths: vec![0; shape.len()],
+            done: !is_partition(&shape),
+            shape,
+            size,
+            rows: Vec::with_capacity(size),
+            started: false,
+        }
+    }
+
+    /// Returns the tableau which the next call to [`next`](Iterator::next) will yield, without
+    /// advancing the iterator, or `None` if none remain.  Since the next tableau is only found
+    /// when the iterator advances, this searches for it using a copy of the iterator, so it costs
+    /// about as much as a call to `next`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use combinatorial::YoungTableaux;
+    ///
+    /// let mut tableaux = YoungTableaux::of_shape(&[2, 1]);
+    /// assert_eq!(tableaux.next(), Some(vec![vec![1, 2], vec![3]]));
+    /// assert_eq!(tableaux.current(), Some(vec![vec![1, 3], vec![2]]));
+    /// assert_eq!(tableaux.next(), Some(vec![vec![1, 3], vec![2]]));
+    /// assert_eq!(tableaux.current(), None);
+    /// ```
+    pub fn current(&self) -> Option<Vec<Vec<usize>>> {
+        self.clone().next()
+    }
+
+    /// Returns `true` if the next number can be placed at the end of the given row.
+    fn can_place(&self, row: usize) -> bool {
+        self.row_lengths[row] < self.shape[row]
+            && (row == 0 || self.row_lengths[row] < self.row_lengths[row - 1])
+    }
+
+    /// Places the next number at the end of the given row.
+    fn place(&mut self, row: usize) {
+        self.row_lengths[row] += 1;
+        self.rows.push(row);
+    }
+
+    /// Removes the most recently placed number and returns the row it was placed in, if any.
+    fn unplace(&mut self) -> Option<usize> {
+        let row = self.rows.pop()?;
+        self.row_lengths[row] -= 1;
+        Some(row)
+    }
+
+    /// Places the remaining numbers in the lowest-indexed rows possible.  If `previous` is given,
+    /// the search resumes by trying rows after `previous` for the next number.  Returns `true` if
+    /// every number was placed, or `false` if no further tableaux exist.
+    fn fill(&mut self, mut previous: Option<usize>) -> bool {
+        loop {
+            if previous.is_none() && self.rows.len() == self.size {
+                return true;
+            }
+            let start = previous.map_or(0, |row| row + 1);
+            match (start..self.shape.len()).find(|row| self.can_place(*row)) {
+                Some(row) => {
+                    self.place(row);
+                    previous = None;
+                }
+                None => match self.unplace() {
+                    Some(row) => previous = Some(row),
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    /// Returns the current tableau as a vector of rows.
+    fn current_tableau(&self) -> Vec<Vec<usize>> {
+        let mut tableau: Vec<Vec<usize>> = self
+            .shape
+            .iter()
+            .map(|len| Vec::with_capacity(*len))
+            .collect();
+        for (index, row) in self.rows.iter().enumerate() {
+            tableau[*row].push(index + 1);
+        }
+        tableau
+    }
+}
+
+impl Iterator for YoungTableaux {
+    type Item = Vec<Vec<usize>>;
+
+    /// Returns the next tableau and advances the internal iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let found = if self.started {
+            match self.unplace() {
+                Some(row) => self.fill(Some(row)),
+                None => false,
+            }
+        } else {
+            self.started = true;
+            self.fill(None)
+        };
+        if !found {
+            self.done = true;
+            return None;
+        }
+        Some(self.current_tableau())
+    }
+}
+
+/// Returns the number of standard Young tableaux of the given shape, computed using the hook
+/// length formula.  If the shape is not a non-increasing list of row lengths, returns zero.
+///
+/// # Examples
+///
+/// ```
+/// use combinatorial::{young_tableaux_count, YoungTableaux};
+///
+/// assert_eq!(young_tableaux_count(&[]), 1);
+/// assert_eq!(young_tableaux_count(&[3, 2]), 5);
+/// assert_eq!(young_tableaux_count(&[5, 4, 1]), 288);
+/// assert_eq!(young_tableaux_count(&[2, 3]), 0);
+///
+/// let shape = [4, 3, 1, 1];
+/// assert_eq!(young_tableaux_count(&shape), YoungTableaux::of_shape(&shape).count());
+/// ```
+pub fn young_tableaux_count(shape: &[usize]) -> usize {
+    let shape = trim_shape(shape);
+    if !is_partition(&shape) {
+        return 0;
+    }
+    let size: usize = shape.iter().sum();
+    let mut numerators: Vec<usize> = (1..=size).collect();
+    for (row, row_length) in shape.iter().enumerate() {
+        for col in 0..*row_length {
+            let below = shape[row + 1..]
+                .iter()
+                .take_while(|len| **len > col)
+                .count();
+            let mut hook = row_length - col + below;
+            for numerator in numerators.iter_mut() {
+                let divisor = gcd(*numerator, hook);
+                *numerator /= divisor;
+                hook /= divisor;
+                if hook == 1 {
+                    break;
+                }
+            }
+        }
+    }
+    numerators.into_iter().product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_young_tableaux_valid() {
+        for tableau in YoungTableaux::of_shape(&[4, 2, 2, 1]) {
+            assert_eq!(
+                tableau.iter().map(|row| row.len()).collect::<Vec<usize>>(),
+                vec![4, 2, 2, 1]
+            );
+            for (r, row) in tableau.iter().enumerate() {
+                assert!(row.windows(2).all(|pair| pair[0] < pair[1]));
+                if r > 0 {
+                    assert!(row.iter().zip(tableau[r - 1].iter()).all(|(x, y)| y < x));
+                }
+            }
+            let mut entries: Vec<usize> = tableau.concat();
+            entries.sort();
+            assert_eq!(entries, (1..=9).collect::<Vec<usize>>());
+        }
+        let mut items = YoungTableaux::of_shape(&[3, 2, 1]);
+        while let Some(current) = items.current() {
+            assert_eq!(items.next(), Some(current));
+        }
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn test_young_tableaux_count() {
+        assert_eq!(young_tableaux_count(&[1]), 1);
+        assert_eq!(young_tableaux_count(&[4]), 1);
+        assert_eq!(young_tableaux_count(&[1, 1, 1, 1]), 1);
+        assert_eq!(young_tableaux_count(&[3, 3, 0]), 5);
+        assert_eq!(young_tableaux_count(&[3, 0, 3]), 0);
+        assert_eq!(young_tableaux_count(&[4, 3, 2, 1]), 768);
+        for shape in [vec![3, 3, 2], vec![5, 1, 1], vec![2, 2, 2, 1], vec![6, 3]] {
+            assert_eq!(
+                young_tableaux_count(&shape),
+                YoungTableaux::of_shape(&shape).count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_young_tableaux_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 1), 1);
+        assert_eq!(gcd(0, 5), 5);
+    }
+}